@@ -2,7 +2,8 @@ use crate::cli::interactive_display::InteractiveDisplay;
 use crate::cli::output::{
     ConfigValidateOutput, OutputFormat, ValidateUserInfo, escape_csv_field, print_json,
 };
-use mbr_core::api::client::MetabaseClient;
+use mbr_core::api::traits::MetabaseApi;
+use mbr_core::core::profile::{ColumnProfile, DistributionStats, profile_query_result, sparkline};
 use mbr_core::display::{
     OperationStatus, ProgressSpinner, TableDisplay, TableHeaderInfoBuilder, display_status,
 };
@@ -12,7 +13,7 @@ use mbr_core::utils::data::OffsetManager;
 
 /// Handle the `queries` command — list available questions.
 pub async fn handle_queries(
-    client: &MetabaseClient,
+    client: &dyn MetabaseApi,
     search: Option<String>,
     limit: u32,
     collection: Option<String>,
@@ -76,7 +77,7 @@ pub async fn handle_queries(
 /// Handle the `run` command — execute a question by ID.
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_run(
-    client: &MetabaseClient,
+    client: &dyn MetabaseApi,
     id: u32,
     param: Vec<String>,
     format: OutputFormat,
@@ -85,6 +86,7 @@ pub async fn handle_run(
     no_fullscreen: bool,
     offset: Option<usize>,
     page_size: usize,
+    profile: bool,
     use_colors: bool,
 ) -> Result<(), AppError> {
     // Convert parameters from Vec<String> to HashMap<String, String>
@@ -128,6 +130,15 @@ pub async fn handle_run(
 
     let table_display = TableDisplay::new().with_colors(use_colors);
 
+    if profile {
+        print_profiles(
+            &profile_query_result(&processed_result),
+            format,
+            &table_display,
+        );
+        return Ok(());
+    }
+
     let display_start = offset.map(|o| o + 1).unwrap_or(1);
     let limit_for_display = if full { None } else { Some(limit) };
     let actual_displayed_rows = if let Some(limit_val) = limit_for_display {
@@ -213,9 +224,90 @@ pub async fn handle_run(
     Ok(())
 }
 
+/// Print column profiles (`run --profile`) in the requested format.
+fn print_profiles(profiles: &[ColumnProfile], format: OutputFormat, table_display: &TableDisplay) {
+    let stat = |profile: &ColumnProfile, pick: fn(&DistributionStats) -> &String| {
+        profile
+            .stats
+            .as_ref()
+            .map(pick)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let top_values = |profile: &ColumnProfile| {
+        profile
+            .top_values
+            .iter()
+            .map(|top| format!("{} ({})", top.value, top.count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let kind = |profile: &ColumnProfile| format!("{:?}", profile.kind).to_lowercase();
+
+    match format {
+        OutputFormat::Json => {
+            print_json(&profiles);
+        }
+        OutputFormat::Csv => {
+            println!("column,type,rows,nulls,distinct,min,p25,median,p75,p95,max,mean,top_values");
+            for profile in profiles {
+                let fields = [
+                    profile.column.clone(),
+                    kind(profile),
+                    profile.count.to_string(),
+                    profile.nulls.to_string(),
+                    profile.distinct.to_string(),
+                    stat(profile, |s| &s.min),
+                    stat(profile, |s| &s.p25),
+                    stat(profile, |s| &s.median),
+                    stat(profile, |s| &s.p75),
+                    stat(profile, |s| &s.p95),
+                    stat(profile, |s| &s.max),
+                    stat(profile, |s| &s.mean),
+                    top_values(profile),
+                ];
+                let csv_row: Vec<String> = fields.iter().map(|f| escape_csv_field(f)).collect();
+                println!("{}", csv_row.join(","));
+            }
+        }
+        OutputFormat::Table => {
+            let headers = [
+                "Column",
+                "Type",
+                "Nulls",
+                "Distinct",
+                "Min",
+                "Median",
+                "Max",
+                "Mean",
+                "Top values",
+                "Distribution",
+            ];
+            let rows: Vec<Vec<String>> = profiles
+                .iter()
+                .map(|profile| {
+                    vec![
+                        profile.column.clone(),
+                        kind(profile),
+                        format!("{}/{}", profile.nulls, profile.count),
+                        profile.distinct.to_string(),
+                        stat(profile, |s| &s.min),
+                        stat(profile, |s| &s.median),
+                        stat(profile, |s| &s.max),
+                        stat(profile, |s| &s.mean),
+                        top_values(profile),
+                        sparkline(&profile.histogram),
+                    ]
+                })
+                .collect();
+            println!("{}", table_display.render_simple_table(&headers, &rows));
+        }
+    }
+}
+
 /// Handle the `collections` command — list all collections.
 pub async fn handle_collections(
-    client: &MetabaseClient,
+    client: &dyn MetabaseApi,
     format: OutputFormat,
     use_colors: bool,
 ) -> Result<(), AppError> {
@@ -291,7 +383,7 @@ pub async fn handle_collections(
 
 /// Handle the `databases` command — list all databases.
 pub async fn handle_databases(
-    client: &MetabaseClient,
+    client: &dyn MetabaseApi,
     format: OutputFormat,
     use_colors: bool,
 ) -> Result<(), AppError> {
@@ -359,7 +451,7 @@ pub async fn handle_databases(
 
 /// Handle the `tables` command — list tables in a database schema.
 pub async fn handle_tables(
-    client: &MetabaseClient,
+    client: &dyn MetabaseApi,
     database_id: u32,
     schema: String,
     format: OutputFormat,
@@ -433,9 +525,82 @@ pub async fn handle_tables(
     Ok(())
 }
 
+/// Handle the `describe` command — list the fields of a table.
+pub async fn handle_describe(
+    client: &dyn MetabaseApi,
+    table_id: u32,
+    format: OutputFormat,
+    use_colors: bool,
+) -> Result<(), AppError> {
+    let mut spinner = ProgressSpinner::new(format!("Fetching table {}...", table_id));
+    spinner.start();
+
+    let table = client.get_table_metadata(table_id).await?;
+    spinner.stop(Some("Table fetched successfully"));
+
+    match format {
+        OutputFormat::Json => {
+            print_json(&table);
+        }
+        OutputFormat::Csv => {
+            println!("id,name,display_name,base_type,semantic_type,fk_target_field_id,description");
+            for f in &table.fields {
+                println!(
+                    "{},{},{},{},{},{},{}",
+                    f.id,
+                    escape_csv_field(&f.name),
+                    escape_csv_field(f.display_name.as_deref().unwrap_or("")),
+                    escape_csv_field(f.base_type.as_deref().unwrap_or("")),
+                    escape_csv_field(f.semantic_type.as_deref().unwrap_or("")),
+                    f.fk_target_field_id
+                        .map_or(String::new(), |id| id.to_string()),
+                    escape_csv_field(f.description.as_deref().unwrap_or(""))
+                );
+            }
+        }
+        OutputFormat::Table => {
+            println!(
+                "Table {}: {} (database {}, schema {})",
+                table.id,
+                table.display_name.as_deref().unwrap_or(&table.name),
+                table.db_id,
+                table.schema.as_deref().unwrap_or("-")
+            );
+            let table_display = TableDisplay::new().with_colors(use_colors);
+            let headers = vec![
+                "ID",
+                "Name",
+                "Type",
+                "Semantic Type",
+                "FK Target",
+                "Description",
+            ];
+            let rows: Vec<Vec<String>> = table
+                .fields
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.id.to_string(),
+                        f.name.clone(),
+                        f.base_type.as_deref().unwrap_or("-").to_string(),
+                        f.semantic_type.as_deref().unwrap_or("-").to_string(),
+                        f.fk_target_field_id
+                            .map_or("-".to_string(), |id| format!("field {}", id)),
+                        f.description.as_deref().unwrap_or("-").to_string(),
+                    ]
+                })
+                .collect();
+            let rendered = table_display.render_simple_table(&headers, &rows);
+            println!("{}", rendered);
+        }
+    }
+
+    Ok(())
+}
+
 /// Handle the `config validate` command.
 pub async fn handle_config_validate(
-    client: &MetabaseClient,
+    client: &dyn MetabaseApi,
     format: OutputFormat,
     _use_colors: bool,
 ) -> Result<(), AppError> {
@@ -0,0 +1,964 @@
+//! Persistent daemon for `mbr-cli serve`, and the client that forwards CLI
+//! commands to it.
+//!
+//! The daemon keeps an authenticated `MetabaseClient` (and its connection
+//! pool) and a metadata cache for its whole lifetime, and answers JSON-RPC
+//! 2.0 requests on a Unix socket, one message per line. Methods mirror the
+//! CLI commands that read from Metabase: `queries`, `run`, `collections`,
+//! `databases`, `tables`, `describe`, `config.validate` and `status`, plus
+//! the other read requests of `MetabaseApi` (`schemas`, `question`,
+//! `collection.items`, `sql`, `preview`, `table.rows`, `field`,
+//! `table.fks`). Commands that change local state (`login`, `logout`,
+//! `config set-url`), `sync-metadata` (which must bypass every cache) and
+//! `mcp` (which holds its own session) always run in the CLI process.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use mbr_core::api::client::MetabaseClient;
+use mbr_core::api::models::{
+    CollectionEntry, CollectionItem, CurrentUser, Database, Field, ForeignKey, QueryResult,
+    Question, QuestionDetail, TableInfo, TableMetadata,
+};
+use mbr_core::api::traits::MetabaseApi;
+use mbr_core::core::cache::TtlCache;
+use mbr_core::error::{
+    ApiError, AppError, AuthError, CliError, ConnectionErrorKind, QuestionError, ServiceError,
+};
+use mbr_core::storage::credentials::load_session;
+use mbr_core::utils::error_helpers::convert_io_to_display_error;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::cli::dispatcher::Dispatcher;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+fn io_error(e: std::io::Error) -> AppError {
+    AppError::Display(convert_io_to_display_error(e, "daemon socket"))
+}
+
+// ── Protocol ───────────────────────────────────────────────────────────
+
+#[derive(Serialize, Deserialize)]
+struct QueriesParams {
+    search: Option<String>,
+    limit: Option<u32>,
+    collection: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RunParams {
+    id: u32,
+    parameters: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TablesParams {
+    database_id: u32,
+    schema: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TableParams {
+    table_id: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SchemasParams {
+    database_id: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct QuestionParams {
+    id: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CollectionItemsParams {
+    collection_id: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SqlParams {
+    database_id: u32,
+    sql: String,
+    max_rows: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PreviewParams {
+    database_id: u32,
+    table_id: u32,
+    limit: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TableRowsParams {
+    database_id: u32,
+    table_id: u32,
+    field_id: u32,
+    value: Value,
+    limit: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FieldParams {
+    field_id: u32,
+}
+
+/// Result of the `status` method.
+#[derive(Serialize, Deserialize)]
+struct DaemonStatus {
+    url: String,
+    version: String,
+    pid: u32,
+}
+
+/// An error carried in the `data` of a JSON-RPC error, detailed enough for
+/// the client to report it (and exit) exactly like a direct invocation.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WireError {
+    Unauthorized {
+        status: u16,
+        endpoint: String,
+        server_message: String,
+    },
+    Forbidden {
+        status: u16,
+        endpoint: String,
+        server_message: String,
+    },
+    Timeout {
+        timeout_secs: u64,
+        endpoint: String,
+    },
+    Connection {
+        kind: ConnectionErrorKind,
+        endpoint: String,
+        message: String,
+    },
+    Http {
+        status: u16,
+        endpoint: String,
+        message: String,
+    },
+    QuestionNotFound {
+        id: u32,
+    },
+    NotLoggedIn,
+    SessionExpired,
+    InvalidArguments {
+        message: String,
+    },
+    NotImplemented {
+        command: String,
+    },
+    Other {
+        message: String,
+    },
+}
+
+impl From<&AppError> for WireError {
+    fn from(error: &AppError) -> Self {
+        match error {
+            AppError::Api(ApiError::Unauthorized {
+                status,
+                endpoint,
+                server_message,
+            }) => WireError::Unauthorized {
+                status: *status,
+                endpoint: endpoint.clone(),
+                server_message: server_message.clone(),
+            },
+            AppError::Api(ApiError::Forbidden {
+                status,
+                endpoint,
+                server_message,
+            }) => WireError::Forbidden {
+                status: *status,
+                endpoint: endpoint.clone(),
+                server_message: server_message.clone(),
+            },
+            AppError::Api(ApiError::Timeout {
+                timeout_secs,
+                endpoint,
+            }) => WireError::Timeout {
+                timeout_secs: *timeout_secs,
+                endpoint: endpoint.clone(),
+            },
+            AppError::Api(ApiError::Connection {
+                kind,
+                endpoint,
+                message,
+            }) => WireError::Connection {
+                kind: *kind,
+                endpoint: endpoint.clone(),
+                message: message.clone(),
+            },
+            AppError::Api(ApiError::Http {
+                status,
+                endpoint,
+                message,
+            }) => WireError::Http {
+                status: *status,
+                endpoint: endpoint.clone(),
+                message: message.clone(),
+            },
+            AppError::Question(QuestionError::NotFound { id }) => {
+                WireError::QuestionNotFound { id: *id }
+            }
+            AppError::Auth(AuthError::NotLoggedIn) => WireError::NotLoggedIn,
+            AppError::Auth(AuthError::SessionExpired) => WireError::SessionExpired,
+            AppError::Cli(CliError::InvalidArguments(message)) => WireError::InvalidArguments {
+                message: message.clone(),
+            },
+            AppError::Cli(CliError::NotImplemented { command }) => WireError::NotImplemented {
+                command: command.clone(),
+            },
+            other => WireError::Other {
+                message: other.display_friendly(),
+            },
+        }
+    }
+}
+
+impl From<WireError> for AppError {
+    fn from(error: WireError) -> Self {
+        match error {
+            WireError::Unauthorized {
+                status,
+                endpoint,
+                server_message,
+            } => AppError::Api(ApiError::Unauthorized {
+                status,
+                endpoint,
+                server_message,
+            }),
+            WireError::Forbidden {
+                status,
+                endpoint,
+                server_message,
+            } => AppError::Api(ApiError::Forbidden {
+                status,
+                endpoint,
+                server_message,
+            }),
+            WireError::Timeout {
+                timeout_secs,
+                endpoint,
+            } => AppError::Api(ApiError::Timeout {
+                timeout_secs,
+                endpoint,
+            }),
+            WireError::Connection {
+                kind,
+                endpoint,
+                message,
+            } => AppError::Api(ApiError::Connection {
+                kind,
+                endpoint,
+                message,
+            }),
+            WireError::Http {
+                status,
+                endpoint,
+                message,
+            } => AppError::Api(ApiError::Http {
+                status,
+                endpoint,
+                message,
+            }),
+            WireError::QuestionNotFound { id } => {
+                AppError::Question(QuestionError::NotFound { id })
+            }
+            WireError::NotLoggedIn => AppError::Auth(AuthError::NotLoggedIn),
+            WireError::SessionExpired => AppError::Auth(AuthError::SessionExpired),
+            WireError::InvalidArguments { message } => {
+                AppError::Cli(CliError::InvalidArguments(message))
+            }
+            WireError::NotImplemented { command } => {
+                AppError::Cli(CliError::NotImplemented { command })
+            }
+            WireError::Other { message } => {
+                AppError::Service(ServiceError::ConfigService { message })
+            }
+        }
+    }
+}
+
+// ── Server ─────────────────────────────────────────────────────────────
+
+struct Daemon {
+    dispatcher: Dispatcher,
+    client: RwLock<MetabaseClient>,
+    /// Collections, databases and tables, which rarely change
+    metadata: TtlCache<String, Value>,
+}
+
+/// Run the daemon on `path` until interrupted (Ctrl+C or SIGTERM).
+pub async fn serve(dispatcher: &Dispatcher, path: &Path) -> Result<(), AppError> {
+    let daemon = Arc::new(Daemon::new(dispatcher.clone(), dispatcher.create_client()?));
+    let listener = bind(path).await?;
+    eprintln!("Listening on {} (Ctrl+C to stop)", path.display());
+
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .map_err(io_error)?;
+    let stop = async {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    };
+    let result = accept_until(daemon, listener, stop).await;
+
+    let _ = std::fs::remove_file(path);
+    eprintln!("Daemon stopped");
+    result
+}
+
+/// Serve the connections made to `listener` until `stop` completes.
+async fn accept_until(
+    daemon: Arc<Daemon>,
+    listener: UnixListener,
+    stop: impl Future<Output = ()>,
+) -> Result<(), AppError> {
+    let mut stop = std::pin::pin!(stop);
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted.map_err(io_error)?;
+                let daemon = Arc::clone(&daemon);
+                tokio::spawn(async move { daemon.serve_connection(stream).await });
+            }
+            _ = &mut stop => return Ok(()),
+        }
+    }
+}
+
+/// Bind the socket, replacing a stale socket file but not a live daemon.
+async fn bind(path: &Path) -> Result<UnixListener, AppError> {
+    if path.exists() {
+        if UnixStream::connect(path).await.is_ok() {
+            return Err(AppError::Cli(CliError::InvalidArguments(format!(
+                "A daemon is already running on {}",
+                path.display()
+            ))));
+        }
+        std::fs::remove_file(path).map_err(io_error)?;
+    }
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(parent).map_err(io_error)?;
+
+    // The daemon acts with the user's session: keep other users out. The
+    // socket is created in a directory only the user can enter and moved into
+    // place once it is private, so it is never reachable with the
+    // permissions the umask would give it
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    let staging = parent.join(format!(".mbr-daemon-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging)
+        .map_err(io_error)?;
+    let staged = staging.join("daemon.sock");
+    let bound = UnixListener::bind(&staged)
+        .map_err(io_error)
+        .and_then(|listener| {
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))
+                .map_err(io_error)?;
+            std::fs::rename(&staged, path).map_err(io_error)?;
+            Ok(listener)
+        });
+    let _ = std::fs::remove_dir_all(&staging);
+    bound
+}
+
+impl Daemon {
+    fn new(dispatcher: Dispatcher, client: MetabaseClient) -> Self {
+        Self {
+            dispatcher,
+            client: RwLock::new(client),
+            metadata: TtlCache::with_default_ttl(),
+        }
+    }
+
+    async fn serve_connection(&self, stream: UnixStream) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle_message(message).await,
+                Err(e) => error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    &AppError::Cli(CliError::InvalidArguments(format!("Parse error: {}", e))),
+                ),
+            };
+            let mut out = response.to_string();
+            out.push('\n');
+            if writer.write_all(out.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    async fn handle_message(&self, message: Value) -> Value {
+        let id = message.get("id").cloned().unwrap_or(Value::Null);
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        self.dispatcher
+            .log_verbose(&format!("daemon: handling '{}'", method));
+
+        let mut outcome = self.call(method, params.clone()).await;
+        if matches!(outcome, Err(ref e) if Dispatcher::is_unauthorized(e))
+            && let Some(client) = self.renew_client().await
+        {
+            *self.client.write().await = client;
+            self.metadata.clear();
+            outcome = self.call(method, params).await;
+        }
+
+        match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => {
+                let code = match error {
+                    AppError::Cli(CliError::NotImplemented { .. }) => METHOD_NOT_FOUND,
+                    AppError::Cli(CliError::InvalidArguments(_)) => INVALID_PARAMS,
+                    _ => SERVER_ERROR,
+                };
+                error_response(id, code, &error)
+            }
+        }
+    }
+
+    /// A client for the session stored since the daemon started (after
+    /// `mbr-cli login`), or one from an automatic re-login.
+    async fn renew_client(&self) -> Option<MetabaseClient> {
+        if let Some(session) = load_session() {
+            let current = self.client.read().await;
+            let changed = session.url == current.base_url
+                && (Some(&session.session_token) != current.session_token.as_ref()
+                    || session.api_key != current.api_key);
+            if changed {
+                self.dispatcher
+                    .log_verbose("daemon: switching to the stored session");
+                let client = MetabaseClient::from_session(&session).ok()?;
+                return self.dispatcher.configure_client(client).ok();
+            }
+        }
+        self.dispatcher.try_auto_relogin().await
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, AppError> {
+        let client = self.client.read().await.clone();
+        match method {
+            "status" => Ok(json!(DaemonStatus {
+                url: client.base_url.clone(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                pid: std::process::id(),
+            })),
+            "queries" => {
+                let p: QueriesParams = parse_params(params)?;
+                Ok(json!(
+                    client
+                        .list_questions(p.search.as_deref(), p.limit, p.collection.as_deref())
+                        .await?
+                ))
+            }
+            "run" => {
+                let p: RunParams = parse_params(params)?;
+                Ok(json!(client.execute_question(p.id, p.parameters).await?))
+            }
+            "collections" => {
+                self.cached("collections".to_string(), client.list_collections())
+                    .await
+            }
+            "databases" => {
+                self.cached("databases".to_string(), client.list_databases())
+                    .await
+            }
+            "tables" => {
+                let p: TablesParams = parse_params(params)?;
+                let key = format!("tables:{}:{}", p.database_id, p.schema);
+                self.cached(key, client.list_tables(p.database_id, &p.schema))
+                    .await
+            }
+            "describe" => {
+                let p: TableParams = parse_params(params)?;
+                let key = format!("describe:{}", p.table_id);
+                self.cached(key, client.get_table_metadata(p.table_id))
+                    .await
+            }
+            "config.validate" => Ok(json!(client.get_current_user().await?)),
+            "schemas" => {
+                let p: SchemasParams = parse_params(params)?;
+                let key = format!("schemas:{}", p.database_id);
+                self.cached(key, client.list_schemas(p.database_id)).await
+            }
+            "question" => {
+                let p: QuestionParams = parse_params(params)?;
+                Ok(json!(client.get_question(p.id).await?))
+            }
+            "collection.items" => {
+                let p: CollectionItemsParams = parse_params(params)?;
+                Ok(json!(client.list_collection_items(p.collection_id).await?))
+            }
+            "sql" => {
+                let p: SqlParams = parse_params(params)?;
+                Ok(json!(
+                    client
+                        .run_native_query(p.database_id, &p.sql, p.max_rows)
+                        .await?
+                ))
+            }
+            "preview" => {
+                let p: PreviewParams = parse_params(params)?;
+                Ok(json!(
+                    client
+                        .preview_table(p.database_id, p.table_id, p.limit)
+                        .await?
+                ))
+            }
+            "table.rows" => {
+                let p: TableRowsParams = parse_params(params)?;
+                Ok(json!(
+                    client
+                        .query_table_rows(p.database_id, p.table_id, p.field_id, &p.value, p.limit)
+                        .await?
+                ))
+            }
+            "field" => {
+                let p: FieldParams = parse_params(params)?;
+                Ok(json!(client.get_field(p.field_id).await?))
+            }
+            "table.fks" => {
+                let p: TableParams = parse_params(params)?;
+                Ok(json!(client.list_table_fks(p.table_id).await?))
+            }
+            _ => Err(AppError::Cli(CliError::NotImplemented {
+                command: method.to_string(),
+            })),
+        }
+    }
+
+    /// Serve a metadata request from the cache, fetching it on a miss.
+    async fn cached<T: Serialize>(
+        &self,
+        key: String,
+        fetch: impl Future<Output = Result<T, AppError>>,
+    ) -> Result<Value, AppError> {
+        if let Some(value) = self.metadata.get(&key) {
+            self.dispatcher
+                .log_verbose(&format!("daemon: cache hit for '{}'", key));
+            return Ok(value);
+        }
+        let value = json!(fetch.await?);
+        self.metadata.insert(key, value.clone());
+        Ok(value)
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, AppError> {
+    serde_json::from_value(params)
+        .map_err(|e| AppError::Cli(CliError::InvalidArguments(format!("Invalid params: {}", e))))
+}
+
+fn error_response(id: Value, code: i64, error: &AppError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": code,
+            "message": error.display_friendly(),
+            "data": WireError::from(error),
+        },
+    })
+}
+
+// ── Client ─────────────────────────────────────────────────────────────
+
+/// Connection to a running daemon.
+pub struct DaemonClient {
+    stream: Mutex<BufReader<UnixStream>>,
+    next_id: AtomicU64,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon on `path` if it runs and serves `url`.
+    pub async fn connect(path: &Path, url: &str) -> Option<Self> {
+        let stream = UnixStream::connect(path).await.ok()?;
+        let client = Self {
+            stream: Mutex::new(BufReader::new(stream)),
+            next_id: AtomicU64::new(1),
+        };
+        let status: DaemonStatus = client.call("status", Value::Null).await.ok()?;
+        (status.url == url.trim_end_matches('/')).then_some(client)
+    }
+
+    async fn call<P: Serialize, T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<T, AppError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut request =
+            json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string();
+        request.push('\n');
+
+        let mut stream = self.stream.lock().await;
+        stream
+            .get_mut()
+            .write_all(request.as_bytes())
+            .await
+            .map_err(io_error)?;
+        let mut line = String::new();
+        if stream.read_line(&mut line).await.map_err(io_error)? == 0 {
+            return Err(io_error(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        let mut response: Value = serde_json::from_str(&line).map_err(|e| {
+            AppError::Service(ServiceError::ConfigService {
+                message: format!("Invalid response from daemon: {}", e),
+            })
+        })?;
+        if let Some(error) = response.get_mut("error") {
+            let wire = serde_json::from_value(error["data"].take()).unwrap_or(WireError::Other {
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+            return Err(wire.into());
+        }
+        serde_json::from_value(response["result"].take()).map_err(|e| {
+            AppError::Service(ServiceError::ConfigService {
+                message: format!("Invalid response from daemon: {}", e),
+            })
+        })
+    }
+}
+
+/// Every request is forwarded to the daemon.
+#[async_trait]
+impl MetabaseApi for DaemonClient {
+    async fn list_questions(
+        &self,
+        search: Option<&str>,
+        limit: Option<u32>,
+        collection: Option<&str>,
+    ) -> Result<Vec<Question>, AppError> {
+        let params = QueriesParams {
+            search: search.map(str::to_string),
+            limit,
+            collection: collection.map(str::to_string),
+        };
+        self.call("queries", params).await
+    }
+
+    async fn execute_question(
+        &self,
+        id: u32,
+        parameters: Option<HashMap<String, String>>,
+    ) -> Result<QueryResult, AppError> {
+        self.call("run", RunParams { id, parameters }).await
+    }
+
+    async fn list_collections(&self) -> Result<Vec<CollectionItem>, AppError> {
+        self.call("collections", Value::Null).await
+    }
+
+    async fn list_databases(&self) -> Result<Vec<Database>, AppError> {
+        self.call("databases", Value::Null).await
+    }
+
+    async fn list_tables(
+        &self,
+        database_id: u32,
+        schema: &str,
+    ) -> Result<Vec<TableInfo>, AppError> {
+        let params = TablesParams {
+            database_id,
+            schema: schema.to_string(),
+        };
+        self.call("tables", params).await
+    }
+
+    async fn get_table_metadata(&self, table_id: u32) -> Result<TableMetadata, AppError> {
+        self.call("describe", TableParams { table_id }).await
+    }
+
+    async fn get_current_user(&self) -> Result<CurrentUser, AppError> {
+        self.call("config.validate", Value::Null).await
+    }
+
+    async fn get_question(&self, id: u32) -> Result<QuestionDetail, AppError> {
+        self.call("question", QuestionParams { id }).await
+    }
+
+    async fn list_collection_items(
+        &self,
+        collection_id: Option<u32>,
+    ) -> Result<Vec<CollectionEntry>, AppError> {
+        self.call("collection.items", CollectionItemsParams { collection_id })
+            .await
+    }
+
+    async fn list_schemas(&self, database_id: u32) -> Result<Vec<String>, AppError> {
+        self.call("schemas", SchemasParams { database_id }).await
+    }
+
+    async fn run_native_query(
+        &self,
+        database_id: u32,
+        sql: &str,
+        max_rows: u32,
+    ) -> Result<QueryResult, AppError> {
+        let params = SqlParams {
+            database_id,
+            sql: sql.to_string(),
+            max_rows,
+        };
+        self.call("sql", params).await
+    }
+
+    async fn preview_table(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        limit: u32,
+    ) -> Result<QueryResult, AppError> {
+        let params = PreviewParams {
+            database_id,
+            table_id,
+            limit,
+        };
+        self.call("preview", params).await
+    }
+
+    async fn query_table_rows(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        field_id: u32,
+        value: &Value,
+        limit: u32,
+    ) -> Result<QueryResult, AppError> {
+        let params = TableRowsParams {
+            database_id,
+            table_id,
+            field_id,
+            value: value.clone(),
+            limit,
+        };
+        self.call("table.rows", params).await
+    }
+
+    async fn get_field(&self, field_id: u32) -> Result<Field, AppError> {
+        self.call("field", FieldParams { field_id }).await
+    }
+
+    async fn list_table_fks(&self, table_id: u32) -> Result<Vec<ForeignKey>, AppError> {
+        self.call("table.fks", TableParams { table_id }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mbr_core::error::ConfigError;
+    use mbr_core::storage::config::Config;
+    use mbr_testkit::{MockMetabase, query_result};
+    use tempfile::TempDir;
+
+    async fn mock_server() -> MockMetabase {
+        MockMetabase::builder()
+            .card(1, "Revenue")
+            .card_result(1, query_result(&["total"], vec![vec![json!(42)]]))
+            .database(Database {
+                id: 1,
+                name: "Sample".to_string(),
+                engine: Some("h2".to_string()),
+                description: None,
+                is_sample: true,
+                is_saved_questions: false,
+            })
+            .schema(
+                1,
+                "PUBLIC",
+                vec![TableInfo {
+                    id: 10,
+                    name: "ORDERS".to_string(),
+                    schema: Some("PUBLIC".to_string()),
+                    display_name: None,
+                    description: None,
+                    entity_type: None,
+                }],
+            )
+            .start()
+            .await
+    }
+
+    fn daemon(server: &MockMetabase) -> Daemon {
+        let dispatcher = Dispatcher::new(Config::default(), false, false, false);
+        Daemon::new(dispatcher, server.client().unwrap())
+    }
+
+    fn error_code(response: &Value) -> Option<i64> {
+        response["error"]["code"].as_i64()
+    }
+
+    #[test]
+    fn test_wire_error_round_trip() {
+        let errors = vec![
+            AppError::Api(ApiError::Unauthorized {
+                status: 401,
+                endpoint: "/api/card".to_string(),
+                server_message: "Unauthenticated".to_string(),
+            }),
+            AppError::Api(ApiError::Forbidden {
+                status: 403,
+                endpoint: "/api/card/1".to_string(),
+                server_message: "No access".to_string(),
+            }),
+            AppError::Api(ApiError::Timeout {
+                timeout_secs: 30,
+                endpoint: "/api/dataset".to_string(),
+            }),
+            AppError::Api(ApiError::Connection {
+                kind: ConnectionErrorKind::Refused,
+                endpoint: "/api/database".to_string(),
+                message: "Connection refused".to_string(),
+            }),
+            AppError::Api(ApiError::Http {
+                status: 500,
+                endpoint: "/api/collection".to_string(),
+                message: "boom".to_string(),
+            }),
+            AppError::Question(QuestionError::NotFound { id: 7 }),
+            AppError::Auth(AuthError::NotLoggedIn),
+            AppError::Auth(AuthError::SessionExpired),
+            AppError::Cli(CliError::InvalidArguments("bad".to_string())),
+            AppError::Cli(CliError::NotImplemented {
+                command: "nope".to_string(),
+            }),
+        ];
+        for error in errors {
+            // Through JSON, as the error travels over the socket
+            let wire = serde_json::to_value(WireError::from(&error)).unwrap();
+            let back: AppError = serde_json::from_value::<WireError>(wire).unwrap().into();
+            assert_eq!(back.to_string(), error.to_string());
+            assert_eq!(back.error_code(), error.error_code());
+        }
+
+        // Errors without a wire form keep their message
+        let other = AppError::Config(ConfigError::MissingField {
+            field: "url".to_string(),
+            field_type: "string".to_string(),
+        });
+        let back: AppError = WireError::from(&other).into();
+        assert!(back.to_string().contains(&other.display_friendly()));
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_errors() {
+        let server = mock_server().await;
+        let daemon = daemon(&server);
+
+        let response = daemon
+            .handle_message(json!({ "jsonrpc": "2.0", "id": 1, "method": "drop_tables" }))
+            .await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(error_code(&response), Some(METHOD_NOT_FOUND));
+
+        let response = daemon
+            .handle_message(json!({ "id": 2, "method": "run", "params": { "id": "one" } }))
+            .await;
+        assert_eq!(error_code(&response), Some(INVALID_PARAMS));
+        let response = daemon
+            .handle_message(json!({ "id": 3, "method": "describe" }))
+            .await;
+        assert_eq!(error_code(&response), Some(INVALID_PARAMS));
+
+        let response = daemon
+            .handle_message(json!({ "id": 4, "method": "run", "params": { "id": 99 } }))
+            .await;
+        assert_eq!(error_code(&response), Some(SERVER_ERROR));
+        assert_eq!(response["error"]["data"]["type"], "http");
+
+        let response = daemon
+            .handle_message(json!({ "id": 5, "method": "queries", "params": {} }))
+            .await;
+        assert_eq!(response["result"][0]["name"], "Revenue");
+    }
+
+    #[tokio::test]
+    async fn test_serve_and_forward() {
+        let server = mock_server().await;
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("daemon.sock");
+
+        let listener = bind(&path).await.unwrap();
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(bind(&path).await.is_err(), "a live daemon is not replaced");
+
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let serving = tokio::spawn(accept_until(
+            Arc::new(daemon(&server)),
+            listener,
+            async move {
+                let _ = stopped.await;
+            },
+        ));
+
+        assert!(
+            DaemonClient::connect(&path, "http://other.test")
+                .await
+                .is_none()
+        );
+        let client = DaemonClient::connect(&path, server.url())
+            .await
+            .expect("daemon serves the mock URL");
+
+        let questions = client.list_questions(None, None, None).await.unwrap();
+        assert_eq!(questions[0].id, 1);
+        let result = client.execute_question(1, None).await.unwrap();
+        assert_eq!(result.data.rows, vec![vec![json!(42)]]);
+        assert_eq!(client.list_schemas(1).await.unwrap(), ["PUBLIC"]);
+        assert_eq!(client.list_tables(1, "PUBLIC").await.unwrap()[0].id, 10);
+
+        // Metadata is served from the daemon's cache the second time
+        assert_eq!(client.list_databases().await.unwrap()[0].name, "Sample");
+        assert_eq!(client.list_databases().await.unwrap().len(), 1);
+        assert_eq!(server.request_count("GET", "/api/database"), 1);
+
+        let error = client.execute_question(99, None).await.unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::Api(ApiError::Http { status: 404, .. })
+        ));
+
+        let _ = stop.send(());
+        serving.await.unwrap().unwrap();
+    }
+}
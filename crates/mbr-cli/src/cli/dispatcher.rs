@@ -1,26 +1,50 @@
+use std::path::PathBuf;
+
 use crate::cli::command_handlers;
-use crate::cli::main_types::{Commands, ConfigCommands};
+#[cfg(unix)]
+use crate::cli::daemon::{self, DaemonClient};
+use crate::cli::main_types::{CacheCommands, Commands, ConfigCommands};
+use crate::cli::mcp;
 use crate::cli::output::{
-    ConfigSetOutput, LoginOutput, LogoutOutput, OutputFormat, SessionInfo, StatusOutput,
-    print_json, resolve_format,
+    CacheClearOutput, CacheStatsOutput, ConfigSetOutput, LoginOutput, LogoutOutput, OutputFormat,
+    SessionInfo, StatusOutput, SyncMetadataOutput, print_json, resolve_format,
 };
 use mbr_core::api::client::MetabaseClient;
+use mbr_core::api::traits::MetabaseApi;
+use mbr_core::api::transport;
 use mbr_core::error::{AppError, AuthError, CliError};
-use mbr_core::storage::config::Config;
+use mbr_core::storage::config::{Config, HttpConfig};
 use mbr_core::storage::credentials::{
-    Session, delete_session, get_credentials, load_session, now_iso8601, save_session,
+    Session, delete_session, get_credentials, iso8601, load_session, now_iso8601, save_session,
 };
+use mbr_core::storage::metadata_snapshot::MetadataSnapshot;
+use mbr_core::storage::response_cache::{self, ResponseCache};
 use mbr_core::utils::logging::print_verbose;
 
+#[derive(Clone)]
 pub struct Dispatcher {
     config: Config,
     verbose: bool,
     use_colors: bool,
     json_mode: bool,
+    /// Daemon socket (`--socket`), or None for the default path
+    socket: Option<PathBuf>,
+    /// Whether commands may be forwarded to a running daemon
+    use_daemon: bool,
+    /// Retry limit from `--retries`
+    retries: Option<u32>,
+    /// Request and query timeout in seconds from `--timeout` (0 = no limit)
+    timeout: Option<u64>,
+    /// Whether the on-disk response cache is used (`--no-cache` disables it)
+    use_cache: bool,
+    /// Refresh cached responses from the server (`--refresh`)
+    refresh: bool,
+    /// Answer from the metadata snapshot (`--offline`)
+    offline: bool,
 }
 
 impl Dispatcher {
-    fn log_verbose(&self, msg: &str) {
+    pub(crate) fn log_verbose(&self, msg: &str) {
         print_verbose(self.verbose, msg);
     }
 
@@ -30,6 +54,52 @@ impl Dispatcher {
             verbose,
             use_colors,
             json_mode,
+            socket: None,
+            use_daemon: true,
+            retries: None,
+            timeout: None,
+            use_cache: true,
+            refresh: false,
+            offline: false,
+        }
+    }
+
+    /// Override the configured retry limit.
+    pub fn with_retries(mut self, retries: Option<u32>) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Override the configured read and query timeouts.
+    pub fn with_timeout(mut self, timeout: Option<u64>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Enable or bypass the response cache.
+    pub fn with_cache_mode(mut self, use_cache: bool, refresh: bool) -> Self {
+        self.use_cache = use_cache;
+        self.refresh = refresh;
+        self
+    }
+
+    /// Answer from the metadata snapshot instead of the server.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Set the daemon socket and whether commands are forwarded to it.
+    pub fn with_daemon(mut self, socket: Option<PathBuf>, use_daemon: bool) -> Self {
+        self.socket = socket;
+        self.use_daemon = use_daemon;
+        self
+    }
+
+    fn socket_path(&self) -> Result<PathBuf, AppError> {
+        match self.socket {
+            Some(ref path) => Ok(path.clone()),
+            None => Ok(Config::daemon_socket_path()?),
         }
     }
 
@@ -45,20 +115,177 @@ impl Dispatcher {
     }
 
     /// Create an authenticated MetabaseClient from stored session.
-    fn create_client(&self) -> Result<MetabaseClient, AppError> {
+    pub(crate) fn create_client(&self) -> Result<MetabaseClient, AppError> {
         let url = self.get_url()?;
         if let Some(session) = load_session() {
             if session.url == url {
-                self.log_verbose("Creating client with stored session token");
-                return Ok(MetabaseClient::with_session_token(
-                    url,
-                    session.session_token,
-                )?);
+                self.log_verbose("Creating client with stored session");
+                return self.configure_client(MetabaseClient::from_session(&session)?);
             }
             self.log_verbose("Stored session URL does not match current URL, ignoring session");
         }
         self.log_verbose("No valid session found, creating unauthenticated client");
-        Ok(MetabaseClient::new(url)?)
+        self.configure_client(MetabaseClient::new(url)?)
+    }
+
+    /// HTTP settings from config.toml with `--timeout` applied.
+    fn http_config(&self) -> HttpConfig {
+        let mut http = self.config.http.clone();
+        if let Some(timeout) = self.timeout {
+            http.read_timeout = Some(timeout);
+            http.query_timeout = Some(timeout);
+        }
+        http
+    }
+
+    /// Apply proxy, TLS and timeout settings, the retry policy (config.toml, then
+    /// `--retries`), the response cache and verbosity.
+    pub(crate) fn configure_client(
+        &self,
+        client: MetabaseClient,
+    ) -> Result<MetabaseClient, AppError> {
+        let mut retry = self.config.http.retry_config()?;
+        if let Some(retries) = self.retries {
+            retry.max_retries = retries;
+        }
+        let mut client = client
+            .with_http(&self.http_config())?
+            .with_retry(retry)
+            .with_verbose(self.verbose);
+        let identity = Self::cache_identity(&client.base_url, client.api_key.as_deref());
+        if self.use_cache
+            && let Some(cache) = self
+                .config
+                .cache
+                .response_cache(&client.base_url, &identity)
+        {
+            client = client.with_cache(cache.with_refresh(self.refresh));
+        }
+        Ok(client)
+    }
+
+    /// Cache directory name of the credentials used on `url`: the API key,
+    /// else the user of the stored session.
+    fn cache_identity(url: &str, api_key: Option<&str>) -> String {
+        let session = load_session().filter(|s| s.url == url);
+        let username = session.as_ref().map(|s| s.username.as_str());
+        let api_key = api_key.or(session.as_ref().and_then(|s| s.api_key.as_deref()));
+        response_cache::identity_name(username, api_key)
+    }
+
+    /// Drop the cached responses of every account on `url`, so that a new
+    /// session never sees what another one was allowed to read.
+    fn clear_response_cache(&self, url: &str) {
+        if let Err(e) = ResponseCache::clear_profile(url) {
+            eprintln!("Warning: failed to clear the response cache: {}", e);
+        }
+    }
+
+    /// Forward to a daemon serving the configured URL if one is running,
+    /// otherwise request Metabase directly.
+    async fn create_backend(&self) -> Result<Box<dyn MetabaseApi>, AppError> {
+        if self.offline {
+            return self.offline_backend();
+        }
+        // The daemon has its own caches, timeouts and transport, so bypassing
+        // the cache, setting a timeout or recording a cassette bypasses it too
+        #[cfg(unix)]
+        if self.use_daemon
+            && self.use_cache
+            && !self.refresh
+            && self.timeout.is_none()
+            && !transport::cassette_active()
+            && let Ok(path) = self.socket_path()
+            && path.exists()
+            && let Some(daemon) = DaemonClient::connect(&path, &self.get_url()?).await
+        {
+            self.log_verbose(&format!("Forwarding to daemon on {}", path.display()));
+            return Ok(Box::new(daemon));
+        }
+        Ok(Box::new(self.create_client()?))
+    }
+
+    /// The metadata snapshot of the configured URL, announcing its age.
+    fn offline_backend(&self) -> Result<Box<dyn MetabaseApi>, AppError> {
+        let url = self.get_url()?;
+        let snapshot = MetadataSnapshot::load(&url)?.ok_or_else(|| {
+            AppError::Cli(CliError::InvalidArguments(format!(
+                "No metadata snapshot for {}. Run 'mbr-cli sync-metadata' while connected",
+                url
+            )))
+        })?;
+        eprintln!("Offline: metadata snapshot {}", snapshot.stale_since());
+        Ok(Box::new(snapshot))
+    }
+
+    /// Handle the `mbr sync-metadata` command.
+    async fn handle_sync_metadata(&self, format: OutputFormat) -> Result<(), AppError> {
+        if self.offline {
+            return Err(AppError::Cli(CliError::InvalidArguments(
+                "sync-metadata needs a connection to Metabase; run without --offline".to_string(),
+            )));
+        }
+        // Take the snapshot from the server, not from cached responses
+        let dispatcher = self.clone().with_cache_mode(self.use_cache, true);
+        let progress = |line: &str| eprintln!("{}", line);
+        let client = dispatcher.create_client()?;
+        let snapshot = match MetadataSnapshot::sync(&client, &client.base_url, progress).await {
+            Err(ref e) if Self::is_unauthorized(e) => {
+                let client = dispatcher
+                    .try_auto_relogin()
+                    .await
+                    .ok_or(AppError::Auth(AuthError::SessionExpired))?;
+                MetadataSnapshot::sync(&client, &client.base_url, progress).await?
+            }
+            other => other?,
+        };
+        let path = snapshot.save()?;
+
+        let output = SyncMetadataOutput {
+            url: snapshot.url.clone(),
+            path: path.display().to_string(),
+            synced_at: iso8601(snapshot.synced_at),
+            databases: snapshot.databases.len(),
+            tables: snapshot.table_metadata.len(),
+            questions: snapshot.questions.len(),
+            collections: snapshot.collections.len(),
+            warnings: snapshot.warnings.clone(),
+        };
+        match resolve_format(self.json_mode, format) {
+            OutputFormat::Json => print_json(&output),
+            _ => {
+                eprintln!(
+                    "Saved metadata snapshot of {} to {} ({} databases, {} tables, {} questions, {} collections)",
+                    output.url,
+                    output.path,
+                    output.databases,
+                    output.tables,
+                    output.questions,
+                    output.collections
+                );
+                if !output.warnings.is_empty() {
+                    eprintln!(
+                        "{} items were skipped; see the warnings above",
+                        output.warnings.len()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle the `mbr serve` command.
+    async fn handle_serve(&self) -> Result<(), AppError> {
+        #[cfg(unix)]
+        {
+            daemon::serve(self, &self.socket_path()?).await
+        }
+        #[cfg(not(unix))]
+        {
+            Err(AppError::Cli(CliError::NotImplemented {
+                command: "serve (Unix sockets are not available on this platform)".to_string(),
+            }))
+        }
     }
 
     /// Handle the `mbr login` command.
@@ -77,13 +304,14 @@ impl Dispatcher {
 
         eprintln!("Logging in to {}...", url);
 
-        let token = MetabaseClient::login(&url, &username, &password).await?;
+        let token = MetabaseClient::login(&url, &username, &password, &self.http_config()).await?;
 
         let session = Session {
             session_token: token,
             url: url.clone(),
             username: username.clone(),
             created_at: now_iso8601(),
+            api_key: None,
         };
 
         save_session(&session).map_err(|e| {
@@ -91,6 +319,7 @@ impl Dispatcher {
                 message: format!("Failed to save session: {}", e),
             })
         })?;
+        self.clear_response_cache(&url);
 
         if self.json_mode {
             print_json(&LoginOutput {
@@ -106,9 +335,16 @@ impl Dispatcher {
 
     /// Handle the `mbr logout` command.
     async fn handle_logout(&self) -> Result<(), AppError> {
-        if let Some(session) = load_session()
+        let session = load_session();
+        if let Some(ref session) = session {
+            self.clear_response_cache(&session.url);
+        }
+        // API keys have no server-side session to invalidate
+        if let Some(session) = session
+            && session.api_key.is_none()
             && let Ok(client) =
                 MetabaseClient::with_session_token(session.url, session.session_token)
+            && let Ok(client) = client.with_http(&self.http_config())
         {
             let _ = client.logout().await;
         }
@@ -134,6 +370,62 @@ impl Dispatcher {
         }
     }
 
+    /// Response cache of the configured URL.
+    fn response_cache(&self) -> Result<ResponseCache, AppError> {
+        let url = self.get_url()?;
+        let identity = Self::cache_identity(&url, None);
+        self.config
+            .cache
+            .response_cache(&url, &identity)
+            .ok_or_else(|| {
+                AppError::Cli(CliError::InvalidArguments(
+                    "Response cache is disabled or the home directory is unknown".to_string(),
+                ))
+            })
+    }
+
+    /// Handle the `mbr cache stats` command.
+    fn handle_cache_stats(&self, format: OutputFormat) -> Result<(), AppError> {
+        let cache = self.response_cache()?;
+        let output = CacheStatsOutput {
+            dir: cache.dir().display().to_string(),
+            size_bytes: cache.size_bytes(),
+            resources: cache.stats(),
+        };
+        match resolve_format(self.json_mode, format) {
+            OutputFormat::Json => print_json(&output),
+            _ => {
+                println!("Cache: {}", output.dir);
+                println!("Size: {} bytes", output.size_bytes);
+                println!();
+                println!(
+                    "{:<12} {:>7} {:>7} {:>7}",
+                    "Resource", "Total", "Fresh", "Expired"
+                );
+                for (resource, stats) in &output.resources {
+                    println!(
+                        "{:<12} {:>7} {:>7} {:>7}",
+                        resource, stats.total_entries, stats.active_entries, stats.expired_entries
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle the `mbr cache clear` command.
+    fn handle_cache_clear(&self, format: OutputFormat) -> Result<(), AppError> {
+        let removed = self.response_cache()?.clear()?;
+        match resolve_format(self.json_mode, format) {
+            OutputFormat::Json => print_json(&CacheClearOutput {
+                success: true,
+                removed,
+            }),
+            _ => eprintln!("Removed {} cached responses", removed),
+        }
+        Ok(())
+    }
+
     /// Handle the `mbr status` command.
     fn handle_status(&self, format: OutputFormat) -> Result<(), AppError> {
         let format = resolve_format(self.json_mode, format);
@@ -174,12 +466,12 @@ impl Dispatcher {
     }
 
     /// Attempt auto re-login using environment variables.
-    async fn try_auto_relogin(&self) -> Option<MetabaseClient> {
+    pub(crate) async fn try_auto_relogin(&self) -> Option<MetabaseClient> {
         let (username, password) = get_credentials()?;
         let url = self.get_url().ok()?;
         self.log_verbose("Session expired, attempting auto re-login...");
         eprintln!("Session expired, re-authenticating...");
-        let token = MetabaseClient::login(&url, &username, &password)
+        let token = MetabaseClient::login(&url, &username, &password, &self.http_config())
             .await
             .ok()?;
         let session = Session {
@@ -187,13 +479,15 @@ impl Dispatcher {
             url: url.clone(),
             username,
             created_at: now_iso8601(),
+            api_key: None,
         };
         save_session(&session).ok()?;
         eprintln!("Re-authenticated successfully");
-        MetabaseClient::with_session_token(url, token).ok()
+        let client = MetabaseClient::with_session_token(url, token).ok()?;
+        self.configure_client(client).ok()
     }
 
-    fn is_unauthorized(err: &AppError) -> bool {
+    pub(crate) fn is_unauthorized(err: &AppError) -> bool {
         matches!(
             err,
             AppError::Api(mbr_core::error::ApiError::Unauthorized { .. })
@@ -203,14 +497,14 @@ impl Dispatcher {
     /// Run a handler with auto-relogin on 401.
     async fn with_auto_relogin<F, Fut>(&self, handler: F) -> Result<(), AppError>
     where
-        F: Fn(MetabaseClient) -> Fut,
+        F: Fn(Box<dyn MetabaseApi>) -> Fut,
         Fut: std::future::Future<Output = Result<(), AppError>>,
     {
-        let client = self.create_client()?;
-        match handler(client).await {
+        let backend = self.create_backend().await?;
+        match handler(backend).await {
             Err(ref e) if Self::is_unauthorized(e) => {
                 if let Some(new_client) = self.try_auto_relogin().await {
-                    handler(new_client).await
+                    handler(Box::new(new_client)).await
                 } else {
                     Err(AppError::Auth(AuthError::SessionExpired))
                 }
@@ -228,6 +522,12 @@ impl Dispatcher {
             Commands::Login => self.handle_login().await,
             Commands::Logout => self.handle_logout().await,
             Commands::Status { format } => self.handle_status(format),
+            Commands::Mcp => mcp::serve(self).await,
+            Commands::Serve => self.handle_serve().await,
+            Commands::Cache { command } => match command {
+                CacheCommands::Stats { format } => self.handle_cache_stats(format),
+                CacheCommands::Clear { format } => self.handle_cache_clear(format),
+            },
 
             Commands::Config { command } => match command {
                 ConfigCommands::SetUrl { url, format } => {
@@ -273,6 +573,7 @@ impl Dispatcher {
                 no_fullscreen,
                 offset,
                 page_size,
+                profile,
             } => {
                 let fmt = resolve_format(self.json_mode, format);
                 let use_colors = self.use_colors;
@@ -289,6 +590,7 @@ impl Dispatcher {
                             no_fullscreen,
                             offset,
                             page_size,
+                            profile,
                             use_colors,
                         )
                         .await
@@ -315,6 +617,17 @@ impl Dispatcher {
                 .await
             }
 
+            Commands::Describe { table_id, format } => {
+                let fmt = resolve_format(self.json_mode, format);
+                let use_colors = self.use_colors;
+                self.with_auto_relogin(|client| async move {
+                    command_handlers::handle_describe(&client, table_id, fmt, use_colors).await
+                })
+                .await
+            }
+
+            Commands::SyncMetadata { format } => self.handle_sync_metadata(format).await,
+
             Commands::Tables {
                 database_id,
                 schema,
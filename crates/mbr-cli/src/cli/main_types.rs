@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::cli::output::OutputFormat;
@@ -42,11 +44,14 @@ impl ColorChoice {
   mbr-cli databases                    # List all databases
   mbr-cli tables 1 public              # List tables in database schema
   mbr-cli -j queries                   # JSON output for any command
+  mbr-cli mcp                          # MCP server over stdio for AI agents
+  mbr-cli serve                        # Daemon that later commands forward to
 
 Environment Variables:
   MBR_USERNAME  Metabase username (for non-interactive login)
   MBR_PASSWORD  Metabase password (for non-interactive login)
-  MBR_URL       Metabase server URL")]
+  MBR_URL       Metabase server URL
+  MBR_SOCKET    Daemon socket path (see 'serve')")]
 pub struct Cli {
     /// Output all results as JSON (overrides --format)
     #[arg(short = 'j', long, global = true)]
@@ -64,6 +69,37 @@ pub struct Cli {
     #[arg(long, global = true, default_value = "auto", value_enum)]
     pub color: ColorChoice,
 
+    /// Unix socket of the daemon (default: ~/.config/mbr-cli/daemon.sock)
+    #[arg(long, global = true, env = "MBR_SOCKET")]
+    pub socket: Option<PathBuf>,
+
+    /// Do not forward commands to a running daemon
+    #[arg(long, global = true)]
+    pub no_daemon: bool,
+
+    /// Maximum retries of a request that failed transiently (overrides
+    /// `[http] retries` in config.toml; 0 disables retries)
+    #[arg(long, global = true)]
+    pub retries: Option<u32>,
+
+    /// Timeout in seconds for requests and queries (overrides `[http]
+    /// read_timeout` and `query_timeout`; 0 waits indefinitely)
+    #[arg(long, global = true, value_name = "SECS", env = "MBR_TIMEOUT")]
+    pub timeout: Option<u64>,
+
+    /// Do not read or write the on-disk response cache
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Ignore cached responses and refresh them from the server
+    #[arg(long, global = true, conflicts_with = "no_cache")]
+    pub refresh: bool,
+
+    /// Answer metadata commands (databases, tables, describe, queries,
+    /// collections) from the snapshot taken by 'sync-metadata'
+    #[arg(long, global = true, env = "MBR_OFFLINE")]
+    pub offline: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -122,6 +158,11 @@ pub enum Commands {
         /// Number of rows per page in interactive mode
         #[arg(long, default_value = "20")]
         page_size: usize,
+
+        /// Print a profile of each column (nulls, distinct, top values,
+        /// distribution) over all returned rows instead of the rows
+        #[arg(long)]
+        profile: bool,
     },
 
     /// List all collections
@@ -154,6 +195,33 @@ pub enum Commands {
         format: OutputFormat,
     },
 
+    /// Show the fields of a table
+    #[command(after_help = "Examples:
+  mbr-cli describe 12                   # Columns, types and foreign keys of table 12
+  mbr-cli --offline describe 12         # Look it up in the metadata snapshot")]
+    Describe {
+        /// Table ID
+        table_id: u32,
+
+        /// Output format
+        #[arg(short, long, default_value = "table", value_enum)]
+        format: OutputFormat,
+    },
+
+    /// Download databases, schemas, tables, fields, questions and
+    /// collections for use with --offline
+    #[command(
+        name = "sync-metadata",
+        after_help = "Examples:
+  mbr-cli sync-metadata                 # Take a snapshot while connected
+  mbr-cli --offline tables 1 public     # Browse it later without a connection"
+    )]
+    SyncMetadata {
+        /// Output format
+        #[arg(short, long, default_value = "table", value_enum)]
+        format: OutputFormat,
+    },
+
     /// Show current configuration and session status
     Status {
         /// Output format
@@ -167,6 +235,30 @@ pub enum Commands {
     /// Logout from Metabase (clear session)
     Logout,
 
+    /// Run an MCP (Model Context Protocol) server over stdio for AI agents
+    Mcp,
+
+    /// Run a daemon that keeps the session and metadata cache warm and
+    /// serves commands on a Unix socket; other invocations forward to it
+    #[command(
+        after_help = "Forwarded: queries, run, collections, databases, tables, describe,
+config validate. Always run directly: login, logout, config set-url,
+sync-metadata, mcp.
+
+Examples:
+  mbr-cli serve                         # Listen on ~/.config/mbr-cli/daemon.sock
+  mbr-cli serve --socket /tmp/mbr.sock  # Listen on a custom socket
+  MBR_SOCKET=/tmp/mbr.sock mbr-cli queries
+  mbr-cli --no-daemon queries           # Bypass the daemon"
+    )]
+    Serve,
+
+    /// Inspect or clear the on-disk response cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
     /// Configuration management
     #[command(visible_alias = "cfg")]
     Config {
@@ -175,6 +267,28 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum CacheCommands {
+    /// Show cached entries per resource for the configured URL
+    #[command(after_help = "Examples:
+  mbr-cli cache stats
+  mbr-cli cache stats --format json")]
+    Stats {
+        /// Output format
+        #[arg(short, long, default_value = "table", value_enum)]
+        format: OutputFormat,
+    },
+
+    /// Remove every cached response for the configured URL
+    #[command(after_help = "Examples:
+  mbr-cli cache clear")]
+    Clear {
+        /// Output format
+        #[arg(short, long, default_value = "table", value_enum)]
+        format: OutputFormat,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommands {
     /// Set Metabase server URL
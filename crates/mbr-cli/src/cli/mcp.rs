@@ -0,0 +1,661 @@
+//! MCP (Model Context Protocol) server for `mbr-cli mcp`.
+//!
+//! Speaks JSON-RPC 2.0 over stdio, one message per line, so that an AI agent
+//! keeps a single authenticated process instead of spawning `mbr-cli -j` for
+//! every step. Status and verbose messages go to stderr; stdout carries only
+//! protocol messages.
+//!
+//! Tools: `search_questions`, `list_collections`, `list_databases`,
+//! `list_tables`, `describe_table`, `run_question`, `run_sql`.
+//! Resources: question definitions (`metabase://question/{id}`) and table
+//! schemas (`metabase://table/{id}`).
+
+use std::collections::HashMap;
+
+use mbr_core::api::client::MetabaseClient;
+use mbr_core::api::models::QueryResult;
+use mbr_core::api::traits::MetabaseApi;
+use mbr_core::error::{AppError, CliError};
+use mbr_core::utils::error_helpers::convert_io_to_display_error;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::cli::dispatcher::Dispatcher;
+use crate::cli::output::JsonErrorOutput;
+
+/// Protocol revisions this server understands, newest first.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Rows returned by the query tools unless `max_rows` is given.
+const DEFAULT_MAX_ROWS: u32 = 100;
+
+/// Upper bound for `max_rows`, to keep results within an agent's context.
+const MAX_ROWS_LIMIT: u32 = 5000;
+
+/// Default and maximum number of questions returned by `search_questions`.
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+const MAX_SEARCH_LIMIT: u32 = 200;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// JSON-RPC error returned to the client.
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<AppError> for RpcError {
+    fn from(error: AppError) -> Self {
+        let code = match error {
+            AppError::Cli(CliError::InvalidArguments(_)) => INVALID_PARAMS,
+            _ => INTERNAL_ERROR,
+        };
+        RpcError::new(code, error.display_friendly())
+    }
+}
+
+/// Run the MCP server until stdin is closed.
+pub async fn serve(dispatcher: &Dispatcher) -> Result<(), AppError> {
+    let mut server = McpServer {
+        dispatcher,
+        client: dispatcher.create_client()?,
+    };
+    dispatcher.log_verbose("MCP server listening on stdio");
+
+    let io_error = |e| AppError::Display(convert_io_to_display_error(e, "MCP stdio"));
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await.map_err(io_error)? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle_message(message).await,
+            Err(e) => Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("Parse error: {}", e)),
+            )),
+        };
+        if let Some(response) = response {
+            let mut out = response.to_string();
+            out.push('\n');
+            stdout.write_all(out.as_bytes()).await.map_err(io_error)?;
+            stdout.flush().await.map_err(io_error)?;
+        }
+    }
+    Ok(())
+}
+
+struct McpServer<'a> {
+    dispatcher: &'a Dispatcher,
+    client: MetabaseClient,
+}
+
+impl McpServer<'_> {
+    /// Handle one message; notifications get no response.
+    async fn handle_message(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "Invalid request: missing method"),
+            ));
+        };
+        // Notifications (initialized, cancelled, ...) need no action
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params).await,
+            "resources/list" => self.list_resources().await,
+            "resources/templates/list" => Ok(json!({ "resourceTemplates": resource_templates() })),
+            "resources/read" => self.read_resource(&params).await,
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    /// `tools/call`: failures of the tool itself are reported in the result
+    /// (`isError`) so that the agent can read them.
+    async fn call_tool(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+        if !tool_definitions()
+            .iter()
+            .any(|tool| tool["name"].as_str() == Some(name))
+        {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown tool: {}", name),
+            ));
+        }
+        let args = params.get("arguments").cloned().unwrap_or(json!({}));
+
+        let mut outcome = run_tool(&self.client, name, &args).await;
+        if matches!(outcome, Err(ref e) if Dispatcher::is_unauthorized(e))
+            && let Some(client) = self.dispatcher.try_auto_relogin().await
+        {
+            self.client = client;
+            outcome = run_tool(&self.client, name, &args).await;
+        }
+
+        let (text, is_error) = match outcome {
+            Ok(value) => (value, false),
+            Err(error) => (json!(JsonErrorOutput::new(&error)), true),
+        };
+        Ok(json!({
+            "content": [{
+                "type": "text",
+                "text": serde_json::to_string_pretty(&text).unwrap_or_default(),
+            }],
+            "isError": is_error,
+        }))
+    }
+
+    /// `resources/list`: every saved question (tables are reached through
+    /// the `metabase://table/{id}` template).
+    async fn list_resources(&mut self) -> Result<Value, RpcError> {
+        let mut outcome = self.client.list_questions(None, None, None).await;
+        if matches!(outcome, Err(ref e) if Dispatcher::is_unauthorized(e))
+            && let Some(client) = self.dispatcher.try_auto_relogin().await
+        {
+            self.client = client;
+            outcome = self.client.list_questions(None, None, None).await;
+        }
+        let resources: Vec<Value> = outcome?
+            .into_iter()
+            .map(|q| {
+                json!({
+                    "uri": format!("metabase://question/{}", q.id),
+                    "name": q.name,
+                    "description": q.description,
+                    "mimeType": "application/json",
+                })
+            })
+            .collect();
+        Ok(json!({ "resources": resources }))
+    }
+
+    /// `resources/read`: a question definition or a table schema.
+    async fn read_resource(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let uri = params
+            .get("uri")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing resource uri"))?;
+        let resource = parse_resource_uri(uri)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown resource: {}", uri)))?;
+
+        let mut outcome = read_resource(&self.client, resource).await;
+        if matches!(outcome, Err(ref e) if Dispatcher::is_unauthorized(e))
+            && let Some(client) = self.dispatcher.try_auto_relogin().await
+        {
+            self.client = client;
+            outcome = read_resource(&self.client, resource).await;
+        }
+        let value = outcome?;
+        Ok(json!({
+            "contents": [{
+                "uri": uri,
+                "mimeType": "application/json",
+                "text": serde_json::to_string_pretty(&value).unwrap_or_default(),
+            }]
+        }))
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// `initialize`: agree on the client's protocol revision if supported.
+fn initialize_result(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = PROTOCOL_VERSIONS
+        .iter()
+        .find(|&&v| Some(v) == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {}, "resources": {} },
+        "serverInfo": { "name": "mbr-cli", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Metabase access: search and run saved questions, browse collections, \
+            databases and tables, and run SQL with a row cap. SQL is sent to Metabase as \
+            written, so what it may change depends on the database connection.",
+    })
+}
+
+/// Tool definitions with JSON schemas for their input.
+fn tool_definitions() -> Vec<Value> {
+    let max_rows = json!({
+        "type": "integer",
+        "minimum": 1,
+        "maximum": MAX_ROWS_LIMIT,
+        "default": DEFAULT_MAX_ROWS,
+        "description": "Maximum number of rows to return",
+    });
+    vec![
+        json!({
+            "name": "search_questions",
+            "description": "Search saved questions (cards) by name or description.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Search text" },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": MAX_SEARCH_LIMIT,
+                        "default": DEFAULT_SEARCH_LIMIT,
+                    },
+                },
+                "required": ["query"],
+                "additionalProperties": false,
+            },
+        }),
+        json!({
+            "name": "list_collections",
+            "description": "List collections (folders of questions and dashboards).",
+            "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false },
+        }),
+        json!({
+            "name": "list_databases",
+            "description": "List the databases connected to Metabase.",
+            "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false },
+        }),
+        json!({
+            "name": "list_tables",
+            "description": "List the tables of a database schema.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "database_id": { "type": "integer", "minimum": 0 },
+                    "schema": { "type": "string", "default": "public" },
+                },
+                "required": ["database_id"],
+                "additionalProperties": false,
+            },
+        }),
+        json!({
+            "name": "describe_table",
+            "description": "Describe a table: its columns with types, keys and foreign-key targets.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "table_id": { "type": "integer", "minimum": 0 } },
+                "required": ["table_id"],
+                "additionalProperties": false,
+            },
+        }),
+        json!({
+            "name": "run_question",
+            "description": "Run a saved question, optionally with parameter values.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "question_id": { "type": "integer", "minimum": 0 },
+                    "parameters": {
+                        "type": "object",
+                        "description": "Parameter values by name",
+                        "additionalProperties": { "type": ["string", "number", "boolean"] },
+                    },
+                    "max_rows": max_rows,
+                },
+                "required": ["question_id"],
+                "additionalProperties": false,
+            },
+        }),
+        json!({
+            "name": "run_sql",
+            "description": "Run a native SQL query against a database. The server stops after max_rows rows.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "database_id": { "type": "integer", "minimum": 0 },
+                    "query": { "type": "string", "description": "SQL query" },
+                    "max_rows": max_rows,
+                },
+                "required": ["database_id", "query"],
+                "additionalProperties": false,
+            },
+        }),
+    ]
+}
+
+fn resource_templates() -> Vec<Value> {
+    vec![
+        json!({
+            "uriTemplate": "metabase://question/{id}",
+            "name": "Question definition",
+            "description": "Query, database and parameters of a saved question",
+            "mimeType": "application/json",
+        }),
+        json!({
+            "uriTemplate": "metabase://table/{id}",
+            "name": "Table schema",
+            "description": "Columns of a table with types, keys and foreign-key targets",
+            "mimeType": "application/json",
+        }),
+    ]
+}
+
+/// Resources that can be read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resource {
+    Question(u32),
+    Table(u32),
+}
+
+fn parse_resource_uri(uri: &str) -> Option<Resource> {
+    let path = uri.strip_prefix("metabase://")?;
+    let (kind, id) = path.split_once('/')?;
+    let id = id.parse().ok()?;
+    match kind {
+        "question" => Some(Resource::Question(id)),
+        "table" => Some(Resource::Table(id)),
+        _ => None,
+    }
+}
+
+async fn read_resource(client: &dyn MetabaseApi, resource: Resource) -> Result<Value, AppError> {
+    match resource {
+        Resource::Question(id) => Ok(json!(client.get_question(id).await?)),
+        Resource::Table(id) => Ok(json!(client.get_table_metadata(id).await?)),
+    }
+}
+
+async fn run_tool(client: &dyn MetabaseApi, name: &str, args: &Value) -> Result<Value, AppError> {
+    match name {
+        "search_questions" => {
+            let query = required_str(args, "query")?;
+            let limit = optional_u32(args, "limit", DEFAULT_SEARCH_LIMIT, MAX_SEARCH_LIMIT)?;
+            Ok(json!(
+                client
+                    .list_questions(Some(query), Some(limit), None)
+                    .await?
+            ))
+        }
+        "list_collections" => Ok(json!(client.list_collections().await?)),
+        "list_databases" => Ok(json!(client.list_databases().await?)),
+        "list_tables" => {
+            let database_id = required_u32(args, "database_id")?;
+            let schema = args
+                .get("schema")
+                .and_then(Value::as_str)
+                .unwrap_or("public");
+            Ok(json!(client.list_tables(database_id, schema).await?))
+        }
+        "describe_table" => {
+            let table_id = required_u32(args, "table_id")?;
+            Ok(json!(client.get_table_metadata(table_id).await?))
+        }
+        "run_question" => {
+            let question_id = required_u32(args, "question_id")?;
+            let max_rows = optional_u32(args, "max_rows", DEFAULT_MAX_ROWS, MAX_ROWS_LIMIT)?;
+            let parameters = question_parameters(args)?;
+            let result = client.execute_question(question_id, parameters).await?;
+            Ok(query_output(result, max_rows))
+        }
+        "run_sql" => {
+            let database_id = required_u32(args, "database_id")?;
+            let query = required_str(args, "query")?;
+            let max_rows = optional_u32(args, "max_rows", DEFAULT_MAX_ROWS, MAX_ROWS_LIMIT)?;
+            // One extra row tells whether the result was cut off
+            let result = client
+                .run_native_query(database_id, query, max_rows + 1)
+                .await?;
+            Ok(query_output(result, max_rows))
+        }
+        _ => Err(invalid_argument(format!("Unknown tool: {}", name))),
+    }
+}
+
+/// Query rows with their columns, cut off after `max_rows`.
+fn query_output(result: QueryResult, max_rows: u32) -> Value {
+    let columns: Vec<Value> = result
+        .data
+        .cols
+        .iter()
+        .map(
+            |c| json!({ "name": c.name, "display_name": c.display_name, "base_type": c.base_type }),
+        )
+        .collect();
+    let mut rows = result.data.rows;
+    let truncated = rows.len() > max_rows as usize;
+    rows.truncate(max_rows as usize);
+    json!({
+        "columns": columns,
+        "row_count": rows.len(),
+        "truncated": truncated,
+        "rows": rows,
+    })
+}
+
+/// Parameter values of `run_question`, as the `--param key=value` pairs of `run`.
+fn question_parameters(args: &Value) -> Result<Option<HashMap<String, String>>, AppError> {
+    let Some(parameters) = args.get("parameters").filter(|p| !p.is_null()) else {
+        return Ok(None);
+    };
+    let object = parameters
+        .as_object()
+        .ok_or_else(|| invalid_argument("'parameters' must be an object"))?;
+    object
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                _ => {
+                    return Err(invalid_argument(format!(
+                        "Parameter '{}' must be a string, number or boolean",
+                        name
+                    )));
+                }
+            };
+            Ok((name.clone(), value))
+        })
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map(|map| Some(map).filter(|m| !m.is_empty()))
+}
+
+fn invalid_argument(message: impl Into<String>) -> AppError {
+    AppError::Cli(CliError::InvalidArguments(message.into()))
+}
+
+fn required_str<'a>(args: &'a Value, name: &str) -> Result<&'a str, AppError> {
+    args.get(name)
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| invalid_argument(format!("'{}' is required (string)", name)))
+}
+
+fn required_u32(args: &Value, name: &str) -> Result<u32, AppError> {
+    args.get(name)
+        .and_then(Value::as_u64)
+        .and_then(|n| u32::try_from(n).ok())
+        .ok_or_else(|| invalid_argument(format!("'{}' is required (non-negative integer)", name)))
+}
+
+fn optional_u32(args: &Value, name: &str, default: u32, max: u32) -> Result<u32, AppError> {
+    match args.get(name).filter(|v| !v.is_null()) {
+        None => Ok(default),
+        Some(value) => value
+            .as_u64()
+            .filter(|n| (1..=max as u64).contains(n))
+            .map(|n| n as u32)
+            .ok_or_else(|| {
+                invalid_argument(format!("'{}' must be an integer from 1 to {}", name, max))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mbr_core::api::models::{Column, QueryData};
+    use mbr_core::storage::config::Config;
+
+    async fn handle(message: Value) -> Option<Value> {
+        let dispatcher = Dispatcher::new(Config::default(), false, false, false);
+        let mut server = McpServer {
+            dispatcher: &dispatcher,
+            client: MetabaseClient::new("http://127.0.0.1:9".to_string()).unwrap(),
+        };
+        server.handle_message(message).await
+    }
+
+    fn request(method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+    }
+
+    #[tokio::test]
+    async fn test_initialize_negotiates_version() {
+        let response = handle(request(
+            "initialize",
+            json!({ "protocolVersion": "2025-03-26" }),
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "mbr-cli");
+
+        // Unknown revisions get the newest one we support
+        let response = handle(request(
+            "initialize",
+            json!({ "protocolVersion": "1999-01-01" }),
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_errors_and_notifications() {
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle(notification).await.is_none());
+
+        let response = handle(request("does/not/exist", Value::Null))
+            .await
+            .unwrap();
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = handle(json!({ "jsonrpc": "2.0", "id": 7 })).await.unwrap();
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+        let response = handle(request("tools/call", json!({ "name": "drop_table" })))
+            .await
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = handle(request("ping", Value::Null)).await.unwrap();
+        assert_eq!(response["result"], json!({}));
+    }
+
+    #[test]
+    fn test_optional_u32() {
+        assert_eq!(optional_u32(&json!({}), "limit", 20, 200).unwrap(), 20);
+        assert_eq!(
+            optional_u32(&json!({ "limit": null }), "limit", 20, 200).unwrap(),
+            20
+        );
+        assert_eq!(
+            optional_u32(&json!({ "limit": 200 }), "limit", 20, 200).unwrap(),
+            200
+        );
+        for bad in [json!(0), json!(201), json!(-1), json!("5"), json!(1.5)] {
+            assert!(optional_u32(&json!({ "limit": bad }), "limit", 20, 200).is_err());
+        }
+    }
+
+    #[test]
+    fn test_question_parameters() {
+        assert_eq!(question_parameters(&json!({})).unwrap(), None);
+        assert_eq!(
+            question_parameters(&json!({ "parameters": {} })).unwrap(),
+            None
+        );
+
+        let parameters = question_parameters(&json!({
+            "parameters": { "state": "CA", "year": 2024, "active": true }
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(parameters["state"], "CA");
+        assert_eq!(parameters["year"], "2024");
+        assert_eq!(parameters["active"], "true");
+
+        assert!(question_parameters(&json!({ "parameters": ["CA"] })).is_err());
+        assert!(question_parameters(&json!({ "parameters": { "state": ["CA"] } })).is_err());
+    }
+
+    #[test]
+    fn test_query_output_truncation() {
+        let result = |rows: usize| QueryResult {
+            data: QueryData {
+                cols: vec![Column {
+                    name: "n".to_string(),
+                    display_name: "N".to_string(),
+                    base_type: "type/Integer".to_string(),
+                    ..Default::default()
+                }],
+                rows: (0..rows).map(|i| vec![json!(i)]).collect(),
+            },
+        };
+
+        let output = query_output(result(3), 2);
+        assert_eq!(output["row_count"], 2);
+        assert_eq!(output["truncated"], true);
+        assert_eq!(output["rows"], json!([[0], [1]]));
+        assert_eq!(output["columns"][0]["name"], "n");
+
+        let output = query_output(result(2), 2);
+        assert_eq!(output["row_count"], 2);
+        assert_eq!(output["truncated"], false);
+    }
+
+    #[test]
+    fn test_parse_resource_uri() {
+        assert_eq!(
+            parse_resource_uri("metabase://question/12"),
+            Some(Resource::Question(12))
+        );
+        assert_eq!(
+            parse_resource_uri("metabase://table/3"),
+            Some(Resource::Table(3))
+        );
+        assert_eq!(parse_resource_uri("metabase://dashboard/1"), None);
+        assert_eq!(parse_resource_uri("metabase://question/abc"), None);
+        assert_eq!(parse_resource_uri("metabase://question"), None);
+        assert_eq!(parse_resource_uri("https://question/1"), None);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod command_handlers;
+#[cfg(unix)]
+pub mod daemon;
 pub mod dispatcher;
 pub mod interactive_display;
 pub mod main_types;
+pub mod mcp;
 pub mod output;
@@ -1,6 +1,8 @@
 use clap::ValueEnum;
+use mbr_core::core::cache::CacheStats;
 use mbr_core::error::AppError;
 use serde::Serialize;
+use std::collections::BTreeMap;
 
 /// Output format for command results
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -50,6 +52,32 @@ pub struct LogoutOutput {
     pub success: bool,
 }
 
+#[derive(Serialize)]
+pub struct CacheStatsOutput {
+    pub dir: String,
+    pub size_bytes: u64,
+    pub resources: BTreeMap<String, CacheStats>,
+}
+
+#[derive(Serialize)]
+pub struct CacheClearOutput {
+    pub success: bool,
+    pub removed: usize,
+}
+
+#[derive(Serialize)]
+pub struct SyncMetadataOutput {
+    pub url: String,
+    pub path: String,
+    pub synced_at: String,
+    pub databases: usize,
+    pub tables: usize,
+    pub questions: usize,
+    pub collections: usize,
+    /// Items the sync left out
+    pub warnings: Vec<String>,
+}
+
 #[derive(Serialize)]
 pub struct ConfigSetOutput {
     pub success: bool,
@@ -79,6 +107,18 @@ pub struct JsonErrorOutput {
     pub error: JsonErrorDetail,
 }
 
+impl JsonErrorOutput {
+    pub fn new(error: &AppError) -> Self {
+        Self {
+            error: JsonErrorDetail {
+                code: error.error_code().to_string(),
+                message: error.display_friendly(),
+                hint: error.troubleshooting_hint(),
+            },
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct JsonErrorDetail {
     pub code: String,
@@ -105,14 +145,7 @@ pub fn print_json<T: Serialize>(data: &T) {
 
 /// Print a structured JSON error to stdout (so callers piping JSON get it).
 pub fn print_json_error(error: &AppError) {
-    let output = JsonErrorOutput {
-        error: JsonErrorDetail {
-            code: error.error_code().to_string(),
-            message: error.display_friendly(),
-            hint: error.troubleshooting_hint(),
-        },
-    };
-    print_json(&output);
+    print_json(&JsonErrorOutput::new(error));
 }
 
 /// Re-export CSV escaping from mbr-core for use in command handlers.
@@ -42,7 +42,12 @@ async fn main() {
     }
 
     let config_dir = cli.config_dir.as_deref();
-    let dispatcher = Dispatcher::new(config, verbose, use_colors, json_mode);
+    let dispatcher = Dispatcher::new(config, verbose, use_colors, json_mode)
+        .with_daemon(cli.socket, !cli.no_daemon)
+        .with_retries(cli.retries)
+        .with_timeout(cli.timeout)
+        .with_cache_mode(!cli.no_cache, cli.refresh)
+        .with_offline(cli.offline);
 
     if let Err(e) = dispatcher.dispatch(cli.command, config_dir).await {
         if json_mode {
@@ -1,25 +1,153 @@
-use crate::error::{ApiError, AppError};
+use crate::api::transport::{self, Cassette, TransportError};
+use crate::error::{ApiError, AppError, ConfigError};
+use crate::storage::config::HttpConfig;
+use crate::storage::credentials::Session;
+use crate::storage::response_cache::ResponseCache;
 use crate::utils::error_helpers::*;
-use reqwest::{Client, Method, RequestBuilder, Response};
+use crate::utils::logging::{log_warning, print_verbose};
+use crate::utils::retry::{AttemptError, RetryConfig, RetryExecutor};
+use reqwest::{Certificate, Client, Identity, Method, NoProxy, Proxy, RequestBuilder, Response};
 use serde::Serialize;
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
-const DEFAULT_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_READ_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_QUERY_TIMEOUT_SECS: u64 = 60;
 const USER_AGENT: &str = concat!("mbr-cli/", env!("CARGO_PKG_VERSION"));
 
+/// Time limits for requests; `None` waits indefinitely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Establishing the connection, including the TLS handshake
+    pub connect: Option<Duration>,
+    /// Login and metadata requests, from connecting to the last byte
+    pub read: Option<Duration>,
+    /// Query execution (saved questions, SQL, table previews)
+    pub query: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            connect: Some(Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS)),
+            read: Some(Duration::from_secs(DEFAULT_READ_TIMEOUT_SECS)),
+            query: Some(Duration::from_secs(DEFAULT_QUERY_TIMEOUT_SECS)),
+        }
+    }
+}
+
+/// Build the HTTP client with the proxy, trusted CAs, client certificate and
+/// verification settings of `http`, falling back to their `MBR_*` variables.
+fn build_http_client(http: &HttpConfig) -> Result<Client, AppError> {
+    let http = http.with_env_fallback();
+    let mut builder = Client::builder().user_agent(USER_AGENT);
+    if let Some(connect) = http.timeouts().connect {
+        builder = builder.connect_timeout(connect);
+    }
+
+    if let Some(ref proxy_url) = http.proxy {
+        let proxy = Proxy::all(proxy_url)
+            .map_err(|e| invalid_http_value("http.proxy", proxy_url, e))?
+            .no_proxy(
+                http.no_proxy
+                    .as_deref()
+                    .and_then(NoProxy::from_string)
+                    .or_else(NoProxy::from_env),
+            );
+        builder = builder.proxy(proxy);
+    }
+
+    for path in &http.ca_bundles {
+        let pem = read_pem(path, "http.ca_bundles")?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| invalid_http_value("http.ca_bundles", &path.display(), e))?;
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    match (&http.client_cert, &http.client_key) {
+        (Some(cert), Some(key)) => {
+            let identity = Identity::from_pkcs8_pem(
+                &read_pem(cert, "http.client_cert")?,
+                &read_pem(key, "http.client_key")?,
+            )
+            .map_err(|e| invalid_http_value("http.client_cert", &cert.display(), e))?;
+            builder = builder.identity(identity);
+        }
+        (None, None) => {}
+        (Some(_), None) | (None, Some(_)) => {
+            return Err(AppError::Config(ConfigError::MissingField {
+                field: if http.client_key.is_none() {
+                    "http.client_key".to_string()
+                } else {
+                    "http.client_cert".to_string()
+                },
+                field_type: "path".to_string(),
+            }));
+        }
+    }
+
+    if http.insecure_skip_verify {
+        // Clients are rebuilt on re-login; say it once per process
+        static WARNED: std::sync::Once = std::sync::Once::new();
+        WARNED.call_once(|| {
+            log_warning(
+                "TLS certificate verification is disabled (http.insecure_skip_verify); \
+                 use this for local development only",
+            )
+        });
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder
+        .build()
+        .map_err(|e| AppError::Api(convert_request_error(e, "client_init")))
+}
+
+fn read_pem(path: &Path, field: &str) -> Result<Vec<u8>, AppError> {
+    std::fs::read(path).map_err(|e| invalid_http_value(field, &path.display(), e))
+}
+
+fn invalid_http_value(
+    field: &str,
+    value: &impl std::fmt::Display,
+    reason: impl std::fmt::Display,
+) -> AppError {
+    AppError::Config(ConfigError::InvalidValue {
+        field: field.to_string(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct MetabaseClient {
     client: Client,
     pub base_url: String,
     pub session_token: Option<String>,
+    /// API key sent instead of a session token
+    pub api_key: Option<String>,
+    /// Policy for retrying transient failures
+    retry: RetryConfig,
+    /// Report retries and cache use on stderr
+    verbose: bool,
+    /// Disk cache for metadata GET requests
+    cache: Option<ResponseCache>,
+    /// Time limits for connecting, metadata requests and queries
+    timeouts: Timeouts,
+    /// Cassette used instead of the one selected by `MBR_RECORD` / `MBR_REPLAY`
+    cassette: Option<Arc<Cassette>>,
 }
 
 impl MetabaseClient {
     // Create baseClient with default settings
     pub fn new(base_url: String) -> Result<Self, ApiError> {
         let client = Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .connect_timeout(Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS))
             .user_agent(USER_AGENT)
             .build()
             .map_err(|e| convert_request_error(e, "client_init"))?;
@@ -28,11 +156,50 @@ impl MetabaseClient {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
             session_token: None,
+            api_key: None,
+            retry: RetryConfig::default(),
+            verbose: false,
+            cache: None,
+            timeouts: Timeouts::default(),
+            cassette: None,
         })
     }
 
+    /// Apply proxy, TLS and timeout settings (`[http]` or their environment
+    /// variables).
+    pub fn with_http(mut self, http: &HttpConfig) -> Result<Self, AppError> {
+        self.client = build_http_client(http)?;
+        self.timeouts = http.timeouts();
+        Ok(self)
+    }
+
+    /// Cache metadata responses on disk.
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set the policy for retrying transient failures.
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Record to or replay from `cassette` instead of the cassette selected
+    /// by `MBR_RECORD` / `MBR_REPLAY`.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Report retries and cache use on stderr (verbose mode).
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
     pub fn is_authenticated(&self) -> bool {
-        self.session_token.is_some()
+        self.session_token.is_some() || self.api_key.is_some()
     }
 
     pub fn with_session_token(base_url: String, session_token: String) -> Result<Self, ApiError> {
@@ -41,9 +208,27 @@ impl MetabaseClient {
         Ok(client)
     }
 
+    /// Create a client authenticating with a Metabase API key.
+    pub fn with_api_key(base_url: String, api_key: String) -> Result<Self, ApiError> {
+        let mut client = MetabaseClient::new(base_url)?;
+        client.api_key = Some(api_key);
+        Ok(client)
+    }
+
+    /// Create a client from a stored session (API key or session token).
+    pub fn from_session(session: &Session) -> Result<Self, ApiError> {
+        match &session.api_key {
+            Some(key) => MetabaseClient::with_api_key(session.url.clone(), key.clone()),
+            None => MetabaseClient::with_session_token(
+                session.url.clone(),
+                session.session_token.clone(),
+            ),
+        }
+    }
+
     /// Build a request with the given HTTP method and path.
     ///
-    /// Automatically adds session token authentication header if configured.
+    /// Automatically adds the session token or API key header if configured.
     /// For query parameters, either:
     /// - Chain `.query()` on the returned `RequestBuilder`
     /// - Use `build_request_with_query()` for typed parameters
@@ -54,6 +239,9 @@ impl MetabaseClient {
         if let Some(token) = &self.session_token {
             request = request.header("X-Metabase-Session", token);
         }
+        if let Some(key) = &self.api_key {
+            request = request.header("X-API-Key", key);
+        }
 
         request
     }
@@ -97,21 +285,21 @@ impl MetabaseClient {
         let endpoint = "/api/user/current";
 
         let response = self
-            .build_request(Method::GET, endpoint)
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+            .send(self.build_request(Method::GET, endpoint), endpoint)
+            .await?;
 
         Self::handle_response(response, endpoint).await
     }
 
     /// Login to Metabase and return a session token.
-    pub async fn login(base_url: &str, username: &str, password: &str) -> Result<String, AppError> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .user_agent(USER_AGENT)
-            .build()
-            .map_err(|e| AppError::Api(convert_request_error(e, "client_init")))?;
+    pub async fn login(
+        base_url: &str,
+        username: &str,
+        password: &str,
+        http: &HttpConfig,
+    ) -> Result<String, AppError> {
+        let client = build_http_client(http)?;
+        let timeouts = http.timeouts();
 
         let url = format!("{}/api/session", base_url.trim_end_matches('/'));
         let body = serde_json::json!({
@@ -119,12 +307,21 @@ impl MetabaseClient {
             "password": password
         });
 
-        let response = client
-            .post(&url)
-            .json(&body)
-            .send()
-            .await
+        let mut request = client.post(&url).json(&body);
+        if let Some(read) = timeouts.read {
+            request = request.timeout(read);
+        }
+        let request = request
+            .build()
             .map_err(|e| AppError::Api(convert_request_error(e, "/api/session")))?;
+        let response = transport::execute(&client, None, request)
+            .await
+            .map_err(|e| match e {
+                TransportError::Http(e) => {
+                    convert_send_error(e, "/api/session", timeouts.connect, timeouts.read)
+                }
+                TransportError::Cassette(error) => error,
+            })?;
 
         if response.status().is_success() {
             #[derive(serde::Deserialize)]
@@ -157,10 +354,13 @@ impl MetabaseClient {
         if self.session_token.is_none() {
             return Ok(());
         }
-        let _ = self
-            .build_request(Method::DELETE, "/api/session")
-            .send()
-            .await;
+        let mut request = self.build_request(Method::DELETE, "/api/session");
+        if let Some(read) = self.timeouts.read {
+            request = request.timeout(read);
+        }
+        if let Ok(request) = request.build() {
+            let _ = transport::execute(&self.client, self.cassette.as_deref(), request).await;
+        }
         Ok(())
     }
 
@@ -182,10 +382,8 @@ impl MetabaseClient {
         // Fetch all cards via /api/card?f=all
         let endpoint = "/api/card?f=all";
         let response = self
-            .build_request(Method::GET, endpoint)
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+            .send(self.build_request(Method::GET, endpoint), endpoint)
+            .await?;
 
         let all_questions: Vec<crate::api::models::Question> =
             Self::handle_response(response, endpoint).await?;
@@ -227,10 +425,11 @@ impl MetabaseClient {
         };
 
         let response = self
-            .build_request_with_query(Method::GET, endpoint, Some(&query))
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+            .send(
+                self.build_request_with_query(Method::GET, endpoint, Some(&query)),
+                endpoint,
+            )
+            .await?;
 
         let search_response: SearchResponse = Self::handle_response(response, endpoint).await?;
 
@@ -267,17 +466,7 @@ impl MetabaseClient {
             request = request.json(&params);
         }
 
-        // Extended timeout for query execution
-        let response = request
-            .timeout(Duration::from_secs(60))
-            .send()
-            .await
-            .map_err(|_e| {
-                AppError::Api(ApiError::Timeout {
-                    timeout_secs: 60,
-                    endpoint: endpoint.clone(),
-                })
-            })?;
+        let response = self.send_query(request, &endpoint).await?;
 
         // Handle 404 with custom message before generic handling
         if response.status() == reqwest::StatusCode::NOT_FOUND {
@@ -291,6 +480,28 @@ impl MetabaseClient {
         Self::handle_response(response, &endpoint).await
     }
 
+    /// Get the definition of a question (query, database, parameters).
+    pub async fn get_question(
+        &self,
+        question_id: u32,
+    ) -> Result<crate::api::models::QuestionDetail, AppError> {
+        let endpoint = format!("/api/card/{}", question_id);
+
+        let response = self
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(AppError::Api(ApiError::Http {
+                status: 404,
+                endpoint,
+                message: format!("Question with ID {} not found", question_id),
+            }));
+        }
+
+        Self::handle_response(response, &endpoint).await
+    }
+
     /// List all collections from Metabase
     pub async fn list_collections(
         &self,
@@ -298,10 +509,8 @@ impl MetabaseClient {
         let endpoint = "/api/collection";
 
         let response = self
-            .build_request(Method::GET, endpoint)
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+            .send(self.build_request(Method::GET, endpoint), endpoint)
+            .await?;
 
         let collections: Vec<crate::api::models::CollectionItem> =
             Self::handle_response(response, endpoint).await?;
@@ -310,15 +519,39 @@ impl MetabaseClient {
         Ok(collections.into_iter().filter(|c| !c.archived).collect())
     }
 
+    /// List the items (sub-collections, dashboards, models, questions) in a collection.
+    /// Pass `None` to list the items of the root collection ("Our analytics").
+    pub async fn list_collection_items(
+        &self,
+        collection_id: Option<u32>,
+    ) -> Result<Vec<crate::api::models::CollectionEntry>, AppError> {
+        let endpoint = match collection_id {
+            Some(id) => format!("/api/collection/{}/items", id),
+            None => "/api/collection/root/items".to_string(),
+        };
+
+        let response = self
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
+
+        // Metabase returns { "data": [...], "total": n } for collection items
+        #[derive(serde::Deserialize)]
+        struct ItemsResponse {
+            data: Vec<crate::api::models::CollectionEntry>,
+        }
+
+        let response_body: ItemsResponse = Self::handle_response(response, &endpoint).await?;
+
+        Ok(response_body.data)
+    }
+
     /// List all databases from Metabase
     pub async fn list_databases(&self) -> Result<Vec<crate::api::models::Database>, AppError> {
         let endpoint = "/api/database";
 
         let response = self
-            .build_request(Method::GET, endpoint)
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+            .send(self.build_request(Method::GET, endpoint), endpoint)
+            .await?;
 
         // Metabase returns { "data": [...] } for databases
         #[derive(serde::Deserialize)]
@@ -337,10 +570,8 @@ impl MetabaseClient {
         let endpoint = format!("/api/database/{}/schemas", database_id);
 
         let response = self
-            .build_request(Method::GET, &endpoint)
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, &endpoint)))?;
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
 
         // Handle 404 with custom message
         if response.status() == reqwest::StatusCode::NOT_FOUND {
@@ -363,10 +594,8 @@ impl MetabaseClient {
         let endpoint = format!("/api/database/{}/schema/{}", database_id, schema);
 
         let response = self
-            .build_request(Method::GET, &endpoint)
-            .send()
-            .await
-            .map_err(|e| AppError::Api(convert_request_error(e, &endpoint)))?;
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
 
         // Handle 404 with custom message
         if response.status() == reqwest::StatusCode::NOT_FOUND {
@@ -380,6 +609,51 @@ impl MetabaseClient {
         Self::handle_response(response, &endpoint).await
     }
 
+    /// Get a table with its fields.
+    pub async fn get_table_metadata(
+        &self,
+        table_id: u32,
+    ) -> Result<crate::api::models::TableMetadata, AppError> {
+        let endpoint = format!("/api/table/{}/query_metadata", table_id);
+
+        let response = self
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(AppError::Api(ApiError::Http {
+                status: 404,
+                endpoint,
+                message: format!("Table with ID {} not found", table_id),
+            }));
+        }
+
+        Self::handle_response(response, &endpoint).await
+    }
+
+    /// Run a native (SQL) query against a database.
+    /// The server stops after `max_rows` rows.
+    pub async fn run_native_query(
+        &self,
+        database_id: u32,
+        sql: &str,
+        max_rows: u32,
+    ) -> Result<crate::api::models::QueryResult, AppError> {
+        let endpoint = "/api/dataset";
+
+        let query_payload = serde_json::json!({
+            "database": database_id,
+            "type": "native",
+            "native": { "query": sql },
+            "constraints": {
+                "max-results": max_rows,
+                "max-results-bare-rows": max_rows
+            }
+        });
+
+        self.run_dataset_query(endpoint, &query_payload).await
+    }
+
     /// Preview table data (fetch sample rows).
     /// Uses POST /api/dataset to query the table directly.
     pub async fn preview_table(
@@ -400,20 +674,256 @@ impl MetabaseClient {
             }
         });
 
+        self.run_dataset_query(endpoint, &query_payload).await
+    }
+
+    /// Fetch rows of a table where a field equals a value.
+    /// Uses POST /api/dataset with an MBQL `=` filter (foreign-key navigation).
+    pub async fn query_table_rows(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        field_id: u32,
+        value: &serde_json::Value,
+        limit: u32,
+    ) -> Result<crate::api::models::QueryResult, AppError> {
+        let endpoint = "/api/dataset";
+
+        let query_payload = serde_json::json!({
+            "database": database_id,
+            "type": "query",
+            "query": {
+                "source-table": table_id,
+                "filter": ["=", ["field", field_id, null], value],
+                "limit": limit
+            }
+        });
+
+        self.run_dataset_query(endpoint, &query_payload).await
+    }
+
+    /// Get field metadata, including the table it belongs to.
+    pub async fn get_field(&self, field_id: u32) -> Result<crate::api::models::Field, AppError> {
+        let endpoint = format!("/api/field/{}", field_id);
+
         let response = self
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
+
+        Self::handle_response(response, &endpoint).await
+    }
+
+    /// List foreign keys in other tables that reference a table.
+    pub async fn list_table_fks(
+        &self,
+        table_id: u32,
+    ) -> Result<Vec<crate::api::models::ForeignKey>, AppError> {
+        let endpoint = format!("/api/table/{}/fks", table_id);
+
+        let response = self
+            .send(self.build_request(Method::GET, &endpoint), &endpoint)
+            .await?;
+
+        Self::handle_response(response, &endpoint).await
+    }
+
+    /// Run an ad-hoc query through POST /api/dataset under the query timeout.
+    async fn run_dataset_query(
+        &self,
+        endpoint: &str,
+        query_payload: &serde_json::Value,
+    ) -> Result<crate::api::models::QueryResult, AppError> {
+        let request = self
             .build_request(Method::POST, endpoint)
-            .json(&query_payload)
-            .timeout(std::time::Duration::from_secs(60))
-            .send()
+            .json(query_payload);
+        let response = self.send_query(request, endpoint).await?;
+
+        // Queries that fail in the database still return a success status,
+        // with the reason in the body
+        let status = response.status().as_u16();
+        let body: serde_json::Value = Self::handle_response(response, endpoint).await?;
+        if body.get("status").and_then(|s| s.as_str()) == Some("failed") {
+            let message = match body.get("error") {
+                Some(serde_json::Value::String(error)) => error.clone(),
+                Some(error) => error.to_string(),
+                None => "Query failed".to_string(),
+            };
+            return Err(AppError::Api(ApiError::Http {
+                status,
+                endpoint: endpoint.to_string(),
+                message,
+            }));
+        }
+        serde_json::from_value(body).map_err(|e| {
+            AppError::Api(ApiError::Http {
+                status: 0,
+                endpoint: endpoint.to_string(),
+                message: format!("JSON parse error: {}", e),
+            })
+        })
+    }
+
+    /// Send a query execution request under the query timeout.
+    async fn send_query(
+        &self,
+        request: RequestBuilder,
+        endpoint: &str,
+    ) -> Result<Response, AppError> {
+        let mut request = request
+            .build()
+            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+        *request.timeout_mut() = self.timeouts.query;
+        self.send_with_retry(request, endpoint).await
+    }
+
+    /// Send a request under the read timeout, answering cacheable GET
+    /// requests from the response cache when fresh and storing what the
+    /// server returns.
+    async fn send(&self, request: RequestBuilder, endpoint: &str) -> Result<Response, AppError> {
+        let mut request = request
+            .build()
+            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+        *request.timeout_mut() = self.timeouts.read;
+
+        // Recorded and replayed requests always go through the transport
+        let cache = self.cache.as_ref().filter(|_| {
+            request.method() == Method::GET
+                && self.cassette.is_none()
+                && !transport::cassette_active()
+        });
+        let Some((cache, resource)) =
+            cache.and_then(|c| Some((c, ResponseCache::resource_for(request.url().path())?)))
+        else {
+            return self.send_with_retry(request, endpoint).await;
+        };
+
+        let url = request.url().to_string();
+        let cached = cache.get(&url);
+        if let Some(ref entry) = cached {
+            if entry.is_fresh() && !cache.is_refresh() {
+                print_verbose(self.verbose, &format!("Cache hit: {}", endpoint));
+                return Ok(cached_response(entry.body.clone()));
+            }
+            let headers = request.headers_mut();
+            if let Some(value) = entry.etag.as_deref().and_then(|v| v.parse().ok()) {
+                headers.insert(reqwest::header::IF_NONE_MATCH, value);
+            }
+            if let Some(value) = entry.last_modified.as_deref().and_then(|v| v.parse().ok()) {
+                headers.insert(reqwest::header::IF_MODIFIED_SINCE, value);
+            }
+        }
+
+        let response = self.send_with_retry(request, endpoint).await?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED
+            && let Some(entry) = cached
+        {
+            print_verbose(self.verbose, &format!("Cache revalidated: {}", endpoint));
+            let _ = cache.touch(&entry);
+            return Ok(cached_response(entry.body));
+        }
+        if !response.status().is_success() {
+            return Ok(response);
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+        let body = response
+            .text()
             .await
-            .map_err(|_e| {
-                AppError::Api(ApiError::Timeout {
-                    timeout_secs: 60,
-                    endpoint: endpoint.to_string(),
-                })
-            })?;
+            .map_err(|e| AppError::Api(convert_request_error(e, endpoint)))?;
+        if let Err(e) = cache.put(&url, resource, etag, last_modified, body.clone()) {
+            print_verbose(self.verbose, &format!("Not cached: {}", e));
+        }
+        Ok(cached_response(body))
+    }
 
-        Self::handle_response(response, endpoint).await
+    /// Send a request under the client's retry policy.
+    ///
+    /// Idempotent requests are retried on connection failures, timeouts and
+    /// 429/502/503/504 responses, waiting at least as long as `Retry-After`
+    /// asks. Other requests (POST) are retried only when no connection
+    /// could be made, so they never run twice on the server.
+    async fn send_with_retry(
+        &self,
+        request: reqwest::Request,
+        endpoint: &str,
+    ) -> Result<Response, AppError> {
+        let method = request.method().clone();
+        let idempotent = method.is_idempotent();
+        let timeout = request.timeout().copied();
+
+        let attempt = || async {
+            let Some(request) = request.try_clone() else {
+                return Err(AttemptError {
+                    error: ApiError::Http {
+                        status: 0,
+                        endpoint: endpoint.to_string(),
+                        message: "Request body cannot be resent".to_string(),
+                    },
+                    retry_after: None,
+                    retryable: false,
+                });
+            };
+            let response = transport::execute(&self.client, self.cassette.as_deref(), request)
+                .await
+                .map_err(|e| match e {
+                    TransportError::Http(e) => AttemptError {
+                        retryable: idempotent || e.is_connect(),
+                        error: convert_send_error(e, endpoint, self.timeouts.connect, timeout),
+                        retry_after: None,
+                    },
+                    TransportError::Cassette(error) => AttemptError {
+                        error,
+                        retry_after: None,
+                        retryable: false,
+                    },
+                })?;
+
+            let status = response.status().as_u16();
+            if idempotent && matches!(status, 429 | 502 | 503 | 504) {
+                let retry_after = retry_after(&response);
+                return Err(AttemptError {
+                    error: Self::status_error(response, endpoint, timeout).await,
+                    retry_after,
+                    retryable: true,
+                });
+            }
+            if matches!(status, 408 | 504) {
+                return Err(AttemptError {
+                    error: Self::status_error(response, endpoint, timeout).await,
+                    retry_after: None,
+                    retryable: false,
+                });
+            }
+            Ok(response)
+        };
+
+        let on_retry = |attempt: u32, delay: Duration, error: &ApiError| {
+            print_verbose(
+                self.verbose,
+                &format!(
+                    "{} {} failed ({}), retrying in {:.1}s (attempt {} of {})",
+                    method,
+                    endpoint,
+                    error,
+                    delay.as_secs_f64(),
+                    attempt,
+                    self.retry.max_retries + 1
+                ),
+            );
+        };
+
+        RetryExecutor::new(self.retry.clone())
+            .execute_with(attempt, on_retry)
+            .await
+            .map_err(AppError::Api)
     }
 
     /// Common HTTP response handler for all API methods.
@@ -422,44 +932,79 @@ impl MetabaseClient {
     where
         T: serde::de::DeserializeOwned,
     {
-        let status = response.status();
-
-        if status.is_success() {
+        if response.status().is_success() {
             response
                 .json::<T>()
                 .await
                 .map_err(|e| AppError::Api(convert_json_error(e, endpoint)))
         } else {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
+            Err(AppError::Api(
+                Self::status_error(response, endpoint, None).await,
+            ))
+        }
+    }
 
-            Err(AppError::Api(match status.as_u16() {
-                401 => ApiError::Unauthorized {
-                    status: status.as_u16(),
-                    endpoint: endpoint.to_string(),
-                    server_message: error_text,
-                },
-                403 => ApiError::Forbidden {
-                    status: status.as_u16(),
-                    endpoint: endpoint.to_string(),
-                    server_message: error_text,
-                },
-                408 | 504 => ApiError::Timeout {
-                    timeout_secs: DEFAULT_TIMEOUT_SECS,
-                    endpoint: endpoint.to_string(),
-                },
-                _ => ApiError::Http {
-                    status: status.as_u16(),
-                    endpoint: endpoint.to_string(),
-                    message: error_text,
-                },
-            }))
+    /// Map an error response to an `ApiError`, keeping the server's message.
+    /// A 408 or 504 is reported as a timeout under `timeout`, the limit the
+    /// request ran under; without one it stays an HTTP error.
+    async fn status_error(
+        response: Response,
+        endpoint: &str,
+        timeout: Option<Duration>,
+    ) -> ApiError {
+        let status = response.status().as_u16();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+
+        match (status, timeout) {
+            (401, _) => ApiError::Unauthorized {
+                status,
+                endpoint: endpoint.to_string(),
+                server_message: error_text,
+            },
+            (403, _) => ApiError::Forbidden {
+                status,
+                endpoint: endpoint.to_string(),
+                server_message: error_text,
+            },
+            (408 | 504, Some(limit)) => ApiError::Timeout {
+                timeout_secs: limit.as_secs(),
+                endpoint: endpoint.to_string(),
+            },
+            _ => ApiError::Http {
+                status,
+                endpoint: endpoint.to_string(),
+                message: error_text,
+            },
         }
     }
 }
 
+/// A successful JSON response with the given body (from the cache).
+fn cached_response(body: String) -> Response {
+    let mut response = http::Response::new(body);
+    response.headers_mut().insert(
+        reqwest::header::CONTENT_TYPE,
+        reqwest::header::HeaderValue::from_static("application/json"),
+    );
+    Response::from(response)
+}
+
+/// Delay asked for by a `Retry-After` header (in seconds; the HTTP-date
+/// form is not used by Metabase and falls back to the backoff delay).
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?;
+    value
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -470,6 +1015,56 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_http_client_proxy_and_tls_settings() {
+        let http = HttpConfig {
+            proxy: Some("http://proxy.test:3128".to_string()),
+            no_proxy: Some("localhost,.internal".to_string()),
+            insecure_skip_verify: true,
+            ..Default::default()
+        };
+        assert!(build_http_client(&http).is_ok());
+
+        let missing_ca = HttpConfig {
+            ca_bundles: vec!["/nonexistent/ca.pem".into()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            build_http_client(&missing_ca),
+            Err(AppError::Config(ConfigError::InvalidValue { ref field, .. })) if field == "http.ca_bundles"
+        ));
+
+        let cert_without_key = HttpConfig {
+            client_cert: Some("/nonexistent/client.pem".into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            build_http_client(&cert_without_key),
+            Err(AppError::Config(ConfigError::MissingField { ref field, .. })) if field == "http.client_key"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_refused_connection_is_not_a_timeout() {
+        // A port that was just free has nothing listening on it
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("Failed to find a free port")
+            .port();
+        let client = MetabaseClient::new(format!("http://127.0.0.1:{}", port))
+            .expect("Failed to create client")
+            .with_retry(RetryConfig::none());
+
+        let error = client.get_current_user().await.unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::Api(ApiError::Connection {
+                kind: crate::error::ConnectionErrorKind::Refused,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_not_authenticated_without_session_token() {
         let client =
@@ -525,6 +1120,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_request_with_api_key() {
+        let session = Session {
+            session_token: String::new(),
+            url: "http://example.test".to_string(),
+            username: "bot@example.test".to_string(),
+            created_at: "2026-03-30T12:00:00Z".to_string(),
+            api_key: Some("mb_key".to_string()),
+        };
+        let client = MetabaseClient::from_session(&session).expect("client creation failed");
+        assert!(client.is_authenticated());
+
+        let built_request = client
+            .build_request(Method::GET, "/api/card")
+            .build()
+            .expect("Failed to build request");
+        let headers = built_request.headers();
+        assert_eq!(
+            headers.get("X-API-Key").unwrap().to_str().unwrap(),
+            "mb_key"
+        );
+        assert!(headers.get("X-Metabase-Session").is_none());
+    }
+
     #[test]
     fn test_base_url_trailing_slash_removed() {
         let client = MetabaseClient::new("http://example.test/".to_string())
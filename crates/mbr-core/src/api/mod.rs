@@ -1,2 +1,4 @@
 pub mod client;
 pub mod models;
+pub mod traits;
+pub mod transport;
@@ -30,6 +30,16 @@ where
     }
 }
 
+/// Custom deserializer: numeric IDs only; other forms (e.g. "card__12" table IDs
+/// or field references of expression columns) become None
+fn deserialize_numeric_id<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Value::deserialize(deserializer)?;
+    Ok(value.as_u64().and_then(|id| u32::try_from(id).ok()))
+}
+
 // User models
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CurrentUser {
@@ -55,6 +65,28 @@ pub struct Question {
     pub collection: Option<Collection>,
 }
 
+/// Question definition from /api/card/:id
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuestionDetail {
+    pub id: u32,
+    pub name: String,
+    pub description: Option<String>,
+    /// See [`Question::collection_id`] for serialization asymmetry notes.
+    #[serde(deserialize_with = "deserialize_collection_id", default)]
+    pub collection_id: Option<u32>,
+    #[serde(default)]
+    pub database_id: Option<u32>,
+    /// `query` (MBQL) or `native` (SQL)
+    #[serde(default)]
+    pub query_type: Option<String>,
+    /// The query as stored by Metabase
+    #[serde(default)]
+    pub dataset_query: Value,
+    /// Parameters the question accepts
+    #[serde(default)]
+    pub parameters: Vec<Value>,
+}
+
 /// Minimal collection info (embedded in Question)
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Collection {
@@ -79,6 +111,43 @@ pub struct CollectionItem {
     pub archived: bool,
 }
 
+/// Single entry from /api/collection/:id/items (sub-collection, dashboard, card, ...)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CollectionEntry {
+    pub id: u32,
+    pub name: String,
+    pub description: Option<String>,
+    /// Model type (e.g., "collection", "dashboard", "card", "dataset")
+    pub model: String,
+    #[serde(default)]
+    pub personal_owner_id: Option<u32>,
+}
+
+/// Kind of a collection entry, derived from its `model` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionEntryKind {
+    Collection,
+    Dashboard,
+    /// Metabase "dataset" (shown as Model in the UI)
+    Model,
+    /// Metabase "card" (saved question)
+    Question,
+    Other,
+}
+
+impl CollectionEntry {
+    /// Classify this entry by its `model` field.
+    pub fn kind(&self) -> CollectionEntryKind {
+        match self.model.as_str() {
+            "collection" => CollectionEntryKind::Collection,
+            "dashboard" => CollectionEntryKind::Dashboard,
+            "dataset" => CollectionEntryKind::Model,
+            "card" | "metric" => CollectionEntryKind::Question,
+            _ => CollectionEntryKind::Other,
+        }
+    }
+}
+
 /// Database info from /api/database
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Database {
@@ -104,6 +173,37 @@ pub struct TableInfo {
     pub entity_type: Option<String>,
 }
 
+/// Table with its fields, from /api/table/:id/query_metadata
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TableMetadata {
+    pub id: u32,
+    pub db_id: u32,
+    pub name: String,
+    pub schema: Option<String>,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<TableField>,
+}
+
+/// Field of a table, from /api/table/:id/query_metadata
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct TableField {
+    pub id: u32,
+    pub name: String,
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub base_type: Option<String>,
+    /// Semantic type such as `type/PK` or `type/FK`
+    #[serde(default)]
+    pub semantic_type: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Field referenced by a foreign key
+    #[serde(default, deserialize_with = "deserialize_numeric_id")]
+    pub fk_target_field_id: Option<u32>,
+}
+
 /// Search API response wrapper from /api/search
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SearchResponse {
@@ -137,11 +237,76 @@ pub struct QueryData {
     pub rows: Vec<Vec<Value>>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct Column {
     pub name: String,
     pub display_name: String,
     pub base_type: String,
+    /// Field ID (absent for native queries and expressions)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_numeric_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<u32>,
+    /// Table the field belongs to
+    #[serde(
+        default,
+        deserialize_with = "deserialize_numeric_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub table_id: Option<u32>,
+    /// Semantic type such as `type/PK` or `type/FK`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic_type: Option<String>,
+    /// Field referenced by a foreign key column
+    #[serde(
+        default,
+        deserialize_with = "deserialize_numeric_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fk_target_field_id: Option<u32>,
+}
+
+impl Column {
+    /// Whether the column is the primary key of its table.
+    pub fn is_primary_key(&self) -> bool {
+        self.semantic_type.as_deref() == Some("type/PK")
+    }
+}
+
+/// Table a field belongs to, from /api/field/:id and /api/table/:id/fks
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FieldTable {
+    pub id: u32,
+    pub db_id: u32,
+    pub name: String,
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub schema: Option<String>,
+}
+
+/// Field metadata from /api/field/:id
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Field {
+    pub id: u32,
+    pub name: String,
+    pub display_name: Option<String>,
+    pub table_id: u32,
+    #[serde(default)]
+    pub base_type: Option<String>,
+    #[serde(default)]
+    pub table: Option<FieldTable>,
+}
+
+/// Foreign key referencing a table, from /api/table/:id/fks
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ForeignKey {
+    /// Referencing (FK) field, in another table
+    pub origin: Field,
+    /// Referenced (PK) field
+    #[serde(default, deserialize_with = "deserialize_numeric_id")]
+    pub destination_id: Option<u32>,
 }
 
 #[cfg(test)]
@@ -206,6 +371,30 @@ mod tests {
         assert_eq!(collection.name, "Our Analytics");
     }
 
+    #[test]
+    fn test_collection_entry_kind() {
+        let json = r#"[
+            {"id": 1, "name": "Sales", "description": null, "model": "collection"},
+            {"id": 2, "name": "KPIs", "description": null, "model": "dashboard"},
+            {"id": 3, "name": "Orders", "description": "Cleaned orders", "model": "dataset"},
+            {"id": 4, "name": "Revenue", "description": null, "model": "card"},
+            {"id": 5, "name": "Weekly", "description": null, "model": "pulse"}
+        ]"#;
+        let entries: Vec<CollectionEntry> = serde_json::from_str(json).unwrap();
+        let kinds: Vec<CollectionEntryKind> = entries.iter().map(|e| e.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CollectionEntryKind::Collection,
+                CollectionEntryKind::Dashboard,
+                CollectionEntryKind::Model,
+                CollectionEntryKind::Question,
+                CollectionEntryKind::Other,
+            ]
+        );
+        assert_eq!(entries[0].personal_owner_id, None);
+    }
+
     #[test]
     fn test_current_user_deserialization() {
         let json = r#"{
@@ -250,5 +439,69 @@ mod tests {
         assert_eq!(result.data.rows.len(), 2);
         assert_eq!(result.data.cols[0].name, "id");
         assert_eq!(result.data.cols[1].display_name, "Name");
+        assert_eq!(result.data.cols[0].id, None);
+    }
+
+    #[test]
+    fn test_column_key_metadata_deserialization() {
+        let json = r#"[
+            {
+                "name": "ID",
+                "display_name": "ID",
+                "base_type": "type/BigInteger",
+                "id": 10,
+                "table_id": 2,
+                "semantic_type": "type/PK"
+            },
+            {
+                "name": "USER_ID",
+                "display_name": "User ID",
+                "base_type": "type/Integer",
+                "id": 11,
+                "table_id": 2,
+                "semantic_type": "type/FK",
+                "fk_target_field_id": 42
+            },
+            {
+                "name": "total",
+                "display_name": "Total",
+                "base_type": "type/Float",
+                "id": ["field", "total", {"base-type": "type/Float"}],
+                "table_id": "card__7"
+            }
+        ]"#;
+
+        let cols: Vec<Column> = serde_json::from_str(json).unwrap();
+        assert!(cols[0].is_primary_key());
+        assert_eq!((cols[0].id, cols[0].table_id), (Some(10), Some(2)));
+        assert_eq!(cols[1].fk_target_field_id, Some(42));
+        assert!(!cols[1].is_primary_key());
+        assert_eq!((cols[2].id, cols[2].table_id), (None, None));
+    }
+
+    #[test]
+    fn test_table_metadata_deserialization() {
+        let json = r#"{
+            "id": 5,
+            "db_id": 1,
+            "name": "ORDERS",
+            "schema": "PUBLIC",
+            "display_name": "Orders",
+            "description": null,
+            "entity_type": "entity/TransactionTable",
+            "fields": [
+                {"id": 40, "name": "ID", "display_name": "ID", "base_type": "type/BigInteger",
+                 "semantic_type": "type/PK", "fk_target_field_id": null},
+                {"id": 41, "name": "USER_ID", "display_name": "User ID",
+                 "base_type": "type/Integer", "semantic_type": "type/FK",
+                 "fk_target_field_id": 12}
+            ]
+        }"#;
+
+        let table: TableMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!((table.id, table.db_id), (5, 1));
+        assert_eq!(table.fields.len(), 2);
+        assert_eq!(table.fields[0].fk_target_field_id, None);
+        assert_eq!(table.fields[1].fk_target_field_id, Some(12));
     }
 }
@@ -0,0 +1,457 @@
+//! The Metabase API as a trait
+//!
+//! Services, the CLI handlers and the TUI's data loading take a
+//! `MetabaseApi` rather than a concrete `MetabaseClient`, so applications can
+//! substitute fakes, caching decorators or logging middleware.
+
+use crate::api::client::MetabaseClient;
+use crate::api::models::{
+    CollectionEntry, CollectionItem, CurrentUser, Database, Field, ForeignKey, QueryResult,
+    Question, QuestionDetail, TableInfo, TableMetadata,
+};
+use crate::error::AppError;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Read and query operations of the Metabase API.
+#[async_trait]
+pub trait MetabaseApi: Send + Sync {
+    /// Whether requests carry credentials.
+    fn is_authenticated(&self) -> bool {
+        true
+    }
+
+    /// The user the credentials belong to (validates the session).
+    async fn get_current_user(&self) -> Result<CurrentUser, AppError>;
+
+    /// Saved questions, optionally searched by name, limited, or filtered by
+    /// collection ID.
+    async fn list_questions(
+        &self,
+        search: Option<&str>,
+        limit: Option<u32>,
+        collection: Option<&str>,
+    ) -> Result<Vec<Question>, AppError>;
+
+    /// Run a saved question with optional parameters.
+    async fn execute_question(
+        &self,
+        id: u32,
+        parameters: Option<HashMap<String, String>>,
+    ) -> Result<QueryResult, AppError>;
+
+    /// Definition of a saved question.
+    async fn get_question(&self, id: u32) -> Result<QuestionDetail, AppError>;
+
+    /// Collections that are not archived.
+    async fn list_collections(&self) -> Result<Vec<CollectionItem>, AppError>;
+
+    /// Items of a collection (None: the root collection).
+    async fn list_collection_items(
+        &self,
+        collection_id: Option<u32>,
+    ) -> Result<Vec<CollectionEntry>, AppError>;
+
+    async fn list_databases(&self) -> Result<Vec<Database>, AppError>;
+
+    async fn list_schemas(&self, database_id: u32) -> Result<Vec<String>, AppError>;
+
+    async fn list_tables(&self, database_id: u32, schema: &str)
+    -> Result<Vec<TableInfo>, AppError>;
+
+    /// A table with its fields.
+    async fn get_table_metadata(&self, table_id: u32) -> Result<TableMetadata, AppError>;
+
+    /// Run SQL against a database, stopping after `max_rows` rows.
+    async fn run_native_query(
+        &self,
+        database_id: u32,
+        sql: &str,
+        max_rows: u32,
+    ) -> Result<QueryResult, AppError>;
+
+    /// First rows of a table.
+    async fn preview_table(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        limit: u32,
+    ) -> Result<QueryResult, AppError>;
+
+    /// Rows of a table where a field equals a value.
+    async fn query_table_rows(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        field_id: u32,
+        value: &Value,
+        limit: u32,
+    ) -> Result<QueryResult, AppError>;
+
+    /// A field with the table it belongs to.
+    async fn get_field(&self, field_id: u32) -> Result<Field, AppError>;
+
+    /// Foreign keys in other tables that reference a table.
+    async fn list_table_fks(&self, table_id: u32) -> Result<Vec<ForeignKey>, AppError>;
+}
+
+#[async_trait]
+impl MetabaseApi for MetabaseClient {
+    fn is_authenticated(&self) -> bool {
+        MetabaseClient::is_authenticated(self)
+    }
+
+    async fn get_current_user(&self) -> Result<CurrentUser, AppError> {
+        MetabaseClient::get_current_user(self).await
+    }
+
+    async fn list_questions(
+        &self,
+        search: Option<&str>,
+        limit: Option<u32>,
+        collection: Option<&str>,
+    ) -> Result<Vec<Question>, AppError> {
+        MetabaseClient::list_questions(self, search, limit, collection).await
+    }
+
+    async fn execute_question(
+        &self,
+        id: u32,
+        parameters: Option<HashMap<String, String>>,
+    ) -> Result<QueryResult, AppError> {
+        MetabaseClient::execute_question(self, id, parameters).await
+    }
+
+    async fn get_question(&self, id: u32) -> Result<QuestionDetail, AppError> {
+        MetabaseClient::get_question(self, id).await
+    }
+
+    async fn list_collections(&self) -> Result<Vec<CollectionItem>, AppError> {
+        MetabaseClient::list_collections(self).await
+    }
+
+    async fn list_collection_items(
+        &self,
+        collection_id: Option<u32>,
+    ) -> Result<Vec<CollectionEntry>, AppError> {
+        MetabaseClient::list_collection_items(self, collection_id).await
+    }
+
+    async fn list_databases(&self) -> Result<Vec<Database>, AppError> {
+        MetabaseClient::list_databases(self).await
+    }
+
+    async fn list_schemas(&self, database_id: u32) -> Result<Vec<String>, AppError> {
+        MetabaseClient::list_schemas(self, database_id).await
+    }
+
+    async fn list_tables(
+        &self,
+        database_id: u32,
+        schema: &str,
+    ) -> Result<Vec<TableInfo>, AppError> {
+        MetabaseClient::list_tables(self, database_id, schema).await
+    }
+
+    async fn get_table_metadata(&self, table_id: u32) -> Result<TableMetadata, AppError> {
+        MetabaseClient::get_table_metadata(self, table_id).await
+    }
+
+    async fn run_native_query(
+        &self,
+        database_id: u32,
+        sql: &str,
+        max_rows: u32,
+    ) -> Result<QueryResult, AppError> {
+        MetabaseClient::run_native_query(self, database_id, sql, max_rows).await
+    }
+
+    async fn preview_table(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        limit: u32,
+    ) -> Result<QueryResult, AppError> {
+        MetabaseClient::preview_table(self, database_id, table_id, limit).await
+    }
+
+    async fn query_table_rows(
+        &self,
+        database_id: u32,
+        table_id: u32,
+        field_id: u32,
+        value: &Value,
+        limit: u32,
+    ) -> Result<QueryResult, AppError> {
+        MetabaseClient::query_table_rows(self, database_id, table_id, field_id, value, limit).await
+    }
+
+    async fn get_field(&self, field_id: u32) -> Result<Field, AppError> {
+        MetabaseClient::get_field(self, field_id).await
+    }
+
+    async fn list_table_fks(&self, table_id: u32) -> Result<Vec<ForeignKey>, AppError> {
+        MetabaseClient::list_table_fks(self, table_id).await
+    }
+}
+
+/// Forward every method to the implementation behind a pointer.
+macro_rules! forward_pointer_impl {
+    ($pointer:ident) => {
+        #[async_trait]
+        impl<T: MetabaseApi + ?Sized> MetabaseApi for $pointer<T> {
+            fn is_authenticated(&self) -> bool {
+                (**self).is_authenticated()
+            }
+
+            async fn get_current_user(&self) -> Result<CurrentUser, AppError> {
+                (**self).get_current_user().await
+            }
+
+            async fn list_questions(
+                &self,
+                search: Option<&str>,
+                limit: Option<u32>,
+                collection: Option<&str>,
+            ) -> Result<Vec<Question>, AppError> {
+                (**self).list_questions(search, limit, collection).await
+            }
+
+            async fn execute_question(
+                &self,
+                id: u32,
+                parameters: Option<HashMap<String, String>>,
+            ) -> Result<QueryResult, AppError> {
+                (**self).execute_question(id, parameters).await
+            }
+
+            async fn get_question(&self, id: u32) -> Result<QuestionDetail, AppError> {
+                (**self).get_question(id).await
+            }
+
+            async fn list_collections(&self) -> Result<Vec<CollectionItem>, AppError> {
+                (**self).list_collections().await
+            }
+
+            async fn list_collection_items(
+                &self,
+                collection_id: Option<u32>,
+            ) -> Result<Vec<CollectionEntry>, AppError> {
+                (**self).list_collection_items(collection_id).await
+            }
+
+            async fn list_databases(&self) -> Result<Vec<Database>, AppError> {
+                (**self).list_databases().await
+            }
+
+            async fn list_schemas(&self, database_id: u32) -> Result<Vec<String>, AppError> {
+                (**self).list_schemas(database_id).await
+            }
+
+            async fn list_tables(
+                &self,
+                database_id: u32,
+                schema: &str,
+            ) -> Result<Vec<TableInfo>, AppError> {
+                (**self).list_tables(database_id, schema).await
+            }
+
+            async fn get_table_metadata(&self, table_id: u32) -> Result<TableMetadata, AppError> {
+                (**self).get_table_metadata(table_id).await
+            }
+
+            async fn run_native_query(
+                &self,
+                database_id: u32,
+                sql: &str,
+                max_rows: u32,
+            ) -> Result<QueryResult, AppError> {
+                (**self).run_native_query(database_id, sql, max_rows).await
+            }
+
+            async fn preview_table(
+                &self,
+                database_id: u32,
+                table_id: u32,
+                limit: u32,
+            ) -> Result<QueryResult, AppError> {
+                (**self).preview_table(database_id, table_id, limit).await
+            }
+
+            async fn query_table_rows(
+                &self,
+                database_id: u32,
+                table_id: u32,
+                field_id: u32,
+                value: &Value,
+                limit: u32,
+            ) -> Result<QueryResult, AppError> {
+                (**self)
+                    .query_table_rows(database_id, table_id, field_id, value, limit)
+                    .await
+            }
+
+            async fn get_field(&self, field_id: u32) -> Result<Field, AppError> {
+                (**self).get_field(field_id).await
+            }
+
+            async fn list_table_fks(&self, table_id: u32) -> Result<Vec<ForeignKey>, AppError> {
+                (**self).list_table_fks(table_id).await
+            }
+        }
+    };
+}
+
+// Boxed and shared implementations (`Box<dyn MetabaseApi>`,
+// `Arc<dyn MetabaseApi>`) are implementations too, so decorators can wrap them
+forward_pointer_impl!(Arc);
+forward_pointer_impl!(Box);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QuestionService;
+    use crate::core::services::types::ListParams;
+    use crate::error::CliError;
+    use std::sync::Mutex;
+
+    /// Answers list_questions from memory and records the calls.
+    #[derive(Default)]
+    struct FakeApi {
+        calls: Mutex<Vec<String>>,
+    }
+
+    fn unsupported<T>() -> Result<T, AppError> {
+        Err(AppError::Cli(CliError::NotImplemented {
+            command: "fake".to_string(),
+        }))
+    }
+
+    #[async_trait]
+    impl MetabaseApi for FakeApi {
+        async fn get_current_user(&self) -> Result<CurrentUser, AppError> {
+            unsupported()
+        }
+
+        async fn list_questions(
+            &self,
+            search: Option<&str>,
+            limit: Option<u32>,
+            _collection: Option<&str>,
+        ) -> Result<Vec<Question>, AppError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("list_questions {:?} {:?}", search, limit));
+            Ok(vec![Question {
+                id: 7,
+                name: "Revenue".to_string(),
+                description: None,
+                collection_id: None,
+                collection: None,
+            }])
+        }
+
+        async fn execute_question(
+            &self,
+            _id: u32,
+            _parameters: Option<HashMap<String, String>>,
+        ) -> Result<QueryResult, AppError> {
+            unsupported()
+        }
+
+        async fn get_question(&self, _id: u32) -> Result<QuestionDetail, AppError> {
+            unsupported()
+        }
+
+        async fn list_collections(&self) -> Result<Vec<CollectionItem>, AppError> {
+            unsupported()
+        }
+
+        async fn list_collection_items(
+            &self,
+            _collection_id: Option<u32>,
+        ) -> Result<Vec<CollectionEntry>, AppError> {
+            unsupported()
+        }
+
+        async fn list_databases(&self) -> Result<Vec<Database>, AppError> {
+            unsupported()
+        }
+
+        async fn list_schemas(&self, _database_id: u32) -> Result<Vec<String>, AppError> {
+            unsupported()
+        }
+
+        async fn list_tables(
+            &self,
+            _database_id: u32,
+            _schema: &str,
+        ) -> Result<Vec<TableInfo>, AppError> {
+            unsupported()
+        }
+
+        async fn get_table_metadata(&self, _table_id: u32) -> Result<TableMetadata, AppError> {
+            unsupported()
+        }
+
+        async fn run_native_query(
+            &self,
+            _database_id: u32,
+            _sql: &str,
+            _max_rows: u32,
+        ) -> Result<QueryResult, AppError> {
+            unsupported()
+        }
+
+        async fn preview_table(
+            &self,
+            _database_id: u32,
+            _table_id: u32,
+            _limit: u32,
+        ) -> Result<QueryResult, AppError> {
+            unsupported()
+        }
+
+        async fn query_table_rows(
+            &self,
+            _database_id: u32,
+            _table_id: u32,
+            _field_id: u32,
+            _value: &Value,
+            _limit: u32,
+        ) -> Result<QueryResult, AppError> {
+            unsupported()
+        }
+
+        async fn get_field(&self, _field_id: u32) -> Result<Field, AppError> {
+            unsupported()
+        }
+
+        async fn list_table_fks(&self, _table_id: u32) -> Result<Vec<ForeignKey>, AppError> {
+            unsupported()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_on_a_fake() {
+        let fake = Arc::new(FakeApi::default());
+        let service = QuestionService::new(fake.clone());
+
+        let params = ListParams {
+            search: Some("rev".to_string()),
+            limit: Some(5),
+            collection: None,
+            offset: None,
+        };
+        let questions = service.list_questions(params).await.unwrap();
+        assert_eq!(questions[0].id, 7);
+        assert_eq!(
+            *fake.calls.lock().unwrap(),
+            ["list_questions Some(\"rev\") Some(5)"]
+        );
+        assert!(fake.is_authenticated());
+    }
+}
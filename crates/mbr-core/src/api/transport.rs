@@ -0,0 +1,563 @@
+//! HTTP transport with record and replay of cassettes
+//!
+//! With `MBR_RECORD=<path>` every request `MetabaseClient` sends, and the
+//! response it gets, is appended to a JSON cassette file. With
+//! `MBR_REPLAY=<path>` requests are answered from that file and never reach
+//! the network, so the CLI and TUI run deterministically without a server.
+//!
+//! Request headers (session tokens, API keys) are never written. Passwords,
+//! the token returned by login and the JSON fields named in the
+//! comma-separated `MBR_RECORD_REDACT` are replaced with `"REDACTED"`, in
+//! request and response bodies alike; replay redacts requests the same way
+//! before matching them.
+
+use crate::error::ApiError;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Client, Method, Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Placeholder written in place of redacted values
+pub const REDACTED: &str = "REDACTED";
+
+/// JSON fields redacted in every cassette
+const DEFAULT_REDACTED_FIELDS: [&str; 3] = ["password", "session_token", "api_key"];
+
+/// Response headers kept in cassettes; the rest (cookies included) are dropped
+const RECORDED_HEADERS: [HeaderName; 4] = [
+    reqwest::header::CONTENT_TYPE,
+    reqwest::header::ETAG,
+    reqwest::header::LAST_MODIFIED,
+    reqwest::header::RETRY_AFTER,
+];
+
+/// Why a request could not be sent
+#[derive(Debug)]
+pub(crate) enum TransportError {
+    /// The network request failed
+    Http(reqwest::Error),
+    /// The cassette could not be read or written, or has no matching response
+    Cassette(ApiError),
+}
+
+/// Send a request over the network, or to `cassette` (by default the one
+/// selected by `MBR_RECORD` / `MBR_REPLAY`).
+pub(crate) async fn execute(
+    client: &Client,
+    cassette: Option<&Cassette>,
+    request: Request,
+) -> Result<Response, TransportError> {
+    let cassette = match cassette {
+        Some(cassette) => Some(cassette),
+        None => active_cassette()
+            .map_err(|message| cassette_error(request.url().path(), message.clone()))?,
+    };
+    match cassette {
+        None => client.execute(request).await.map_err(TransportError::Http),
+        Some(cassette) => match cassette.mode {
+            Mode::Record => cassette.record(client, request).await,
+            Mode::Replay => cassette.replay(&request),
+        },
+    }
+}
+
+/// Whether requests are being recorded or replayed (or a cassette failed to
+/// load). Caches that would answer requests without the transport should
+/// stand aside.
+pub fn cassette_active() -> bool {
+    !matches!(active_cassette(), Ok(None))
+}
+
+fn active_cassette() -> Result<Option<&'static Cassette>, &'static String> {
+    static CASSETTE: OnceLock<Result<Option<Cassette>, String>> = OnceLock::new();
+    CASSETTE
+        .get_or_init(Cassette::from_env)
+        .as_ref()
+        .map(Option::as_ref)
+}
+
+fn cassette_error(endpoint: &str, message: String) -> TransportError {
+    TransportError::Cassette(ApiError::Http {
+        status: 0,
+        endpoint: endpoint.to_string(),
+        message,
+    })
+}
+
+/// Contents of a cassette file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CassetteFile {
+    /// Fields redacted besides the defaults, applied again when replaying
+    /// so redacted requests still match
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub redacted_fields: BTreeSet<String>,
+    pub interactions: Vec<Interaction>,
+}
+
+/// A request and the response it got.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// What a replayed request is matched on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path and query string, so a cassette replays against any base URL
+    pub path: String,
+    /// JSON body, redacted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// JSON body (redacted), or the raw text when `text` is set
+    #[serde(default)]
+    pub body: Value,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub text: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Record,
+    Replay,
+}
+
+/// A cassette being recorded or replayed.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    /// JSON field names replaced with `REDACTED`
+    redacted_fields: BTreeSet<String>,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    file: CassetteFile,
+    /// Interactions already replayed by this process
+    played: Vec<bool>,
+    /// Whether the file on disk holds `file`, so that new interactions can
+    /// be appended to it
+    written: bool,
+}
+
+impl Cassette {
+    /// The cassette named by `MBR_RECORD` or `MBR_REPLAY`, if any.
+    fn from_env() -> Result<Option<Self>, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let redact = var("MBR_RECORD_REDACT").unwrap_or_default();
+        let redact = redact.split(',').map(str::trim).filter(|f| !f.is_empty());
+        match (var("MBR_RECORD"), var("MBR_REPLAY")) {
+            (Some(_), Some(_)) => Err("MBR_RECORD and MBR_REPLAY cannot both be set".to_string()),
+            (Some(path), None) => Self::record_to(path, redact).map(Some),
+            (None, Some(path)) => Self::replay_from(path).map(Some),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Record to `path`, appending to the interactions already in it.
+    pub fn record_to<'a>(
+        path: impl Into<PathBuf>,
+        redacted_fields: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self, String> {
+        let path = path.into();
+        let mut file = if path.exists() {
+            load(&path)?
+        } else {
+            CassetteFile::default()
+        };
+        file.redacted_fields
+            .extend(redacted_fields.into_iter().map(str::to_string));
+        Ok(Self::new(path, Mode::Record, file))
+    }
+
+    /// Replay the interactions in `path`.
+    pub fn replay_from(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let file = load(&path)?;
+        Ok(Self::new(path, Mode::Replay, file))
+    }
+
+    fn new(path: PathBuf, mode: Mode, file: CassetteFile) -> Self {
+        let redacted_fields = DEFAULT_REDACTED_FIELDS
+            .iter()
+            .map(|f| f.to_string())
+            .chain(file.redacted_fields.iter().cloned())
+            .collect();
+        let played = vec![false; file.interactions.len()];
+        Self {
+            path,
+            mode,
+            redacted_fields,
+            state: Mutex::new(State {
+                file,
+                played,
+                written: false,
+            }),
+        }
+    }
+
+    /// The request as written to the cassette.
+    fn recorded_request(&self, request: &Request) -> RecordedRequest {
+        let url = request.url();
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(|bytes| serde_json::from_slice(bytes).ok())
+            .map(|mut body| {
+                self.redact(&mut body);
+                body
+            });
+        RecordedRequest {
+            method: request.method().to_string(),
+            path,
+            body,
+        }
+    }
+
+    /// Replace redacted fields, at any depth, with `REDACTED`.
+    fn redact(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.redacted_fields.contains(key) {
+                        *value = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(value);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact(item)),
+            _ => {}
+        }
+    }
+
+    async fn record(&self, client: &Client, request: Request) -> Result<Response, TransportError> {
+        let recorded = self.recorded_request(&request);
+        let response = client
+            .execute(request)
+            .await
+            .map_err(TransportError::Http)?;
+        let status = response.status();
+        let headers: BTreeMap<String, String> = RECORDED_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = response.headers().get(name)?.to_str().ok()?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+        let text = response.text().await.map_err(TransportError::Http)?;
+
+        let (body, is_text) = match serde_json::from_str::<Value>(&text) {
+            Ok(mut body) => {
+                // The login response carries the new session token as `id`
+                if recorded.method == Method::POST.as_str()
+                    && recorded.path == "/api/session"
+                    && let Some(id) = body.get_mut("id")
+                {
+                    *id = Value::String(REDACTED.to_string());
+                }
+                self.redact(&mut body);
+                (body, false)
+            }
+            Err(_) => (Value::String(text.clone()), true),
+        };
+        let endpoint = recorded.path.clone();
+        let interaction = Interaction {
+            request: recorded,
+            response: RecordedResponse {
+                status: status.as_u16(),
+                headers: headers.clone(),
+                body,
+                text: is_text,
+            },
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let first = state.file.interactions.is_empty();
+        state.file.interactions.push(interaction);
+        state.played.push(true);
+        // The first recording rewrites the file (with this run's redacted
+        // fields); later ones only append
+        let written = state.written
+            && append(&self.path, state.file.interactions.last(), first)
+                .map_err(|message| cassette_error(&endpoint, message))?;
+        if !written {
+            save(&self.path, &state.file).map_err(|message| cassette_error(&endpoint, message))?;
+            state.written = true;
+        }
+        Ok(build_response(status, &headers, text))
+    }
+
+    /// Answer with the first interaction matching the request that this
+    /// process has not replayed yet, or the last match when all have been.
+    fn replay(&self, request: &Request) -> Result<Response, TransportError> {
+        let wanted = self.recorded_request(request);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let matches: Vec<usize> = state
+            .file
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| interaction.request == wanted)
+            .map(|(index, _)| index)
+            .collect();
+        let index = matches
+            .iter()
+            .find(|&&index| !state.played[index])
+            .or(matches.last())
+            .copied()
+            .ok_or_else(|| {
+                cassette_error(
+                    &wanted.path,
+                    format!(
+                        "No response recorded for {} {} in cassette {}",
+                        wanted.method,
+                        wanted.path,
+                        self.path.display()
+                    ),
+                )
+            })?;
+        state.played[index] = true;
+
+        let response = &state.file.interactions[index].response;
+        let status = StatusCode::from_u16(response.status).map_err(|e| {
+            cassette_error(&wanted.path, format!("Invalid status in cassette: {}", e))
+        })?;
+        let body = match (&response.body, response.text) {
+            (Value::String(text), true) => text.clone(),
+            (body, _) => body.to_string(),
+        };
+        Ok(build_response(status, &response.headers, body))
+    }
+}
+
+fn build_response(
+    status: StatusCode,
+    headers: &BTreeMap<String, String>,
+    body: String,
+) -> Response {
+    let mut response = http::Response::new(body);
+    *response.status_mut() = status;
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+    Response::from(response)
+}
+
+fn load(path: &Path) -> Result<CassetteFile, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read cassette {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid cassette {}: {}", path.display(), e))
+}
+
+/// End of a cassette file as `save` writes it. `append` writes each new
+/// interaction over it and puts it back after.
+const FILE_END: &str = "\n  ]\n}\n";
+
+/// Write a whole cassette, one interaction per line.
+fn save(path: &Path, file: &CassetteFile) -> Result<(), String> {
+    let error = |e: std::io::Error| format!("Cannot write cassette {}: {}", path.display(), e);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(error)?;
+    }
+    let mut json = String::from("{\n");
+    if !file.redacted_fields.is_empty() {
+        json.push_str(&format!(
+            "  \"redacted_fields\": {},\n",
+            to_json(&file.redacted_fields)?
+        ));
+    }
+    json.push_str("  \"interactions\": [");
+    for (i, interaction) in file.interactions.iter().enumerate() {
+        json.push_str(if i == 0 { "\n    " } else { ",\n    " });
+        json.push_str(&to_json(interaction)?);
+    }
+    json.push_str(FILE_END);
+    // Write through a temporary file so an interrupted run keeps the
+    // interactions recorded so far
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, json).map_err(error)?;
+    fs::rename(&temp, path).map_err(error)
+}
+
+/// Append an interaction to a cassette written by `save`. Returns false,
+/// writing nothing, when the file does not end as `save` left it.
+fn append(path: &Path, interaction: Option<&Interaction>, first: bool) -> Result<bool, String> {
+    let error = |e: std::io::Error| format!("Cannot write cassette {}: {}", path.display(), e);
+    let Some(interaction) = interaction else {
+        return Ok(false);
+    };
+    let mut file = match fs::OpenOptions::new().read(true).write(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(error(e)),
+    };
+    let Some(end) = file
+        .metadata()
+        .map_err(error)?
+        .len()
+        .checked_sub(FILE_END.len() as u64)
+    else {
+        return Ok(false);
+    };
+    let mut tail = Vec::new();
+    file.seek(SeekFrom::Start(end)).map_err(error)?;
+    file.read_to_end(&mut tail).map_err(error)?;
+    if tail != FILE_END.as_bytes() {
+        return Ok(false);
+    }
+    let json = format!(
+        "{}{}{}",
+        if first { "\n    " } else { ",\n    " },
+        to_json(interaction)?,
+        FILE_END
+    );
+    file.seek(SeekFrom::Start(end)).map_err(error)?;
+    file.write_all(json.as_bytes()).map_err(error)?;
+    Ok(true)
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| format!("Cannot serialize cassette: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn interaction(path: &str, body: Value) -> Interaction {
+        Interaction {
+            request: RecordedRequest {
+                method: "GET".to_string(),
+                path: path.to_string(),
+                body: None,
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: BTreeMap::new(),
+                body,
+                text: false,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_path_and_order() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join("cassette.json");
+        let file = CassetteFile {
+            redacted_fields: BTreeSet::new(),
+            interactions: vec![
+                interaction("/api/card?f=all", serde_json::json!([{"id": 1}])),
+                interaction("/api/card?f=all", serde_json::json!([{"id": 2}])),
+            ],
+        };
+        save(&path, &file).expect("Failed to save cassette");
+
+        let cassette = Cassette::replay_from(&path).expect("Failed to load cassette");
+        let client = Client::new();
+        let request = || {
+            client
+                .get("http://replayed.invalid/api/card?f=all")
+                .build()
+                .unwrap()
+        };
+        let mut bodies = Vec::new();
+        for _ in 0..3 {
+            let response = cassette.replay(&request()).expect("Recorded response");
+            assert_eq!(response.status(), StatusCode::OK);
+            bodies.push(response.text().await.unwrap());
+        }
+        // Recorded order first, then the last match again
+        assert_eq!(bodies, [r#"[{"id":1}]"#, r#"[{"id":2}]"#, r#"[{"id":2}]"#]);
+
+        let missing = client
+            .get("http://replayed.invalid/api/database")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            cassette.replay(&missing),
+            Err(TransportError::Cassette(ApiError::Http { status: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_append_keeps_cassette_valid() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join("cassette.json");
+        let first = interaction("/api/card", serde_json::json!([]));
+        let second = interaction("/api/database", serde_json::json!({"data": []}));
+
+        // Nothing to append to yet
+        assert!(!append(&path, Some(&first), true).unwrap());
+
+        let mut file = CassetteFile {
+            redacted_fields: BTreeSet::from(["email".to_string()]),
+            interactions: Vec::new(),
+        };
+        save(&path, &file).unwrap();
+        assert_eq!(load(&path).unwrap(), file);
+        for (i, interaction) in [first, second].into_iter().enumerate() {
+            assert!(append(&path, Some(&interaction), i == 0).unwrap());
+            file.interactions.push(interaction);
+            assert_eq!(load(&path).unwrap(), file);
+        }
+
+        // A file edited by hand is rewritten instead
+        let pretty = serde_json::to_string_pretty(&file).unwrap();
+        fs::write(&path, pretty).unwrap();
+        assert!(!append(&path, file.interactions.first(), false).unwrap());
+    }
+
+    #[test]
+    fn test_record_redacts_fields() {
+        let dir = tempdir().expect("Failed to create temp dir");
+        let cassette = Cassette::record_to(dir.path().join("c.json"), ["email"])
+            .expect("Failed to create cassette");
+
+        let request = Client::new()
+            .post("http://recorded.invalid/api/session")
+            .json(&serde_json::json!({"username": "a@b.c", "password": "secret"}))
+            .build()
+            .unwrap();
+        let recorded = cassette.recorded_request(&request);
+        assert_eq!(recorded.path, "/api/session");
+        assert_eq!(
+            recorded.body,
+            Some(serde_json::json!({"username": "a@b.c", "password": REDACTED}))
+        );
+
+        let mut body = serde_json::json!({"data": [{"email": "a@b.c", "name": "Ann"}]});
+        cassette.redact(&mut body);
+        assert_eq!(
+            body,
+            serde_json::json!({"data": [{"email": REDACTED, "name": "Ann"}]})
+        );
+    }
+}
@@ -137,7 +137,7 @@ where
 }
 
 /// Cache statistics
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct CacheStats {
     pub total_entries: usize,
     pub active_entries: usize,
@@ -0,0 +1,395 @@
+//! Column profiling for query results.
+//!
+//! Computes per-column distribution statistics: distinct and null counts,
+//! the most frequent values, min/max/mean/percentiles for numeric and date
+//! columns, and a histogram that renders as a sparkline. Used by the TUI
+//! profile popup and `mbr-cli run --profile`.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api::models::QueryResult;
+
+/// Number of most frequent values kept in a profile.
+pub const TOP_VALUES: usize = 5;
+
+/// Number of histogram bins for numeric and date columns.
+pub const HISTOGRAM_BINS: usize = 16;
+
+/// How the values of a column are interpreted for statistics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileKind {
+    #[default]
+    Text,
+    Number,
+    /// Dates and datetimes (ISO 8601 text)
+    Date,
+}
+
+impl ProfileKind {
+    /// Map a Metabase base type such as `type/Integer` or `type/DateTime`.
+    pub fn from_base_type(base_type: &str) -> Self {
+        let base = base_type.trim_start_matches("type/");
+        match base {
+            "Integer" | "BigInteger" | "Float" | "Decimal" | "Number" => ProfileKind::Number,
+            _ if base.starts_with("Date") => ProfileKind::Date,
+            _ => ProfileKind::Text,
+        }
+    }
+}
+
+/// A value and how often it occurs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueCount {
+    pub value: String,
+    pub count: usize,
+}
+
+/// Distribution of a numeric or date column, formatted for display.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DistributionStats {
+    pub min: String,
+    pub max: String,
+    pub mean: String,
+    pub p25: String,
+    pub median: String,
+    pub p75: String,
+    pub p95: String,
+}
+
+/// Profile of a single result column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnProfile {
+    pub column: String,
+    pub kind: ProfileKind,
+    /// Number of rows profiled (including nulls)
+    pub count: usize,
+    pub nulls: usize,
+    /// Number of distinct non-null values
+    pub distinct: usize,
+    /// Most frequent values, most common first
+    pub top_values: Vec<ValueCount>,
+    /// Statistics for numeric and date columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<DistributionStats>,
+    /// Equal-width histogram between min and max (empty for text)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub histogram: Vec<usize>,
+}
+
+/// Profile a column from its cell values (`None` is NULL).
+///
+/// Text columns whose values all parse as numbers are profiled as numbers.
+/// Values that do not parse are left out of the statistics.
+pub fn profile_column(name: &str, kind: ProfileKind, values: &[Option<&str>]) -> ColumnProfile {
+    let present: Vec<&str> = values.iter().flatten().copied().collect();
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in &present {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    let distinct = counts.len();
+    let mut top_values: Vec<ValueCount> = counts
+        .into_iter()
+        .map(|(value, count)| ValueCount {
+            value: value.to_string(),
+            count,
+        })
+        .collect();
+    top_values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    top_values.truncate(TOP_VALUES);
+
+    let kind = match kind {
+        ProfileKind::Text
+            if !present.is_empty() && present.iter().all(|v| parse_number(v).is_some()) =>
+        {
+            ProfileKind::Number
+        }
+        kind => kind,
+    };
+
+    let (stats, histogram) = match kind {
+        ProfileKind::Number => {
+            let nums: Vec<f64> = present.iter().filter_map(|v| parse_number(v)).collect();
+            distribution(nums, format_number)
+        }
+        ProfileKind::Date => {
+            let parsed: Vec<(i64, bool)> = present.iter().filter_map(|v| parse_date(v)).collect();
+            let with_time = parsed.iter().any(|&(_, has_time)| has_time);
+            let secs = parsed.iter().map(|&(s, _)| s as f64).collect();
+            distribution(secs, |s| format_date(s.round() as i64, with_time))
+        }
+        ProfileKind::Text => (None, Vec::new()),
+    };
+
+    ColumnProfile {
+        column: name.to_string(),
+        kind,
+        count: values.len(),
+        nulls: values.len() - present.len(),
+        distinct,
+        top_values,
+        stats,
+        histogram,
+    }
+}
+
+/// Profile every column of a query result.
+pub fn profile_query_result(result: &QueryResult) -> Vec<ColumnProfile> {
+    let cells: Vec<Vec<Option<String>>> = result
+        .data
+        .rows
+        .iter()
+        .map(|row| row.iter().map(cell_text).collect())
+        .collect();
+
+    result
+        .data
+        .cols
+        .iter()
+        .enumerate()
+        .map(|(idx, col)| {
+            let values: Vec<Option<&str>> = cells
+                .iter()
+                .map(|row| row.get(idx).and_then(|c| c.as_deref()))
+                .collect();
+            profile_column(
+                &col.display_name,
+                ProfileKind::from_base_type(&col.base_type),
+                &values,
+            )
+        })
+        .collect()
+}
+
+/// Render histogram bins as a block sparkline (empty bins are blank).
+pub fn sparkline(bins: &[usize]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = bins.iter().copied().max().unwrap_or(0);
+    bins.iter()
+        .map(|&n| match n {
+            0 => ' ',
+            _ => BARS[(n * BARS.len()).div_ceil(max) - 1],
+        })
+        .collect()
+}
+
+/// Format a number for display: integers without decimals, otherwise
+/// up to two decimals.
+pub fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        let s = format!("{:.2}", value);
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Statistics and histogram of numeric values, formatted with `format`.
+fn distribution(
+    mut values: Vec<f64>,
+    format: impl Fn(f64) -> String,
+) -> (Option<DistributionStats>, Vec<usize>) {
+    if values.is_empty() {
+        return (None, Vec::new());
+    }
+    values.sort_by(f64::total_cmp);
+    let min = values[0];
+    let max = values[values.len() - 1];
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+
+    let stats = DistributionStats {
+        min: format(min),
+        max: format(max),
+        mean: format(mean),
+        p25: format(percentile(&values, 25.0)),
+        median: format(percentile(&values, 50.0)),
+        p75: format(percentile(&values, 75.0)),
+        p95: format(percentile(&values, 95.0)),
+    };
+
+    let histogram = if max > min {
+        let mut bins = vec![0; HISTOGRAM_BINS];
+        let width = (max - min) / HISTOGRAM_BINS as f64;
+        for v in &values {
+            let bin = (((v - min) / width) as usize).min(HISTOGRAM_BINS - 1);
+            bins[bin] += 1;
+        }
+        bins
+    } else {
+        vec![values.len()]
+    };
+
+    (Some(stats), histogram)
+}
+
+/// Percentile of sorted values, interpolating between the closest ranks.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+/// Parse `YYYY-MM-DD` with an optional `THH:MM[:SS]` (or space-separated)
+/// time into seconds since the epoch. Fractions and time zones are ignored.
+/// Returns whether a time was present.
+fn parse_date(value: &str) -> Option<(i64, bool)> {
+    let value = value.trim();
+    let date = value.get(..10)?;
+    let mut parts = date.split('-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+
+    let rest = &value[10..];
+    let time = rest.strip_prefix('T').or_else(|| rest.strip_prefix(' '));
+    let secs = match time {
+        Some(time) if time.len() >= 5 => {
+            let hours: i64 = time.get(0..2)?.parse().ok()?;
+            let minutes: i64 = time.get(3..5)?.parse().ok()?;
+            let seconds: i64 = time.get(6..8).and_then(|s| s.parse().ok()).unwrap_or(0);
+            hours * 3600 + minutes * 60 + seconds
+        }
+        _ => return Some((days * 86400, false)),
+    };
+    Some((days * 86400 + secs, true))
+}
+
+/// Format seconds since the epoch as `YYYY-MM-DD` or `YYYY-MM-DD HH:MM:SS`.
+fn format_date(secs: i64, with_time: bool) -> String {
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    if !with_time {
+        return format!("{:04}-{:02}-{:02}", year, month, day);
+    }
+    let time = secs.rem_euclid(86400);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Proleptic Gregorian date of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+/// Cell as profile input: `None` for NULL, display text otherwise.
+fn cell_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_numeric_column() {
+        let values = [Some("1"), Some("2"), None, Some("2"), Some("x"), Some("10")];
+        let profile = profile_column("amount", ProfileKind::Number, &values);
+        assert_eq!(profile.count, 6);
+        assert_eq!(profile.nulls, 1);
+        assert_eq!(profile.distinct, 4);
+        assert_eq!(
+            profile.top_values[0],
+            ValueCount {
+                value: "2".to_string(),
+                count: 2
+            }
+        );
+
+        // "x" does not parse and is left out of the statistics
+        let stats = profile.stats.unwrap();
+        assert_eq!((stats.min.as_str(), stats.max.as_str()), ("1", "10"));
+        assert_eq!(stats.mean, "3.75");
+        assert_eq!(stats.median, "2");
+        assert_eq!(stats.p25, "1.75");
+        assert_eq!(profile.histogram.len(), HISTOGRAM_BINS);
+        assert_eq!(profile.histogram.iter().sum::<usize>(), 4);
+        assert_eq!(profile.histogram[..2], [1, 2]);
+        assert_eq!(profile.histogram[HISTOGRAM_BINS - 1], 1);
+    }
+
+    #[test]
+    fn test_profile_date_and_text_columns() {
+        let values = [Some("2024-01-01"), Some("2024-01-03T00:00:00"), None];
+        let profile = profile_column("created", ProfileKind::Date, &values);
+        let stats = profile.stats.unwrap();
+        assert_eq!(stats.min, "2024-01-01 00:00:00");
+        assert_eq!(stats.mean, "2024-01-02 00:00:00");
+
+        let values = [Some("2023-12-31"), Some("2024-03-01")];
+        let stats = profile_column("d", ProfileKind::Date, &values)
+            .stats
+            .unwrap();
+        assert_eq!(
+            (stats.min.as_str(), stats.max.as_str()),
+            ("2023-12-31", "2024-03-01")
+        );
+
+        // Untyped numbers are profiled as numbers; other text has no stats
+        let profile = profile_column("n", ProfileKind::Text, &[Some("3"), Some("4")]);
+        assert_eq!(profile.kind, ProfileKind::Number);
+        let profile = profile_column("s", ProfileKind::Text, &[Some("a"), Some("b")]);
+        assert_eq!(profile.stats, None);
+        assert!(profile.histogram.is_empty());
+    }
+
+    #[test]
+    fn test_sparkline_and_base_types() {
+        assert_eq!(sparkline(&[0, 1, 4, 8]), " ▁▄█");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(
+            ProfileKind::from_base_type("type/DateTimeWithLocalTZ"),
+            ProfileKind::Date
+        );
+        assert_eq!(
+            ProfileKind::from_base_type("type/Float"),
+            ProfileKind::Number
+        );
+        assert_eq!(
+            ProfileKind::from_base_type("type/Boolean"),
+            ProfileKind::Text
+        );
+    }
+}
@@ -1,17 +1,21 @@
 use super::types::{ExecuteParams, ListParams};
 use crate::AppError;
-use crate::api::client::MetabaseClient;
 use crate::api::models::{QueryResult, Question};
+use crate::api::traits::MetabaseApi;
+use std::sync::Arc;
 
 /// Question service for managing Metabase questions and queries
 pub struct QuestionService {
-    client: MetabaseClient,
+    client: Arc<dyn MetabaseApi>,
 }
 
 impl QuestionService {
-    /// Create new QuestionService instance
-    pub fn new(client: MetabaseClient) -> Self {
-        Self { client }
+    /// Create new QuestionService instance on any `MetabaseApi`
+    /// implementation (a `MetabaseClient`, a fake, a decorator)
+    pub fn new(client: impl MetabaseApi + 'static) -> Self {
+        Self {
+            client: Arc::new(client),
+        }
     }
 
     /// List questions with optional filtering
@@ -58,6 +62,7 @@ impl QuestionService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::client::MetabaseClient;
 
     #[tokio::test]
     async fn test_get_question_details_returns_result() {
@@ -509,11 +509,13 @@ mod tests {
                         name: "id".to_string(),
                         display_name: "ID".to_string(),
                         base_type: "type/Integer".to_string(),
+                        ..Default::default()
                     },
                     Column {
                         name: "name".to_string(),
                         display_name: "Name".to_string(),
                         base_type: "type/Text".to_string(),
+                        ..Default::default()
                     },
                 ],
                 rows: vec![
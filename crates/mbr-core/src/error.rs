@@ -36,6 +36,12 @@ pub enum CliError {
 pub enum ApiError {
     #[error("Request timed out after {timeout_secs}s")]
     Timeout { timeout_secs: u64, endpoint: String },
+    #[error("{kind}: {message}")]
+    Connection {
+        kind: ConnectionErrorKind,
+        endpoint: String,
+        message: String,
+    },
     #[error("HTTP error: {status} {message}")]
     Http {
         status: u16,
@@ -56,6 +62,34 @@ pub enum ApiError {
     },
 }
 
+/// Why no connection to the server could be made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionErrorKind {
+    /// The host name could not be resolved
+    Dns,
+    /// The TLS handshake failed (untrusted or invalid certificate, protocol mismatch)
+    Tls,
+    /// Nothing is listening on the port
+    Refused,
+    /// The proxy could not be reached or refused the tunnel
+    Proxy,
+    /// Any other connection failure
+    Other,
+}
+
+impl std::fmt::Display for ConnectionErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConnectionErrorKind::Dns => "DNS lookup failed",
+            ConnectionErrorKind::Tls => "TLS handshake failed",
+            ConnectionErrorKind::Refused => "Connection refused",
+            ConnectionErrorKind::Proxy => "Proxy connection failed",
+            ConnectionErrorKind::Other => "Connection failed",
+        })
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum AuthError {
     #[error("Not logged in")]
@@ -160,6 +194,7 @@ impl AppError {
                 ApiError::Unauthorized { .. } => ErrorSeverity::High,
                 ApiError::Forbidden { .. } => ErrorSeverity::Medium,
                 ApiError::Timeout { .. } => ErrorSeverity::Medium,
+                ApiError::Connection { .. } => ErrorSeverity::High,
                 ApiError::Http { status, .. } if *status >= 500 => ErrorSeverity::High,
                 _ => ErrorSeverity::Medium,
             },
@@ -187,6 +222,13 @@ impl AppError {
             },
             AppError::Api(e) => match e {
                 ApiError::Timeout { .. } => "API_TIMEOUT",
+                ApiError::Connection { kind, .. } => match kind {
+                    ConnectionErrorKind::Dns => "API_DNS_ERROR",
+                    ConnectionErrorKind::Tls => "API_TLS_ERROR",
+                    ConnectionErrorKind::Refused => "API_CONNECTION_REFUSED",
+                    ConnectionErrorKind::Proxy => "API_PROXY_ERROR",
+                    ConnectionErrorKind::Other => "API_CONNECTION_ERROR",
+                },
                 ApiError::Http { .. } => "API_HTTP_ERROR",
                 ApiError::Unauthorized { .. } => "API_UNAUTHORIZED",
                 ApiError::Forbidden { .. } => "API_FORBIDDEN",
@@ -261,8 +303,15 @@ impl AppError {
                 Some("Run 'mbr-cli config set --url <url>' to create a configuration".to_string())
             }
             AppError::Api(ApiError::Timeout { .. }) => {
-                Some("Check your network connection and Metabase server availability".to_string())
+                Some("Check your network connection and Metabase server availability.\nFor long queries, raise [http] query_timeout or pass --timeout 0 (no limit).".to_string())
             }
+            AppError::Api(ApiError::Connection { kind, .. }) => Some(match kind {
+                ConnectionErrorKind::Dns => "Check the Metabase URL and your DNS or VPN connection".to_string(),
+                ConnectionErrorKind::Tls => "Add your CA to [http] ca_bundles (or MBR_CA_BUNDLE), or check the client certificate".to_string(),
+                ConnectionErrorKind::Refused => "Check that Metabase is running and the URL's port is correct".to_string(),
+                ConnectionErrorKind::Proxy => "Check [http] proxy (or MBR_PROXY / HTTPS_PROXY) and no_proxy".to_string(),
+                ConnectionErrorKind::Other => "Check your network connection and Metabase server availability".to_string(),
+            }),
             AppError::Api(ApiError::Unauthorized { .. }) => {
                 Some("Your session may be expired. Run 'mbr-cli login' to re-authenticate.".to_string())
             }
@@ -461,6 +510,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connection_error() {
+        let error = AppError::Api(ApiError::Connection {
+            kind: ConnectionErrorKind::Tls,
+            endpoint: "/api/card".into(),
+            message: "certificate verify failed".into(),
+        });
+        assert_eq!(error.error_code(), "API_TLS_ERROR");
+        assert_eq!(
+            error.to_string(),
+            "ApiError: TLS handshake failed: certificate verify failed"
+        );
+        assert!(error.troubleshooting_hint().unwrap().contains("ca_bundles"));
+    }
+
     #[test]
     fn test_error_code_api() {
         assert_eq!(
@@ -71,6 +71,7 @@ pub mod prelude {
     // API client and models
     pub use crate::api::client::MetabaseClient;
     pub use crate::api::models::{Collection, QueryResult, Question};
+    pub use crate::api::traits::MetabaseApi;
 
     // Services
     pub use crate::core::services::config_service::ConfigService;
@@ -91,6 +92,7 @@ pub mod prelude {
 /// Contains the service layer that orchestrates API calls and business rules:
 /// - [`core::services::config_service`]: Configuration management
 /// - [`core::services::question_service`]: Question operations
+/// - [`core::profile`]: Column profiling (distribution statistics) of query results
 pub mod core;
 
 /// Storage layer - configuration and credential persistence.
@@ -98,6 +100,7 @@ pub mod core;
 /// Manages persistent data:
 /// - [`storage::config`]: TOML configuration with multi-profile support
 /// - [`storage::credentials`]: Session token persistence and environment credential retrieval
+/// - [`storage::tui_state`]: TUI state kept between sessions (column layouts)
 pub mod storage;
 
 /// Utilities layer - shared helpers and common functionality.
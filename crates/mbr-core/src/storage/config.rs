@@ -4,18 +4,292 @@
 //! Priority: CLI argument > MBR_URL environment variable > config.toml
 
 use super::Result;
-use crate::error::StorageError;
+use crate::api::client::Timeouts;
+use crate::error::{ConfigError, StorageError};
+use crate::storage::response_cache::ResponseCache;
+use crate::utils::retry::RetryConfig;
 use dirs;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Application configuration
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Config {
     /// Metabase server URL
     pub url: Option<String>,
+    /// Terminal UI settings (`[tui]` section)
+    #[serde(default, skip_serializing_if = "TuiConfig::is_empty")]
+    pub tui: TuiConfig,
+    /// HTTP client settings (`[http]` section)
+    #[serde(default, skip_serializing_if = "HttpConfig::is_empty")]
+    pub http: HttpConfig,
+    /// Response cache settings (`[cache]` section)
+    #[serde(default, skip_serializing_if = "CacheConfig::is_empty")]
+    pub cache: CacheConfig,
+}
+
+/// Response cache settings.
+///
+/// ```toml
+/// [cache]
+/// enabled = true
+///
+/// [cache.ttl]      # seconds per resource
+/// cards = 60
+/// tables = 86400
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CacheConfig {
+    /// Whether metadata responses are cached on disk (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// TTL overrides by resource name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ttl: BTreeMap<String, u64>,
+}
+
+impl CacheConfig {
+    pub fn is_empty(&self) -> bool {
+        self.enabled.is_none() && self.ttl.is_empty()
+    }
+
+    /// The response cache of an identity (see
+    /// [`identity_name`](crate::storage::response_cache::identity_name)) on a
+    /// Metabase URL, or None when disabled.
+    pub fn response_cache(&self, base_url: &str, identity: &str) -> Option<ResponseCache> {
+        if self.enabled == Some(false) {
+            return None;
+        }
+        let cache = ResponseCache::for_profile(base_url, identity)?;
+        Some(self.ttl.iter().fold(cache, |cache, (resource, secs)| {
+            cache.with_ttl(resource, std::time::Duration::from_secs(*secs))
+        }))
+    }
+}
+
+/// HTTP client settings.
+///
+/// ```toml
+/// [http]
+/// retry_policy = "aggressive"
+/// retries = 8
+/// proxy = "http://proxy.corp:3128"
+/// no_proxy = "localhost,.corp"
+/// ca_bundles = ["/etc/ssl/corp-root.pem"]
+/// client_cert = "/etc/mbr-cli/client.pem"
+/// client_key = "/etc/mbr-cli/client.key"
+/// connect_timeout = 5   # seconds; 0 waits indefinitely
+/// read_timeout = 30
+/// query_timeout = 0
+/// ```
+///
+/// Proxy and TLS settings missing here are read from `MBR_PROXY`,
+/// `MBR_NO_PROXY`, `MBR_CA_BUNDLE` (a path list), `MBR_CLIENT_CERT`,
+/// `MBR_CLIENT_KEY` and `MBR_INSECURE_SKIP_VERIFY` (1, true, yes or on);
+/// without a proxy setting the standard `HTTPS_PROXY`/`NO_PROXY` variables
+/// apply, and `NO_PROXY` also applies to a proxy without `no_proxy`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HttpConfig {
+    /// Retry preset: "default", "quick", "aggressive" or "none"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<String>,
+    /// Maximum retries of a failed request (overrides the preset's)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// Proxy for HTTP and HTTPS requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Comma-separated hosts and domains reached without the proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// PEM files with extra trusted root certificates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ca_bundles: Vec<PathBuf>,
+    /// PEM client certificate for mutual TLS (with `client_key`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<PathBuf>,
+    /// PEM (PKCS#8) private key of `client_cert`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<PathBuf>,
+    /// Accept any server certificate (local development only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub insecure_skip_verify: bool,
+    /// Seconds to establish a connection (default: 10, 0 = no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout: Option<u64>,
+    /// Seconds for login and metadata requests (default: 30, 0 = no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout: Option<u64>,
+    /// Seconds for query execution (default: 60, 0 = no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_timeout: Option<u64>,
+}
+
+impl HttpConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill proxy and TLS settings missing from the config file from their
+    /// `MBR_*` environment variables (as `get_url` falls back to `MBR_URL`).
+    pub fn with_env_fallback(&self) -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let mut http = self.clone();
+        http.proxy = http.proxy.or_else(|| var("MBR_PROXY"));
+        http.no_proxy = http.no_proxy.or_else(|| var("MBR_NO_PROXY"));
+        if http.ca_bundles.is_empty()
+            && let Some(paths) = var("MBR_CA_BUNDLE")
+        {
+            http.ca_bundles = std::env::split_paths(&paths).collect();
+        }
+        http.client_cert = http
+            .client_cert
+            .or_else(|| var("MBR_CLIENT_CERT").map(PathBuf::from));
+        http.client_key = http
+            .client_key
+            .or_else(|| var("MBR_CLIENT_KEY").map(PathBuf::from));
+        http.insecure_skip_verify |= var("MBR_INSECURE_SKIP_VERIFY").is_some_and(|v| is_truthy(&v));
+        http
+    }
+
+    /// Request time limits, with defaults for unset values.
+    pub fn timeouts(&self) -> Timeouts {
+        let limit = |secs: Option<u64>, default: Option<Duration>| match secs {
+            None => default,
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+        };
+        let defaults = Timeouts::default();
+        Timeouts {
+            connect: limit(self.connect_timeout, defaults.connect),
+            read: limit(self.read_timeout, defaults.read),
+            query: limit(self.query_timeout, defaults.query),
+        }
+    }
+
+    /// Retry policy for API requests.
+    pub fn retry_config(&self) -> std::result::Result<RetryConfig, ConfigError> {
+        let mut retry = match self.retry_policy.as_deref() {
+            None => RetryConfig::default(),
+            Some(name) => RetryConfig::preset(name).ok_or_else(|| ConfigError::InvalidValue {
+                field: "http.retry_policy".to_string(),
+                value: name.to_string(),
+                reason: "expected default, quick, aggressive or none".to_string(),
+            })?,
+        };
+        if let Some(retries) = self.retries {
+            retry.max_retries = retries;
+        }
+        Ok(retry)
+    }
+}
+
+/// Terminal UI settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TuiConfig {
+    /// Theme name: a built-in theme or a file in `~/.config/mbr-cli/themes/`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Key bindings (`[tui.keys]` section)
+    #[serde(default, skip_serializing_if = "KeysConfig::is_empty")]
+    pub keys: KeysConfig,
+}
+
+impl TuiConfig {
+    pub fn is_empty(&self) -> bool {
+        self.theme.is_none() && self.keys.is_empty()
+    }
+}
+
+/// User-defined TUI theme, read from `~/.config/mbr-cli/themes/<name>.toml`.
+///
+/// ```toml
+/// base = "dark"
+/// accent = "#268bd2"
+/// muted = "gray"
+/// ```
+///
+/// Color slot names and values are interpreted by the TUI.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ThemeFile {
+    /// Built-in theme the file starts from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// Slot name -> color
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
+impl ThemeFile {
+    /// Load a named theme from the themes directory.
+    pub fn load(name: &str) -> Result<Self> {
+        let path = Config::themes_dir()?.join(format!("{}.toml", name));
+        Self::load_from(&path)
+    }
+
+    /// Load a theme from an explicit path.
+    pub fn load_from(path: &std::path::Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|source| StorageError::FileIo {
+            path: path.to_string_lossy().to_string(),
+            source,
+        })?;
+
+        let theme: ThemeFile =
+            toml::from_str(&content).map_err(|e| StorageError::ConfigParseError {
+                message: format!("Failed to parse theme file: {}", e),
+            })?;
+
+        Ok(theme)
+    }
+}
+
+/// Key binding overrides.
+///
+/// ```toml
+/// [tui.keys]
+/// preset = "emacs"
+/// quit = ["q", "ctrl+c"]
+/// command_palette = "alt+x"
+/// ```
+///
+/// Action names and key syntax are interpreted by the TUI; an action listed
+/// here replaces all of the preset's keys for that action.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct KeysConfig {
+    /// Base preset name (e.g. "default", "vim", "emacs")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    /// Action name -> key(s)
+    #[serde(flatten)]
+    pub bindings: BTreeMap<String, KeyList>,
+}
+
+impl KeysConfig {
+    pub fn is_empty(&self) -> bool {
+        self.preset.is_none() && self.bindings.is_empty()
+    }
+}
+
+/// One key or a list of keys bound to an action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    /// All key specs in this entry.
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
 }
 
 impl Config {
@@ -70,12 +344,32 @@ impl Config {
     }
 
     fn config_file_path() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("config.toml"))
+    }
+
+    fn config_dir() -> Result<PathBuf> {
         let home_dir = dirs::home_dir().ok_or(StorageError::ConfigDirNotFound)?;
+        Ok(home_dir.join(".config").join("mbr-cli"))
+    }
 
-        let app_config_dir = home_dir.join(".config").join("mbr-cli");
-        let config_file = app_config_dir.join("config.toml");
+    /// Directory holding user-defined TUI themes.
+    pub fn themes_dir() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("themes"))
+    }
 
-        Ok(config_file)
+    /// Directory holding the response caches of every profile.
+    pub fn cache_dir() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("cache"))
+    }
+
+    /// Directory holding the offline metadata snapshots.
+    pub fn snapshots_dir() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("snapshots"))
+    }
+
+    /// Default Unix socket of the `mbr-cli serve` daemon.
+    pub fn daemon_socket_path() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("daemon.sock"))
     }
 
     /// Get URL with fallback to environment variable.
@@ -98,6 +392,14 @@ impl Config {
     }
 }
 
+/// Whether an environment flag is explicitly switched on.
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +441,156 @@ mod tests {
         assert_eq!(loaded_config.url, Some("http://example.test".to_string()));
     }
 
+    #[test]
+    fn test_config_tui_keys() {
+        let config: Config = toml::from_str(
+            r#"
+url = "http://example.test"
+
+[tui.keys]
+preset = "emacs"
+quit = ["q", "ctrl+c"]
+command_palette = "alt+x"
+"#,
+        )
+        .expect("Failed to parse config");
+
+        let keys = &config.tui.keys;
+        assert_eq!(keys.preset.as_deref(), Some("emacs"));
+        assert_eq!(keys.bindings["quit"].keys(), vec!["q", "ctrl+c"]);
+        assert_eq!(keys.bindings["command_palette"].keys(), vec!["alt+x"]);
+
+        // Empty TUI section is not written back
+        let plain = Config {
+            url: Some("http://example.test".to_string()),
+            ..Default::default()
+        };
+        assert!(!toml::to_string(&plain).unwrap().contains("tui"));
+    }
+
+    #[test]
+    fn test_config_cache() {
+        let config: Config = toml::from_str(
+            r#"
+[cache]
+enabled = false
+
+[cache.ttl]
+cards = 60
+"#,
+        )
+        .expect("Failed to parse config");
+
+        assert_eq!(config.cache.ttl["cards"], 60);
+        assert!(
+            config
+                .cache
+                .response_cache("http://mb.test", "anonymous")
+                .is_none()
+        );
+        assert!(
+            !toml::to_string(&Config::default())
+                .unwrap()
+                .contains("cache")
+        );
+    }
+
+    #[test]
+    fn test_config_http_retry() {
+        let config: Config = toml::from_str(
+            r#"
+[http]
+retry_policy = "quick"
+retries = 4
+"#,
+        )
+        .expect("Failed to parse config");
+
+        let retry = config.http.retry_config().expect("valid retry policy");
+        assert_eq!(retry.max_retries, 4);
+        assert_eq!(retry.initial_delay, RetryConfig::quick().initial_delay);
+
+        assert_eq!(
+            Config::default().http.retry_config().unwrap().max_retries,
+            2
+        );
+
+        let unknown = HttpConfig {
+            retry_policy: Some("forever".to_string()),
+            ..Default::default()
+        };
+        assert!(unknown.retry_config().is_err());
+    }
+
+    #[test]
+    fn test_config_http_tls() {
+        let config: Config = toml::from_str(
+            r#"
+[http]
+proxy = "http://proxy.corp:3128"
+no_proxy = "localhost,.corp"
+ca_bundles = ["/etc/ssl/corp.pem", "/etc/ssl/extra.pem"]
+client_cert = "/etc/mbr-cli/client.pem"
+client_key = "/etc/mbr-cli/client.key"
+"#,
+        )
+        .expect("Failed to parse config");
+
+        assert_eq!(config.http.ca_bundles.len(), 2);
+        assert_eq!(
+            config.http.client_key,
+            Some(PathBuf::from("/etc/mbr-cli/client.key"))
+        );
+        assert!(!config.http.insecure_skip_verify);
+        // Settings from the file win over environment variables
+        assert_eq!(
+            config.http.with_env_fallback().proxy.as_deref(),
+            Some("http://proxy.corp:3128")
+        );
+        assert!(HttpConfig::default().is_empty());
+    }
+
+    #[test]
+    fn test_is_truthy() {
+        for value in ["1", "true", "TRUE", "yes", "On", " on "] {
+            assert!(is_truthy(value), "{value}");
+        }
+        for value in ["", "0", "false", "no", "off", "disabled", "2"] {
+            assert!(!is_truthy(value), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_config_http_timeouts() {
+        let config: Config = toml::from_str(
+            r#"
+[http]
+connect_timeout = 5
+query_timeout = 0
+"#,
+        )
+        .expect("Failed to parse config");
+
+        let timeouts = config.http.timeouts();
+        assert_eq!(timeouts.connect, Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.read, Timeouts::default().read);
+        assert_eq!(timeouts.query, None);
+        assert_eq!(HttpConfig::default().timeouts(), Timeouts::default());
+    }
+
+    #[test]
+    fn test_theme_file_load() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("ocean.toml");
+        fs::write(&path, "base = \"light\"\naccent = \"#268bd2\"\n").unwrap();
+
+        let theme = ThemeFile::load_from(&path).expect("Failed to load theme");
+        assert_eq!(theme.base.as_deref(), Some("light"));
+        assert_eq!(theme.colors["accent"], "#268bd2");
+
+        assert!(ThemeFile::load_from(&temp_dir.path().join("missing.toml")).is_err());
+    }
+
     #[test]
     fn test_load_nonexistent_file() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
@@ -3,6 +3,7 @@
 //! This module handles session-based authentication with Metabase.
 //! Sessions are stored in ~/.config/mbr-cli/session.json.
 //! Username/password can be provided via MBR_USERNAME/MBR_PASSWORD environment variables.
+//! A session may instead hold a Metabase API key, sent in place of a session token.
 
 use serde::{Deserialize, Serialize};
 use std::env;
@@ -11,12 +12,15 @@ use std::path::PathBuf;
 use std::time::SystemTime;
 
 /// Stored session data
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Session {
     pub session_token: String,
     pub url: String,
     pub username: String,
     pub created_at: String,
+    /// API key used instead of the session token (empty token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
 }
 
 /// Format current time as ISO 8601 string (e.g., "2026-03-30T12:00:00Z").
@@ -24,7 +28,11 @@ pub fn now_iso8601() -> String {
     let dur = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap_or_default();
-    let secs = dur.as_secs();
+    iso8601(dur.as_secs())
+}
+
+/// Format a Unix time (seconds) as an ISO 8601 string.
+pub fn iso8601(secs: u64) -> String {
     let days = secs / 86400;
     let time_of_day = secs % 86400;
     let hours = time_of_day / 3600;
@@ -125,8 +133,10 @@ mod tests {
             url: "http://localhost:3000".to_string(),
             username: "test@example.com".to_string(),
             created_at: "2026-03-30T12:00:00Z".to_string(),
+            api_key: None,
         };
         let json = serde_json::to_string(&session).unwrap();
+        assert!(!json.contains("api_key"));
         let deserialized: Session = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.session_token, "test-token-123");
         assert_eq!(deserialized.url, "http://localhost:3000");
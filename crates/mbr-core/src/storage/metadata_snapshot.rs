@@ -0,0 +1,704 @@
+//! Offline metadata snapshot
+//!
+//! `mbr-cli sync-metadata` downloads databases, schemas, tables with their
+//! fields, questions and collections into
+//! ~/.config/mbr-cli/snapshots/<profile>.json, where the profile is derived
+//! from the Metabase URL. With `--offline` the CLI and the TUI answer metadata
+//! lookups from this snapshot instead of the server, telling how old it is.
+
+use crate::api::models::{
+    CollectionEntry, CollectionItem, CurrentUser, Database, Field, ForeignKey, QueryResult,
+    Question, QuestionDetail, TableInfo, TableMetadata,
+};
+use crate::api::traits::MetabaseApi;
+use crate::error::{ApiError, AppError, CliError, StorageError};
+use crate::storage::Result;
+use crate::storage::config::Config;
+use crate::storage::credentials::iso8601;
+use crate::storage::response_cache::profile_name;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Metadata of one Metabase instance at the time of the last sync.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct MetadataSnapshot {
+    /// Metabase URL the snapshot was taken from
+    pub url: String,
+    /// Unix time of the sync
+    pub synced_at: u64,
+    pub databases: Vec<Database>,
+    /// Schema names by database ID
+    pub schemas: BTreeMap<u32, Vec<String>>,
+    /// Tables by database ID
+    pub tables: BTreeMap<u32, Vec<TableInfo>>,
+    /// Tables with their fields by table ID
+    pub table_metadata: BTreeMap<u32, TableMetadata>,
+    pub questions: Vec<Question>,
+    pub collections: Vec<CollectionItem>,
+    /// Items of each collection by ID ("root" for the root collection)
+    #[serde(default)]
+    pub collection_entries: BTreeMap<String, Vec<CollectionEntry>>,
+    /// What the sync had to leave out
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+impl MetadataSnapshot {
+    /// Download the metadata of the Metabase at `url` through `client`.
+    /// `progress` is called with a line per database and per skipped item:
+    /// schemas, tables and table metadata that fail to download are left out
+    /// and listed in `warnings`. An expired session aborts the sync.
+    pub async fn sync(
+        client: &dyn MetabaseApi,
+        url: &str,
+        mut progress: impl FnMut(&str),
+    ) -> std::result::Result<Self, AppError> {
+        let mut snapshot = Self {
+            url: url.to_string(),
+            ..Self::default()
+        };
+        let mut warnings = Vec::new();
+
+        // The virtual "Saved Questions" database has no tables of its own
+        let databases: Vec<Database> = client
+            .list_databases()
+            .await?
+            .into_iter()
+            .filter(|db| !db.is_saved_questions)
+            .collect();
+        for (i, database) in databases.iter().enumerate() {
+            let schemas = match client.list_schemas(database.id).await {
+                Ok(schemas) => schemas,
+                Err(e) => {
+                    let what = format!("schemas of database {}", database.name);
+                    skip(e, what, &mut warnings, &mut progress)?;
+                    continue;
+                }
+            };
+            let mut tables = Vec::new();
+            for schema in &schemas {
+                match client.list_tables(database.id, schema).await {
+                    Ok(schema_tables) => tables.extend(schema_tables),
+                    Err(e) => {
+                        let what = format!("tables of {}.{}", database.name, schema);
+                        skip(e, what, &mut warnings, &mut progress)?;
+                    }
+                }
+            }
+            for table in &tables {
+                match client.get_table_metadata(table.id).await {
+                    Ok(metadata) => {
+                        snapshot.table_metadata.insert(table.id, metadata);
+                    }
+                    Err(e) => {
+                        let what = format!("fields of table {} ({})", table.name, table.id);
+                        skip(e, what, &mut warnings, &mut progress)?;
+                    }
+                }
+            }
+            progress(&format!(
+                "Database {}/{}: {} ({} schemas, {} tables)",
+                i + 1,
+                databases.len(),
+                database.name,
+                schemas.len(),
+                tables.len()
+            ));
+            snapshot.schemas.insert(database.id, schemas);
+            snapshot.tables.insert(database.id, tables);
+        }
+        snapshot.databases = databases;
+
+        snapshot.questions = client.list_questions(None, None, None).await?;
+        snapshot.collections = client.list_collections().await?;
+        let collection_ids =
+            std::iter::once(None).chain(snapshot.collections.iter().filter_map(|c| c.id.map(Some)));
+        for collection_id in collection_ids {
+            match client.list_collection_items(collection_id).await {
+                Ok(entries) => {
+                    snapshot
+                        .collection_entries
+                        .insert(collection_key(collection_id), entries);
+                }
+                Err(e) => {
+                    let what = format!("items of collection {}", collection_key(collection_id));
+                    skip(e, what, &mut warnings, &mut progress)?;
+                }
+            }
+        }
+        progress(&format!(
+            "{} questions, {} collections",
+            snapshot.questions.len(),
+            snapshot.collections.len()
+        ));
+
+        snapshot.warnings = warnings;
+        snapshot.synced_at = now_secs();
+        Ok(snapshot)
+    }
+
+    /// Path of the snapshot of a Metabase URL.
+    pub fn path_for(base_url: &str) -> Result<PathBuf> {
+        Ok(Config::snapshots_dir()?.join(format!("{}.json", profile_name(base_url))))
+    }
+
+    /// Load the snapshot of a Metabase URL, if one was synced.
+    pub fn load(base_url: &str) -> Result<Option<Self>> {
+        Self::load_from(&Self::path_for(base_url)?)
+    }
+
+    /// Save the snapshot under the path of its URL; returns that path.
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::path_for(&self.url)?;
+        self.save_to(&path)?;
+        Ok(path)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path).map_err(|source| StorageError::FileIo {
+            path: path.display().to_string(),
+            source,
+        })?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| StorageError::ConfigParseError {
+                message: format!("Invalid metadata snapshot {}: {}", path.display(), e),
+            })
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        let io_error = |source| StorageError::FileIo {
+            path: path.display().to_string(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| StorageError::ConfigParseError {
+            message: format!("Failed to serialize metadata snapshot: {}", e),
+        })?;
+        // Write through a temporary file so an interrupted sync keeps the
+        // previous snapshot intact
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, json).map_err(io_error)?;
+        fs::rename(&temp, path).map_err(io_error)
+    }
+
+    /// Time since the sync.
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now_secs().saturating_sub(self.synced_at))
+    }
+
+    /// "stale since <time> (<age> ago)", for telling users the data may be out of date.
+    pub fn stale_since(&self) -> String {
+        format!(
+            "stale since {} ({} ago)",
+            iso8601(self.synced_at),
+            format_age(self.age())
+        )
+    }
+
+    /// Questions, filtered like `MetabaseClient::list_questions`: by a
+    /// case-insensitive name/description match or by collection.
+    pub fn list_questions(
+        &self,
+        search: Option<&str>,
+        limit: Option<u32>,
+        collection: Option<&str>,
+    ) -> Vec<Question> {
+        let search = search.filter(|s| !s.is_empty()).map(str::to_lowercase);
+        let collection_id = collection
+            .filter(|c| !c.is_empty())
+            .map(|c| c.parse::<u32>().ok());
+        let matches = self.questions.iter().filter(|q| {
+            let search_match = search.as_ref().is_none_or(|term| {
+                q.name.to_lowercase().contains(term)
+                    || q.description
+                        .as_ref()
+                        .is_some_and(|d| d.to_lowercase().contains(term))
+            });
+            let collection_match = collection_id.is_none_or(|id| q.collection_id == id);
+            search_match && collection_match
+        });
+        match (limit, &search) {
+            // The server applies the limit to searches only
+            (Some(limit), Some(_)) => matches.take(limit as usize).cloned().collect(),
+            _ => matches.cloned().collect(),
+        }
+    }
+
+    /// Schemas of a database, or None if the database is unknown.
+    pub fn list_schemas(&self, database_id: u32) -> Option<Vec<String>> {
+        self.schemas.get(&database_id).cloned()
+    }
+
+    /// Tables in a schema, or None if the database or schema is unknown.
+    pub fn list_tables(&self, database_id: u32, schema: &str) -> Option<Vec<TableInfo>> {
+        if !self.schemas.get(&database_id)?.iter().any(|s| s == schema) {
+            return None;
+        }
+        Some(
+            self.tables
+                .get(&database_id)?
+                .iter()
+                .filter(|t| t.schema.as_deref() == Some(schema))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// A table with its fields.
+    pub fn table_metadata(&self, table_id: u32) -> Option<&TableMetadata> {
+        self.table_metadata.get(&table_id)
+    }
+
+    /// Items of a collection (None = root), as
+    /// `MetabaseClient::list_collection_items` returned them at the sync.
+    /// Snapshots without them (older ones, or skipped collections) fall back
+    /// to the sub-collections and questions.
+    pub fn collection_items(&self, collection_id: Option<u32>) -> Vec<CollectionEntry> {
+        if let Some(entries) = self.collection_entries.get(&collection_key(collection_id)) {
+            return entries.clone();
+        }
+        let parent_location = match collection_id {
+            None => "/".to_string(),
+            Some(id) => {
+                let location = self
+                    .collections
+                    .iter()
+                    .find(|c| c.id == Some(id))
+                    .and_then(|c| c.location.clone())
+                    .unwrap_or_else(|| "/".to_string());
+                format!("{}{}/", location, id)
+            }
+        };
+        let sub_collections = self.collections.iter().filter_map(|c| {
+            let id = c.id?;
+            (c.location.as_deref() == Some(parent_location.as_str())).then(|| CollectionEntry {
+                id,
+                name: c.name.clone(),
+                description: c.description.clone(),
+                model: "collection".to_string(),
+                personal_owner_id: c.personal_owner_id,
+            })
+        });
+        let questions = self
+            .questions
+            .iter()
+            .filter(|q| q.collection_id == collection_id)
+            .map(|q| CollectionEntry {
+                id: q.id,
+                name: q.name.clone(),
+                description: q.description.clone(),
+                model: "card".to_string(),
+                personal_owner_id: None,
+            });
+        sub_collections.chain(questions).collect()
+    }
+}
+
+/// Key of a collection in `MetadataSnapshot::collection_entries`.
+fn collection_key(collection_id: Option<u32>) -> String {
+    collection_id.map_or_else(|| "root".to_string(), |id| id.to_string())
+}
+
+/// Record an item the sync could not download, unless the session expired.
+fn skip(
+    error: AppError,
+    what: String,
+    warnings: &mut Vec<String>,
+    progress: &mut impl FnMut(&str),
+) -> std::result::Result<(), AppError> {
+    if matches!(error, AppError::Api(ApiError::Unauthorized { .. })) {
+        return Err(error);
+    }
+    let warning = format!("Skipped {}: {}", what, error.display_friendly());
+    progress(&format!("Warning: {}", warning));
+    warnings.push(warning);
+    Ok(())
+}
+
+/// Error for requests that need the server while offline, telling how old
+/// the snapshot answering the rest is.
+fn needs_connection(snapshot: &MetadataSnapshot, what: &str) -> AppError {
+    AppError::Cli(CliError::InvalidArguments(format!(
+        "{} needs a connection to Metabase; run without --offline (metadata {})",
+        what,
+        snapshot.stale_since()
+    )))
+}
+
+/// Error for metadata the snapshot does not have.
+fn not_in_snapshot(what: String) -> AppError {
+    AppError::Cli(CliError::InvalidArguments(format!(
+        "{} is not in the metadata snapshot",
+        what
+    )))
+}
+
+/// Offline answers: metadata from the snapshot, everything that needs the
+/// server fails with a hint to reconnect.
+#[async_trait]
+impl MetabaseApi for MetadataSnapshot {
+    async fn get_current_user(&self) -> std::result::Result<CurrentUser, AppError> {
+        Err(needs_connection(self, "Validating the session"))
+    }
+
+    async fn list_questions(
+        &self,
+        search: Option<&str>,
+        limit: Option<u32>,
+        collection: Option<&str>,
+    ) -> std::result::Result<Vec<Question>, AppError> {
+        Ok(MetadataSnapshot::list_questions(
+            self, search, limit, collection,
+        ))
+    }
+
+    async fn execute_question(
+        &self,
+        _id: u32,
+        _parameters: Option<HashMap<String, String>>,
+    ) -> std::result::Result<QueryResult, AppError> {
+        Err(needs_connection(self, "Running a question"))
+    }
+
+    async fn get_question(&self, _id: u32) -> std::result::Result<QuestionDetail, AppError> {
+        Err(needs_connection(self, "Showing a question"))
+    }
+
+    async fn list_collections(&self) -> std::result::Result<Vec<CollectionItem>, AppError> {
+        Ok(self.collections.clone())
+    }
+
+    async fn list_collection_items(
+        &self,
+        collection_id: Option<u32>,
+    ) -> std::result::Result<Vec<CollectionEntry>, AppError> {
+        Ok(self.collection_items(collection_id))
+    }
+
+    async fn list_databases(&self) -> std::result::Result<Vec<Database>, AppError> {
+        Ok(self.databases.clone())
+    }
+
+    async fn list_schemas(&self, database_id: u32) -> std::result::Result<Vec<String>, AppError> {
+        MetadataSnapshot::list_schemas(self, database_id)
+            .ok_or_else(|| not_in_snapshot(format!("Database {}", database_id)))
+    }
+
+    async fn list_tables(
+        &self,
+        database_id: u32,
+        schema: &str,
+    ) -> std::result::Result<Vec<TableInfo>, AppError> {
+        MetadataSnapshot::list_tables(self, database_id, schema).ok_or_else(|| {
+            not_in_snapshot(format!("Schema '{}' of database {}", schema, database_id))
+        })
+    }
+
+    async fn get_table_metadata(
+        &self,
+        table_id: u32,
+    ) -> std::result::Result<TableMetadata, AppError> {
+        self.table_metadata(table_id)
+            .cloned()
+            .ok_or_else(|| not_in_snapshot(format!("Table {}", table_id)))
+    }
+
+    async fn run_native_query(
+        &self,
+        _database_id: u32,
+        _sql: &str,
+        _max_rows: u32,
+    ) -> std::result::Result<QueryResult, AppError> {
+        Err(needs_connection(self, "Running a query"))
+    }
+
+    async fn preview_table(
+        &self,
+        _database_id: u32,
+        _table_id: u32,
+        _limit: u32,
+    ) -> std::result::Result<QueryResult, AppError> {
+        Err(needs_connection(self, "Previewing a table"))
+    }
+
+    async fn query_table_rows(
+        &self,
+        _database_id: u32,
+        _table_id: u32,
+        _field_id: u32,
+        _value: &Value,
+        _limit: u32,
+    ) -> std::result::Result<QueryResult, AppError> {
+        Err(needs_connection(self, "Fetching linked rows"))
+    }
+
+    async fn get_field(&self, _field_id: u32) -> std::result::Result<Field, AppError> {
+        Err(needs_connection(self, "Following a link"))
+    }
+
+    async fn list_table_fks(
+        &self,
+        _table_id: u32,
+    ) -> std::result::Result<Vec<ForeignKey>, AppError> {
+        Err(needs_connection(self, "Finding references"))
+    }
+}
+
+/// Compact age such as "45s", "12m", "3h" or "2d".
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::TableField;
+    use tempfile::TempDir;
+
+    fn question(id: u32, name: &str, collection_id: Option<u32>) -> Question {
+        Question {
+            id,
+            name: name.to_string(),
+            description: None,
+            collection_id,
+            collection: None,
+        }
+    }
+
+    fn collection(id: u32, name: &str, location: &str) -> CollectionItem {
+        CollectionItem {
+            id: Some(id),
+            name: name.to_string(),
+            description: None,
+            location: Some(location.to_string()),
+            personal_owner_id: None,
+            archived: false,
+        }
+    }
+
+    fn table(id: u32, name: &str, schema: &str) -> TableInfo {
+        TableInfo {
+            id,
+            name: name.to_string(),
+            schema: Some(schema.to_string()),
+            display_name: None,
+            description: None,
+            entity_type: None,
+        }
+    }
+
+    fn snapshot() -> MetadataSnapshot {
+        MetadataSnapshot {
+            url: "http://mb.test".to_string(),
+            synced_at: now_secs() - 7200,
+            schemas: BTreeMap::from([(1, vec!["public".to_string(), "sales".to_string()])]),
+            tables: BTreeMap::from([(
+                1,
+                vec![table(10, "users", "public"), table(11, "orders", "sales")],
+            )]),
+            table_metadata: BTreeMap::from([(
+                10,
+                TableMetadata {
+                    id: 10,
+                    db_id: 1,
+                    name: "users".to_string(),
+                    schema: Some("public".to_string()),
+                    display_name: None,
+                    description: None,
+                    fields: vec![TableField {
+                        id: 100,
+                        name: "id".to_string(),
+                        display_name: None,
+                        base_type: Some("type/Integer".to_string()),
+                        semantic_type: Some("type/PK".to_string()),
+                        description: None,
+                        fk_target_field_id: None,
+                    }],
+                },
+            )]),
+            questions: vec![
+                question(1, "Monthly Revenue", Some(5)),
+                question(2, "Active users", None),
+                question(3, "Revenue by region", Some(5)),
+            ],
+            collections: vec![
+                collection(5, "Finance", "/"),
+                collection(6, "Archive", "/5/"),
+            ],
+            ..MetadataSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn test_snapshot_lookups() {
+        let snapshot = snapshot();
+
+        let names =
+            |questions: Vec<Question>| -> Vec<u32> { questions.iter().map(|q| q.id).collect() };
+        assert_eq!(
+            names(snapshot.list_questions(Some("REVENUE"), None, None)),
+            [1, 3]
+        );
+        assert_eq!(
+            names(snapshot.list_questions(Some("revenue"), Some(1), None)),
+            [1]
+        );
+        assert_eq!(
+            names(snapshot.list_questions(None, None, Some("5"))),
+            [1, 3]
+        );
+
+        assert_eq!(snapshot.list_tables(1, "sales").unwrap()[0].name, "orders");
+        assert!(snapshot.list_tables(1, "missing").is_none());
+        assert!(snapshot.list_tables(2, "public").is_none());
+        assert_eq!(snapshot.table_metadata(10).unwrap().fields.len(), 1);
+
+        let root: Vec<(u32, String)> = snapshot
+            .collection_items(None)
+            .into_iter()
+            .map(|e| (e.id, e.model))
+            .collect();
+        assert_eq!(
+            root,
+            [(5, "collection".to_string()), (2, "card".to_string())]
+        );
+        let finance = snapshot.collection_items(Some(5));
+        assert_eq!(finance.len(), 3);
+        assert_eq!(finance[0].name, "Archive");
+
+        assert!(snapshot.stale_since().ends_with("(2h ago)"));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_as_api() {
+        let snapshot = snapshot();
+        let api: &dyn MetabaseApi = &snapshot;
+
+        assert_eq!(api.list_schemas(1).await.unwrap().len(), 2);
+        assert!(
+            api.list_schemas(2)
+                .await
+                .unwrap_err()
+                .to_string()
+                .contains("Database 2 is not in the metadata snapshot")
+        );
+        let offline = api.execute_question(1, None).await.unwrap_err().to_string();
+        assert!(offline.contains("needs a connection to Metabase"));
+        assert!(offline.ends_with("(2h ago))"));
+
+        // Syncing from a snapshot reproduces it
+        let copy = MetadataSnapshot::sync(api, &snapshot.url, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(copy.questions, snapshot.questions);
+        assert_eq!(copy.collections, snapshot.collections);
+        assert_eq!(copy.databases, snapshot.databases);
+    }
+
+    #[tokio::test]
+    async fn test_collection_entries() {
+        let entry = |id: u32, name: &str, model: &str| CollectionEntry {
+            id,
+            name: name.to_string(),
+            description: None,
+            model: model.to_string(),
+            personal_owner_id: None,
+        };
+        let finance = vec![
+            entry(6, "Archive", "collection"),
+            entry(9, "KPIs", "dashboard"),
+            entry(4, "Orders model", "dataset"),
+            entry(1, "Monthly Revenue", "card"),
+        ];
+        let source = MetadataSnapshot {
+            collection_entries: BTreeMap::from([("5".to_string(), finance.clone())]),
+            ..snapshot()
+        };
+        assert_eq!(source.collection_items(Some(5)), finance);
+        // Collections the sync did not store are rebuilt from the rest
+        assert_eq!(source.collection_items(None).len(), 2);
+
+        let copy = MetadataSnapshot::sync(&source, &source.url, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(copy.collection_items(Some(5)), finance);
+        assert_eq!(
+            copy.collection_entries.keys().collect::<Vec<_>>(),
+            ["5", "6", "root"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_failed_items() {
+        let database = |id: u32, name: &str| Database {
+            id,
+            name: name.to_string(),
+            engine: None,
+            description: None,
+            is_sample: false,
+            is_saved_questions: false,
+        };
+        // Database 2 has no schemas and table 11 no fields in the source
+        let source = MetadataSnapshot {
+            databases: vec![database(1, "Main"), database(2, "Broken")],
+            ..snapshot()
+        };
+
+        let mut lines = Vec::new();
+        let copy =
+            MetadataSnapshot::sync(&source, &source.url, |line| lines.push(line.to_string()))
+                .await
+                .unwrap();
+        assert_eq!(copy.databases.len(), 2);
+        assert_eq!(copy.tables[&1].len(), 2);
+        assert_eq!(copy.table_metadata.keys().collect::<Vec<_>>(), [&10]);
+        assert!(!copy.schemas.contains_key(&2));
+        assert_eq!(copy.warnings.len(), 2);
+        assert!(copy.warnings[0].starts_with("Skipped fields of table orders (11)"));
+        assert!(copy.warnings[1].starts_with("Skipped schemas of database Broken"));
+        assert!(lines.iter().any(|l| l.starts_with("Warning: Skipped")));
+        assert_eq!(copy.questions, source.questions);
+    }
+
+    #[test]
+    fn test_snapshot_save_and_load() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("snapshots").join("mb.json");
+
+        assert_eq!(MetadataSnapshot::load_from(&path).unwrap(), None);
+        let snapshot = snapshot();
+        snapshot.save_to(&path).unwrap();
+        assert_eq!(MetadataSnapshot::load_from(&path).unwrap(), Some(snapshot));
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(59)), "59s");
+        assert_eq!(format_age(Duration::from_secs(600)), "10m");
+        assert_eq!(format_age(Duration::from_secs(3 * 86400)), "3d");
+    }
+}
@@ -2,6 +2,9 @@ use crate::error::StorageError;
 
 pub mod config;
 pub mod credentials;
+pub mod metadata_snapshot;
+pub mod response_cache;
+pub mod tui_state;
 // pub mod cache;
 
 type Result<T> = std::result::Result<T, StorageError>;
@@ -0,0 +1,436 @@
+//! Disk-backed API response cache
+//!
+//! Successful GET responses for metadata (cards, collections, databases,
+//! tables, fields, search results) are stored under
+//! ~/.config/mbr-cli/cache/<profile>/<identity>/, one file per request URL,
+//! where the profile is derived from the Metabase URL and the identity from
+//! the user or API key, so accounts with different permissions never share
+//! entries. Each resource has its own TTL;
+//! stale entries that carry an `ETag` or `Last-Modified` are revalidated with
+//! a conditional request instead of being downloaded again.
+
+use crate::core::cache::CacheStats;
+use crate::error::StorageError;
+use crate::storage::config::Config;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Cached resources and their default TTLs, in seconds.
+pub const RESOURCE_TTLS: &[(&str, u64)] = &[
+    ("cards", 300),
+    ("search", 300),
+    ("collections", 1800),
+    ("databases", 3600),
+    ("tables", 3600),
+    ("fields", 3600),
+];
+
+/// A stored response.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    /// Full request URL (the cache key)
+    pub url: String,
+    /// Resource name, one of `RESOURCE_TTLS`
+    pub resource: String,
+    /// Unix time the response was stored or last revalidated
+    pub stored_at: u64,
+    pub ttl_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+impl CachedResponse {
+    /// Whether the entry is within its TTL.
+    pub fn is_fresh(&self) -> bool {
+        now_secs() < self.stored_at.saturating_add(self.ttl_secs)
+    }
+
+    /// Whether the entry can be revalidated with a conditional request.
+    pub fn has_validator(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// Response cache of one profile.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttls: BTreeMap<String, Duration>,
+    /// Revalidate or refetch even fresh entries (`--refresh`)
+    refresh: bool,
+}
+
+impl ResponseCache {
+    /// Cache stored in a specific directory, with the default TTLs.
+    pub fn new(dir: PathBuf) -> Self {
+        let ttls = RESOURCE_TTLS
+            .iter()
+            .map(|&(name, secs)| (name.to_string(), Duration::from_secs(secs)))
+            .collect();
+        Self {
+            dir,
+            ttls,
+            refresh: false,
+        }
+    }
+
+    /// Cache of `identity` (see [`identity_name`]) in the profile for
+    /// `base_url` under ~/.config/mbr-cli/cache/.
+    pub fn for_profile(base_url: &str, identity: &str) -> Option<Self> {
+        Some(Self::new(profile_dir(base_url)?.join(identity)))
+    }
+
+    /// Remove the cached responses of every identity in the profile for
+    /// `base_url`, as logging in or out does.
+    pub fn clear_profile(base_url: &str) -> Result<(), StorageError> {
+        let Some(dir) = profile_dir(base_url) else {
+            return Ok(());
+        };
+        match fs::remove_dir_all(&dir) {
+            Err(source) if source.kind() != std::io::ErrorKind::NotFound => {
+                Err(StorageError::FileIo {
+                    path: dir.display().to_string(),
+                    source,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Override the TTL of a resource.
+    pub fn with_ttl(mut self, resource: &str, ttl: Duration) -> Self {
+        self.ttls.insert(resource.to_string(), ttl);
+        self
+    }
+
+    /// Ignore freshness: every lookup goes to the server (revalidating when
+    /// possible) and the answer replaces the entry.
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    pub fn is_refresh(&self) -> bool {
+        self.refresh
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The cached resource an API path belongs to, if it is cached at all.
+    pub fn resource_for(path: &str) -> Option<&'static str> {
+        let rest = path.strip_prefix("/api/")?;
+        let resource = match rest.split('/').next()? {
+            "card" if !rest.ends_with("/query") => "cards",
+            "search" => "search",
+            "collection" => "collections",
+            "database" => "databases",
+            "table" => "tables",
+            "field" => "fields",
+            _ => return None,
+        };
+        Some(resource)
+    }
+
+    /// Look up the entry for a URL, fresh or not.
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        let content = fs::read_to_string(self.entry_path(url)).ok()?;
+        let entry: CachedResponse = serde_json::from_str(&content).ok()?;
+        // Guard against hash collisions
+        (entry.url == url).then_some(entry)
+    }
+
+    /// Store a response.
+    pub fn put(
+        &self,
+        url: &str,
+        resource: &str,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        body: String,
+    ) -> Result<(), String> {
+        let ttl = self.ttls.get(resource).copied().unwrap_or_default();
+        self.write(&CachedResponse {
+            url: url.to_string(),
+            resource: resource.to_string(),
+            stored_at: now_secs(),
+            ttl_secs: ttl.as_secs(),
+            etag,
+            last_modified,
+            body,
+        })
+    }
+
+    /// Restart the TTL of an entry the server confirmed unchanged.
+    pub fn touch(&self, entry: &CachedResponse) -> Result<(), String> {
+        let ttl = self.ttls.get(&entry.resource).copied().unwrap_or_default();
+        self.write(&CachedResponse {
+            stored_at: now_secs(),
+            ttl_secs: ttl.as_secs(),
+            ..entry.clone()
+        })
+    }
+
+    /// Entry counts per resource.
+    pub fn stats(&self) -> BTreeMap<String, CacheStats> {
+        let mut stats: BTreeMap<String, CacheStats> = RESOURCE_TTLS
+            .iter()
+            .map(|&(name, _)| (name.to_string(), CacheStats::default()))
+            .collect();
+        for entry in self.entries() {
+            let resource = stats.entry(entry.resource.clone()).or_default();
+            resource.total_entries += 1;
+            if entry.is_fresh() {
+                resource.active_entries += 1;
+            } else {
+                resource.expired_entries += 1;
+            }
+        }
+        stats
+    }
+
+    /// Total size of the cache files in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        self.entry_files()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|meta| meta.len())
+            .sum()
+    }
+
+    /// Remove every entry; returns how many were removed.
+    pub fn clear(&self) -> Result<usize, StorageError> {
+        let mut removed = 0;
+        for path in self.entry_files() {
+            fs::remove_file(&path).map_err(|source| StorageError::FileIo {
+                path: path.display().to_string(),
+                source,
+            })?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn entries(&self) -> impl Iterator<Item = CachedResponse> {
+        self.entry_files().filter_map(|path| {
+            let content = fs::read_to_string(path).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+    }
+
+    fn entry_files(&self) -> impl Iterator<Item = PathBuf> {
+        fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(url)))
+    }
+
+    /// Write an entry through a temporary file so that readers never see a
+    /// partial entry. Responses can hold query results, so the directories
+    /// are private (0700) and the entries readable by the owner only (0600),
+    /// like the session file.
+    fn write(&self, entry: &CachedResponse) -> Result<(), String> {
+        let dir_error = |e| format!("Failed to create cache directory: {}", e);
+        let mut dirs = fs::DirBuilder::new();
+        dirs.recursive(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+            dirs.mode(0o700);
+            dirs.create(&self.dir).map_err(dir_error)?;
+            // Directories made by earlier versions were world-readable
+            fs::set_permissions(&self.dir, fs::Permissions::from_mode(0o700)).map_err(dir_error)?;
+        }
+        #[cfg(not(unix))]
+        dirs.create(&self.dir).map_err(dir_error)?;
+        let json = serde_json::to_string(entry)
+            .map_err(|e| format!("Failed to serialize cache entry: {}", e))?;
+        let path = self.entry_path(&entry.url);
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, json).map_err(|e| format!("Failed to write cache entry: {}", e))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&temp, fs::Permissions::from_mode(0o600))
+                .map_err(|e| format!("Failed to set cache entry permissions: {}", e))?;
+        }
+
+        fs::rename(&temp, &path).map_err(|e| format!("Failed to write cache entry: {}", e))
+    }
+}
+
+/// Cache directory of the profile for `base_url`.
+fn profile_dir(base_url: &str) -> Option<PathBuf> {
+    Some(Config::cache_dir().ok()?.join(profile_name(base_url)))
+}
+
+/// Directory name of the credentials responses are cached for: a hash of the
+/// API key (never the key itself), else the username (readable, with its
+/// hash so that usernames differing only in replaced characters stay apart),
+/// else "anonymous".
+pub fn identity_name(username: Option<&str>, api_key: Option<&str>) -> String {
+    match (api_key, username.filter(|u| !u.is_empty())) {
+        (Some(key), _) => format!("key-{:016x}", fnv1a(key)),
+        (None, Some(username)) => {
+            let readable: String = sanitize(username).chars().take(32).collect();
+            format!("user-{}-{:016x}", readable, fnv1a(username))
+        }
+        (None, None) => "anonymous".to_string(),
+    }
+}
+
+/// Directory name of a profile: the Metabase URL with everything but
+/// letters, digits, dots and dashes replaced.
+pub(crate) fn profile_name(base_url: &str) -> String {
+    let url = base_url
+        .trim_end_matches('/')
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest);
+    sanitize(url)
+}
+
+/// Replace everything but letters, digits, dots and dashes.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// FNV-1a hash, stable across Rust versions (unlike `DefaultHasher`).
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resource_for_path() {
+        assert_eq!(ResponseCache::resource_for("/api/card"), Some("cards"));
+        assert_eq!(ResponseCache::resource_for("/api/card/5"), Some("cards"));
+        assert_eq!(ResponseCache::resource_for("/api/card/5/query"), None);
+        assert_eq!(
+            ResponseCache::resource_for("/api/database/1/schema/public"),
+            Some("databases")
+        );
+        assert_eq!(
+            ResponseCache::resource_for("/api/table/3/query_metadata"),
+            Some("tables")
+        );
+        assert_eq!(ResponseCache::resource_for("/api/user/current"), None);
+        assert_eq!(ResponseCache::resource_for("/api/dataset"), None);
+    }
+
+    #[test]
+    fn test_put_get_touch_and_stats() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let cache = ResponseCache::new(temp_dir.path().join("profile"))
+            .with_ttl("databases", Duration::ZERO);
+
+        let url = "http://mb.test/api/card?f=all";
+        cache
+            .put(
+                url,
+                "cards",
+                Some("\"v1\"".to_string()),
+                None,
+                "[]".to_string(),
+            )
+            .unwrap();
+        cache
+            .put(
+                "http://mb.test/api/database",
+                "databases",
+                None,
+                None,
+                "{}".to_string(),
+            )
+            .unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&temp_dir.path().join("profile")), 0o700);
+            assert_eq!(mode(&cache.entry_path(url)), 0o600);
+        }
+
+        let entry = cache.get(url).expect("entry stored");
+        assert!(entry.is_fresh());
+        assert!(entry.has_validator());
+        assert_eq!(entry.body, "[]");
+        assert!(cache.get("http://mb.test/api/collection").is_none());
+
+        let stale = cache.get("http://mb.test/api/database").unwrap();
+        assert!(!stale.is_fresh());
+
+        let stats = cache.stats();
+        assert_eq!(stats["cards"].active_entries, 1);
+        assert_eq!(stats["databases"].expired_entries, 1);
+        assert_eq!(stats["tables"].total_entries, 0);
+        assert!(cache.size_bytes() > 0);
+
+        cache.touch(&entry).unwrap();
+        assert_eq!(cache.get(url).unwrap().etag.as_deref(), Some("\"v1\""));
+
+        assert_eq!(cache.clear().unwrap(), 2);
+        assert!(cache.get(url).is_none());
+    }
+
+    #[test]
+    fn test_profile_name() {
+        assert_eq!(
+            profile_name("https://metabase.example.com:8443/"),
+            "metabase.example.com_8443"
+        );
+        assert_eq!(profile_name("http://localhost:3000"), "localhost_3000");
+    }
+
+    #[test]
+    fn test_identity_name() {
+        let ana = identity_name(Some("ana@example.com"), None);
+        assert!(ana.starts_with("user-ana_example.com-"));
+        // Usernames that sanitize alike still get their own directory
+        assert_ne!(ana, identity_name(Some("ana_example.com"), None));
+        assert_ne!(
+            identity_name(Some("a+x@b.c"), None),
+            identity_name(Some("a_x@b.c"), None)
+        );
+        assert_eq!(identity_name(Some(""), None), "anonymous");
+        assert_eq!(identity_name(None, None), "anonymous");
+
+        let key = identity_name(Some("ana@example.com"), Some("mb_secret"));
+        assert!(key.starts_with("key-"));
+        assert!(!key.contains("secret"));
+        assert_ne!(key, identity_name(None, Some("mb_other")));
+    }
+}
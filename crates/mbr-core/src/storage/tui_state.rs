@@ -0,0 +1,269 @@
+//! Persistent TUI state
+//!
+//! This module stores TUI state that should survive between sessions:
+//! per-question column layouts and filter/sort/search of the results table,
+//! where the last session left off, and the history of executed questions.
+//! State is stored in ~/.config/mbr-cli/tui_state.json.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// State persisted by the TUI between sessions
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TuiState {
+    /// Results table column layouts keyed by question id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_layouts: BTreeMap<u32, ColumnLayout>,
+    /// Results table filter, sort and search keyed by question id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub result_views: BTreeMap<u32, ResultView>,
+    /// Views open when the last session ended
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<Session>,
+    /// Executed questions, newest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<HistoryEntry>,
+}
+
+/// Maximum number of history entries kept.
+pub const HISTORY_LIMIT: usize = 200;
+
+/// Column layout of a results table
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ColumnLayout {
+    /// Columns in display order
+    pub columns: Vec<ColumnSetting>,
+    /// Number of leading columns frozen while scrolling horizontally
+    #[serde(default)]
+    pub pinned: usize,
+}
+
+/// Display settings of a single column, matched by column name
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ColumnSetting {
+    pub name: String,
+    #[serde(default)]
+    pub hidden: bool,
+    /// Width override in characters (None = automatic)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+}
+
+/// Filter, sort and search of a results table, matched by column name
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ResultView {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<SavedFilter>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort: Vec<SavedSortKey>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub search: String,
+}
+
+impl ResultView {
+    /// Whether nothing is filtered, sorted or searched.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.sort.is_empty() && self.search.is_empty()
+    }
+}
+
+/// A filter condition as typed on the TUI command line
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedFilter {
+    /// Join with the previous condition ("and" or "or")
+    pub join: String,
+    pub column: String,
+    /// Operator symbol (e.g. "=", ">=", "~", "is null")
+    pub op: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+/// A sort key of a results table
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedSortKey {
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// Navigation path of the last session, from the tab's root view to the
+/// view that was shown
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Session {
+    pub views: Vec<SessionView>,
+}
+
+/// A view that can be reopened when restoring a session
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "view", rename_all = "snake_case")]
+pub enum SessionView {
+    Questions,
+    Collections,
+    Databases,
+    QueryResult {
+        question_id: u32,
+    },
+    DatabaseSchemas {
+        db_id: u32,
+        db_name: String,
+    },
+    SchemaTables {
+        db_id: u32,
+        schema_name: String,
+    },
+    TablePreview {
+        db_id: u32,
+        table_id: u32,
+        table_name: String,
+    },
+}
+
+/// An executed question
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub question_id: u32,
+    pub name: String,
+    /// ISO 8601 time of execution (UTC)
+    pub executed_at: String,
+    pub rows: usize,
+    /// Time until the result arrived
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+/// Get the state file path: ~/.config/mbr-cli/tui_state.json
+fn state_file_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".config").join("mbr-cli").join("tui_state.json"))
+}
+
+impl TuiState {
+    /// Load state from disk. Returns the default state if the file is missing or unreadable.
+    pub fn load() -> Self {
+        state_file_path()
+            .and_then(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    /// Load state from a specific file. Returns None if it does not exist or fails to parse.
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Record an execution at the top of the history, dropping the oldest
+    /// entries beyond `HISTORY_LIMIT`.
+    pub fn record_execution(&mut self, entry: HistoryEntry) {
+        self.history.insert(0, entry);
+        self.history.truncate(HISTORY_LIMIT);
+    }
+
+    /// Save state to disk.
+    pub fn save(&self) -> Result<(), String> {
+        let path = state_file_path().ok_or("Could not determine home directory")?;
+        self.save_to(&path)
+    }
+
+    /// Save state to a specific file, creating parent directories as needed.
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize TUI state: {}", e))?;
+
+        fs::write(path, json).map_err(|e| format!("Failed to write TUI state file: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tui_state_round_trip() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("state").join("tui_state.json");
+
+        let mut state = TuiState::default();
+        state.column_layouts.insert(
+            42,
+            ColumnLayout {
+                columns: vec![
+                    ColumnSetting {
+                        name: "name".to_string(),
+                        hidden: false,
+                        width: Some(30),
+                    },
+                    ColumnSetting {
+                        name: "id".to_string(),
+                        hidden: true,
+                        width: None,
+                    },
+                ],
+                pinned: 1,
+            },
+        );
+
+        state.result_views.insert(
+            42,
+            ResultView {
+                filters: vec![SavedFilter {
+                    join: "and".to_string(),
+                    column: "name".to_string(),
+                    op: "~".to_string(),
+                    value: "foo".to_string(),
+                }],
+                sort: vec![SavedSortKey {
+                    column: "id".to_string(),
+                    descending: true,
+                }],
+                search: String::new(),
+            },
+        );
+        state.session = Some(Session {
+            views: vec![
+                SessionView::Databases,
+                SessionView::DatabaseSchemas {
+                    db_id: 1,
+                    db_name: "Sample".to_string(),
+                },
+            ],
+        });
+
+        state.save_to(&path).expect("Failed to save state");
+        let loaded = TuiState::load_from(&path).expect("Failed to load state");
+        assert_eq!(loaded, state);
+    }
+
+    #[test]
+    fn test_record_execution_keeps_newest() {
+        let mut state = TuiState::default();
+        for id in 0..(HISTORY_LIMIT as u32 + 5) {
+            state.record_execution(HistoryEntry {
+                question_id: id,
+                name: format!("Q{}", id),
+                executed_at: "2026-01-01T00:00:00Z".to_string(),
+                rows: 1,
+                duration_ms: 0,
+            });
+        }
+        assert_eq!(state.history.len(), HISTORY_LIMIT);
+        assert_eq!(state.history[0].question_id, HISTORY_LIMIT as u32 + 4);
+
+        // Older state files without the new fields still load
+        let old: TuiState = serde_json::from_str(r#"{"column_layouts":{}}"#).unwrap();
+        assert!(old.history.is_empty() && old.session.is_none());
+    }
+
+    #[test]
+    fn test_tui_state_load_missing_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        assert!(TuiState::load_from(&temp_dir.path().join("missing.json")).is_none());
+    }
+}
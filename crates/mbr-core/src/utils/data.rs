@@ -101,11 +101,13 @@ mod tests {
                 name: "id".into(),
                 display_name: "ID".into(),
                 base_type: "type/Integer".into(),
+                ..Default::default()
             },
             Column {
                 name: "name".into(),
                 display_name: "Name".into(),
                 base_type: "type/Text".into(),
+                ..Default::default()
             },
         ]
     }
@@ -1,5 +1,6 @@
-use crate::error::{ApiError, DisplayError};
+use crate::error::{ApiError, ConnectionErrorKind, DisplayError};
 use std::io;
+use std::time::Duration;
 
 /// Helper functions for standardizing error conversions across the codebase
 /// This module reduces boilerplate and provides consistent error handling patterns.
@@ -20,6 +21,82 @@ pub fn convert_timeout_error(endpoint: &str, timeout_secs: u64) -> ApiError {
     }
 }
 
+/// Classify a failure to send a request: a timeout of ours (`connect_timeout`
+/// while connecting, `timeout` afterwards), a connection failure by cause, or
+/// any other transport error.
+pub fn convert_send_error(
+    error: reqwest::Error,
+    endpoint: &str,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+) -> ApiError {
+    if error.is_timeout() {
+        let limit = if error.is_connect() {
+            connect_timeout
+        } else {
+            timeout
+        };
+        if let Some(limit) = limit {
+            return convert_timeout_error(endpoint, limit.as_secs());
+        }
+    }
+    match connection_error_kind(&error) {
+        Some(kind) => ApiError::Connection {
+            kind,
+            endpoint: endpoint.to_string(),
+            message: root_cause(&error),
+        },
+        None => convert_request_error(error, endpoint),
+    }
+}
+
+/// Why a connection failed, judged from the error's source chain; None when
+/// the error is not a connection failure.
+fn connection_error_kind(error: &reqwest::Error) -> Option<ConnectionErrorKind> {
+    let mut messages = Vec::new();
+    let mut refused = false;
+    let mut source: Option<&dyn std::error::Error> = Some(error);
+    while let Some(e) = source {
+        messages.push(e.to_string().to_lowercase());
+        if let Some(io_error) = e.downcast_ref::<io::Error>() {
+            refused |= io_error.kind() == io::ErrorKind::ConnectionRefused;
+        }
+        source = e.source();
+    }
+    let mentions = |words: &[&str]| {
+        messages
+            .iter()
+            .any(|m| words.iter().any(|word| m.contains(word)))
+    };
+
+    if mentions(&["tunnel", "proxy"]) {
+        Some(ConnectionErrorKind::Proxy)
+    } else if mentions(&[
+        "dns error",
+        "failed to lookup address",
+        "name or service not known",
+    ]) {
+        Some(ConnectionErrorKind::Dns)
+    } else if mentions(&["certificate", "ssl", "tls", "handshake"]) {
+        Some(ConnectionErrorKind::Tls)
+    } else if refused || mentions(&["connection refused"]) {
+        Some(ConnectionErrorKind::Refused)
+    } else if error.is_connect() {
+        Some(ConnectionErrorKind::Other)
+    } else {
+        None
+    }
+}
+
+/// Message of the innermost error, which names the actual cause.
+fn root_cause(error: &reqwest::Error) -> String {
+    let mut cause: &dyn std::error::Error = error;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string()
+}
+
 /// Convert JSON deserialization errors to ApiError with endpoint context
 pub fn convert_json_error(error: reqwest::Error, endpoint: &str) -> ApiError {
     ApiError::Http {
@@ -40,11 +40,7 @@ impl MemoryEstimator {
             .map(|row| Self::estimate_row_memory(row))
             .sum();
 
-        let avg_row_memory = if sample_size > 0 {
-            sample_memory / sample_size
-        } else {
-            0
-        };
+        let avg_row_memory = sample_memory.checked_div(sample_size).unwrap_or(0);
 
         // Estimate for all rows
         avg_row_memory * rows.len() + columns.len() * 8 * rows.len() // Vec<Value> pointer overhead
@@ -239,6 +235,7 @@ mod tests {
                 name: format!("col_{}", i),
                 display_name: format!("Column {}", i),
                 base_type: "type/Text".to_string(),
+                ..Default::default()
             })
             .collect();
 
@@ -1,4 +1,4 @@
-use crate::error::ApiError;
+use crate::error::{ApiError, ConnectionErrorKind};
 use backoff::{ExponentialBackoff, backoff::Backoff};
 use std::future::Future;
 use std::time::Duration;
@@ -6,7 +6,7 @@ use std::time::Duration;
 /// Retry configuration for API operations
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
-    /// Maximum number of retry attempts
+    /// Maximum number of retries after the first attempt (0 never retries)
     pub max_retries: u32,
     /// Initial retry delay
     pub initial_delay: Duration,
@@ -14,6 +14,9 @@ pub struct RetryConfig {
     pub max_delay: Duration,
     /// Multiplier for exponential backoff
     pub multiplier: f64,
+    /// Random spread applied to each delay (0.5 = ±50%), so that clients
+    /// failing together do not retry together
+    pub jitter: f64,
     /// Whether to retry on client errors (4xx)
     pub retry_client_errors: bool,
 }
@@ -21,10 +24,11 @@ pub struct RetryConfig {
 impl Default for RetryConfig {
     fn default() -> Self {
         Self {
-            max_retries: 3,
+            max_retries: 2,
             initial_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(60),
             multiplier: 2.0,
+            jitter: 0.5,
             retry_client_errors: false,
         }
     }
@@ -34,10 +38,11 @@ impl RetryConfig {
     /// Create a config for aggressive retry (longer delays, more attempts)
     pub fn aggressive() -> Self {
         Self {
-            max_retries: 5,
+            max_retries: 4,
             initial_delay: Duration::from_millis(200),
             max_delay: Duration::from_secs(120),
             multiplier: 2.5,
+            jitter: 0.5,
             retry_client_errors: false,
         }
     }
@@ -45,13 +50,55 @@ impl RetryConfig {
     /// Create a config for quick retry (shorter delays, fewer attempts)
     pub fn quick() -> Self {
         Self {
-            max_retries: 2,
+            max_retries: 1,
             initial_delay: Duration::from_millis(50),
             max_delay: Duration::from_secs(10),
             multiplier: 1.5,
+            jitter: 0.5,
             retry_client_errors: false,
         }
     }
+
+    /// Create a config that never retries
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Look up a preset by name: "default", "quick", "aggressive" or "none"
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "quick" => Some(Self::quick()),
+            "aggressive" => Some(Self::aggressive()),
+            "none" => Some(Self::none()),
+            _ => None,
+        }
+    }
+}
+
+/// A failed attempt, as reported to `RetryExecutor::execute_with`.
+#[derive(Debug)]
+pub struct AttemptError {
+    pub error: ApiError,
+    /// Delay the server asked for (`Retry-After`), used instead of the
+    /// backoff delay when longer
+    pub retry_after: Option<Duration>,
+    /// False when repeating the operation is unsafe (a non-idempotent
+    /// request that may have reached the server)
+    pub retryable: bool,
+}
+
+impl From<ApiError> for AttemptError {
+    fn from(error: ApiError) -> Self {
+        Self {
+            error,
+            retry_after: None,
+            retryable: true,
+        }
+    }
 }
 
 /// Enhanced retry executor with configurable policies
@@ -70,11 +117,33 @@ impl RetryExecutor {
     where
         F: Fn() -> Fut,
         Fut: Future<Output = Result<T, ApiError>>,
+    {
+        self.execute_with(
+            || {
+                let attempt = operation();
+                async move { attempt.await.map_err(AttemptError::from) }
+            },
+            |_, _, _| {},
+        )
+        .await
+    }
+
+    /// Execute an async operation with retry logic, honouring the delay
+    /// and retryability reported with each failure. `on_retry` is called
+    /// with the number of the next attempt, the delay before it and the
+    /// error, before waiting.
+    pub async fn execute_with<F, Fut, T, R>(&self, operation: F, on_retry: R) -> Result<T, ApiError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, AttemptError>>,
+        R: Fn(u32, Duration, &ApiError),
     {
         let mut backoff = ExponentialBackoff {
+            current_interval: self.config.initial_delay,
             initial_interval: self.config.initial_delay,
             max_interval: self.config.max_delay,
             multiplier: self.config.multiplier,
+            randomization_factor: self.config.jitter,
             max_elapsed_time: None,
             ..Default::default()
         };
@@ -86,22 +155,28 @@ impl RetryExecutor {
 
             match operation().await {
                 Ok(result) => return Ok(result),
-                Err(error) => {
-                    let should_retry = self.should_retry(&error, attempt);
+                Err(failure) => {
+                    let should_retry =
+                        failure.retryable && self.should_retry(&failure.error, attempt);
 
                     if !should_retry {
-                        return Err(error);
+                        return Err(failure.error);
                     }
 
                     if let Some(delay) = backoff.next_backoff() {
+                        let delay = match failure.retry_after {
+                            Some(requested) => delay.max(requested.min(self.config.max_delay)),
+                            None => delay,
+                        };
                         log::debug!("Retrying operation after {:?} (attempt {})", delay, attempt);
+                        on_retry(attempt + 1, delay, &failure.error);
                         tokio::time::sleep(delay).await;
                     } else {
                         log::warn!(
                             "Max retry attempts reached ({}), giving up",
                             self.config.max_retries
                         );
-                        return Err(error);
+                        return Err(failure.error);
                     }
                 }
             }
@@ -110,16 +185,22 @@ impl RetryExecutor {
 
     /// Determine if an error should trigger a retry
     fn should_retry(&self, error: &ApiError, attempt: u32) -> bool {
-        if attempt >= self.config.max_retries {
+        if attempt > self.config.max_retries {
             return false;
         }
 
         match error {
-            // Always retry on server errors and timeouts
+            // Always retry on server errors, timeouts and failed connections
             ApiError::Http {
                 status: 500..=599, ..
             } => true,
             ApiError::Timeout { .. } => true,
+            ApiError::Http { status: 0, .. } => true,
+            // Certificate problems do not go away by retrying
+            ApiError::Connection { kind, .. } => *kind != ConnectionErrorKind::Tls,
+
+            // Rate limited: wait and try again
+            ApiError::Http { status: 429, .. } => true,
 
             // Retry on client errors only if configured
             ApiError::Http {
@@ -190,18 +271,122 @@ mod tests {
     #[test]
     fn test_retry_config_presets() {
         let default = RetryConfig::default();
-        assert_eq!(default.max_retries, 3);
+        assert_eq!(default.max_retries, 2);
         assert_eq!(default.initial_delay, Duration::from_millis(100));
 
         let aggressive = RetryConfig::aggressive();
-        assert_eq!(aggressive.max_retries, 5);
+        assert_eq!(aggressive.max_retries, 4);
         assert_eq!(aggressive.initial_delay, Duration::from_millis(200));
 
         let quick = RetryConfig::quick();
-        assert_eq!(quick.max_retries, 2);
+        assert_eq!(quick.max_retries, 1);
         assert_eq!(quick.initial_delay, Duration::from_millis(50));
     }
 
+    fn fast_config(max_retries: u32) -> RetryConfig {
+        RetryConfig {
+            max_retries,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_secs(1),
+            jitter: 0.0,
+            ..RetryConfig::default()
+        }
+    }
+
+    fn unavailable() -> ApiError {
+        ApiError::Http {
+            status: 503,
+            endpoint: "/test".to_string(),
+            message: "Service Unavailable".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let executor = RetryExecutor::new(fast_config(3));
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+
+        let result = executor
+            .execute(|| async {
+                match attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                    0 | 1 => Err(unavailable()),
+                    _ => Ok(7),
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_attempt_limit_and_retryability() {
+        let executor = RetryExecutor::new(fast_config(2));
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let result: Result<(), ApiError> = executor
+            .execute(|| async {
+                attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                Err(unavailable())
+            })
+            .await;
+        assert!(result.is_err());
+        // One attempt plus two retries
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        attempts.store(0, std::sync::atomic::Ordering::SeqCst);
+        let result: Result<(), ApiError> = executor
+            .execute_with(
+                || async {
+                    attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    Err(AttemptError {
+                        error: unavailable(),
+                        retry_after: None,
+                        retryable: false,
+                    })
+                },
+                |_, _, _| {},
+            )
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_overrides_backoff() {
+        let executor = RetryExecutor::new(fast_config(1));
+        let delays = std::sync::Mutex::new(Vec::new());
+
+        let result: Result<(), ApiError> = executor
+            .execute_with(
+                || async {
+                    Err(AttemptError {
+                        error: ApiError::Http {
+                            status: 429,
+                            endpoint: "/test".to_string(),
+                            message: "Too Many Requests".to_string(),
+                        },
+                        retry_after: Some(Duration::from_millis(20)),
+                        retryable: true,
+                    })
+                },
+                |attempt, delay, _| delays.lock().unwrap().push((attempt, delay)),
+            )
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *delays.lock().unwrap(),
+            vec![(2, Duration::from_millis(20))]
+        );
+    }
+
+    #[test]
+    fn test_retry_config_preset_names() {
+        assert_eq!(RetryConfig::preset("quick").unwrap().max_retries, 1);
+        assert_eq!(RetryConfig::preset("none").unwrap().max_retries, 0);
+        assert!(RetryConfig::preset("forever").is_none());
+    }
+
     #[tokio::test]
     async fn test_convenience_functions() {
         let result = with_retry(|| async { Ok::<String, ApiError>("success".to_string()) }).await;
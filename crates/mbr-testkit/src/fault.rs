@@ -0,0 +1,157 @@
+//! Fault injection: latency, error statuses and expired sessions
+
+use hyper::{Body, Response};
+use std::time::Duration;
+
+/// A failure the mock server injects into matching requests.
+///
+/// ```ignore
+/// // Two 503s with Retry-After, then normal responses
+/// Fault::status(503).on("/api/card").times(2).retry_after(1)
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fault {
+    kind: FaultKind,
+    method: Option<String>,
+    path: Option<String>,
+    remaining: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum FaultKind {
+    Latency(Duration),
+    Status {
+        status: u16,
+        message: Option<String>,
+        retry_after: Option<u64>,
+    },
+    Unauthorized,
+}
+
+impl Fault {
+    /// Delay the response; the request is then answered normally.
+    pub fn latency(delay: Duration) -> Self {
+        Self::new(FaultKind::Latency(delay))
+    }
+
+    /// Answer with an error status instead of the endpoint's response.
+    pub fn status(status: u16) -> Self {
+        Self::new(FaultKind::Status {
+            status,
+            message: None,
+            retry_after: None,
+        })
+    }
+
+    /// Answer 401 as Metabase does for an expired or unknown session.
+    pub fn unauthorized() -> Self {
+        Self::new(FaultKind::Unauthorized)
+    }
+
+    fn new(kind: FaultKind) -> Self {
+        Self {
+            kind,
+            method: None,
+            path: None,
+            remaining: None,
+        }
+    }
+
+    /// Only affect requests to `path` and the paths beneath it.
+    pub fn on(mut self, path: &str) -> Self {
+        self.path = Some(path.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Only affect requests with this method (e.g. "POST").
+    pub fn method(mut self, method: &str) -> Self {
+        self.method = Some(method.to_ascii_uppercase());
+        self
+    }
+
+    /// Affect only the next `count` matching requests (default: all).
+    pub fn times(mut self, count: usize) -> Self {
+        self.remaining = Some(count);
+        self
+    }
+
+    /// Error message of a status fault.
+    pub fn message(mut self, text: &str) -> Self {
+        if let FaultKind::Status {
+            ref mut message, ..
+        } = self.kind
+        {
+            *message = Some(text.to_string());
+        }
+        self
+    }
+
+    /// `Retry-After` header (seconds) of a status fault.
+    pub fn retry_after(mut self, secs: u64) -> Self {
+        if let FaultKind::Status {
+            ref mut retry_after,
+            ..
+        } = self.kind
+        {
+            *retry_after = Some(secs);
+        }
+        self
+    }
+
+    fn matches(&self, method: &str, path: &str) -> bool {
+        let method_matches = self.method.as_deref().is_none_or(|m| m == method);
+        let path_matches = self.path.as_deref().is_none_or(|prefix| {
+            path == prefix
+                || path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        });
+        method_matches && path_matches && self.remaining != Some(0)
+    }
+}
+
+/// What the faults matching a request do to it.
+#[derive(Default)]
+pub(crate) struct Injected {
+    pub delay: Duration,
+    pub response: Option<Response<Body>>,
+}
+
+/// Apply the faults matching a request, using up counted ones: every
+/// matching latency adds up, and the first matching error answers it.
+pub(crate) fn inject(faults: &mut Vec<Fault>, method: &str, path: &str) -> Injected {
+    let mut injected = Injected::default();
+    for fault in faults.iter_mut().filter(|f| f.matches(method, path)) {
+        match fault.kind {
+            FaultKind::Latency(delay) => injected.delay += delay,
+            _ if injected.response.is_some() => continue,
+            FaultKind::Status {
+                status,
+                ref message,
+                retry_after,
+            } => {
+                let message = message
+                    .clone()
+                    .unwrap_or_else(|| format!("Injected {} response", status));
+                let mut response = crate::routes::json_response(
+                    status,
+                    &serde_json::json!({ "message": message }),
+                );
+                if let Some(secs) = retry_after {
+                    response
+                        .headers_mut()
+                        .insert(hyper::header::RETRY_AFTER, secs.into());
+                }
+                injected.response = Some(response);
+            }
+            FaultKind::Unauthorized => {
+                injected.response = Some(crate::routes::unauthenticated());
+            }
+        }
+        if let Some(ref mut remaining) = fault.remaining {
+            *remaining -= 1;
+        }
+    }
+    faults.retain(|f| f.remaining != Some(0));
+    injected
+}
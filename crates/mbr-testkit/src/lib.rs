@@ -0,0 +1,535 @@
+//! In-process mock Metabase server for testing code built on `mbr-core`
+//!
+//! [`MockMetabase`] answers the endpoints `MetabaseClient` calls (session,
+//! current user, cards and their queries, search, collections, databases,
+//! schemas, tables, dataset) from data seeded through its builder, on a
+//! random local port. [`Fault`]s inject latency, error statuses and expired
+//! sessions to exercise retries and auto-relogin.
+//!
+//! ```ignore
+//! let server = MockMetabase::builder()
+//!     .card(1, "Revenue")
+//!     .card_result(1, query_result(&["month", "total"], vec![vec![json!("2024-01"), json!(42)]]))
+//!     .fault(Fault::status(503).on("/api/card").times(1))
+//!     .start()
+//!     .await;
+//!
+//! let client = server.client()?;
+//! let questions = client.list_questions(None, None, None).await?;
+//! ```
+
+mod fault;
+mod routes;
+
+pub use fault::Fault;
+
+use hyper::Server;
+use hyper::service::{make_service_fn, service_fn};
+use mbr_core::api::client::MetabaseClient;
+use mbr_core::api::models::{
+    CollectionItem, Column, CurrentUser, Database, QueryData, QueryResult, Question, TableInfo,
+    TableMetadata,
+};
+use mbr_core::error::ApiError;
+use routes::State;
+use serde_json::Value;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::oneshot;
+
+/// Username accepted by `POST /api/session` unless set with
+/// [`MockMetabaseBuilder::credentials`]
+pub const DEFAULT_USERNAME: &str = "admin@example.com";
+/// Password accepted by `POST /api/session` unless set with
+/// [`MockMetabaseBuilder::credentials`]
+pub const DEFAULT_PASSWORD: &str = "password";
+
+/// A request the mock server received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedRequest {
+    pub method: String,
+    /// Path with query string
+    pub path: String,
+    /// JSON body, if any
+    pub body: Option<Value>,
+}
+
+/// A running mock Metabase server, stopped when dropped.
+pub struct MockMetabase {
+    url: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockMetabase {
+    pub fn builder() -> MockMetabaseBuilder {
+        MockMetabaseBuilder::default()
+    }
+
+    /// Base URL to point `MetabaseClient` (or `MBR_URL`) at.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Start a new session, as logging in would.
+    pub fn session_token(&self) -> String {
+        self.state().issue_token()
+    }
+
+    /// A client with a fresh session on this server.
+    pub fn client(&self) -> Result<MetabaseClient, ApiError> {
+        MetabaseClient::with_session_token(self.url.clone(), self.session_token())
+    }
+
+    /// Inject a fault into the requests from now on.
+    pub fn inject(&self, fault: Fault) {
+        self.state().faults.push(fault);
+    }
+
+    /// End every session, so the next authenticated request gets a 401
+    /// until the client logs in again.
+    pub fn expire_sessions(&self) {
+        self.state().tokens.clear();
+    }
+
+    /// Requests received so far, in order.
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.state().requests.clone()
+    }
+
+    /// Number of requests received with this method and path (query
+    /// string excluded).
+    pub fn request_count(&self, method: &str, path: &str) -> usize {
+        self.state()
+            .requests
+            .iter()
+            .filter(|r| r.method == method && r.path.split('?').next() == Some(path))
+            .count()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockMetabase {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+/// Seeds the data a [`MockMetabase`] serves.
+#[derive(Debug, Default)]
+pub struct MockMetabaseBuilder {
+    state: State,
+}
+
+impl MockMetabaseBuilder {
+    /// Username and password accepted by login.
+    pub fn credentials(mut self, username: &str, password: &str) -> Self {
+        self.state.username = username.to_string();
+        self.state.password = password.to_string();
+        self
+    }
+
+    /// Accept this API key (`X-API-Key`) besides session tokens.
+    pub fn api_key(mut self, key: &str) -> Self {
+        self.state.api_key = Some(key.to_string());
+        self
+    }
+
+    /// User returned by `/api/user/current` (default: the login username).
+    pub fn user(mut self, user: CurrentUser) -> Self {
+        self.state.user = Some(user);
+        self
+    }
+
+    /// Add a saved question.
+    pub fn question(mut self, question: Question) -> Self {
+        self.state.questions.push(question);
+        self
+    }
+
+    /// Add a saved question outside any collection.
+    pub fn card(self, id: u32, name: &str) -> Self {
+        self.question(Question {
+            id,
+            name: name.to_string(),
+            description: None,
+            collection_id: None,
+            collection: None,
+        })
+    }
+
+    /// Result of running a question (default: no rows).
+    pub fn card_result(mut self, id: u32, result: QueryResult) -> Self {
+        self.state.card_results.insert(id, result);
+        self
+    }
+
+    /// Add a collection. Its `location` places it: `"/"` at the root,
+    /// `"/5/"` inside collection 5.
+    pub fn collection(mut self, collection: CollectionItem) -> Self {
+        self.state.collections.push(collection);
+        self
+    }
+
+    /// Add a database.
+    pub fn database(mut self, database: Database) -> Self {
+        self.state.databases.push(database);
+        self
+    }
+
+    /// Add a schema and its tables to a database.
+    pub fn schema(mut self, database_id: u32, schema: &str, tables: Vec<TableInfo>) -> Self {
+        self.state
+            .schemas
+            .entry(database_id)
+            .or_default()
+            .insert(schema.to_string(), tables);
+        self
+    }
+
+    /// Fields of a table, for `/api/table/:id/query_metadata`.
+    pub fn table_metadata(mut self, metadata: TableMetadata) -> Self {
+        self.state.table_metadata.insert(metadata.id, metadata);
+        self
+    }
+
+    /// Result of every ad-hoc query (`POST /api/dataset`; default: no rows).
+    pub fn dataset_result(mut self, result: QueryResult) -> Self {
+        self.state.dataset_result = Some(result);
+        self
+    }
+
+    /// Answer requests to `path` (and the paths beneath it) with an error.
+    pub fn error(self, method: &str, path: &str, status: u16, message: &str) -> Self {
+        self.fault(
+            Fault::status(status)
+                .method(method)
+                .on(path)
+                .message(message),
+        )
+    }
+
+    /// Inject a fault from the start.
+    pub fn fault(mut self, fault: Fault) -> Self {
+        self.state.faults.push(fault);
+        self
+    }
+
+    /// Serve on a random local port. Must be called within a Tokio runtime.
+    pub async fn start(self) -> MockMetabase {
+        let state = Arc::new(Mutex::new(self.state));
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    routes::handle(state.clone(), request)
+                }))
+            }
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url = format!("http://{}", server.local_addr());
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        tokio::spawn(server.with_graceful_shutdown(async {
+            let _ = stopped.await;
+        }));
+
+        MockMetabase {
+            url,
+            state,
+            shutdown: Some(shutdown),
+        }
+    }
+}
+
+/// A query result with the given columns and rows, typed from the first row.
+pub fn query_result(columns: &[&str], rows: Vec<Vec<Value>>) -> QueryResult {
+    let cols = columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let base_type = match rows.first().and_then(|row| row.get(i)) {
+                Some(Value::Number(n)) if n.is_f64() => "type/Float",
+                Some(Value::Number(_)) => "type/Integer",
+                Some(Value::Bool(_)) => "type/Boolean",
+                _ => "type/Text",
+            };
+            Column {
+                name: name.to_string(),
+                display_name: name.to_string(),
+                base_type: base_type.to_string(),
+                ..Default::default()
+            }
+        })
+        .collect();
+    QueryResult {
+        data: QueryData { cols, rows },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mbr_core::api::models::TableField;
+    use mbr_core::api::transport::{Cassette, CassetteFile};
+    use mbr_core::error::AppError;
+    use mbr_core::storage::config::HttpConfig;
+    use mbr_core::utils::retry::RetryConfig;
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn sample() -> MockMetabaseBuilder {
+        MockMetabase::builder()
+            .card(1, "Revenue by month")
+            .card(2, "Active users")
+            .card_result(
+                1,
+                query_result(&["month", "total"], vec![vec![json!("2024-01"), json!(42)]]),
+            )
+            .database(Database {
+                id: 1,
+                name: "Sample".to_string(),
+                engine: Some("h2".to_string()),
+                description: None,
+                is_sample: true,
+                is_saved_questions: false,
+            })
+            .schema(
+                1,
+                "PUBLIC",
+                vec![TableInfo {
+                    id: 10,
+                    name: "ORDERS".to_string(),
+                    schema: Some("PUBLIC".to_string()),
+                    display_name: Some("Orders".to_string()),
+                    description: None,
+                    entity_type: None,
+                }],
+            )
+    }
+
+    #[tokio::test]
+    async fn test_login_and_seeded_endpoints() {
+        let server = sample().start().await;
+        let token = MetabaseClient::login(
+            server.url(),
+            DEFAULT_USERNAME,
+            DEFAULT_PASSWORD,
+            &HttpConfig::default(),
+        )
+        .await
+        .expect("login");
+        let client = MetabaseClient::with_session_token(server.url().to_string(), token).unwrap();
+
+        let questions = client.list_questions(None, None, None).await.unwrap();
+        assert_eq!(questions.len(), 2);
+        let found = client
+            .list_questions(Some("revenue"), None, None)
+            .await
+            .unwrap();
+        assert_eq!(found[0].id, 1);
+
+        let result = client.execute_question(1, None).await.unwrap();
+        assert_eq!(result.data.rows, vec![vec![json!("2024-01"), json!(42)]]);
+        assert_eq!(result.data.cols[1].base_type, "type/Integer");
+
+        assert_eq!(client.list_schemas(1).await.unwrap(), vec!["PUBLIC"]);
+        assert_eq!(client.list_tables(1, "PUBLIC").await.unwrap()[0].id, 10);
+        assert!(client.execute_question(99, None).await.is_err());
+
+        let wrong = MetabaseClient::login(
+            server.url(),
+            DEFAULT_USERNAME,
+            "nope",
+            &HttpConfig::default(),
+        )
+        .await;
+        assert!(matches!(wrong, Err(AppError::Auth(_))));
+    }
+
+    fn table(id: u32, name: &str, fields: Vec<TableField>) -> TableMetadata {
+        TableMetadata {
+            id,
+            db_id: 1,
+            name: name.to_string(),
+            schema: Some("PUBLIC".to_string()),
+            display_name: None,
+            description: None,
+            fields,
+        }
+    }
+
+    fn table_field(id: u32, name: &str, fk_target_field_id: Option<u32>) -> TableField {
+        TableField {
+            id,
+            name: name.to_string(),
+            display_name: None,
+            base_type: Some("type/Integer".to_string()),
+            semantic_type: None,
+            description: None,
+            fk_target_field_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_field_and_foreign_key_endpoints() {
+        let server = sample()
+            .table_metadata(table(10, "ORDERS", vec![table_field(100, "ID", None)]))
+            .table_metadata(table(
+                11,
+                "ITEMS",
+                vec![
+                    table_field(110, "ID", None),
+                    table_field(111, "ORDER_ID", Some(100)),
+                ],
+            ))
+            .start()
+            .await;
+        let client = server.client().unwrap();
+
+        let field = client.get_field(111).await.unwrap();
+        assert_eq!(field.table_id, 11);
+        assert_eq!(field.table.unwrap().name, "ITEMS");
+        assert!(client.get_field(999).await.is_err());
+
+        let fks = client.list_table_fks(10).await.unwrap();
+        assert_eq!(fks.len(), 1);
+        assert_eq!(fks[0].origin.id, 111);
+        assert_eq!(fks[0].destination_id, Some(100));
+        assert!(client.list_table_fks(11).await.unwrap().is_empty());
+        assert!(client.list_table_fks(99).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_faults_are_retried() {
+        let server = sample()
+            .fault(Fault::status(503).on("/api/card").method("GET").times(2))
+            .start()
+            .await;
+        let client = server.client().unwrap().with_retry(RetryConfig {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            ..RetryConfig::default()
+        });
+
+        let questions = client.list_questions(None, None, None).await.unwrap();
+        assert_eq!(questions.len(), 2);
+        assert_eq!(server.request_count("GET", "/api/card"), 3);
+
+        server.inject(Fault::status(500).on("/api/database").message("boom"));
+        let error = client.list_databases().await.unwrap_err();
+        assert!(error.to_string().contains("boom"));
+
+        // Gateway timeouts surface as timeouts once the retries are used up
+        server.inject(Fault::status(504).on("/api/collection"));
+        assert!(matches!(
+            client.list_collections().await,
+            Err(AppError::Api(ApiError::Timeout { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_gateway_timeouts_report_the_applied_limit() {
+        let server = sample()
+            .fault(Fault::status(504).on("/api/database"))
+            .fault(Fault::status(408).on("/api/card/1/query"))
+            .start()
+            .await;
+        let http = HttpConfig {
+            read_timeout: Some(7),
+            query_timeout: Some(0),
+            ..HttpConfig::default()
+        };
+        let client = server
+            .client()
+            .unwrap()
+            .with_http(&http)
+            .unwrap()
+            .with_retry(RetryConfig::none());
+
+        assert!(matches!(
+            client.list_databases().await,
+            Err(AppError::Api(ApiError::Timeout {
+                timeout_secs: 7,
+                ..
+            }))
+        ));
+        // Queries run without a limit here, so no local timeout is claimed
+        assert!(matches!(
+            client.execute_question(1, None).await,
+            Err(AppError::Api(ApiError::Http { status: 408, .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_sessions_and_latency() {
+        let server = sample().start().await;
+        let client = server.client().unwrap();
+        assert!(client.get_current_user().await.is_ok());
+
+        server.expire_sessions();
+        assert!(matches!(
+            client.get_current_user().await,
+            Err(AppError::Api(ApiError::Unauthorized { .. }))
+        ));
+        // A new session works again
+        assert!(server.client().unwrap().get_current_user().await.is_ok());
+
+        server.inject(Fault::latency(Duration::from_millis(50)).times(1));
+        let started = std::time::Instant::now();
+        server.client().unwrap().list_databases().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let server = sample().start().await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+
+        let cassette = Arc::new(Cassette::record_to(&path, []).unwrap());
+        let recorder = server.client().unwrap().with_cassette(cassette);
+        let questions = recorder.list_questions(None, None, None).await.unwrap();
+        let result = recorder.execute_question(1, None).await.unwrap();
+        assert!(recorder.execute_question(99, None).await.is_err());
+        let databases = recorder.list_databases().await.unwrap();
+
+        // Every append leaves a complete cassette behind
+        let file: CassetteFile =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(file.interactions.len(), 4);
+        assert_eq!(file.interactions[2].response.status, 404);
+        let requests = server.request_count("GET", "/api/card");
+
+        // Replay needs neither the server nor a session
+        let cassette = Arc::new(Cassette::replay_from(&path).unwrap());
+        let player = MetabaseClient::new("http://replayed.invalid".to_string())
+            .unwrap()
+            .with_cassette(cassette);
+        assert_eq!(
+            player.list_questions(None, None, None).await.unwrap().len(),
+            questions.len()
+        );
+        assert_eq!(
+            player.execute_question(1, None).await.unwrap().data.rows,
+            result.data.rows
+        );
+        assert!(matches!(
+            player.execute_question(99, None).await,
+            Err(AppError::Api(ApiError::Http { status: 404, .. }))
+        ));
+        assert_eq!(
+            player.list_databases().await.unwrap()[0].id,
+            databases[0].id
+        );
+        assert!(player.list_collections().await.is_err());
+        assert_eq!(server.request_count("GET", "/api/card"), requests);
+    }
+}
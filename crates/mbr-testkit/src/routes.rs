@@ -0,0 +1,433 @@
+//! Seeded data and the Metabase endpoints answered from it
+
+use crate::ReceivedRequest;
+use crate::fault::{self, Fault};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use mbr_core::api::models::{
+    CollectionEntry, CollectionItem, CurrentUser, Database, Field, FieldTable, ForeignKey,
+    QueryResult, Question, TableField, TableInfo, TableMetadata,
+};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+/// Everything the mock server knows: credentials, sessions, seeded data,
+/// pending faults and the requests received so far.
+#[derive(Debug)]
+pub(crate) struct State {
+    pub username: String,
+    pub password: String,
+    pub api_key: Option<String>,
+    pub user: Option<CurrentUser>,
+    /// Session tokens that authenticate requests
+    pub tokens: BTreeSet<String>,
+    pub issued_tokens: u32,
+    pub questions: Vec<Question>,
+    pub card_results: BTreeMap<u32, QueryResult>,
+    pub collections: Vec<CollectionItem>,
+    pub databases: Vec<Database>,
+    /// Database id -> schema name -> tables
+    pub schemas: BTreeMap<u32, BTreeMap<String, Vec<TableInfo>>>,
+    pub table_metadata: BTreeMap<u32, TableMetadata>,
+    pub dataset_result: Option<QueryResult>,
+    pub faults: Vec<Fault>,
+    pub requests: Vec<ReceivedRequest>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            username: crate::DEFAULT_USERNAME.to_string(),
+            password: crate::DEFAULT_PASSWORD.to_string(),
+            api_key: None,
+            user: None,
+            tokens: BTreeSet::new(),
+            issued_tokens: 0,
+            questions: Vec::new(),
+            card_results: BTreeMap::new(),
+            collections: Vec::new(),
+            databases: Vec::new(),
+            schemas: BTreeMap::new(),
+            table_metadata: BTreeMap::new(),
+            dataset_result: None,
+            faults: Vec::new(),
+            requests: Vec::new(),
+        }
+    }
+}
+
+impl State {
+    /// Start a new valid session.
+    pub fn issue_token(&mut self) -> String {
+        self.issued_tokens += 1;
+        let token = format!("mock-session-{}", self.issued_tokens);
+        self.tokens.insert(token.clone());
+        token
+    }
+
+    fn is_authenticated(&self, request: &Request<Body>) -> bool {
+        let header = |name| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|v: &hyper::header::HeaderValue| v.to_str().ok())
+        };
+        header("x-metabase-session").is_some_and(|token| self.tokens.contains(token))
+            || header("x-api-key").is_some_and(|key| self.api_key.as_deref() == Some(key))
+    }
+
+    fn respond(&self, method: &Method, path: &str, query: &str, body: &Value) -> Response<Body> {
+        let segments: Vec<&str> = path
+            .trim_start_matches("/api/")
+            .trim_end_matches('/')
+            .split('/')
+            .collect();
+        let id = |segment: &str| segment.parse::<u32>().ok();
+
+        match (method.as_str(), segments.as_slice()) {
+            // The session itself is ended in `handle`
+            ("DELETE", ["session"]) => Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .body(Body::empty())
+                .unwrap_or_default(),
+            ("GET", ["user", "current"]) => {
+                let user = self.user.clone().unwrap_or_else(|| CurrentUser {
+                    id: 1,
+                    email: self.username.clone(),
+                    first_name: None,
+                    last_name: None,
+                    common_name: None,
+                    is_superuser: Some(true),
+                });
+                json_response(200, &json!(user))
+            }
+            ("GET", ["card"]) => json_response(200, &json!(self.questions)),
+            ("GET", ["card", card_id]) => match self.question(id(card_id)) {
+                Some(question) => json_response(
+                    200,
+                    &json!({
+                        "id": question.id,
+                        "name": question.name,
+                        "description": question.description,
+                        "collection_id": question.collection_id,
+                        "database_id": self.databases.first().map(|db| db.id),
+                        "query_type": "native",
+                        "dataset_query": {},
+                        "parameters": [],
+                    }),
+                ),
+                None => not_found(),
+            },
+            ("POST", ["card", card_id, "query"]) => match self.question(id(card_id)) {
+                // Metabase answers query executions with 202 Accepted
+                Some(question) => json_response(
+                    202,
+                    &json!(
+                        self.card_results
+                            .get(&question.id)
+                            .cloned()
+                            .unwrap_or_else(|| crate::query_result(&[], Vec::new()))
+                    ),
+                ),
+                None => not_found(),
+            },
+            ("GET", ["search"]) => {
+                let term = query_param(query, "q").unwrap_or_default().to_lowercase();
+                let limit = query_param(query, "limit").and_then(|l| l.parse().ok());
+                let data: Vec<Value> = self
+                    .questions
+                    .iter()
+                    .filter(|q| q.name.to_lowercase().contains(&term))
+                    .take(limit.unwrap_or(usize::MAX))
+                    .map(|q| {
+                        json!({
+                            "id": q.id,
+                            "name": q.name,
+                            "description": q.description,
+                            "collection_id": q.collection_id,
+                            "collection": q.collection,
+                            "model": "card",
+                        })
+                    })
+                    .collect();
+                json_response(200, &json!({ "data": data, "total": data.len() }))
+            }
+            ("GET", ["collection"]) => json_response(200, &json!(self.collections)),
+            ("GET", ["collection", collection_id, "items"]) => {
+                let parent = match *collection_id {
+                    "root" => None,
+                    other => match id(other) {
+                        Some(id) if self.collections.iter().any(|c| c.id == Some(id)) => Some(id),
+                        _ => return not_found(),
+                    },
+                };
+                let data = self.collection_items(parent);
+                json_response(200, &json!({ "data": data, "total": data.len() }))
+            }
+            ("GET", ["database"]) => json_response(200, &json!({ "data": self.databases })),
+            ("GET", ["database", db_id, "schemas"]) => match self.database(id(db_id)) {
+                Some(db_id) => {
+                    let schemas: Vec<&String> = self
+                        .schemas
+                        .get(&db_id)
+                        .map(|schemas| schemas.keys().collect())
+                        .unwrap_or_default();
+                    json_response(200, &json!(schemas))
+                }
+                None => not_found(),
+            },
+            ("GET", ["database", db_id, "schema", schema]) => {
+                match self
+                    .database(id(db_id))
+                    .and_then(|db_id| self.schemas.get(&db_id)?.get(*schema))
+                {
+                    Some(tables) => json_response(200, &json!(tables)),
+                    None => not_found(),
+                }
+            }
+            ("GET", ["table", table_id, "query_metadata"]) => {
+                match id(table_id).and_then(|id| self.table_metadata.get(&id)) {
+                    Some(metadata) => json_response(200, &json!(metadata)),
+                    None => not_found(),
+                }
+            }
+            ("GET", ["table", table_id, "fks"]) => match id(table_id) {
+                Some(table_id) if self.table_metadata.contains_key(&table_id) => {
+                    json_response(200, &json!(self.foreign_keys_to(table_id)))
+                }
+                _ => not_found(),
+            },
+            ("GET", ["field", field_id]) => {
+                let found = self.table_metadata.values().find_map(|table| {
+                    let field = table.fields.iter().find(|f| Some(f.id) == id(field_id))?;
+                    Some(field_details(table, field))
+                });
+                match found {
+                    Some(field) => json_response(200, &json!(field)),
+                    None => not_found(),
+                }
+            }
+            ("POST", ["dataset"]) if body.get("database").is_some() => json_response(
+                202,
+                &json!(
+                    self.dataset_result
+                        .clone()
+                        .unwrap_or_else(|| crate::query_result(&[], Vec::new()))
+                ),
+            ),
+            ("POST", ["dataset"]) => {
+                json_response(400, &json!({ "message": "database is required" }))
+            }
+            _ => not_found(),
+        }
+    }
+
+    fn login(&mut self, body: &Value) -> Response<Body> {
+        let field = |name| body.get(name).and_then(Value::as_str);
+        if field("username") == Some(self.username.as_str())
+            && field("password") == Some(self.password.as_str())
+        {
+            let token = self.issue_token();
+            json_response(200, &json!({ "id": token }))
+        } else {
+            json_response(
+                401,
+                &json!({ "errors": { "password": "did not match stored password" } }),
+            )
+        }
+    }
+
+    fn question(&self, id: Option<u32>) -> Option<&Question> {
+        self.questions.iter().find(|q| Some(q.id) == id)
+    }
+
+    fn database(&self, id: Option<u32>) -> Option<u32> {
+        self.databases
+            .iter()
+            .find(|db| Some(db.id) == id)
+            .map(|db| db.id)
+    }
+
+    /// Fields of other tables whose foreign keys point into `table_id`.
+    fn foreign_keys_to(&self, table_id: u32) -> Vec<ForeignKey> {
+        let targets: BTreeSet<u32> = self.table_metadata[&table_id]
+            .fields
+            .iter()
+            .map(|f| f.id)
+            .collect();
+        self.table_metadata
+            .values()
+            .flat_map(|table| table.fields.iter().map(move |field| (table, field)))
+            .filter(|(_, field)| {
+                field
+                    .fk_target_field_id
+                    .is_some_and(|target| targets.contains(&target))
+            })
+            .map(|(table, field)| ForeignKey {
+                origin: field_details(table, field),
+                destination_id: field.fk_target_field_id,
+            })
+            .collect()
+    }
+
+    /// Sub-collections and questions directly in a collection (None: root).
+    fn collection_items(&self, parent: Option<u32>) -> Vec<CollectionEntry> {
+        // A collection's location lists its ancestors: "/" at the root,
+        // "/5/7/" inside collection 7 inside collection 5
+        let in_parent = |location: Option<&str>| match parent {
+            None => matches!(location, None | Some("/")),
+            Some(id) => location.is_some_and(|l| l.ends_with(&format!("/{}/", id))),
+        };
+        let collections = self
+            .collections
+            .iter()
+            .filter(|c| in_parent(c.location.as_deref()))
+            .filter_map(|c| {
+                Some(CollectionEntry {
+                    id: c.id?,
+                    name: c.name.clone(),
+                    description: c.description.clone(),
+                    model: "collection".to_string(),
+                    personal_owner_id: c.personal_owner_id,
+                })
+            });
+        let questions = self
+            .questions
+            .iter()
+            .filter(|q| q.collection_id == parent)
+            .map(|q| CollectionEntry {
+                id: q.id,
+                name: q.name.clone(),
+                description: q.description.clone(),
+                model: "card".to_string(),
+                personal_owner_id: None,
+            });
+        collections.chain(questions).collect()
+    }
+}
+
+/// Answer one request: record it, apply faults, check authentication and
+/// route it.
+pub(crate) async fn handle(
+    state: Arc<Mutex<State>>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let bytes = hyper::body::to_bytes(body).await.unwrap_or_default();
+    let body: Value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    let request = Request::from_parts(parts, Body::empty());
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().unwrap_or_default().to_string();
+
+    let injected = {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        state.requests.push(ReceivedRequest {
+            method: method.to_string(),
+            path: match query.as_str() {
+                "" => path.clone(),
+                query => format!("{}?{}", path, query),
+            },
+            body: (!body.is_null()).then(|| body.clone()),
+        });
+        fault::inject(&mut state.faults, method.as_str(), &path)
+    };
+    if !injected.delay.is_zero() {
+        tokio::time::sleep(injected.delay).await;
+    }
+    if let Some(response) = injected.response {
+        return Ok(response);
+    }
+
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    if method == Method::POST && path == "/api/session" {
+        return Ok(state.login(&body));
+    }
+    if !state.is_authenticated(&request) {
+        return Ok(unauthenticated());
+    }
+    if method == Method::DELETE
+        && path == "/api/session"
+        && let Some(token) = request
+            .headers()
+            .get("x-metabase-session")
+            .and_then(|v| v.to_str().ok())
+    {
+        state.tokens.remove(token);
+    }
+    Ok(state.respond(&method, &path, &query, &body))
+}
+
+/// A field of a seeded table as /api/field/:id returns it.
+fn field_details(table: &TableMetadata, field: &TableField) -> Field {
+    Field {
+        id: field.id,
+        name: field.name.clone(),
+        display_name: field.display_name.clone(),
+        table_id: table.id,
+        base_type: field.base_type.clone(),
+        table: Some(FieldTable {
+            id: table.id,
+            db_id: table.db_id,
+            name: table.name.clone(),
+            display_name: table.display_name.clone(),
+            schema: table.schema.clone(),
+        }),
+    }
+}
+
+pub(crate) fn json_response(status: u16, body: &Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap_or_default()
+}
+
+/// Metabase's answer to a missing or expired session.
+pub(crate) fn unauthenticated() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::UNAUTHORIZED)
+        .body(Body::from("Unauthenticated"))
+        .unwrap_or_default()
+}
+
+fn not_found() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Body::from("Not found."))
+        .unwrap_or_default()
+}
+
+/// A decoded query string parameter.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == name).then(|| percent_decode(value))
+    })
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
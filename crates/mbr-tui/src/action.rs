@@ -6,9 +6,13 @@
 // Allow unused variants as they are designed for Phase 4+ implementation
 #![allow(dead_code)]
 
-use mbr_core::api::models::{CollectionItem, CurrentUser, Database, Question, TableInfo};
+use mbr_core::api::models::{
+    CollectionEntry, CollectionItem, CurrentUser, Database, Question, TableInfo,
+};
+use mbr_core::storage::credentials::Session;
 
-use crate::components::QueryResultData;
+use crate::components::{FilterClause, QueryResultData, RecordLink, SortOrder};
+use crate::service::ServiceError;
 
 /// Application-level actions for component-to-app communication.
 ///
@@ -56,8 +60,8 @@ pub enum AppAction {
     /// Authentication validated successfully
     AuthValidated(CurrentUser),
 
-    /// Data loading failed with context and error message
-    LoadFailed(DataRequest, String),
+    /// Data loading failed with context and error
+    LoadFailed(DataRequest, ServiceError),
 
     // === Query Execution (Phase 6) ===
     /// Execute a question query
@@ -67,17 +71,14 @@ pub enum AppAction {
     QueryResultLoaded(u64, QueryResultData),
 
     /// Query execution failed (request_id, error)
-    QueryFailed(u64, String),
+    QueryFailed(u64, ServiceError),
 
     /// Return to Questions list from query result view
     BackToQuestions,
 
-    // === Collection Drill-down (Phase 3) ===
-    /// Drill down into a collection to view its questions
-    DrillDownCollection(u32, String), // (collection_id, collection_name)
-
-    /// Return to Collections list from collection questions view
-    BackToCollections,
+    // === Collection Tree ===
+    /// Collection items loaded successfully from API (collection_id, items)
+    CollectionItemsLoaded(Option<u32>, Vec<CollectionEntry>), // None = root collection
 
     // === Database Drill-down (Phase 3) ===
     /// Drill down into a database to view its schemas
@@ -106,6 +107,105 @@ pub enum AppAction {
 
     /// Table preview data loaded successfully
     TablePreviewLoaded(QueryResultData),
+
+    // === Foreign-key Navigation ===
+    /// Rows reached through a key loaded successfully
+    LinkedRowsLoaded(QueryResultData),
+
+    /// Tables referencing a primary key loaded (links, key value)
+    ReferencesLoaded(Vec<RecordLink>, serde_json::Value),
+
+    /// Return from linked rows to the result they were opened from
+    BackFromLinkedRows,
+
+    // === Command Palette / Command Line ===
+    /// Execute a question looked up by name among loaded questions
+    ExecuteQuestionByName(String),
+
+    /// Show the help overlay
+    ShowHelp,
+
+    /// Refresh data for the current view
+    Refresh,
+
+    /// Open the sort modal for the current result
+    OpenSortModal,
+
+    /// Open the filter modal for the current result
+    OpenFilterModal,
+
+    /// Open the column manager for the current result
+    OpenColumnManager,
+
+    /// Open the group-by column selection for the current result
+    OpenGroupModal,
+
+    /// Group result rows by column names
+    GroupBy(Vec<String>),
+
+    /// Remove the result grouping
+    Ungroup,
+
+    /// Show or hide the result summary footer
+    ToggleSummary,
+
+    /// Open the column profile popup (named column, or the current one)
+    ProfileColumn(Option<String>),
+
+    /// Open the cell viewer on the selected row (named column, or the current one)
+    ViewCell(Option<String>),
+
+    /// Replace the result filter with the given conditions
+    ApplyFilter(Vec<FilterClause>),
+
+    /// Add a condition to the active result filter
+    AddFilter(FilterClause),
+
+    /// Remove the active result filter
+    ClearFilter,
+
+    /// Sort result rows by column names, highest priority first
+    ApplySort(Vec<(String, SortOrder)>),
+
+    /// Restore the original result row order
+    ClearSort,
+
+    /// Export visible result rows to a file (format from extension)
+    ExportResult(String),
+
+    /// Show the history of executed questions
+    ShowHistory,
+
+    /// Open the list of open results
+    ShowBuffers,
+
+    /// Show an open result (0-based buffer index)
+    SwitchBuffer(usize),
+
+    /// Close an open result (the shown one if None)
+    CloseBuffer(Option<usize>),
+
+    /// Compare the shown result with another one (toggles the split if None)
+    SplitView(Option<usize>),
+
+    /// Reopen the navigation path saved at the end of the last session
+    RestoreSession,
+
+    // === Login ===
+    /// Open the login screen
+    ShowLogin,
+
+    /// Logged in from the login screen (saved session, its user)
+    LoggedIn(Session, CurrentUser),
+
+    /// Login from the login screen failed
+    LoginFailed(String),
+
+    /// Switch to the next color theme
+    CycleTheme,
+
+    /// Switch to a color theme by name
+    SetTheme(String),
 }
 
 /// Target content views for navigation
@@ -128,10 +228,10 @@ pub enum DataRequest {
     Questions,
     /// Search questions by query string
     SearchQuestions(String),
-    /// Load questions filtered by collection ID
-    FilterQuestionsByCollection(u32),
     /// Load collections list
     Collections,
+    /// Load items (sub-collections, dashboards, cards) of a collection
+    CollectionItems(Option<u32>), // None = root collection
     /// Load databases list
     Databases,
     /// Load a specific question's details
@@ -146,6 +246,12 @@ pub enum DataRequest {
     Tables(u32, String), // (database_id, schema_name)
     /// Preview table data
     TablePreview(u32, u32), // (database_id, table_id)
+    /// Rows referenced by a foreign key value (target_field_id, value)
+    ForeignKeyRows(u32, serde_json::Value),
+    /// Tables referencing a primary key value (table_id, field_id, value)
+    References(u32, u32, serde_json::Value),
+    /// Rows of a table whose key equals a value
+    LinkedRows(RecordLink, serde_json::Value),
 }
 
 impl From<usize> for ContentTarget {
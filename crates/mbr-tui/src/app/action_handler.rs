@@ -3,8 +3,9 @@
 //! Processes AppAction events and updates application state accordingly.
 
 use crate::action::{AppAction, ContentTarget, DataRequest};
-use crate::components::{ContentView, QueryResultData};
-use crate::service::LoadState;
+use crate::components::{ActiveTab, ContentView, QueryResultData, RecordLink};
+use crate::keymap::KeyAction;
+use crate::service::{LoadState, ServiceError};
 
 use super::App;
 
@@ -21,15 +22,12 @@ impl App {
             AppAction::PreviousPanel => {
                 self.switch_to_tab(self.active_tab.previous());
             }
-            AppAction::Navigate(target) => {
-                let view = match target {
-                    ContentTarget::Welcome => ContentView::Welcome,
-                    ContentTarget::Questions => ContentView::Questions,
-                    ContentTarget::Collections => ContentView::Collections,
-                    ContentTarget::Databases => ContentView::Databases,
-                };
-                self.content.set_view(view);
-            }
+            AppAction::Navigate(target) => match target {
+                ContentTarget::Welcome => self.content.set_view(ContentView::Welcome),
+                ContentTarget::Questions => self.switch_to_tab(ActiveTab::Questions),
+                ContentTarget::Collections => self.switch_to_tab(ActiveTab::Collections),
+                ContentTarget::Databases => self.switch_to_tab(ActiveTab::Databases),
+            },
             AppAction::LoadData(request) => {
                 self.handle_data_request(request);
             }
@@ -75,17 +73,14 @@ impl App {
                 self.handle_query_failed(request_id, error);
             }
             AppAction::BackToQuestions => {
+                self.remember_result_view();
                 self.data.query_result = None;
                 self.content.back_to_questions();
                 self.status_bar.set_message("Returned to Questions list");
             }
-            // === Collection Drill-down ===
-            AppAction::DrillDownCollection(collection_id, collection_name) => {
-                self.handle_drill_down_collection(collection_id, collection_name);
-            }
-            AppAction::BackToCollections => {
-                self.content.exit_collection_questions();
-                self.status_bar.set_message("Returned to Collections list");
+            // === Collection Tree ===
+            AppAction::CollectionItemsLoaded(collection_id, items) => {
+                self.handle_collection_items_loaded(collection_id, items);
             }
             // === Database Drill-down ===
             AppAction::DrillDownDatabase(database_id, database_name) => {
@@ -118,6 +113,146 @@ impl App {
             AppAction::TablePreviewLoaded(data) => {
                 self.handle_table_preview_loaded(data);
             }
+            // === Foreign-key Navigation ===
+            AppAction::LinkedRowsLoaded(data) => {
+                self.handle_linked_rows_loaded(data);
+            }
+            AppAction::ReferencesLoaded(links, value) => {
+                self.handle_references_loaded(links, value);
+            }
+            AppAction::BackFromLinkedRows => {
+                self.content.exit_linked_rows();
+                self.status_bar.set_message("Returned to previous result");
+            }
+            // === Session and History ===
+            AppAction::ShowHistory => {
+                self.show_history();
+            }
+            AppAction::RestoreSession => {
+                self.restore_session();
+            }
+            // === Result Buffers ===
+            AppAction::ShowBuffers => {
+                self.show_buffer_list();
+            }
+            AppAction::SwitchBuffer(index) => {
+                self.switch_buffer(index);
+            }
+            AppAction::CloseBuffer(index) => {
+                self.close_buffer(index);
+            }
+            AppAction::SplitView(index) => {
+                self.split_view(index);
+            }
+            // === Command Palette / Command Line ===
+            AppAction::ExecuteQuestionByName(name) => {
+                self.execute_question_by_name(&name);
+            }
+            AppAction::ShowHelp => {
+                self.show_help = true;
+            }
+            AppAction::Refresh => {
+                self.handle_refresh();
+            }
+            AppAction::OpenSortModal
+            | AppAction::OpenFilterModal
+            | AppAction::OpenColumnManager
+            | AppAction::OpenGroupModal
+            | AppAction::ToggleSummary
+            | AppAction::ProfileColumn(_)
+            | AppAction::ViewCell(_)
+                if !self.content.is_result_view() =>
+            {
+                self.status_bar
+                    .set_message("Sort, filter, grouping and columns require a query result");
+            }
+            AppAction::OpenSortModal => {
+                self.content.open_sort_modal();
+            }
+            AppAction::OpenFilterModal => {
+                self.content.open_filter_modal();
+            }
+            AppAction::OpenColumnManager => {
+                self.content.open_column_manager();
+            }
+            AppAction::OpenGroupModal => {
+                self.content.open_group_modal();
+            }
+            AppAction::ProfileColumn(None) => {
+                self.content.open_column_profile();
+            }
+            AppAction::ViewCell(None) => {
+                self.open_cell_viewer(None);
+            }
+            AppAction::ViewCell(Some(column)) => match self.content.find_column_index(&column) {
+                Some(idx) => self.open_cell_viewer(Some(idx)),
+                None => self
+                    .status_bar
+                    .set_message(format!("Unknown column: {}", column)),
+            },
+            AppAction::ProfileColumn(Some(column)) => {
+                match self.content.find_column_index(&column) {
+                    Some(idx) => self.content.show_column_profile(idx),
+                    None => self
+                        .status_bar
+                        .set_message(format!("Unknown column: {}", column)),
+                }
+            }
+            AppAction::GroupBy(columns) => {
+                self.apply_result_grouping(&columns);
+            }
+            AppAction::Ungroup => {
+                self.content.ungroup();
+                self.status_bar.set_message("Grouping removed");
+            }
+            AppAction::ToggleSummary => {
+                self.content.toggle_summary();
+                let state = if self.content.is_summary_visible() {
+                    "on"
+                } else {
+                    "off"
+                };
+                self.status_bar
+                    .set_message(format!("Summary footer {}", state));
+            }
+            AppAction::ApplyFilter(clauses) => {
+                self.apply_result_filter(clauses, false);
+            }
+            AppAction::AddFilter(clause) => {
+                self.apply_result_filter(vec![clause], true);
+            }
+            AppAction::ClearFilter => {
+                self.content.clear_filter();
+                self.status_bar.set_message("Filter cleared");
+            }
+            AppAction::ApplySort(keys) => {
+                self.apply_result_sort(&keys);
+            }
+            AppAction::ClearSort => {
+                self.content.clear_sort();
+                self.status_bar.set_message("Sort cleared");
+            }
+            AppAction::ExportResult(path) => {
+                self.export_result(&path);
+            }
+            AppAction::CycleTheme => {
+                self.cycle_theme();
+            }
+            // === Login ===
+            AppAction::ShowLogin => {
+                if self.login_form.is_none() {
+                    self.show_login();
+                }
+            }
+            AppAction::LoggedIn(session, user) => {
+                self.handle_logged_in(session, user);
+            }
+            AppAction::LoginFailed(error) => {
+                self.handle_login_failed(error);
+            }
+            AppAction::SetTheme(name) => {
+                self.select_theme(&name);
+            }
         }
     }
 
@@ -141,6 +276,21 @@ impl App {
         self.content.update_collections(&self.data.collections);
         self.status_bar
             .set_message(format!("Loaded {} collections", count));
+        // The tree starts with the root expanded, so fetch its items right away
+        let _ = self
+            .action_tx
+            .send(AppAction::LoadData(DataRequest::CollectionItems(None)));
+    }
+
+    fn handle_collection_items_loaded(
+        &mut self,
+        collection_id: Option<u32>,
+        items: Vec<mbr_core::api::models::CollectionEntry>,
+    ) {
+        let count = items.len();
+        self.content.set_collection_items(collection_id, &items);
+        self.status_bar
+            .set_message(format!("Loaded {} collection items", count));
     }
 
     fn handle_databases_loaded(&mut self, databases: Vec<mbr_core::api::models::Database>) {
@@ -151,7 +301,7 @@ impl App {
             .set_message(format!("Loaded {} databases", count));
     }
 
-    fn handle_auth_validated(&mut self, user: mbr_core::api::models::CurrentUser) {
+    pub(super) fn handle_auth_validated(&mut self, user: mbr_core::api::models::CurrentUser) {
         let display_name = user
             .common_name
             .clone()
@@ -163,12 +313,11 @@ impl App {
         self.data.current_user = Some(user);
     }
 
-    fn handle_load_failed(&mut self, request: DataRequest, error: String) {
+    fn handle_load_failed(&mut self, request: DataRequest, error: ServiceError) {
+        let unauthorized = error.is_unauthorized();
+        let error = error.to_string();
         match request {
-            DataRequest::Questions
-            | DataRequest::Refresh
-            | DataRequest::SearchQuestions(_)
-            | DataRequest::FilterQuestionsByCollection(_) => {
+            DataRequest::Questions | DataRequest::Refresh | DataRequest::SearchQuestions(_) => {
                 self.data.questions = LoadState::Error(error.clone());
                 self.content.update_questions(&self.data.questions);
             }
@@ -176,40 +325,89 @@ impl App {
                 self.data.collections = LoadState::Error(error.clone());
                 self.content.update_collections(&self.data.collections);
             }
+            DataRequest::CollectionItems(collection_id) => {
+                self.content
+                    .set_collection_items_error(collection_id, error.clone());
+            }
             DataRequest::Databases => {
                 self.data.databases = LoadState::Error(error.clone());
                 self.content.update_databases(&self.data.databases);
             }
+            DataRequest::Schemas(_) => {
+                self.data.schemas = LoadState::Error(error.clone());
+                self.content.update_schemas(&self.data.schemas);
+            }
+            DataRequest::Tables(..) => {
+                self.data.tables = LoadState::Error(error.clone());
+                self.content.update_tables(&self.data.tables);
+            }
             _ => {}
         }
         self.status_bar.set_message(format!("Error: {}", error));
+        if unauthorized {
+            self.request_login(Some(AppAction::LoadData(request)));
+        }
     }
 
     fn handle_query_result_loaded(&mut self, request_id: u64, result_data: QueryResultData) {
-        if request_id == self.current_request_id {
-            let row_count = result_data.rows.len();
-            let name = result_data.question_name.clone();
-            self.content.set_query_result(result_data);
-            self.status_bar
-                .set_message(format!("Query '{}': {} rows", name, row_count));
+        let duration = self
+            .running_queries
+            .remove(&request_id)
+            .map(|(_, started)| started.elapsed());
+        let row_count = result_data.rows.len();
+        let name = result_data.question_name.clone();
+        let question_id = result_data.question_id;
+        self.record_execution(&result_data, duration);
+
+        // Show the latest execution if the user is still where it was started
+        // (or it refreshes the shown result); otherwise keep it in the background
+        let foreground = (request_id == self.current_request_id
+            && self.foreground_view.as_ref() == Some(&self.content.current_view()))
+            || self.content.active_buffer_question() == Some(question_id);
+        if foreground {
+            // Keep the view state of the shown result
+            self.remember_result_view();
         }
-    }
 
-    fn handle_query_failed(&mut self, request_id: u64, error: String) {
-        if request_id == self.current_request_id {
-            self.status_bar
-                .set_message(format!("Query failed: {}", error));
+        // The column layout and view state remembered for this question are
+        // applied when the buffer is shown
+        let layout = self.tui_state.column_layouts.get(&question_id).cloned();
+        let saved_view = self.tui_state.result_views.get(&question_id).cloned();
+        let restored = saved_view.is_some();
+        let index = self.content.store_result(result_data, layout, saved_view);
+
+        if foreground {
+            self.foreground_view = None;
+            self.content.show_buffer(index);
+            let mut message = format!("Query '{}': {} rows", name, row_count);
+            if restored {
+                message.push_str(" (saved filter/sort/search restored)");
+            }
+            if self.content.buffer_count() > 1 {
+                message.push_str(&format!(" [{}/{}]", index + 1, self.content.buffer_count()));
+            }
+            self.status_bar.set_message(message);
+        } else {
+            self.status_bar.set_message(format!(
+                "Query '{}' finished in the background: {} rows (result {}, {} to list)",
+                name,
+                row_count,
+                index + 1,
+                self.keymap.describe(KeyAction::Buffers)
+            ));
         }
     }
 
-    fn handle_drill_down_collection(&mut self, collection_id: u32, collection_name: String) {
-        self.content
-            .enter_collection_questions(collection_id, collection_name.clone());
+    fn handle_query_failed(&mut self, request_id: u64, error: ServiceError) {
+        let question_id = self.running_queries.remove(&request_id).map(|(id, _)| id);
+        if request_id == self.current_request_id {
+            self.foreground_view = None;
+        }
         self.status_bar
-            .set_message(format!("Viewing questions in '{}'", collection_name));
-        let _ = self.action_tx.send(AppAction::LoadData(
-            DataRequest::FilterQuestionsByCollection(collection_id),
-        ));
+            .set_message(format!("Query failed: {}", error));
+        if error.is_unauthorized() {
+            self.request_login(question_id.map(AppAction::ExecuteQuestion));
+        }
     }
 
     fn handle_drill_down_database(&mut self, database_id: u32, database_name: String) {
@@ -267,9 +465,47 @@ impl App {
     }
 
     fn handle_table_preview_loaded(&mut self, data: QueryResultData) {
+        // The preview may have been left for an open result meanwhile
+        if !self.content.is_table_preview_view() {
+            return;
+        }
         let row_count = data.rows.len();
         self.content.set_table_preview_data(data);
         self.status_bar
             .set_message(format!("Preview: {} rows loaded", row_count));
     }
+
+    /// Show rows reached through a key; a single row opens in the record detail.
+    fn handle_linked_rows_loaded(&mut self, data: QueryResultData) {
+        self.show_record_detail = false;
+        self.record_detail = None;
+        let row_count = data.rows.len();
+        let title = data.question_name.clone();
+        self.content.enter_linked_rows(data);
+        if row_count == 1 {
+            self.open_record_detail();
+        }
+        self.status_bar
+            .set_message(format!("{}: {} rows (Esc to go back)", title, row_count));
+    }
+
+    /// Open the only referencing table directly, or let the user choose.
+    fn handle_references_loaded(&mut self, mut links: Vec<RecordLink>, value: serde_json::Value) {
+        match links.len() {
+            0 => self
+                .status_bar
+                .set_message("No other tables reference this key"),
+            1 => {
+                let link = links.remove(0);
+                let _ = self
+                    .action_tx
+                    .send(AppAction::LoadData(DataRequest::LinkedRows(link, value)));
+            }
+            _ => {
+                if let Some(ref mut detail) = self.record_detail {
+                    detail.show_references(links, value);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,156 @@
+//! Open result buffers and the split view.
+//!
+//! Query results are kept in buffers of the content panel; this module
+//! handles the buffer list overlay and switching, closing and comparing
+//! buffers.
+
+use crossterm::event::KeyCode;
+
+use crate::components::{BufferList, ContentView};
+use crate::keymap::KeyAction;
+
+use super::App;
+
+impl App {
+    /// Open the buffer list with the shown buffer selected.
+    pub(super) fn show_buffer_list(&mut self) {
+        if self.content.buffer_count() == 0 {
+            self.status_bar.set_message("No open results");
+            return;
+        }
+        let selected = self.content.active_buffer().unwrap_or(0);
+        self.buffer_list = Some(BufferList::new(self.content.buffer_infos(), selected));
+    }
+
+    /// Handle keys while the buffer list is shown.
+    pub(super) fn handle_buffer_list_key(&mut self, code: KeyCode, action: Option<KeyAction>) {
+        let Some(ref mut list) = self.buffer_list else {
+            return;
+        };
+        match (code, action) {
+            (KeyCode::Esc, _) | (_, Some(KeyAction::Buffers)) => {
+                self.buffer_list = None;
+            }
+            (_, Some(KeyAction::Up)) => list.select_up(),
+            (_, Some(KeyAction::Down)) => list.select_down(),
+            (KeyCode::Enter, _) => {
+                let index = list.selected();
+                self.buffer_list = None;
+                self.switch_buffer(index);
+            }
+            (_, Some(KeyAction::SplitView)) => {
+                let index = list.selected();
+                self.buffer_list = None;
+                self.split_view(Some(index));
+            }
+            (KeyCode::Char('x') | KeyCode::Char('d'), _) => {
+                let index = list.selected();
+                self.close_buffer(Some(index));
+                let entries = self.content.buffer_infos();
+                if let Some(ref mut list) = self.buffer_list {
+                    list.set_entries(entries);
+                    if list.is_empty() {
+                        self.buffer_list = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Show an open result.
+    pub(super) fn switch_buffer(&mut self, index: usize) {
+        self.remember_result_view();
+        if self.content.show_buffer(index) {
+            self.set_buffer_status(index);
+        } else {
+            self.status_bar
+                .set_message(format!("No result buffer {}", index + 1));
+        }
+    }
+
+    /// Show the next (or previous) open result.
+    pub(super) fn cycle_buffer(&mut self, forward: bool) {
+        self.remember_result_view();
+        match self.content.cycle_buffer(forward) {
+            Some(index) => self.set_buffer_status(index),
+            None => self.status_bar.set_message("No open results"),
+        }
+    }
+
+    /// Close an open result (the shown one if None).
+    pub(super) fn close_buffer(&mut self, index: Option<usize>) {
+        let Some(index) = index.or(self.content.active_buffer()) else {
+            self.status_bar.set_message("No result is shown");
+            return;
+        };
+        let name = self
+            .content
+            .buffer_infos()
+            .get(index)
+            .map(|b| b.name.clone());
+        if self.content.active_buffer() == Some(index) {
+            self.remember_result_view();
+        }
+        match name {
+            Some(name) if self.content.close_buffer(index) => {
+                self.status_bar.set_message(format!("Closed '{}'", name));
+            }
+            _ => self
+                .status_bar
+                .set_message(format!("No result buffer {}", index + 1)),
+        }
+    }
+
+    /// Compare the shown result with another one side by side. Without a
+    /// buffer, toggles the split (comparing with the previously shown result).
+    pub(super) fn split_view(&mut self, index: Option<usize>) {
+        if self.content.active_buffer().is_none()
+            || self.content.current_view() != ContentView::QueryResult
+        {
+            self.status_bar
+                .set_message("Split view compares the shown query result with another one");
+            return;
+        }
+        if index.is_none() && self.content.split_buffer().is_some() {
+            self.content.set_split(None);
+            self.status_bar.set_message("Split view closed");
+            return;
+        }
+        let Some(index) = index.or(self.content.alternate_buffer()) else {
+            self.status_bar
+                .set_message("Run another question to compare results");
+            return;
+        };
+        if self.content.set_split(Some(index)) {
+            let name = self
+                .content
+                .buffer_infos()
+                .get(index)
+                .map(|b| b.name.clone())
+                .unwrap_or_default();
+            self.status_bar.set_message(format!(
+                "Comparing with '{}' ({} to close)",
+                name,
+                self.keymap.describe(KeyAction::SplitView)
+            ));
+        } else {
+            self.status_bar
+                .set_message(format!("Cannot compare with buffer {}", index + 1));
+        }
+    }
+
+    /// Status message for the shown buffer.
+    fn set_buffer_status(&mut self, index: usize) {
+        let infos = self.content.buffer_infos();
+        if let Some(info) = infos.get(index) {
+            self.status_bar.set_message(format!(
+                "Result {}/{}: '{}' ({} rows)",
+                index + 1,
+                infos.len(),
+                info.name,
+                info.rows
+            ));
+        }
+    }
+}
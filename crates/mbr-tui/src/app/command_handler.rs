@@ -0,0 +1,402 @@
+//! Command palette and `:` command line handling.
+//!
+//! Both entry points resolve to `AppAction`s which are dispatched through the
+//! regular action channel, so every command behaves exactly like its key binding.
+
+use crossterm::event::KeyCode;
+
+use crate::action::{AppAction, ContentTarget};
+use crate::command::parse_command;
+use crate::components::clipboard::{format_records_csv, format_records_json, format_records_tsv};
+use crate::components::theme::set_theme;
+use crate::components::{
+    CommandPalette, FilterClause, FilterCondition, PaletteEntry, SortKey, SortOrder,
+};
+use crate::keymap::KeyAction;
+
+use super::App;
+
+impl App {
+    // === Opening ===
+
+    /// Open the command palette with all currently available actions.
+    pub(super) fn open_command_palette(&mut self) {
+        self.command_palette = Some(CommandPalette::new(self.palette_entries()));
+        self.show_command_palette = true;
+    }
+
+    /// Open the `:` command line.
+    pub(super) fn open_command_line(&mut self) {
+        self.command_line = Some(String::new());
+    }
+
+    /// Build the palette entries: static commands plus one entry per loaded question.
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        let default_export = self.default_export_name();
+        let keys = &self.keymap;
+        let mut entries = vec![
+            PaletteEntry::new(
+                "Go to Questions",
+                keys.describe(KeyAction::TabQuestions),
+                AppAction::Navigate(ContentTarget::Questions),
+            ),
+            PaletteEntry::new(
+                "Go to Collections",
+                keys.describe(KeyAction::TabCollections),
+                AppAction::Navigate(ContentTarget::Collections),
+            ),
+            PaletteEntry::new(
+                "Go to Databases",
+                keys.describe(KeyAction::TabDatabases),
+                AppAction::Navigate(ContentTarget::Databases),
+            ),
+            PaletteEntry::new(
+                "Refresh current view",
+                keys.describe(KeyAction::Refresh),
+                AppAction::Refresh,
+            ),
+            PaletteEntry::new(
+                "Sort results by column",
+                keys.describe(KeyAction::Sort),
+                AppAction::OpenSortModal,
+            ),
+            PaletteEntry::new(
+                "Filter results by column",
+                keys.describe(KeyAction::Filter),
+                AppAction::OpenFilterModal,
+            ),
+            PaletteEntry::new(
+                "Manage result columns",
+                keys.describe(KeyAction::Columns),
+                AppAction::OpenColumnManager,
+            ),
+            PaletteEntry::new(
+                "Group results by columns",
+                keys.describe(KeyAction::GroupBy),
+                AppAction::OpenGroupModal,
+            ),
+            PaletteEntry::new(
+                "Toggle result summary footer",
+                keys.describe(KeyAction::Summary),
+                AppAction::ToggleSummary,
+            ),
+            PaletteEntry::new(
+                "Profile result column",
+                keys.describe(KeyAction::Profile),
+                AppAction::ProfileColumn(None),
+            ),
+            PaletteEntry::new(
+                "View full cell value",
+                keys.describe(KeyAction::ViewCell),
+                AppAction::ViewCell(None),
+            ),
+            PaletteEntry::new("Remove result grouping", ":group", AppAction::Ungroup),
+            PaletteEntry::new("Clear result filter", ":filter", AppAction::ClearFilter),
+            PaletteEntry::new("Clear result sort", ":sort", AppAction::ClearSort),
+            PaletteEntry::new(
+                "Export results as CSV",
+                format!(":export {}.csv", default_export),
+                AppAction::ExportResult(format!("{}.csv", default_export)),
+            ),
+            PaletteEntry::new(
+                "Export results as TSV",
+                format!(":export {}.tsv", default_export),
+                AppAction::ExportResult(format!("{}.tsv", default_export)),
+            ),
+            PaletteEntry::new(
+                "Export results as JSON",
+                format!(":export {}.json", default_export),
+                AppAction::ExportResult(format!("{}.json", default_export)),
+            ),
+            PaletteEntry::new(
+                "Show execution history",
+                keys.describe(KeyAction::History),
+                AppAction::ShowHistory,
+            ),
+            PaletteEntry::new(
+                "Restore last session",
+                ":restore",
+                AppAction::RestoreSession,
+            ),
+            PaletteEntry::new(
+                "List open results",
+                keys.describe(KeyAction::Buffers),
+                AppAction::ShowBuffers,
+            ),
+            PaletteEntry::new(
+                "Compare result side by side",
+                keys.describe(KeyAction::SplitView),
+                AppAction::SplitView(None),
+            ),
+            PaletteEntry::new("Close result", ":bclose", AppAction::CloseBuffer(None)),
+            PaletteEntry::new("Log in", ":login", AppAction::ShowLogin),
+            PaletteEntry::new(
+                "Toggle theme",
+                keys.describe(KeyAction::CycleTheme),
+                AppAction::CycleTheme,
+            ),
+            PaletteEntry::new(
+                "Show help",
+                keys.describe(KeyAction::Help),
+                AppAction::ShowHelp,
+            ),
+            PaletteEntry::new("Quit", keys.describe(KeyAction::Quit), AppAction::Quit),
+        ];
+
+        if let Some(questions) = self.data.questions.data() {
+            entries.extend(questions.iter().map(|q| {
+                PaletteEntry::new(
+                    format!("Run: {}", q.name),
+                    format!(":run {}", q.id),
+                    AppAction::ExecuteQuestion(q.id),
+                )
+            }));
+        }
+
+        entries
+    }
+
+    /// File name stem for exports, derived from the current result name.
+    fn default_export_name(&self) -> String {
+        let name = self
+            .content
+            .get_query_result_name()
+            .unwrap_or_else(|| "result".to_string());
+        let stem: String = name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        let stem = stem.trim_matches('_').to_string();
+        if stem.is_empty() {
+            "result".to_string()
+        } else {
+            stem
+        }
+    }
+
+    // === Key handling ===
+
+    /// Handle keys while the command palette is open.
+    pub(super) fn handle_command_palette_key(&mut self, code: KeyCode) {
+        let Some(palette) = self.command_palette.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.close_command_palette(),
+            KeyCode::Up => palette.select_up(),
+            KeyCode::Down => palette.select_down(),
+            KeyCode::Backspace => palette.delete_char(),
+            KeyCode::Char(c) => palette.input_char(c),
+            KeyCode::Enter => {
+                let action = palette.selected_action();
+                self.close_command_palette();
+                if let Some(action) = action {
+                    let _ = self.action_tx.send(action);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle keys while the command line is open.
+    pub(super) fn handle_command_line_key(&mut self, code: KeyCode) {
+        let Some(line) = self.command_line.as_mut() else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.command_line = None,
+            // Backspace on an empty line closes it (vim behavior)
+            KeyCode::Backspace if line.pop().is_none() => self.command_line = None,
+            KeyCode::Backspace => {}
+            KeyCode::Char(c) => line.push(c),
+            KeyCode::Enter => {
+                let input = self.command_line.take().unwrap_or_default();
+                match parse_command(&input) {
+                    Ok(action) => {
+                        let _ = self.action_tx.send(action);
+                    }
+                    Err(e) => self.status_bar.set_message(e),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn close_command_palette(&mut self) {
+        self.show_command_palette = false;
+        self.command_palette = None;
+    }
+
+    // === Command execution ===
+
+    /// Execute a question by (case-insensitive) name among loaded questions.
+    /// Exact matches win over substring matches.
+    pub(super) fn execute_question_by_name(&mut self, name: &str) {
+        let needle = name.to_lowercase();
+        let found = self.data.questions.data().and_then(|qs| {
+            qs.iter()
+                .find(|q| q.name.to_lowercase() == needle)
+                .or_else(|| qs.iter().find(|q| q.name.to_lowercase().contains(&needle)))
+                .map(|q| q.id)
+        });
+        match found {
+            Some(id) => self.execute_question(id),
+            None => self
+                .status_bar
+                .set_message(format!("No loaded question matches '{}'", name)),
+        }
+    }
+
+    /// Apply filter conditions to the current result, replacing the active
+    /// filter or (with `append`) adding to it.
+    pub(super) fn apply_result_filter(&mut self, clauses: Vec<FilterClause>, append: bool) {
+        if !self.content.is_result_view() {
+            self.status_bar
+                .set_message("Filter requires a query result");
+            return;
+        }
+        if append && self.content.filters().is_empty() {
+            self.status_bar
+                .set_message("No active filter to extend (use :filter)");
+            return;
+        }
+
+        let mut conditions = Vec::with_capacity(clauses.len());
+        for clause in &clauses {
+            let Some(idx) = self.content.find_column_index(&clause.column) else {
+                self.status_bar
+                    .set_message(format!("Unknown column: {}", clause.column));
+                return;
+            };
+            match FilterCondition::new(clause.join, idx, clause.op, &clause.value) {
+                Ok(condition) => conditions.push(condition),
+                Err(e) => {
+                    self.status_bar.set_message(format!("Filter: {}", e));
+                    return;
+                }
+            }
+        }
+
+        if append {
+            for condition in conditions {
+                self.content.add_filter(condition);
+            }
+        } else {
+            self.content.set_filters(conditions);
+        }
+        let count = self.content.filters().len();
+        self.status_bar.set_message(format!(
+            "Filter: {} condition{}",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+
+    /// Sort the current result by one or more columns.
+    pub(super) fn apply_result_sort(&mut self, keys: &[(String, SortOrder)]) {
+        if !self.content.is_result_view() {
+            self.status_bar.set_message("Sort requires a query result");
+            return;
+        }
+
+        let mut sort_keys = Vec::with_capacity(keys.len());
+        let mut labels = Vec::with_capacity(keys.len());
+        for (column, order) in keys {
+            let Some(idx) = self.content.find_column_index(column) else {
+                self.status_bar
+                    .set_message(format!("Unknown column: {}", column));
+                return;
+            };
+            sort_keys.push(SortKey {
+                column: idx,
+                order: *order,
+            });
+            let arrow = if *order == SortOrder::Descending {
+                "↓"
+            } else {
+                "↑"
+            };
+            labels.push(format!("{} {}", column, arrow));
+        }
+
+        self.content.set_sort_keys(sort_keys);
+        self.status_bar
+            .set_message(format!("Sort: {}", labels.join(", ")));
+    }
+
+    /// Group the current result by column names (of the ungrouped result).
+    pub(super) fn apply_result_grouping(&mut self, columns: &[String]) {
+        if !self.content.is_result_view() {
+            self.status_bar
+                .set_message("Grouping requires a query result");
+            return;
+        }
+
+        // Names refer to the ungrouped result
+        let mut indices = Vec::with_capacity(columns.len());
+        for column in columns {
+            match self.content.find_group_column_index(column) {
+                Some(idx) => indices.push(idx),
+                None => {
+                    self.status_bar
+                        .set_message(format!("Unknown column: {}", column));
+                    return;
+                }
+            }
+        }
+
+        self.content.group_by(indices);
+        self.status_bar
+            .set_message(format!("Grouped by {}", columns.join(", ")));
+    }
+
+    /// Switch to the next theme.
+    pub(super) fn cycle_theme(&mut self) {
+        let name = self.themes.next().to_string();
+        set_theme(self.themes.current());
+        self.status_bar.set_message(format!("Theme: {}", name));
+    }
+
+    /// Switch to a theme by name.
+    pub(super) fn select_theme(&mut self, name: &str) {
+        if self.themes.select(name) {
+            set_theme(self.themes.current());
+            self.status_bar
+                .set_message(format!("Theme: {}", self.themes.current_name()));
+        } else {
+            self.status_bar.set_message(format!(
+                "Unknown theme: {} (available: {})",
+                name,
+                self.themes.names().join(", ")
+            ));
+        }
+    }
+
+    /// Write visible result rows to a file. Format is chosen by extension
+    /// (`.json`, `.tsv`, anything else is CSV).
+    pub(super) fn export_result(&mut self, path: &str) {
+        if !self.content.is_result_view() {
+            self.status_bar
+                .set_message("Export requires a query result");
+            return;
+        }
+        let records = self.content.get_visible_records();
+        let lower = path.to_lowercase();
+        let text = if lower.ends_with(".json") {
+            format_records_json(&records)
+        } else if lower.ends_with(".tsv") {
+            format_records_tsv(&records, true)
+        } else {
+            format_records_csv(&records, true)
+        };
+
+        match std::fs::write(path, text) {
+            Ok(()) => {
+                self.status_bar
+                    .set_message(format!("Exported {} rows to {}", records.len(), path))
+            }
+            Err(e) => self.status_bar.set_message(format!("Export failed: {}", e)),
+        }
+    }
+}
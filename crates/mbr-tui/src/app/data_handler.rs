@@ -3,13 +3,17 @@
 //! Handles DataRequest events by spawning async tasks to fetch data from the API.
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use crate::action::{AppAction, DataRequest};
-use crate::components::QueryResultData;
+use crate::components::{ColumnKey, ColumnType, QueryResultData, RecordLink};
 use crate::service::LoadState;
 
 use super::App;
 
+/// Maximum number of rows fetched when following a key.
+const LINKED_ROWS_LIMIT: u32 = 500;
+
 /// Transform a QueryResult from the API into a QueryResultData for TUI display.
 fn transform_query_result(
     result: mbr_core::api::models::QueryResult,
@@ -23,6 +27,26 @@ fn transform_query_result(
         .map(|c| c.display_name.clone())
         .collect();
 
+    let column_types: Vec<ColumnType> = result
+        .data
+        .cols
+        .iter()
+        .map(|c| ColumnType::from_base_type(&c.base_type))
+        .collect();
+
+    let column_keys: Vec<ColumnKey> = result
+        .data
+        .cols
+        .iter()
+        .map(|c| match (c.id, c.table_id, c.fk_target_field_id) {
+            (Some(field_id), Some(table_id), _) if c.is_primary_key() => {
+                ColumnKey::Primary { table_id, field_id }
+            }
+            (_, _, Some(target_field_id)) => ColumnKey::Foreign { target_field_id },
+            _ => ColumnKey::None,
+        })
+        .collect();
+
     let rows: Vec<Vec<String>> = result
         .data
         .rows
@@ -44,18 +68,32 @@ fn transform_query_result(
         question_id,
         question_name,
         columns,
+        column_types,
+        column_keys,
         rows,
     }
 }
 
+/// Build the result of following a key, titled "Table.Field = value".
+fn linked_rows_data(
+    result: mbr_core::api::models::QueryResult,
+    link: &RecordLink,
+    value: &serde_json::Value,
+) -> QueryResultData {
+    let value = match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    transform_query_result(result, link.table_id, format!("{} = {}", link.label, value))
+}
+
 impl App {
     /// Handle data loading request with background task spawning.
     pub(super) fn handle_data_request(&mut self, request: DataRequest) {
         let service = match &self.service {
             Some(s) => Arc::clone(s),
             None => {
-                self.status_bar
-                    .set_message("Error: Not connected to Metabase");
+                self.request_login(Some(AppAction::LoadData(request)));
                 return;
             }
         };
@@ -69,12 +107,12 @@ impl App {
             DataRequest::SearchQuestions(query) => {
                 self.search_questions(service, tx, query);
             }
-            DataRequest::FilterQuestionsByCollection(collection_id) => {
-                self.load_collection_questions(service, tx, collection_id);
-            }
             DataRequest::Collections => {
                 self.load_collections(service, tx);
             }
+            DataRequest::CollectionItems(collection_id) => {
+                self.load_collection_items(service, tx, collection_id);
+            }
             DataRequest::Databases => {
                 self.load_databases(service, tx);
             }
@@ -95,6 +133,15 @@ impl App {
             DataRequest::TablePreview(database_id, table_id) => {
                 self.load_table_preview(service, tx, database_id, table_id);
             }
+            DataRequest::ForeignKeyRows(field_id, value) => {
+                self.load_foreign_key_rows(service, tx, field_id, value);
+            }
+            DataRequest::References(table_id, field_id, value) => {
+                self.load_references(service, tx, table_id, field_id, value);
+            }
+            DataRequest::LinkedRows(link, value) => {
+                self.load_linked_rows(service, tx, link, value);
+            }
         }
     }
 
@@ -150,59 +197,49 @@ impl App {
         });
     }
 
-    fn load_collection_questions(
+    fn load_collections(
         &mut self,
         service: Arc<crate::service::ServiceClient>,
         tx: tokio::sync::mpsc::UnboundedSender<AppAction>,
-        collection_id: u32,
     ) {
-        if matches!(self.data.questions, LoadState::Loading) {
+        if matches!(self.data.collections, LoadState::Loading) {
             return;
         }
 
-        self.data.questions = LoadState::Loading;
-        self.content.update_questions(&self.data.questions);
-
-        let collection_str = collection_id.to_string();
+        self.data.collections = LoadState::Loading;
+        self.content.update_collections(&self.data.collections);
+        self.status_bar.set_message("Loading collections...");
 
         tokio::spawn(async move {
-            match service
-                .fetch_questions_by_collection(&collection_str, Some(100))
-                .await
-            {
-                Ok(questions) => {
-                    let _ = tx.send(AppAction::QuestionsLoaded(questions));
+            match service.fetch_collections().await {
+                Ok(collections) => {
+                    let _ = tx.send(AppAction::CollectionsLoaded(collections));
                 }
                 Err(e) => {
-                    let _ = tx.send(AppAction::LoadFailed(
-                        DataRequest::FilterQuestionsByCollection(collection_id),
-                        e,
-                    ));
+                    let _ = tx.send(AppAction::LoadFailed(DataRequest::Collections, e));
                 }
             }
         });
     }
 
-    fn load_collections(
+    fn load_collection_items(
         &mut self,
         service: Arc<crate::service::ServiceClient>,
         tx: tokio::sync::mpsc::UnboundedSender<AppAction>,
+        collection_id: Option<u32>,
     ) {
-        if matches!(self.data.collections, LoadState::Loading) {
-            return;
-        }
-
-        self.data.collections = LoadState::Loading;
-        self.content.update_collections(&self.data.collections);
-        self.status_bar.set_message("Loading collections...");
+        self.content.mark_collection_items_loading(collection_id);
 
         tokio::spawn(async move {
-            match service.fetch_collections().await {
-                Ok(collections) => {
-                    let _ = tx.send(AppAction::CollectionsLoaded(collections));
+            match service.fetch_collection_items(collection_id).await {
+                Ok(items) => {
+                    let _ = tx.send(AppAction::CollectionItemsLoaded(collection_id, items));
                 }
                 Err(e) => {
-                    let _ = tx.send(AppAction::LoadFailed(DataRequest::Collections, e));
+                    let _ = tx.send(AppAction::LoadFailed(
+                        DataRequest::CollectionItems(collection_id),
+                        e,
+                    ));
                 }
             }
         });
@@ -316,13 +353,101 @@ impl App {
         });
     }
 
+    fn load_foreign_key_rows(
+        &mut self,
+        service: Arc<crate::service::ServiceClient>,
+        tx: tokio::sync::mpsc::UnboundedSender<AppAction>,
+        field_id: u32,
+        value: serde_json::Value,
+    ) {
+        self.status_bar.set_message("Loading referenced rows...");
+
+        tokio::spawn(async move {
+            let request = DataRequest::ForeignKeyRows(field_id, value.clone());
+            let link = match service.resolve_field_link(field_id).await {
+                Ok(link) => link,
+                Err(e) => {
+                    let _ = tx.send(AppAction::LoadFailed(request, e));
+                    return;
+                }
+            };
+            match service
+                .fetch_linked_rows(&link, &value, LINKED_ROWS_LIMIT)
+                .await
+            {
+                Ok(result) => {
+                    let _ = tx.send(AppAction::LinkedRowsLoaded(linked_rows_data(
+                        result, &link, &value,
+                    )));
+                }
+                Err(e) => {
+                    let _ = tx.send(AppAction::LoadFailed(request, e));
+                }
+            }
+        });
+    }
+
+    fn load_references(
+        &mut self,
+        service: Arc<crate::service::ServiceClient>,
+        tx: tokio::sync::mpsc::UnboundedSender<AppAction>,
+        table_id: u32,
+        field_id: u32,
+        value: serde_json::Value,
+    ) {
+        self.status_bar.set_message("Loading references...");
+
+        tokio::spawn(async move {
+            match service.fetch_references(table_id, field_id).await {
+                Ok(links) => {
+                    let _ = tx.send(AppAction::ReferencesLoaded(links, value));
+                }
+                Err(e) => {
+                    let _ = tx.send(AppAction::LoadFailed(
+                        DataRequest::References(table_id, field_id, value),
+                        e,
+                    ));
+                }
+            }
+        });
+    }
+
+    fn load_linked_rows(
+        &mut self,
+        service: Arc<crate::service::ServiceClient>,
+        tx: tokio::sync::mpsc::UnboundedSender<AppAction>,
+        link: RecordLink,
+        value: serde_json::Value,
+    ) {
+        self.status_bar
+            .set_message(format!("Loading {} rows...", link.label));
+
+        tokio::spawn(async move {
+            match service
+                .fetch_linked_rows(&link, &value, LINKED_ROWS_LIMIT)
+                .await
+            {
+                Ok(result) => {
+                    let _ = tx.send(AppAction::LinkedRowsLoaded(linked_rows_data(
+                        result, &link, &value,
+                    )));
+                }
+                Err(e) => {
+                    let _ = tx.send(AppAction::LoadFailed(
+                        DataRequest::LinkedRows(link, value),
+                        e,
+                    ));
+                }
+            }
+        });
+    }
+
     /// Execute a question query.
     pub(super) fn execute_question(&mut self, id: u32) {
         let service = match &self.service {
             Some(s) => Arc::clone(s),
             None => {
-                self.status_bar
-                    .set_message("Error: Not connected to Metabase");
+                self.request_login(Some(AppAction::ExecuteQuestion(id)));
                 return;
             }
         };
@@ -336,10 +461,30 @@ impl App {
             .data()
             .and_then(|qs| qs.iter().find(|q| q.id == id))
             .map(|q| q.name.clone())
+            .or_else(|| self.content.find_collection_card_name(id))
+            .or_else(|| {
+                self.tui_state
+                    .history
+                    .iter()
+                    .find(|e| e.question_id == id)
+                    .map(|e| e.name.clone())
+            })
             .unwrap_or_else(|| format!("Question #{}", id));
 
-        self.status_bar
-            .set_message(format!("Executing '{}'...", question_name));
+        // Earlier executions keep running and open in the background
+        self.running_queries
+            .insert(request_id, (id, Instant::now()));
+        self.foreground_view = Some(self.content.current_view());
+        let running = self.running_queries.len();
+        if running > 1 {
+            self.status_bar.set_message(format!(
+                "Executing '{}'... ({} queries running)",
+                question_name, running
+            ));
+        } else {
+            self.status_bar
+                .set_message(format!("Executing '{}'...", question_name));
+        }
 
         let tx = self.action_tx.clone();
 
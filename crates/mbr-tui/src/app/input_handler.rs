@@ -10,7 +10,10 @@ use crate::components::clipboard::{
     CopyFormat, copy_to_clipboard, format_record_csv, format_record_json, format_record_tsv,
     format_records_csv, format_records_json, format_records_tsv,
 };
-use crate::components::{ContentView, CopyMenu, InputMode, RecordDetailOverlay};
+use crate::components::{
+    ActiveTab, CellViewer, ColumnKey, ContentView, CopyMenu, InputMode, RecordDetailOverlay,
+};
+use crate::keymap::KeyAction;
 use crate::service::LoadState;
 
 use super::App;
@@ -18,8 +21,10 @@ use super::App;
 impl App {
     /// Handle keyboard input with delegated responsibility.
     pub(super) fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let action = self.keymap.resolve(code, modifiers);
+
         // 1. Overlay handling (highest priority)
-        if self.handle_overlay_keys(code) {
+        if self.handle_overlay_keys(code, action) {
             return;
         }
 
@@ -29,23 +34,62 @@ impl App {
         }
 
         // 3. Global keybindings
-        if self.handle_global_keys(code, modifiers) {
+        if let Some(action) = action
+            && self.handle_global_keys(action)
+        {
             return;
         }
 
-        // 4. Enter key for content actions
-        if code == KeyCode::Enter && self.handle_enter_key() {
+        // 4. Open (Enter) for content actions
+        if action == Some(KeyAction::Open) && self.handle_enter_key() {
             return;
         }
 
         // 5. Delegate remaining keys to content panel
         self.content
             .handle_key_event(crossterm::event::KeyEvent::new(code, modifiers));
+        self.persist_column_layout();
     }
 
-    /// Handle keyboard input when overlay is active (CopyMenu, RecordDetail, Help).
+    /// Save the column layout of the current question result if it changed.
+    fn persist_column_layout(&mut self) {
+        let Some((question_id, layout)) = self.content.take_column_layout_change() else {
+            return;
+        };
+        match layout {
+            Some(layout) => {
+                self.tui_state.column_layouts.insert(question_id, layout);
+            }
+            None => {
+                self.tui_state.column_layouts.remove(&question_id);
+            }
+        }
+        if let Err(e) = self.tui_state.save() {
+            self.status_bar
+                .set_message(format!("Failed to save column layout: {}", e));
+        }
+    }
+
+    /// Handle keyboard input when overlay is active
+    /// (LoginForm, CommandPalette, command line, CopyMenu, RecordDetail, Help).
     /// Returns true if the key was handled.
-    pub(super) fn handle_overlay_keys(&mut self, code: KeyCode) -> bool {
+    pub(super) fn handle_overlay_keys(&mut self, code: KeyCode, action: Option<KeyAction>) -> bool {
+        // The login form captures all input while shown
+        if self.login_form.is_some() {
+            self.handle_login_key(code);
+            return true;
+        }
+
+        // Command palette and command line capture all input while open
+        if self.show_command_palette {
+            self.handle_command_palette_key(code);
+            return true;
+        }
+        if self.command_line.is_some() {
+            self.handle_command_line_key(code);
+            return true;
+        }
+
         // Copy menu takes highest priority when shown
         if self.show_copy_menu {
             match code {
@@ -91,24 +135,66 @@ impl App {
             return true;
         }
 
+        if self.buffer_list.is_some() {
+            self.handle_buffer_list_key(code, action);
+            return true;
+        }
+
+        // Cell viewer is drawn above the record detail it may be opened from
+        if self.cell_viewer.is_some() {
+            self.handle_cell_viewer_key(code, action);
+            return true;
+        }
+
         // Record detail overlay takes priority when shown
         if self.show_record_detail {
-            match code {
-                KeyCode::Esc | KeyCode::Enter => {
+            // Reference chooser takes priority within the record detail
+            if let Some(ref mut detail) = self.record_detail
+                && detail.is_choosing_reference()
+            {
+                match (code, action) {
+                    (KeyCode::Esc, _) => detail.close_references(),
+                    (KeyCode::Enter, _) => {
+                        if let Some((link, value)) = detail.selected_reference() {
+                            detail.close_references();
+                            let _ = self
+                                .action_tx
+                                .send(AppAction::LoadData(DataRequest::LinkedRows(link, value)));
+                        }
+                    }
+                    (_, Some(KeyAction::Up)) => detail.reference_up(),
+                    (_, Some(KeyAction::Down)) => detail.reference_down(),
+                    _ => {}
+                }
+                return true;
+            }
+            match (code, action) {
+                (KeyCode::Enter, _) if self.follow_selected_key() => {}
+                (KeyCode::Esc | KeyCode::Enter, _) => {
                     self.show_record_detail = false;
                     self.record_detail = None;
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
+                (_, Some(KeyAction::Up)) => {
                     if let Some(ref mut detail) = self.record_detail {
                         detail.scroll_up();
                     }
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
+                (_, Some(KeyAction::Down)) => {
                     if let Some(ref mut detail) = self.record_detail {
                         detail.scroll_down();
                     }
                 }
-                KeyCode::Char('c') => {
+                (_, Some(KeyAction::ViewCell)) => {
+                    if let Some(ref detail) = self.record_detail {
+                        let (columns, values) = detail.record();
+                        self.cell_viewer = Some(CellViewer::new(
+                            columns.to_vec(),
+                            values.to_vec(),
+                            detail.selected_index(),
+                        ));
+                    }
+                }
+                (_, Some(KeyAction::Copy)) => {
                     if let Some(ref detail) = self.record_detail
                         && let Some(value) = detail.selected_value()
                     {
@@ -129,11 +215,8 @@ impl App {
 
         // Help overlay takes priority when shown
         if self.show_help {
-            match code {
-                KeyCode::Char('?') | KeyCode::Esc => {
-                    self.show_help = false;
-                }
-                _ => {}
+            if code == KeyCode::Esc || matches!(action, Some(KeyAction::Help | KeyAction::Back)) {
+                self.show_help = false;
             }
             return true;
         }
@@ -177,73 +260,119 @@ impl App {
 
     /// Handle global keybindings (quit, help, tab switch, refresh).
     /// Returns true if the key was handled.
-    pub(super) fn handle_global_keys(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
-        match code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => {
+    pub(super) fn handle_global_keys(&mut self, action: KeyAction) -> bool {
+        let modal_active = self.is_modal_active();
+        match action {
+            KeyAction::Quit => {
                 self.should_quit = true;
                 true
             }
-            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
-                self.should_quit = true;
+            KeyAction::Back => self.handle_escape_key(),
+            // Command palette and vim-style command line
+            KeyAction::CommandPalette if !modal_active => {
+                self.open_command_palette();
+                true
+            }
+            KeyAction::CommandLine if !modal_active => {
+                self.open_command_line();
                 true
             }
-            KeyCode::Esc => self.handle_escape_key(),
-            KeyCode::Char('?') if !self.is_modal_active() => {
+            KeyAction::Help if !modal_active => {
                 self.show_help = true;
                 true
             }
-            // Tab switching with number keys 1/2/3
-            KeyCode::Char('1') if !self.is_modal_active() => {
-                self.switch_to_tab(crate::components::ActiveTab::Questions);
+            // Direct tab switching
+            KeyAction::TabQuestions if !modal_active => {
+                self.switch_to_tab(ActiveTab::Questions);
                 true
             }
-            KeyCode::Char('2') if !self.is_modal_active() => {
-                self.switch_to_tab(crate::components::ActiveTab::Collections);
+            KeyAction::TabCollections if !modal_active => {
+                self.switch_to_tab(ActiveTab::Collections);
                 true
             }
-            KeyCode::Char('3') if !self.is_modal_active() => {
-                self.switch_to_tab(crate::components::ActiveTab::Databases);
+            KeyAction::TabDatabases if !modal_active => {
+                self.switch_to_tab(ActiveTab::Databases);
                 true
             }
-            // Tab cycling with Tab/Shift+Tab
-            KeyCode::Tab if !self.is_modal_active() => {
-                let new_tab = if modifiers.contains(KeyModifiers::SHIFT) {
-                    self.active_tab.previous()
-                } else {
-                    self.active_tab.next()
-                };
-                self.switch_to_tab(new_tab);
+            // Tab cycling
+            KeyAction::NextTab if !modal_active => {
+                self.switch_to_tab(self.active_tab.next());
                 true
             }
-            KeyCode::BackTab if !self.is_modal_active() => {
+            KeyAction::PrevTab if !modal_active => {
                 self.switch_to_tab(self.active_tab.previous());
                 true
             }
-            // Refresh data with 'r'
-            KeyCode::Char('r') if !self.is_modal_active() => {
+            KeyAction::Refresh if !modal_active => {
                 self.handle_refresh();
                 true
             }
-            // Questions list pagination: n = next page, p = previous page
-            // Works in both Questions and CollectionQuestions views
-            KeyCode::Char('n')
+            KeyAction::CycleTheme if !modal_active => {
+                self.cycle_theme();
+                true
+            }
+            KeyAction::History if !modal_active => {
+                self.show_history();
+                true
+            }
+            // Open results
+            KeyAction::Buffers if !modal_active => {
+                self.show_buffer_list();
+                true
+            }
+            KeyAction::NextBuffer if !modal_active => {
+                self.cycle_buffer(true);
+                true
+            }
+            KeyAction::PrevBuffer if !modal_active => {
+                self.cycle_buffer(false);
+                true
+            }
+            KeyAction::SplitView
+                if self.content.current_view() == ContentView::QueryResult && !modal_active =>
+            {
+                self.split_view(None);
+                true
+            }
+            // Collection tree: right expands (fetching items lazily), left collapses
+            KeyAction::Right if self.content.is_collections_view() && !modal_active => {
+                if let Some(collection_id) = self.content.expand_selected_collection() {
+                    let _ = self
+                        .action_tx
+                        .send(AppAction::LoadData(DataRequest::CollectionItems(
+                            collection_id,
+                        )));
+                }
+                true
+            }
+            KeyAction::Left if self.content.is_collections_view() && !modal_active => {
+                self.content.collapse_selected_collection();
+                true
+            }
+            // Questions list pagination
+            KeyAction::NextPage
                 if self.content.is_questions_view()
-                    && !self.is_modal_active()
+                    && !modal_active
                     && self.content.has_next_questions_page() =>
             {
                 self.content.next_questions_page();
                 true
             }
-            KeyCode::Char('p')
+            KeyAction::PrevPage
                 if self.content.is_questions_view()
-                    && !self.is_modal_active()
+                    && !modal_active
                     && self.content.has_prev_questions_page() =>
             {
                 self.content.prev_questions_page();
                 true
             }
-            // Copy record(s) in result view with 'c'
-            KeyCode::Char('c') if self.content.is_result_view() && !self.is_modal_active() => {
+            // Full value of a cell of the selected row
+            KeyAction::ViewCell if self.content.is_result_view() && !modal_active => {
+                self.open_cell_viewer(None);
+                true
+            }
+            // Copy record(s) in result view
+            KeyAction::Copy if self.content.is_result_view() && !modal_active => {
                 // Check if multi-select is active
                 if self.content.has_selection() {
                     let records = self.content.get_selected_records();
@@ -277,17 +406,28 @@ impl App {
             return true;
         }
 
+        // Step out of a grouped result before leaving the view
+        if self.content.is_result_view() && self.content.collapse_group() {
+            if !self.content.is_grouped() {
+                self.status_bar.set_message("Grouping removed");
+            }
+            return true;
+        }
+
         // Navigate back based on current view
         if self.content.current_view() == ContentView::QueryResult {
             let _ = self.action_tx.send(AppAction::BackToQuestions);
-        } else if self.content.is_collection_questions_view() {
-            let _ = self.action_tx.send(AppAction::BackToCollections);
         } else if self.content.is_database_schemas_view() {
             let _ = self.action_tx.send(AppAction::BackToDatabases);
         } else if self.content.is_schema_tables_view() {
             let _ = self.action_tx.send(AppAction::BackToSchemas);
         } else if self.content.is_table_preview_view() {
             let _ = self.action_tx.send(AppAction::BackToTables);
+        } else if self.content.is_linked_rows_view() {
+            let _ = self.action_tx.send(AppAction::BackFromLinkedRows);
+        } else if self.content.is_history_view() {
+            self.content.exit_history();
+            self.status_bar.set_message("Returned from History");
         } else if self.content.get_active_search().is_some() {
             self.content.clear_search();
             let _ = self
@@ -300,7 +440,12 @@ impl App {
     }
 
     /// Handle refresh action for current view.
-    fn handle_refresh(&mut self) {
+    pub(super) fn handle_refresh(&mut self) {
+        // History is local; reload the entries
+        if self.content.is_history_view() {
+            self.content.update_history(self.tui_state.history.clone());
+            return;
+        }
         let request = match self.content.current_view() {
             ContentView::Questions => DataRequest::Questions,
             ContentView::Collections => DataRequest::Collections,
@@ -321,8 +466,18 @@ impl App {
     /// Returns true if the key was handled.
     pub(super) fn handle_enter_key(&mut self) -> bool {
         let view = self.content.current_view();
-        let modal_active =
-            self.content.is_sort_mode_active() || self.content.is_filter_mode_active();
+        let modal_active = self.content.is_sort_mode_active()
+            || self.content.is_filter_mode_active()
+            || self.content.is_column_manager_active()
+            || self.content.is_group_modal_active()
+            || self.content.is_column_profile_active();
+
+        // Enter on an aggregated row shows the rows of its group
+        if !modal_active && self.content.is_result_view() && self.content.expand_selected_group() {
+            self.status_bar
+                .set_message("Showing group rows (Esc to go back)");
+            return true;
+        }
 
         match view {
             ContentView::Questions => {
@@ -332,10 +487,24 @@ impl App {
                 }
             }
             ContentView::Collections => {
-                if let Some((id, name)) = self.content.get_selected_collection_info() {
-                    let _ = self
-                        .action_tx
-                        .send(AppAction::DrillDownCollection(id, name));
+                if let Some(node) = self.content.get_selected_collection_node() {
+                    if node.kind.is_runnable() {
+                        if let Some(id) = node.id {
+                            let _ = self.action_tx.send(AppAction::ExecuteQuestion(id));
+                        }
+                    } else if node.kind.is_container() {
+                        if let Some(collection_id) = self.content.toggle_selected_collection() {
+                            let _ = self.action_tx.send(AppAction::LoadData(
+                                DataRequest::CollectionItems(collection_id),
+                            ));
+                        }
+                    } else {
+                        self.status_bar.set_message(format!(
+                            "{} '{}' cannot be opened in the TUI",
+                            node.kind.label(),
+                            node.name
+                        ));
+                    }
                     return true;
                 }
             }
@@ -345,23 +514,21 @@ impl App {
                     return true;
                 }
             }
-            ContentView::QueryResult if !modal_active => {
-                if let Some((columns, values)) = self.content.get_selected_record() {
-                    self.record_detail = Some(RecordDetailOverlay::new(columns, values));
-                    self.show_record_detail = true;
+            ContentView::History => {
+                if let Some(question_id) = self.content.get_selected_history_question() {
+                    let _ = self.action_tx.send(AppAction::ExecuteQuestion(question_id));
                     return true;
                 }
             }
+            ContentView::QueryResult | ContentView::LinkedRows { .. }
+                if !modal_active && self.open_record_detail() =>
+            {
+                return true;
+            }
             _ => {}
         }
 
         // Handle drill-down views
-        if self.content.is_collection_questions_view()
-            && let Some(question_id) = self.content.get_selected_question_id()
-        {
-            let _ = self.action_tx.send(AppAction::ExecuteQuestion(question_id));
-            return true;
-        }
         if self.content.is_database_schemas_view()
             && let Some(schema_name) = self.content.get_selected_schema()
         {
@@ -376,18 +543,105 @@ impl App {
                 .send(AppAction::DrillDownTable(table_id, table_name));
             return true;
         }
-        if self.content.is_table_preview_view()
-            && !modal_active
-            && let Some((columns, values)) = self.content.get_selected_record()
-        {
-            self.record_detail = Some(RecordDetailOverlay::new(columns, values));
-            self.show_record_detail = true;
+        if self.content.is_table_preview_view() && !modal_active && self.open_record_detail() {
             return true;
         }
 
         false
     }
 
+    /// Show the cell viewer on a column of the selected row
+    /// (the column in focus if None).
+    pub(super) fn open_cell_viewer(&mut self, column: Option<usize>) {
+        let Some((columns, values)) = self.content.get_selected_record() else {
+            self.status_bar.set_message("No row selected");
+            return;
+        };
+        let column = column.or(self.content.focus_column()).unwrap_or(0);
+        self.cell_viewer = Some(CellViewer::new(columns, values, column));
+    }
+
+    /// Handle keyboard input in the cell viewer.
+    fn handle_cell_viewer_key(&mut self, code: KeyCode, action: Option<KeyAction>) {
+        let Some(ref mut viewer) = self.cell_viewer else {
+            return;
+        };
+        match (code, action) {
+            (KeyCode::Esc | KeyCode::Enter, _) | (_, Some(KeyAction::ViewCell)) => {
+                self.cell_viewer = None;
+            }
+            (_, Some(KeyAction::Up)) => viewer.scroll_up(1),
+            (_, Some(KeyAction::Down)) => viewer.scroll_down(1),
+            (_, Some(KeyAction::PageUp)) => viewer.page_up(),
+            (_, Some(KeyAction::PageDown)) => viewer.page_down(),
+            (_, Some(KeyAction::First)) => viewer.scroll_to_top(),
+            (_, Some(KeyAction::Last)) => viewer.scroll_to_bottom(),
+            (_, Some(KeyAction::Left)) => viewer.prev_field(),
+            (_, Some(KeyAction::Right)) => viewer.next_field(),
+            (_, Some(KeyAction::Copy)) => {
+                let message = match copy_to_clipboard(viewer.value()) {
+                    Ok(()) => "Copied to clipboard".to_string(),
+                    Err(e) => format!("Copy failed: {}", e),
+                };
+                self.status_bar.set_message(message);
+            }
+            (KeyCode::Char('u'), _) => {
+                let message = match viewer.next_url() {
+                    Some((n, total, url)) => match copy_to_clipboard(url) {
+                        Ok(()) => format!("Copied URL {}/{}: {}", n, total, url),
+                        Err(e) => format!("Copy failed: {}", e),
+                    },
+                    None => "No URL in this value".to_string(),
+                };
+                self.status_bar.set_message(message);
+            }
+            _ => {}
+        }
+    }
+
+    /// Show the record detail of the selected row.
+    /// Returns false if no row is selected.
+    pub(super) fn open_record_detail(&mut self) -> bool {
+        let Some((columns, values)) = self.content.get_selected_record() else {
+            return false;
+        };
+        self.record_detail =
+            Some(RecordDetailOverlay::new(columns, values).with_keys(self.content.column_keys()));
+        self.show_record_detail = true;
+        true
+    }
+
+    /// Follow the key of the selected record field: a foreign key opens the
+    /// referenced row, a primary key the rows referencing it.
+    /// Returns false if the field is not a key.
+    fn follow_selected_key(&mut self) -> bool {
+        let Some(ref detail) = self.record_detail else {
+            return false;
+        };
+        let key = detail.selected_key();
+        if key == ColumnKey::None {
+            return false;
+        }
+        let Some(value) = detail
+            .selected_value()
+            .and_then(|cell| self.content.key_value(detail.selected_index(), cell))
+        else {
+            self.status_bar.set_message("Key is empty");
+            return true;
+        };
+        let request = match key {
+            ColumnKey::Foreign { target_field_id } => {
+                DataRequest::ForeignKeyRows(target_field_id, value)
+            }
+            ColumnKey::Primary { table_id, field_id } => {
+                DataRequest::References(table_id, field_id, value)
+            }
+            ColumnKey::None => return false,
+        };
+        let _ = self.action_tx.send(AppAction::LoadData(request));
+        true
+    }
+
     /// Execute copy with the specified format.
     fn execute_copy(&mut self, format: CopyFormat) {
         if let Some(ref menu) = self.copy_menu {
@@ -0,0 +1,136 @@
+//! Login screen and re-authentication.
+//!
+//! The login form opens when there is no session or the server rejects it
+//! (HTTP 401). Requests that failed for that reason are kept and sent again
+//! once the user has logged in.
+
+use std::sync::Arc;
+
+use crossterm::event::KeyCode;
+use mbr_core::api::models::CurrentUser;
+use mbr_core::storage::credentials::{Session, get_credentials, load_session};
+
+use crate::action::AppAction;
+use crate::components::{LoginForm, LoginMethod};
+use crate::service::{self, ConnectionStatus, ServiceClient, configured_url};
+
+use super::App;
+
+impl App {
+    /// Open the login form (unless already shown) and queue an action to
+    /// retry after logging in.
+    pub(super) fn request_login(&mut self, retry: Option<AppAction>) {
+        if let Some(action) = retry
+            && !self.pending_actions.contains(&action)
+        {
+            self.pending_actions.push(action);
+        }
+        if self.login_form.is_none() {
+            self.show_login();
+            self.status_bar
+                .set_message("Not logged in or session expired — log in to continue");
+        }
+    }
+
+    /// Open the login form, prefilled from the configuration and the
+    /// stored session.
+    pub(super) fn show_login(&mut self) {
+        let url = configured_url();
+        let session = load_session().filter(|s| s.url == url);
+        let method = match session {
+            Some(ref s) if s.api_key.is_some() => LoginMethod::ApiKey,
+            _ => LoginMethod::Password,
+        };
+        let username = session
+            .filter(|s| s.api_key.is_none())
+            .map(|s| s.username)
+            .or_else(|| get_credentials().map(|(username, _)| username))
+            .unwrap_or_default();
+
+        let mut form = LoginForm::new(method, url, username);
+        if let ConnectionStatus::Error(ref error) = self.connection_status {
+            form.set_error(error.clone());
+        }
+        self.login_form = Some(form);
+    }
+
+    /// Handle keys while the login form is shown.
+    pub(super) fn handle_login_key(&mut self, code: KeyCode) {
+        let Some(ref mut form) = self.login_form else {
+            return;
+        };
+        if code == KeyCode::Esc {
+            self.login_form = None;
+            let dropped = std::mem::take(&mut self.pending_actions).len();
+            if dropped > 0 {
+                self.status_bar.set_message(format!(
+                    "Login cancelled ({} requests not retried)",
+                    dropped
+                ));
+            } else {
+                self.status_bar.set_message("Login cancelled");
+            }
+            return;
+        }
+        if form.is_submitting() {
+            return;
+        }
+        match code {
+            KeyCode::Tab | KeyCode::Down => form.focus_next(),
+            KeyCode::BackTab | KeyCode::Up => form.focus_previous(),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if form.is_method_focused() => {
+                form.toggle_method();
+            }
+            KeyCode::Backspace => form.delete_char(),
+            KeyCode::Char(c) => form.input_char(c),
+            KeyCode::Enter => {
+                let Ok((url, credentials)) = form.submit() else {
+                    return;
+                };
+                let tx = self.action_tx.clone();
+                tokio::spawn(async move {
+                    let action = match service::login(&url, credentials).await {
+                        Ok((session, user)) => AppAction::LoggedIn(session, user),
+                        Err(e) => AppAction::LoginFailed(e),
+                    };
+                    let _ = tx.send(action);
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Switch to the new session and retry the requests that needed it.
+    pub(super) fn handle_logged_in(&mut self, session: Session, user: CurrentUser) {
+        let client = match ServiceClient::from_session(&session) {
+            Ok(client) => client,
+            Err(e) => {
+                self.handle_login_failed(e);
+                return;
+            }
+        };
+        self.service = Some(Arc::new(client));
+        self.login_form = None;
+        self.handle_auth_validated(user);
+
+        let pending = std::mem::take(&mut self.pending_actions);
+        let mut message = format!("Logged in to {} as {}", session.url, session.username);
+        if !pending.is_empty() {
+            message.push_str(&format!(" — retrying {} requests", pending.len()));
+        }
+        for action in pending {
+            let _ = self.action_tx.send(action);
+        }
+        self.status_bar.set_message(message);
+    }
+
+    /// Show why the login failed and let the user try again.
+    pub(super) fn handle_login_failed(&mut self, error: String) {
+        if let Some(ref mut form) = self.login_form {
+            form.set_error(error);
+        } else {
+            self.status_bar
+                .set_message(format!("Login failed: {}", error));
+        }
+    }
+}
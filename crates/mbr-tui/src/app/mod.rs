@@ -6,33 +6,52 @@
 //! ## Module Structure
 //! - `mod.rs`: App struct definition, initialization, and rendering
 //! - `action_handler.rs`: AppAction event processing
+//! - `buffer_handler.rs`: Open result buffers, buffer list and split view
+//! - `command_handler.rs`: Command palette and `:` command line
 //! - `data_handler.rs`: Async data loading with tokio tasks
 //! - `input_handler.rs`: Keyboard event processing
+//! - `login_handler.rs`: Login screen and retrying requests after re-login
+//! - `mouse_handler.rs`: Mouse clicks and wheel scrolling
+//! - `session_handler.rs`: Saved result views, execution history and session restore
 
 mod action_handler;
+mod buffer_handler;
+mod command_handler;
 mod data_handler;
 mod input_handler;
+mod login_handler;
+mod mouse_handler;
+mod session_handler;
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use crossterm::event::EventStream;
 use futures::StreamExt;
+use mbr_core::storage::tui_state::TuiState;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 use tokio::sync::mpsc;
 
 use crate::action::{AppAction, DataRequest};
+use crate::components::styles::{text_dim, text_error, text_key, text_normal, text_success};
+use crate::components::theme::theme;
+use crate::components::theme::{ThemeCycle, set_theme};
 use crate::components::{
-    ActiveTab, Component, ContentPanel, ContentView, CopyMenu, HelpOverlay, RecordDetailOverlay,
-    StatusBar,
+    ActiveTab, BufferList, CellViewer, CommandPalette, Component, ContentPanel, ContentView,
+    CopyMenu, HelpOverlay, LoginForm, RecordDetailOverlay, StatusBar,
 };
+use crate::keymap::Keymap;
 use crate::layout::main::{HEADER_HEIGHT, STATUS_BAR_HEIGHT};
-use crate::service::{AppData, ConnectionStatus, LoadState, ServiceClient, init_service};
+use crate::service::{
+    AppData, ConnectionStatus, LoadState, ServiceClient, configured_url, init_service,
+};
 
 /// The main application state.
 pub struct App {
@@ -56,21 +75,64 @@ pub struct App {
     action_rx: mpsc::UnboundedReceiver<AppAction>,
     /// Whether to show help overlay
     pub(crate) show_help: bool,
-    /// Current query request ID for race condition prevention
+    /// ID of the latest question execution (shown when it finishes)
     pub(crate) current_request_id: u64,
+    /// Running question executions (question, start time)
+    running_queries: HashMap<u64, (u32, Instant)>,
+    /// View the latest execution was started from; its result is shown only
+    /// if the user is still there, otherwise it opens in the background
+    foreground_view: Option<ContentView>,
     /// Whether to show record detail overlay
     pub(crate) show_record_detail: bool,
     /// Record detail overlay state
     pub(crate) record_detail: Option<RecordDetailOverlay>,
+    /// Cell viewer overlay (Some while shown)
+    pub(crate) cell_viewer: Option<CellViewer>,
+    /// Open results list overlay (Some while shown)
+    pub(crate) buffer_list: Option<BufferList>,
     /// Whether to show copy menu overlay
     pub(crate) show_copy_menu: bool,
     /// Copy menu state
     pub(crate) copy_menu: Option<CopyMenu>,
+    /// Whether to show command palette overlay
+    pub(crate) show_command_palette: bool,
+    /// Command palette state
+    pub(crate) command_palette: Option<CommandPalette>,
+    /// `:` command line input (Some while the command line is open)
+    pub(crate) command_line: Option<String>,
+    /// Login form overlay (Some while shown)
+    pub(crate) login_form: Option<LoginForm>,
+    /// Actions rejected for missing credentials, retried after logging in
+    pending_actions: Vec<AppAction>,
+    /// Active key bindings
+    pub(crate) keymap: Keymap,
+    /// Available color themes and the active one
+    pub(crate) themes: ThemeCycle,
+    /// Problems found while loading `[tui]` settings, reported once the UI is up
+    config_errors: Vec<String>,
+    /// State kept between sessions (per-question result views, history, last session)
+    pub(crate) tui_state: TuiState,
+    /// Header area from the last frame (for tab clicks)
+    header_area: Rect,
+    /// Last row click as (time, screen row, row index), for double-click detection
+    last_click: Option<(Instant, u16, usize)>,
+}
+
+/// Tabs in header order.
+const TABS: [ActiveTab; 3] = [
+    ActiveTab::Questions,
+    ActiveTab::Collections,
+    ActiveTab::Databases,
+];
+
+/// Header title of a tab, including its number key hint.
+fn tab_title(index: usize, tab: ActiveTab) -> String {
+    format!(" {} {} ", index + 1, tab.label())
 }
 
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(false)
     }
 }
 
@@ -84,15 +146,28 @@ impl App {
         self.content.is_sort_mode_active()
             || self.content.is_filter_mode_active()
             || self.content.is_result_search_active()
+            || self.content.is_column_manager_active()
+            || self.content.is_group_modal_active()
+            || self.content.is_column_profile_active()
             || self.show_copy_menu
+            || self.buffer_list.is_some()
+            || self.show_command_palette
+            || self.command_line.is_some()
+            || self.login_form.is_some()
     }
 
-    /// Create a new application instance.
-    pub fn new() -> Self {
+    /// Create a new application instance, browsing the metadata snapshot
+    /// instead of the server when `offline` is set.
+    pub fn new(offline: bool) -> Self {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
 
         // Initialize service client
-        let (service, connection_status) = {
+        let (service, connection_status) = if offline {
+            match ServiceClient::offline(&configured_url()) {
+                Ok(client) => (Some(Arc::new(client)), ConnectionStatus::Offline),
+                Err(e) => (None, ConnectionStatus::Error(e)),
+            }
+        } else {
             let rt = tokio::runtime::Handle::current();
             match tokio::task::block_in_place(|| rt.block_on(init_service())) {
                 Ok(client) => {
@@ -107,9 +182,22 @@ impl App {
             }
         };
 
+        // Load key bindings and theme; fall back to defaults on config errors
+        let mut config_errors = Vec::new();
+        let keymap = Keymap::load().unwrap_or_else(|e| {
+            config_errors.push(format!("{} (using default keys)", e));
+            Keymap::default()
+        });
+        let themes = ThemeCycle::load().unwrap_or_else(|e| {
+            config_errors.push(format!("{} (using dark theme)", e));
+            ThemeCycle::default()
+        });
+        set_theme(themes.current());
+
         // Set initial view to Questions
         let mut content = ContentPanel::new();
         content.set_view(ContentView::Questions);
+        content.set_keymap(keymap.clone());
 
         Self {
             should_quit: false,
@@ -123,10 +211,25 @@ impl App {
             action_rx,
             show_help: false,
             current_request_id: 0,
+            running_queries: HashMap::new(),
+            foreground_view: None,
             show_record_detail: false,
             record_detail: None,
+            cell_viewer: None,
+            buffer_list: None,
             show_copy_menu: false,
             copy_menu: None,
+            show_command_palette: false,
+            command_palette: None,
+            command_line: None,
+            login_form: None,
+            pending_actions: Vec::new(),
+            keymap,
+            themes,
+            config_errors,
+            tui_state: TuiState::load(),
+            header_area: Rect::default(),
+            last_click: None,
         }
     }
 
@@ -139,8 +242,10 @@ impl App {
         let mut reader = EventStream::new();
 
         // Validate authentication on startup if we have a service client
+        // (offline, the snapshot has no session to validate)
         if let Some(service) = &self.service
             && service.is_authenticated()
+            && service.snapshot().is_none()
         {
             self.validate_auth_async().await;
         }
@@ -154,6 +259,24 @@ impl App {
                 .send(AppAction::LoadData(DataRequest::Questions));
         }
 
+        // Offer the last session unless there was nothing beyond a tab to reopen
+        if self
+            .tui_state
+            .session
+            .as_ref()
+            .is_some_and(|s| s.views.len() > 1)
+        {
+            let _ = self.action_tx.send(AppAction::SetStatus(
+                "Last session saved — :restore to reopen it".to_string(),
+            ));
+        }
+
+        // Report config problems after startup loading messages
+        if !self.config_errors.is_empty() {
+            let message = std::mem::take(&mut self.config_errors).join(" | ");
+            let _ = self.action_tx.send(AppAction::SetStatus(message));
+        }
+
         // Initial render
         terminal.draw(|frame| self.draw(frame))?;
 
@@ -175,6 +298,8 @@ impl App {
                                     self.handle_key(key.code, key.modifiers);
                                     needs_render = true;
                                 }
+                            } else if let crossterm::event::Event::Mouse(mouse) = event {
+                                needs_render |= self.handle_mouse(mouse);
                             } else if let crossterm::event::Event::Resize(_, _) = event {
                                 needs_render = true;
                             }
@@ -201,6 +326,7 @@ impl App {
             }
         }
 
+        self.save_session();
         crate::restore_terminal()?;
         Ok(())
     }
@@ -221,8 +347,11 @@ impl App {
                     self.data.current_user = Some(user);
                 }
                 Err(e) => {
-                    self.connection_status = ConnectionStatus::Error(e.clone());
+                    self.connection_status = ConnectionStatus::Error(e.to_string());
                     self.status_bar.set_message(format!("Auth failed: {}", e));
+                    if e.is_unauthorized() {
+                        self.request_login(None);
+                    }
                 }
             }
         }
@@ -230,6 +359,7 @@ impl App {
 
     /// Switch to a specific tab and update content view.
     pub(crate) fn switch_to_tab(&mut self, tab: ActiveTab) {
+        self.remember_result_view();
         self.active_tab = tab;
         let view = match tab {
             ActiveTab::Questions => ContentView::Questions,
@@ -286,6 +416,7 @@ impl App {
             .split(size);
 
         // Draw header with integrated tabs
+        self.header_area = main_chunks[0];
         self.draw_header_with_tabs(frame, main_chunks[0]);
 
         // Draw content panel (full width, always focused)
@@ -295,12 +426,25 @@ impl App {
         self.status_bar
             .set_selection_count(self.content.selected_count());
 
-        // Draw status bar
-        self.status_bar.draw(frame, main_chunks[2], false);
+        // Draw status bar (replaced by the command line while it is open)
+        if let Some(ref line) = self.command_line {
+            let command_line = Paragraph::new(Line::from(vec![
+                Span::styled(":", text_key()),
+                Span::raw(format!("{}_", line)),
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(text_key()),
+            );
+            frame.render_widget(command_line, main_chunks[2]);
+        } else {
+            self.status_bar.draw(frame, main_chunks[2], false);
+        }
 
         // Draw help overlay if visible
         if self.show_help {
-            HelpOverlay::render(frame, size);
+            HelpOverlay::render(frame, size, &self.keymap);
         }
 
         // Draw record detail overlay if visible
@@ -310,65 +454,94 @@ impl App {
             detail.render(frame, size);
         }
 
+        // Draw cell viewer overlay (above the record detail it may be opened from)
+        if let Some(ref mut viewer) = self.cell_viewer {
+            viewer.render(frame, size);
+        }
+
+        // Draw open results list if visible
+        if let Some(ref list) = self.buffer_list {
+            list.render(frame, size);
+        }
+
         // Draw copy menu overlay if visible
         if self.show_copy_menu
             && let Some(ref menu) = self.copy_menu
         {
             menu.render(frame, size);
         }
+
+        // Draw command palette overlay if visible
+        if self.show_command_palette
+            && let Some(ref palette) = self.command_palette
+        {
+            palette.render(frame, size);
+        }
+
+        // Draw login form above everything else
+        if let Some(ref form) = self.login_form {
+            form.render(frame, size);
+        }
     }
 
     /// Draw the header with integrated tab bar.
     fn draw_header_with_tabs(&self, frame: &mut Frame, area: Rect) {
         // Build connection indicator
         let connection_indicator = match &self.connection_status {
-            ConnectionStatus::Disconnected => {
-                Span::styled(" ○ ", Style::default().fg(Color::DarkGray))
+            ConnectionStatus::Disconnected => Span::styled(" ○ ", text_dim()),
+            ConnectionStatus::Connecting => Span::styled(" ◐ ", text_key()),
+            ConnectionStatus::Connected(_) => Span::styled(" ● ", text_success()),
+            ConnectionStatus::Error(_) => Span::styled(" ✗ ", text_error()),
+            ConnectionStatus::Offline => {
+                let stale_since = self
+                    .service
+                    .as_ref()
+                    .and_then(|s| s.snapshot())
+                    .map(|s| s.stale_since())
+                    .unwrap_or_default();
+                Span::styled(format!(" ◌ offline, {} ", stale_since), text_key())
             }
-            ConnectionStatus::Connecting => Span::styled(" ◐ ", Style::default().fg(Color::Yellow)),
-            ConnectionStatus::Connected(_) => {
-                Span::styled(" ● ", Style::default().fg(Color::Green))
-            }
-            ConnectionStatus::Error(_) => Span::styled(" ✗ ", Style::default().fg(Color::Red)),
         };
 
         // Build tab bar
-        let tabs = [
-            ActiveTab::Questions,
-            ActiveTab::Collections,
-            ActiveTab::Databases,
-        ];
         let mut tab_spans: Vec<Span> = vec![Span::raw(" ")];
 
-        for (i, tab) in tabs.iter().enumerate() {
+        for (i, tab) in TABS.iter().enumerate() {
             let is_active = *tab == self.active_tab;
             let style = if is_active {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme().accent)
                     .add_modifier(Modifier::BOLD | Modifier::REVERSED)
             } else {
-                Style::default().fg(Color::White)
+                text_normal()
             };
 
             // Add tab with number key hint
-            tab_spans.push(Span::styled(format!(" {} {} ", i + 1, tab.label()), style));
+            tab_spans.push(Span::styled(tab_title(i, *tab), style));
             tab_spans.push(Span::raw(" "));
         }
 
         // Add connection status at the end
-        tab_spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
+        tab_spans.push(Span::styled("│", text_dim()));
         tab_spans.push(connection_indicator);
 
+        // Breadcrumb path of the selected node in the collection tree
+        let breadcrumb = self.content.collection_breadcrumb();
+        if !breadcrumb.is_empty() {
+            tab_spans.push(Span::styled("│ ", text_dim()));
+            tab_spans.push(Span::styled(breadcrumb.join(" › "), text_key()));
+        }
+
         let header = Paragraph::new(Line::from(tab_spans)).block(
             Block::default()
                 .title(" mbr-tui ")
                 .title_style(
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme().accent)
                         .add_modifier(Modifier::BOLD),
                 )
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(text_dim()),
         );
         frame.render_widget(header, area);
     }
@@ -0,0 +1,135 @@
+//! Mouse input handling for the application.
+//!
+//! Maps clicks and wheel events onto the same operations as the keyboard:
+//! tab switching, row selection, drill-down/execute on double-click, and
+//! scrolling of lists, results and the record detail overlay.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::text::Span;
+
+use crate::components::{ActiveTab, ContentClick, InputMode};
+
+use super::{App, TABS, tab_title};
+
+/// Maximum delay between two clicks on the same row to count as a double-click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Rows moved per wheel tick.
+const WHEEL_STEP: usize = 3;
+
+impl App {
+    /// Handle a mouse event.
+    /// Returns true if the UI needs to be redrawn.
+    pub(super) fn handle_mouse(&mut self, event: MouseEvent) -> bool {
+        match event.kind {
+            MouseEventKind::ScrollUp => self.handle_wheel(false),
+            MouseEventKind::ScrollDown => self.handle_wheel(true),
+            MouseEventKind::Down(MouseButton::Left) => self.handle_click(
+                event.column,
+                event.row,
+                event.modifiers.contains(KeyModifiers::SHIFT),
+            ),
+            _ => false,
+        }
+    }
+
+    /// Scroll the cell viewer or record detail overlay, or the selection of the current view.
+    fn handle_wheel(&mut self, down: bool) -> bool {
+        if let Some(ref mut viewer) = self.cell_viewer {
+            if down {
+                viewer.scroll_down(WHEEL_STEP);
+            } else {
+                viewer.scroll_up(WHEEL_STEP);
+            }
+            return true;
+        }
+        if self.show_record_detail {
+            if let Some(ref mut detail) = self.record_detail {
+                for _ in 0..WHEEL_STEP {
+                    if down {
+                        detail.scroll_down();
+                    } else {
+                        detail.scroll_up();
+                    }
+                }
+            }
+            return true;
+        }
+        if self.is_mouse_blocked() {
+            return false;
+        }
+
+        for _ in 0..WHEEL_STEP {
+            self.content.scroll_selection(down);
+        }
+        true
+    }
+
+    /// Handle a left click on a tab, a table row or a column header.
+    /// Shift+click on a header adds the column as a sort key.
+    fn handle_click(&mut self, column: u16, row: u16, shift: bool) -> bool {
+        if self.is_mouse_blocked() || self.show_record_detail || self.cell_viewer.is_some() {
+            return false;
+        }
+
+        if let Some(tab) = self.tab_at(column, row) {
+            self.last_click = None;
+            if tab != self.active_tab {
+                self.switch_to_tab(tab);
+            }
+            return true;
+        }
+
+        match self.content.click_at(column, row, shift) {
+            Some(ContentClick::Row(index)) => {
+                let now = Instant::now();
+                let is_double_click = self.last_click.is_some_and(|(at, last_row, last_index)| {
+                    last_row == row
+                        && last_index == index
+                        && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+                });
+
+                if is_double_click {
+                    self.last_click = None;
+                    self.handle_enter_key();
+                } else {
+                    self.last_click = Some((now, row, index));
+                }
+                true
+            }
+            Some(ContentClick::Header(_)) => {
+                self.last_click = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether an overlay or text input is capturing input.
+    fn is_mouse_blocked(&self) -> bool {
+        self.show_help || self.is_modal_active() || self.content.input_mode() == InputMode::Search
+    }
+
+    /// Find the header tab at a screen position.
+    fn tab_at(&self, column: u16, row: u16) -> Option<ActiveTab> {
+        let area = self.header_area;
+        // Tabs sit on the first line inside the header border
+        if row != area.y + 1 {
+            return None;
+        }
+
+        // Border plus the leading space
+        let mut x = area.x + 2;
+        for (i, tab) in TABS.iter().enumerate() {
+            let width = Span::raw(tab_title(i, *tab)).width() as u16;
+            if (x..x + width).contains(&column) {
+                return Some(*tab);
+            }
+            // Tab title plus the separating space
+            x += width + 1;
+        }
+        None
+    }
+}
@@ -0,0 +1,122 @@
+//! Session state and execution history.
+//!
+//! Keeps `TuiState` up to date with the filter, sort and search of question
+//! results and the history of executed questions, and saves and restores the
+//! navigation path of the last session.
+
+use std::time::Duration;
+
+use mbr_core::storage::credentials::now_iso8601;
+use mbr_core::storage::tui_state::{HistoryEntry, Session, SessionView};
+
+use crate::action::AppAction;
+use crate::components::{ActiveTab, QueryResultData};
+
+use super::App;
+
+impl App {
+    /// Save the filter, sort and search of the shown question result.
+    /// Called before the result is left or replaced.
+    pub(super) fn remember_result_view(&mut self) {
+        let Some((question_id, view)) = self.content.result_view_settings() else {
+            return;
+        };
+        let changed = if view.is_empty() {
+            self.tui_state.result_views.remove(&question_id).is_some()
+        } else if self.tui_state.result_views.get(&question_id) == Some(&view) {
+            false
+        } else {
+            self.tui_state.result_views.insert(question_id, view);
+            true
+        };
+        if changed && let Err(e) = self.tui_state.save() {
+            self.status_bar
+                .set_message(format!("Failed to save result view: {}", e));
+        }
+    }
+
+    /// Add an executed question to the history.
+    pub(super) fn record_execution(
+        &mut self,
+        result: &QueryResultData,
+        duration: Option<Duration>,
+    ) {
+        let duration_ms = duration.map_or(0, |d| d.as_millis() as u64);
+        self.tui_state.record_execution(HistoryEntry {
+            question_id: result.question_id,
+            name: result.question_name.clone(),
+            executed_at: now_iso8601(),
+            rows: result.rows.len(),
+            duration_ms,
+        });
+        if let Err(e) = self.tui_state.save() {
+            self.status_bar
+                .set_message(format!("Failed to save history: {}", e));
+        }
+    }
+
+    /// Show the History view.
+    pub(super) fn show_history(&mut self) {
+        self.remember_result_view();
+        self.content.enter_history(self.tui_state.history.clone());
+        self.status_bar.set_message(format!(
+            "History: {} executions (Enter to re-run, Esc to go back)",
+            self.tui_state.history.len()
+        ));
+    }
+
+    /// Save the navigation path for `:restore` in a later session.
+    /// Called on quit; failures are ignored since the UI is closing.
+    pub(super) fn save_session(&mut self) {
+        self.remember_result_view();
+        let views = self.content.session_views();
+        self.tui_state.session = (!views.is_empty()).then_some(Session { views });
+        let _ = self.tui_state.save();
+    }
+
+    /// Reopen the navigation path of the last session.
+    ///
+    /// Switches to the saved tab and replays the drill-downs and question
+    /// executions; they are processed in order, so each step finds the
+    /// context of the previous one.
+    pub(super) fn restore_session(&mut self) {
+        let Some(session) = self.tui_state.session.clone() else {
+            self.status_bar.set_message("No saved session to restore");
+            return;
+        };
+        let mut views = session.views.into_iter();
+        let tab = match views.next() {
+            Some(SessionView::Questions) => ActiveTab::Questions,
+            Some(SessionView::Collections) => ActiveTab::Collections,
+            Some(SessionView::Databases) => ActiveTab::Databases,
+            _ => {
+                self.status_bar.set_message("Saved session is empty");
+                return;
+            }
+        };
+        self.switch_to_tab(tab);
+        for view in views {
+            let action = match view {
+                SessionView::QueryResult { question_id } => AppAction::ExecuteQuestion(question_id),
+                SessionView::DatabaseSchemas { db_id, db_name } => {
+                    AppAction::DrillDownDatabase(db_id, db_name)
+                }
+                SessionView::SchemaTables { schema_name, .. } => {
+                    AppAction::DrillDownSchema(schema_name)
+                }
+                SessionView::TablePreview {
+                    table_id,
+                    table_name,
+                    ..
+                } => AppAction::DrillDownTable(table_id, table_name),
+                // Tab root views only start a path
+                SessionView::Questions | SessionView::Collections | SessionView::Databases => {
+                    continue;
+                }
+            };
+            let _ = self.action_tx.send(action);
+        }
+        self.status_bar
+            .set_message(format!("Restored last session ({})", tab.label()));
+    }
+}
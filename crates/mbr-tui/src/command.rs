@@ -0,0 +1,404 @@
+//! Vim-style `:` command line parsing.
+//!
+//! Maps typed commands onto `AppAction`s:
+//! - `:run 123` / `:run <question name>`: execute a question
+//! - `:filter revenue>100 and status in a,b` / `:filter`: filter result rows / clear filter
+//! - `:and <condition>` / `:or <condition>`: add a condition to the active filter
+//! - `:sort country, -revenue` / `:sort`: sort results by one or more columns
+//!   (`-` = descending) / clear sort
+//! - `:group country, region` / `:group`: group result rows / remove grouping
+//! - `:summary`: toggle the result summary footer
+//! - `:profile revenue` / `:profile`: profile a column / the current column
+//! - `:view notes` / `:view`: show the full value of a cell of the selected row
+//! - `:export out.csv`: write visible result rows to a file (csv, tsv, json)
+//! - `:history`: show executed questions; `:restore`: reopen the last session
+//! - `:buffers` / `:ls`: list open results; `:buffer 2`: show one; `:bclose [n]`: close one
+//! - `:split 2` / `:split`: compare the result with another one / toggle the split
+//! - `:questions`, `:collections`, `:databases`, `:tab <n>`: switch tab
+//! - `:theme` / `:theme <name>`: cycle or pick the color theme
+//! - `:login`: log in again (other account, URL or API key)
+//! - `:refresh`, `:help`, `:quit`
+
+use crate::action::{AppAction, ContentTarget};
+use crate::components::{FilterClause, FilterJoin, FilterOp, SortOrder};
+
+/// Operators recognized in `:filter` expressions, longest first so that
+/// `>=` is not mistaken for `>`. Word operators are matched with
+/// surrounding whitespace.
+const FILTER_OPERATORS: &[(&str, FilterOp)] = &[
+    (" is not null", FilterOp::NotNull),
+    (" is null", FilterOp::IsNull),
+    (" starts-with ", FilterOp::StartsWith),
+    (" contains ", FilterOp::Contains),
+    (" between ", FilterOp::Between),
+    (" regex ", FilterOp::Regex),
+    (" in ", FilterOp::In),
+    (">=", FilterOp::Ge),
+    ("<=", FilterOp::Le),
+    ("!=", FilterOp::Ne),
+    ("=~", FilterOp::Regex),
+    ("^=", FilterOp::StartsWith),
+    (">", FilterOp::Gt),
+    ("<", FilterOp::Lt),
+    ("=", FilterOp::Eq),
+    ("~", FilterOp::Contains),
+];
+
+/// Parse a command line (without the leading `:`) into an action.
+pub fn parse_command(input: &str) -> Result<AppAction, String> {
+    let input = input.trim();
+    let (name, args) = match input.split_once(char::is_whitespace) {
+        Some((name, args)) => (name, args.trim()),
+        None => (input, ""),
+    };
+
+    match name {
+        "" => Err("Empty command".to_string()),
+        "q" | "q!" | "quit" => Ok(AppAction::Quit),
+        "h" | "help" => Ok(AppAction::ShowHelp),
+        "r" | "refresh" => Ok(AppAction::Refresh),
+        "questions" => Ok(AppAction::Navigate(ContentTarget::Questions)),
+        "collections" => Ok(AppAction::Navigate(ContentTarget::Collections)),
+        "databases" => Ok(AppAction::Navigate(ContentTarget::Databases)),
+        "tab" => parse_tab(args),
+        "run" => parse_run(args),
+        "filter" => parse_filter(args),
+        "and" => parse_condition(args, FilterJoin::And).map(AppAction::AddFilter),
+        "or" => parse_condition(args, FilterJoin::Or).map(AppAction::AddFilter),
+        "sort" => parse_sort(args),
+        "group" => parse_group(args),
+        "summary" => Ok(AppAction::ToggleSummary),
+        "profile" => Ok(AppAction::ProfileColumn(
+            Some(args.to_string()).filter(|c| !c.is_empty()),
+        )),
+        "view" => Ok(AppAction::ViewCell(
+            Some(args.to_string()).filter(|c| !c.is_empty()),
+        )),
+        "history" => Ok(AppAction::ShowHistory),
+        "buffers" | "ls" => Ok(AppAction::ShowBuffers),
+        "b" | "buffer" => parse_buffer_number(args)
+            .and_then(|n| n.ok_or_else(|| "Usage: :buffer <n>".to_string()))
+            .map(AppAction::SwitchBuffer),
+        "bclose" | "bd" => parse_buffer_number(args).map(AppAction::CloseBuffer),
+        "split" => parse_buffer_number(args).map(AppAction::SplitView),
+        "restore" => Ok(AppAction::RestoreSession),
+        "login" => Ok(AppAction::ShowLogin),
+        "theme" if args.is_empty() => Ok(AppAction::CycleTheme),
+        "theme" => Ok(AppAction::SetTheme(args.to_string())),
+        "export" => {
+            if args.is_empty() {
+                Err("Usage: :export <file.csv|file.tsv|file.json>".to_string())
+            } else {
+                Ok(AppAction::ExportResult(args.to_string()))
+            }
+        }
+        other => Err(format!("Unknown command: {}", other)),
+    }
+}
+
+fn parse_tab(args: &str) -> Result<AppAction, String> {
+    let target = match args.to_lowercase().as_str() {
+        "1" | "questions" => ContentTarget::Questions,
+        "2" | "collections" => ContentTarget::Collections,
+        "3" | "databases" => ContentTarget::Databases,
+        _ => return Err("Usage: :tab <1|2|3|questions|collections|databases>".to_string()),
+    };
+    Ok(AppAction::Navigate(target))
+}
+
+/// Parse an optional 1-based buffer number into a buffer index.
+fn parse_buffer_number(args: &str) -> Result<Option<usize>, String> {
+    if args.is_empty() {
+        return Ok(None);
+    }
+    match args.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(Some(n - 1)),
+        _ => Err(format!("Invalid buffer number: {}", args)),
+    }
+}
+
+fn parse_run(args: &str) -> Result<AppAction, String> {
+    if args.is_empty() {
+        return Err("Usage: :run <question id or name>".to_string());
+    }
+    match args.parse::<u32>() {
+        Ok(id) => Ok(AppAction::ExecuteQuestion(id)),
+        Err(_) => Ok(AppAction::ExecuteQuestionByName(args.to_string())),
+    }
+}
+
+fn parse_filter(args: &str) -> Result<AppAction, String> {
+    if args.is_empty() {
+        return Ok(AppAction::ClearFilter);
+    }
+
+    let mut clauses = Vec::new();
+    let mut join = FilterJoin::And;
+    let mut rest = args;
+    while let Some((pos, len, next_join)) = find_join(rest) {
+        clauses.push(parse_condition(&rest[..pos], join)?);
+        join = next_join;
+        rest = &rest[pos + len..];
+    }
+    clauses.push(parse_condition(rest, join)?);
+    Ok(AppAction::ApplyFilter(clauses))
+}
+
+/// Find the first ` and ` / ` or ` keyword: (byte position, length, join).
+fn find_join(text: &str) -> Option<(usize, usize, FilterJoin)> {
+    // ASCII lowercasing keeps byte offsets valid for the original string
+    let lower = text.to_ascii_lowercase();
+    [(" and ", FilterJoin::And), (" or ", FilterJoin::Or)]
+        .into_iter()
+        .filter_map(|(keyword, join)| lower.find(keyword).map(|pos| (pos, keyword.len(), join)))
+        .min_by_key(|(pos, _, _)| *pos)
+}
+
+/// Parse a single `column <op> value` condition.
+fn parse_condition(text: &str, join: FilterJoin) -> Result<FilterClause, String> {
+    let text = text.trim();
+    // Pad so that word operators at the end (`x is null`) are found
+    let lower = format!("{} ", text.to_ascii_lowercase());
+
+    // Find the earliest operator; at equal positions the longer one wins
+    let found = FILTER_OPERATORS
+        .iter()
+        .filter_map(|(symbol, op)| lower.find(symbol).map(|pos| (pos, *symbol, *op)))
+        .min_by_key(|(pos, symbol, _)| (*pos, std::cmp::Reverse(symbol.len())));
+
+    match found {
+        Some((pos, symbol, op)) => {
+            let column = text[..pos].trim();
+            let value = text.get(pos + symbol.len()..).unwrap_or("").trim();
+            if column.is_empty() {
+                return Err("Filter is missing a column name".to_string());
+            }
+            if op.needs_value() && value.is_empty() {
+                return Err(format!("Filter '{}' needs a value", symbol.trim()));
+            }
+            Ok(FilterClause {
+                join,
+                column: column.to_string(),
+                op,
+                value: value.to_string(),
+            })
+        }
+        None => Err(format!(
+            "Filter needs an operator (one of {}): {}",
+            FILTER_OPERATORS
+                .iter()
+                .map(|(s, _)| s.trim())
+                .collect::<Vec<_>>()
+                .join(" "),
+            text
+        )),
+    }
+}
+
+fn parse_group(args: &str) -> Result<AppAction, String> {
+    if args.is_empty() {
+        return Ok(AppAction::Ungroup);
+    }
+    let columns: Vec<String> = args.split(',').map(|c| c.trim().to_string()).collect();
+    if columns.iter().any(String::is_empty) {
+        return Err(format!("Group is missing a column name: {}", args));
+    }
+    Ok(AppAction::GroupBy(columns))
+}
+
+fn parse_sort(args: &str) -> Result<AppAction, String> {
+    if args.is_empty() {
+        return Ok(AppAction::ClearSort);
+    }
+    let keys = args
+        .split(',')
+        .map(str::trim)
+        .map(|key| {
+            let (column, order) = if let Some(column) = key.strip_prefix('-') {
+                (column, SortOrder::Descending)
+            } else {
+                (key.strip_prefix('+').unwrap_or(key), SortOrder::Ascending)
+            };
+            let column = column.trim();
+            if column.is_empty() {
+                Err(format!("Sort key is missing a column name: {}", args))
+            } else {
+                Ok((column.to_string(), order))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(AppAction::ApplySort(keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse_command("run 123"),
+            Ok(AppAction::ExecuteQuestion(123))
+        );
+        assert_eq!(
+            parse_command("run Monthly Revenue"),
+            Ok(AppAction::ExecuteQuestionByName(
+                "Monthly Revenue".to_string()
+            ))
+        );
+        assert!(parse_command("run").is_err());
+    }
+
+    fn clause(join: FilterJoin, column: &str, op: FilterOp, value: &str) -> FilterClause {
+        FilterClause {
+            join,
+            column: column.to_string(),
+            op,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            parse_command("filter revenue>100"),
+            Ok(AppAction::ApplyFilter(vec![clause(
+                FilterJoin::And,
+                "revenue",
+                FilterOp::Gt,
+                "100"
+            )]))
+        );
+        assert_eq!(
+            parse_command("filter created at >= 2024-01-01"),
+            Ok(AppAction::ApplyFilter(vec![clause(
+                FilterJoin::And,
+                "created at",
+                FilterOp::Ge,
+                "2024-01-01"
+            )]))
+        );
+        assert_eq!(
+            parse_command("filter status != done"),
+            Ok(AppAction::ApplyFilter(vec![clause(
+                FilterJoin::And,
+                "status",
+                FilterOp::Ne,
+                "done"
+            )]))
+        );
+        assert_eq!(parse_command("filter"), Ok(AppAction::ClearFilter));
+        assert!(parse_command("filter revenue").is_err());
+        assert!(parse_command("filter >5").is_err());
+        assert!(parse_command("filter revenue >").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_expression() {
+        assert_eq!(
+            parse_command("filter price between 10..20 AND status in new, open or note is null"),
+            Ok(AppAction::ApplyFilter(vec![
+                clause(FilterJoin::And, "price", FilterOp::Between, "10..20"),
+                clause(FilterJoin::And, "status", FilterOp::In, "new, open"),
+                clause(FilterJoin::Or, "note", FilterOp::IsNull, ""),
+            ]))
+        );
+        assert_eq!(
+            parse_command("filter email =~ ^a.*@example and name ^= Jo"),
+            Ok(AppAction::ApplyFilter(vec![
+                clause(FilterJoin::And, "email", FilterOp::Regex, "^a.*@example"),
+                clause(FilterJoin::And, "name", FilterOp::StartsWith, "Jo"),
+            ]))
+        );
+        assert_eq!(
+            parse_command("or deleted at is not null"),
+            Ok(AppAction::AddFilter(clause(
+                FilterJoin::Or,
+                "deleted at",
+                FilterOp::NotNull,
+                ""
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        assert_eq!(
+            parse_command("sort -date"),
+            Ok(AppAction::ApplySort(vec![(
+                "date".to_string(),
+                SortOrder::Descending
+            )]))
+        );
+        assert_eq!(
+            parse_command("sort name"),
+            Ok(AppAction::ApplySort(vec![(
+                "name".to_string(),
+                SortOrder::Ascending
+            )]))
+        );
+        assert_eq!(
+            parse_command("sort country, -total revenue"),
+            Ok(AppAction::ApplySort(vec![
+                ("country".to_string(), SortOrder::Ascending),
+                ("total revenue".to_string(), SortOrder::Descending),
+            ]))
+        );
+        assert!(parse_command("sort name,").is_err());
+    }
+
+    #[test]
+    fn test_parse_group() {
+        assert_eq!(
+            parse_command("group country, region"),
+            Ok(AppAction::GroupBy(vec![
+                "country".to_string(),
+                "region".to_string()
+            ]))
+        );
+        assert_eq!(parse_command("group"), Ok(AppAction::Ungroup));
+        assert!(parse_command("group a,,b").is_err());
+        assert_eq!(parse_command("summary"), Ok(AppAction::ToggleSummary));
+        assert_eq!(
+            parse_command("profile unit price"),
+            Ok(AppAction::ProfileColumn(Some("unit price".to_string())))
+        );
+        assert_eq!(parse_command("profile"), Ok(AppAction::ProfileColumn(None)));
+        assert_eq!(
+            parse_command("view notes"),
+            Ok(AppAction::ViewCell(Some("notes".to_string())))
+        );
+        assert_eq!(parse_command("sort"), Ok(AppAction::ClearSort));
+    }
+
+    #[test]
+    fn test_parse_misc() {
+        assert_eq!(
+            parse_command("export out.csv"),
+            Ok(AppAction::ExportResult("out.csv".to_string()))
+        );
+        assert!(parse_command("export").is_err());
+        assert_eq!(parse_command("q"), Ok(AppAction::Quit));
+        assert_eq!(
+            parse_command("tab 2"),
+            Ok(AppAction::Navigate(ContentTarget::Collections))
+        );
+        assert_eq!(parse_command("theme"), Ok(AppAction::CycleTheme));
+        assert_eq!(parse_command("login"), Ok(AppAction::ShowLogin));
+        assert_eq!(
+            parse_command("theme light"),
+            Ok(AppAction::SetTheme("light".to_string()))
+        );
+        assert_eq!(parse_command("history"), Ok(AppAction::ShowHistory));
+        assert_eq!(parse_command("restore"), Ok(AppAction::RestoreSession));
+        assert_eq!(parse_command("ls"), Ok(AppAction::ShowBuffers));
+        assert_eq!(parse_command("buffer 2"), Ok(AppAction::SwitchBuffer(1)));
+        assert!(parse_command("buffer").is_err());
+        assert!(parse_command("b 0").is_err());
+        assert_eq!(parse_command("bclose"), Ok(AppAction::CloseBuffer(None)));
+        assert_eq!(parse_command("split 3"), Ok(AppAction::SplitView(Some(2))));
+        assert_eq!(parse_command("split"), Ok(AppAction::SplitView(None)));
+        assert!(parse_command("frobnicate").is_err());
+        assert!(parse_command("   ").is_err());
+    }
+}
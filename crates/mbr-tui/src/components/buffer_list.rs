@@ -0,0 +1,128 @@
+//! Buffer list overlay component.
+//!
+//! Lists the open query results so that one can be shown, compared with
+//! the live result, or closed.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::content::BufferInfo;
+use crate::components::styles::{popup_style, text_accent, text_dim, text_key, text_normal};
+use crate::components::theme::theme;
+
+/// Buffer list state.
+pub struct BufferList {
+    /// Open buffers, as listed
+    entries: Vec<BufferInfo>,
+    /// Selected entry
+    selected: usize,
+}
+
+impl BufferList {
+    /// Create a buffer list with an entry selected.
+    pub fn new(entries: Vec<BufferInfo>, selected: usize) -> Self {
+        let mut list = Self {
+            entries: Vec::new(),
+            selected,
+        };
+        list.set_entries(entries);
+        list
+    }
+
+    /// Replace the entries (after a buffer was closed), keeping the selection in range.
+    pub fn set_entries(&mut self, entries: Vec<BufferInfo>) {
+        self.entries = entries;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+
+    /// Whether no buffer is open.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Selected buffer index.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Move selection up.
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Move selection down.
+    pub fn select_down(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Render the buffer list as a centered overlay.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let height = (self.entries.len() as u16 + 4).min(area.height);
+        let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(50)]).flex(Flex::Center);
+        let [popup_area] = vertical.areas(area);
+        let [popup_area] = horizontal.areas(popup_area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let mut lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let is_selected = i == self.selected;
+                let prefix = if is_selected { " ► " } else { "   " };
+                let marker = if entry.live {
+                    " (shown)"
+                } else if entry.unseen {
+                    " (new)"
+                } else {
+                    ""
+                };
+                let style = if is_selected {
+                    theme()
+                        .highlight(theme().accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    text_normal()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{}{}. ", prefix, i + 1), style),
+                    Span::styled(entry.name.clone(), style),
+                    Span::styled(format!("  {} rows{}", entry.rows, marker), text_dim()),
+                ])
+            })
+            .collect();
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(" Enter", text_key()),
+            Span::styled(" Show  ", text_dim()),
+            Span::styled("V", text_key()),
+            Span::styled(" Compare  ", text_dim()),
+            Span::styled("x", text_key()),
+            Span::styled(" Close  ", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(" Cancel", text_dim()),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" Results ({}) ", self.entries.len()))
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(text_accent()),
+            )
+            .style(popup_style());
+
+        frame.render_widget(paragraph, popup_area);
+    }
+}
@@ -0,0 +1,606 @@
+//! Cell viewer overlay component.
+//!
+//! Shows the full value of one cell with word wrap and scrolling, where the
+//! results table and record detail truncate it. JSON and XML values are
+//! pretty-printed and syntax-coloured, and URLs are highlighted for copying.
+
+use std::ops::Range;
+
+use crate::components::styles::{text_accent, text_dim, text_key, text_normal, text_success};
+use crate::components::theme::theme;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthChar;
+
+/// How a cell value is displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellFormat {
+    Text,
+    Json,
+    Xml,
+}
+
+impl CellFormat {
+    /// Label shown in the viewer title.
+    fn label(&self) -> &'static str {
+        match self {
+            CellFormat::Text => "Text",
+            CellFormat::Json => "JSON",
+            CellFormat::Xml => "XML",
+        }
+    }
+}
+
+/// Cell viewer showing the full value of one field of a record.
+pub struct CellViewer {
+    /// Column names of the record
+    columns: Vec<String>,
+    /// Values of the record
+    values: Vec<String>,
+    /// Field being shown
+    index: usize,
+    /// Display format of the field
+    format: CellFormat,
+    /// Formatted value, one entry per logical line
+    lines: Vec<String>,
+    /// URLs found in the value
+    urls: Vec<String>,
+    /// Next URL to copy
+    url_index: usize,
+    /// First visible (wrapped) row
+    scroll: usize,
+    /// Wrapped rows for the last render width
+    wrapped: Option<(usize, Vec<Line<'static>>)>,
+    /// Viewport height from the last render (for paging)
+    page_height: usize,
+}
+
+impl CellViewer {
+    /// Create a viewer for the field at `index` of a record.
+    pub fn new(columns: Vec<String>, values: Vec<String>, index: usize) -> Self {
+        let mut viewer = Self {
+            columns,
+            values,
+            index: 0,
+            format: CellFormat::Text,
+            lines: Vec::new(),
+            urls: Vec::new(),
+            url_index: 0,
+            scroll: 0,
+            wrapped: None,
+            page_height: 1,
+        };
+        viewer.show_field(index);
+        viewer
+    }
+
+    /// Show the next field of the record (wrapping around).
+    pub fn next_field(&mut self) {
+        if !self.values.is_empty() {
+            self.show_field((self.index + 1) % self.values.len());
+        }
+    }
+
+    /// Show the previous field of the record (wrapping around).
+    pub fn prev_field(&mut self) {
+        if !self.values.is_empty() {
+            self.show_field((self.index + self.values.len() - 1) % self.values.len());
+        }
+    }
+
+    /// Scroll up by `rows`.
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Scroll down by `rows` (clamped when rendering).
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_add(rows);
+    }
+
+    /// Scroll up by one viewport.
+    pub fn page_up(&mut self) {
+        self.scroll_up(self.page_height);
+    }
+
+    /// Scroll down by one viewport.
+    pub fn page_down(&mut self) {
+        self.scroll_down(self.page_height);
+    }
+
+    /// Scroll to the start of the value.
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Scroll to the end of the value.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = usize::MAX;
+    }
+
+    /// Raw value of the shown field.
+    pub fn value(&self) -> &str {
+        self.values.get(self.index).map_or("", |v| v.as_str())
+    }
+
+    /// Next URL to copy, cycling through the URLs of the value.
+    /// Returns (position, total, url).
+    pub fn next_url(&mut self) -> Option<(usize, usize, &str)> {
+        if self.urls.is_empty() {
+            return None;
+        }
+        let position = self.url_index % self.urls.len();
+        self.url_index = position + 1;
+        Some((position + 1, self.urls.len(), &self.urls[position]))
+    }
+
+    /// Switch to a field and format its value.
+    fn show_field(&mut self, index: usize) {
+        self.index = index.min(self.values.len().saturating_sub(1));
+        let value = self.value().replace("\r\n", "\n").replace('\t', "    ");
+        let (format, text) = format_value(&value);
+        self.format = format;
+        self.lines = text.lines().map(str::to_string).collect();
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.urls = self
+            .lines
+            .iter()
+            .flat_map(|line| {
+                let chars: Vec<char> = line.chars().collect();
+                find_urls(line)
+                    .into_iter()
+                    .map(move |r| chars[r].iter().collect::<String>())
+            })
+            .collect();
+        self.url_index = 0;
+        self.scroll = 0;
+        self.wrapped = None;
+    }
+
+    /// Styled, wrapped rows of the value for a text width.
+    fn wrapped_rows(&mut self, width: usize) -> &[Line<'static>] {
+        if self.wrapped.as_ref().is_none_or(|(w, _)| *w != width) {
+            let rows = self
+                .lines
+                .iter()
+                .flat_map(|line| styled_rows(line, self.format, width))
+                .collect();
+            self.wrapped = Some((width, rows));
+        }
+        self.wrapped.as_ref().map_or(&[], |(_, rows)| rows)
+    }
+
+    /// Render the cell viewer centered on screen.
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let popup_area = Self::centered_rect(80, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        // Borders (2) + blank line and info (2) + help (1)
+        let visible_rows = popup_area.height.saturating_sub(5).max(1) as usize;
+        let text_width = popup_area.width.saturating_sub(4).max(1) as usize;
+        self.page_height = visible_rows;
+
+        let total_rows = self.wrapped_rows(text_width).len();
+        self.scroll = self.scroll.min(total_rows.saturating_sub(visible_rows));
+        let scroll = self.scroll;
+        let end = (scroll + visible_rows).min(total_rows);
+
+        let mut lines: Vec<Line> = self.wrapped_rows(text_width)[scroll..end].to_vec();
+        lines.resize(visible_rows, Line::from(""));
+        lines.push(Line::from(""));
+
+        let mut info = format!(
+            "Rows {}-{}/{} · {} chars",
+            (scroll + 1).min(total_rows),
+            end,
+            total_rows,
+            self.value().chars().count()
+        );
+        if !self.urls.is_empty() {
+            info.push_str(&format!(" · {} URL(s)", self.urls.len()));
+        }
+        lines.push(Line::from(Span::styled(info, text_dim())));
+        lines.push(Line::from(vec![
+            Span::styled("[", text_dim()),
+            Span::styled("↑↓/jk", text_key()),
+            Span::styled(" Scroll] [", text_dim()),
+            Span::styled("←→/hl", text_key()),
+            Span::styled(" Field] [", text_dim()),
+            Span::styled("c", text_key()),
+            Span::styled(" Copy] [", text_dim()),
+            Span::styled("u", text_key()),
+            Span::styled(" Copy URL] [", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(" Close]", text_dim()),
+        ]));
+
+        let column = self.columns.get(self.index).map_or("", |c| c.as_str());
+        let title = format!(
+            " {} ({}/{}) · {} ",
+            column,
+            self.index + 1,
+            self.columns.len(),
+            self.format.label()
+        );
+        let viewer = Paragraph::new(lines).block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(text_accent())
+                .padding(ratatui::widgets::Padding::horizontal(1)),
+        );
+        frame.render_widget(viewer, popup_area);
+    }
+
+    /// Calculate a centered rect with percentage-based dimensions.
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
+
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
+    }
+}
+
+// === Formatting ===
+
+/// Detect the format of a value and pretty-print it.
+pub fn format_value(value: &str) -> (CellFormat, String) {
+    let trimmed = value.trim();
+    if let Some(json) = pretty_json(trimmed) {
+        return (CellFormat::Json, json);
+    }
+    if is_xml(trimmed) {
+        return (CellFormat::Xml, pretty_xml(trimmed));
+    }
+    (CellFormat::Text, value.to_string())
+}
+
+/// Re-indent a JSON object or array, keeping key order and number formatting.
+/// Returns None if the value is not a JSON object or array.
+fn pretty_json(value: &str) -> Option<String> {
+    if !(value.starts_with('{') || value.starts_with('[')) {
+        return None;
+    }
+    serde_json::from_str::<serde_json::Value>(value).ok()?;
+
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = value.chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                // Keep empty containers on one line
+                if let Some(close) = chars.next_if(|&n| n == '}' || n == ']') {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// Whether a value looks like an XML document or fragment.
+fn is_xml(value: &str) -> bool {
+    value.starts_with('<')
+        && value.ends_with('>')
+        && (value.contains("</") || value.contains("/>") || value.starts_with("<?xml"))
+}
+
+/// Indent XML by element depth. Elements holding only text stay on one line.
+fn pretty_xml(value: &str) -> String {
+    // Split into tags and text between them
+    let mut tokens: Vec<&str> = Vec::new();
+    let mut rest = value;
+    while !rest.is_empty() {
+        let end = if rest.starts_with('<') {
+            rest.find('>').map_or(rest.len(), |i| i + 1)
+        } else {
+            rest.find('<').unwrap_or(rest.len())
+        };
+        let token = rest[..end].trim();
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+        rest = &rest[end..];
+    }
+
+    let is_open = |t: &str| {
+        t.starts_with('<')
+            && !t.starts_with("</")
+            && !t.starts_with("<?")
+            && !t.starts_with("<!")
+            && !t.ends_with("/>")
+    };
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let indent = "  ".repeat(depth);
+        if token.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            lines.push(format!("{}{}", "  ".repeat(depth), token));
+        } else if is_open(token)
+            && let (Some(text), Some(close)) = (tokens.get(i + 1), tokens.get(i + 2))
+            && !text.starts_with('<')
+            && close.starts_with("</")
+        {
+            lines.push(format!("{}{}{}{}", indent, token, text, close));
+            i += 2;
+        } else {
+            lines.push(format!("{}{}", indent, token));
+            if is_open(token) {
+                depth += 1;
+            }
+        }
+        i += 1;
+    }
+    lines.join("\n")
+}
+
+// === Styling ===
+
+/// Per-char syntax styles of one formatted line.
+fn syntax_styles(line: &str, format: CellFormat) -> Vec<Style> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut styles = vec![text_normal(); chars.len()];
+    match format {
+        CellFormat::Text => {}
+        CellFormat::Json => {
+            let mut i = 0;
+            while i < chars.len() {
+                let start = i;
+                let style = match chars[i] {
+                    '"' => {
+                        i += 1;
+                        while i < chars.len() && chars[i] != '"' {
+                            i += if chars[i] == '\\' { 2 } else { 1 };
+                        }
+                        i += 1;
+                        let is_key = chars[i.min(chars.len())..]
+                            .iter()
+                            .find(|c| !c.is_whitespace())
+                            == Some(&':');
+                        if is_key { text_key() } else { text_success() }
+                    }
+                    c if c == '-' || c.is_ascii_digit() => {
+                        while i < chars.len()
+                            && (chars[i].is_ascii_digit() || "+-.eE".contains(chars[i]))
+                        {
+                            i += 1;
+                        }
+                        text_accent()
+                    }
+                    c if c.is_ascii_alphabetic() => {
+                        while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                            i += 1;
+                        }
+                        Style::default().fg(theme().secondary)
+                    }
+                    _ => {
+                        i += 1;
+                        text_dim()
+                    }
+                };
+                let end = i.min(chars.len());
+                styles[start..end].fill(style);
+                i = end;
+            }
+        }
+        CellFormat::Xml => {
+            let mut quote: Option<char> = None;
+            let mut in_tag = false;
+            for (i, &c) in chars.iter().enumerate() {
+                if let Some(q) = quote {
+                    styles[i] = text_success();
+                    if c == q {
+                        quote = None;
+                    }
+                } else if in_tag {
+                    styles[i] = text_key();
+                    match c {
+                        '"' | '\'' => {
+                            quote = Some(c);
+                            styles[i] = text_success();
+                        }
+                        '>' => in_tag = false,
+                        _ => {}
+                    }
+                } else if c == '<' {
+                    in_tag = true;
+                    styles[i] = text_key();
+                }
+            }
+        }
+    }
+    styles
+}
+
+/// Char ranges of `http://` and `https://` URLs in a line.
+fn find_urls(line: &str) -> Vec<Range<usize>> {
+    let chars: Vec<char> = line.chars().collect();
+    let starts_with = |i: usize, prefix: &str| {
+        prefix
+            .chars()
+            .enumerate()
+            .all(|(k, p)| chars.get(i + k) == Some(&p))
+    };
+    let mut urls = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if !(starts_with(i, "http://") || starts_with(i, "https://")) {
+            i += 1;
+            continue;
+        }
+        let mut end = i;
+        while end < chars.len() && !chars[end].is_whitespace() && !"\"'<>`".contains(chars[end]) {
+            end += 1;
+        }
+        // Trailing punctuation belongs to the surrounding text
+        while end > i && ".,;:!?)]}".contains(chars[end - 1]) {
+            end -= 1;
+        }
+        urls.push(i..end);
+        i = end.max(i + 1);
+    }
+    urls
+}
+
+/// Word-wrap a line to `width` display columns.
+/// Returns char ranges of the rows; breaks after spaces where possible.
+fn wrap(line: &str, width: usize) -> Vec<Range<usize>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut rows = Vec::new();
+    let mut start = 0;
+    let mut row_width = 0;
+    let mut last_space: Option<usize> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let char_width = chars[i].width().unwrap_or(0);
+        if row_width + char_width > width && i > start {
+            let end = match last_space {
+                Some(space) if space >= start => space + 1,
+                _ => i,
+            };
+            rows.push(start..end);
+            start = end;
+            i = end;
+            row_width = 0;
+            last_space = None;
+            continue;
+        }
+        if chars[i] == ' ' {
+            last_space = Some(i);
+        }
+        row_width += char_width;
+        i += 1;
+    }
+    rows.push(start..chars.len());
+    rows
+}
+
+/// Styled, wrapped rows of one formatted line.
+fn styled_rows(line: &str, format: CellFormat, width: usize) -> Vec<Line<'static>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut styles = syntax_styles(line, format);
+    for url in find_urls(line) {
+        for style in &mut styles[url] {
+            *style = text_accent().add_modifier(Modifier::UNDERLINED);
+        }
+    }
+
+    wrap(line, width)
+        .into_iter()
+        .map(|row| {
+            // Group consecutive chars of the same style into spans
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut text = String::new();
+            let mut current: Option<Style> = None;
+            for i in row {
+                if current.is_some_and(|s| s != styles[i]) {
+                    spans.push(Span::styled(std::mem::take(&mut text), current.unwrap()));
+                }
+                current = Some(styles[i]);
+                text.push(chars[i]);
+            }
+            if let Some(style) = current {
+                spans.push(Span::styled(text, style));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value_pretty_prints_json_and_xml() {
+        let (format, text) = format_value(r#"{"b":1,"a":[true, {}],"s":"x,{y}"}"#);
+        assert_eq!(format, CellFormat::Json);
+        // Key order and string contents are kept
+        assert_eq!(
+            text,
+            "{\n  \"b\": 1,\n  \"a\": [\n    true,\n    {}\n  ],\n  \"s\": \"x,{y}\"\n}"
+        );
+
+        let (format, text) = format_value("<a><b>1</b><c/></a>");
+        assert_eq!(format, CellFormat::Xml);
+        assert_eq!(text, "<a>\n  <b>1</b>\n  <c/>\n</a>");
+
+        assert_eq!(format_value("42").0, CellFormat::Text);
+        assert_eq!(format_value("{not json").0, CellFormat::Text);
+    }
+
+    #[test]
+    fn test_wrap_and_urls() {
+        let rows: Vec<Range<usize>> = wrap("hello world foo", 11);
+        assert_eq!(rows, vec![0..6, 6..15]);
+        // Words longer than the width are split
+        assert_eq!(wrap("abcdef", 4), vec![0..4, 4..6]);
+        assert_eq!(wrap("", 4), vec![0..0]);
+
+        let line = "see https://example.com/a?b=1), or http://x.io.";
+        let urls: Vec<String> = find_urls(line)
+            .into_iter()
+            .map(|r| line.chars().skip(r.start).take(r.len()).collect())
+            .collect();
+        assert_eq!(urls, vec!["https://example.com/a?b=1", "http://x.io"]);
+
+        let mut viewer = CellViewer::new(vec!["Link".into()], vec![line.into()], 0);
+        assert_eq!(viewer.next_url().map(|u| u.0), Some(1));
+        assert_eq!(
+            viewer.next_url().map(|u| u.2.to_string()).unwrap(),
+            "http://x.io"
+        );
+        assert_eq!(viewer.next_url().map(|u| u.0), Some(1));
+    }
+}
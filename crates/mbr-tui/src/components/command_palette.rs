@@ -0,0 +1,304 @@
+//! Command palette component (Ctrl-P).
+//!
+//! Lists every available action with fuzzy filtering. Selecting an entry
+//! yields its `AppAction` for the App to dispatch.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::action::AppAction;
+use crate::components::styles::{popup_style, text_accent, text_dim, text_key, text_normal};
+use crate::components::theme::theme;
+
+/// A single palette entry.
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    /// Text matched against the query and shown in the list
+    pub label: String,
+    /// Equivalent key or `:` command shown on the right
+    pub hint: String,
+    /// Action dispatched when the entry is chosen
+    pub action: AppAction,
+}
+
+impl PaletteEntry {
+    pub fn new(label: impl Into<String>, hint: impl Into<String>, action: AppAction) -> Self {
+        Self {
+            label: label.into(),
+            hint: hint.into(),
+            action,
+        }
+    }
+}
+
+/// Command palette state.
+pub struct CommandPalette {
+    query: String,
+    entries: Vec<PaletteEntry>,
+    /// Indices into `entries` matching the query, best match first
+    matches: Vec<usize>,
+    /// Selected position within `matches`
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Create a palette over the given entries (all shown until a query is typed).
+    pub fn new(entries: Vec<PaletteEntry>) -> Self {
+        let matches = (0..entries.len()).collect();
+        Self {
+            query: String::new(),
+            entries,
+            matches,
+            selected: 0,
+        }
+    }
+
+    /// Append a character to the query.
+    pub fn input_char(&mut self, c: char) {
+        self.query.push(c);
+        self.update_matches();
+    }
+
+    /// Remove the last character from the query.
+    pub fn delete_char(&mut self) {
+        self.query.pop();
+        self.update_matches();
+    }
+
+    /// Move selection up.
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Move selection down.
+    pub fn select_down(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Action of the currently selected entry.
+    pub fn selected_action(&self) -> Option<AppAction> {
+        self.matches
+            .get(self.selected)
+            .and_then(|&i| self.entries.get(i))
+            .map(|e| e.action.clone())
+    }
+
+    fn update_matches(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| fuzzy_score(&self.query, &e.label).map(|s| (s, i)))
+            .collect();
+        // Stable sort keeps original order among equal scores
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+
+    /// Render the palette as a centered overlay.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = Self::centered_rect(60, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let mut lines: Vec<Line> = Vec::new();
+        lines.push(Line::from(vec![
+            Span::styled(" > ", text_key()),
+            Span::styled(
+                format!("{}_", self.query),
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(Line::from(Span::styled(
+            " ──────────────────────────────────",
+            text_dim(),
+        )));
+
+        // Borders (2) + input (1) + separator (1) + blank (1) + footer (1)
+        let list_height = popup_area.height.saturating_sub(6).max(1) as usize;
+        let window_start = self.selected.saturating_sub(list_height.saturating_sub(1));
+
+        if self.matches.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No matching commands",
+                text_dim(),
+            )));
+        }
+
+        for (pos, &idx) in self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(window_start)
+            .take(list_height)
+        {
+            let entry = &self.entries[idx];
+            let is_selected = pos == self.selected;
+            let style = if is_selected {
+                theme()
+                    .highlight(theme().accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                text_normal()
+            };
+            let prefix = if is_selected { " ► " } else { "   " };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(entry.label.clone(), style),
+                Span::styled(format!("  {}", entry.hint), text_dim()),
+            ]));
+        }
+
+        // Pad so the footer stays at the bottom
+        let used = lines.len();
+        let target = popup_area.height.saturating_sub(3) as usize;
+        lines.extend((used..target).map(|_| Line::from("")));
+
+        lines.push(Line::from(vec![
+            Span::styled("  [", text_dim()),
+            Span::styled("↑↓", text_key()),
+            Span::styled(" Move] [", text_dim()),
+            Span::styled("Enter", text_key()),
+            Span::styled(" Run] [", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(" Close]", text_dim()),
+        ]));
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(
+                    " Command Palette ({}/{}) ",
+                    self.matches.len(),
+                    self.entries.len()
+                ))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(text_accent())
+                .style(popup_style()),
+        );
+
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    /// Calculate centered rect with percentage-based dimensions.
+    fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+        let vertical = Layout::vertical([Constraint::Percentage(percent_y)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(percent_x)]).flex(Flex::Center);
+        let [area] = vertical.areas(area);
+        let [area] = horizontal.areas(area);
+        area
+    }
+}
+
+/// Score `candidate` against `query` as a case-insensitive subsequence match.
+///
+/// Returns None if not all query characters appear in order. Consecutive
+/// matches and matches at word starts score higher; an empty query matches
+/// everything with score 0.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    let mut qi = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for (ci, &c) in candidate.iter().enumerate() {
+        if qi == query.len() {
+            break;
+        }
+        if c != query[qi] {
+            continue;
+        }
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == ci) {
+            score += 5;
+        }
+        if ci == 0 || !candidate[ci - 1].is_alphanumeric() {
+            score += 8;
+        }
+        prev_match = Some(ci);
+        qi += 1;
+    }
+
+    if qi < query.len() {
+        return None;
+    }
+    // Prefer shorter candidates among equal matches
+    Some(score * 100 - candidate.len() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette() -> CommandPalette {
+        CommandPalette::new(vec![
+            PaletteEntry::new("Go to Questions", "1", AppAction::ShowHelp),
+            PaletteEntry::new("Go to Collections", "2", AppAction::Refresh),
+            PaletteEntry::new(
+                "Run: Revenue by Month",
+                ":run 7",
+                AppAction::ExecuteQuestion(7),
+            ),
+            PaletteEntry::new("Quit", "q", AppAction::Quit),
+        ])
+    }
+
+    /// Labels of the matching entries in display order.
+    fn matching_labels(p: &CommandPalette) -> Vec<&str> {
+        p.matches
+            .iter()
+            .map(|&i| p.entries[i].label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score_subsequence() {
+        assert!(fuzzy_score("gtc", "Go to Collections").is_some());
+        assert!(fuzzy_score("xyz", "Go to Collections").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts() {
+        let word_start = fuzzy_score("q", "Quit").unwrap();
+        let mid_word = fuzzy_score("q", "Run: Frequency").unwrap();
+        assert!(word_start > mid_word);
+    }
+
+    #[test]
+    fn test_palette_filtering_and_selection() {
+        let mut p = palette();
+        assert_eq!(matching_labels(&p).len(), 4);
+
+        for c in "revenue".chars() {
+            p.input_char(c);
+        }
+        assert_eq!(matching_labels(&p), vec!["Run: Revenue by Month"]);
+        assert_eq!(p.selected_action(), Some(AppAction::ExecuteQuestion(7)));
+
+        for _ in 0.."revenue".len() {
+            p.delete_char();
+        }
+        p.select_down();
+        p.select_down();
+        p.select_down();
+        p.select_down();
+        assert_eq!(p.selected_action(), Some(AppAction::Quit));
+        p.select_up();
+        assert_eq!(p.selected_action(), Some(AppAction::ExecuteQuestion(7)));
+    }
+}
@@ -0,0 +1,220 @@
+//! Aggregations over query results.
+//!
+//! Column summaries (count, sum, avg, min, max) for the results footer and
+//! group-by collapsing of rows into an aggregated result. Both work on row
+//! indices so that they follow the current search and filter.
+
+use std::collections::HashMap;
+
+pub use mbr_core::core::profile::format_number;
+
+use super::filter_expr::is_null;
+use super::types::{ColumnType, QueryResultData};
+
+/// Summary of a result column over a set of rows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnSummary {
+    /// Number of non-null values
+    pub count: usize,
+    /// Statistics for numeric columns
+    pub numeric: Option<NumericSummary>,
+}
+
+/// Statistics of a numeric column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericSummary {
+    pub sum: f64,
+    pub avg: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Summarize every column of `data` over the given rows.
+pub fn summarize(data: &QueryResultData, rows: &[usize]) -> Vec<ColumnSummary> {
+    (0..data.columns.len())
+        .map(|col| summarize_column(data, rows, col))
+        .collect()
+}
+
+/// Summarize one column over the given rows.
+fn summarize_column(data: &QueryResultData, rows: &[usize], col: usize) -> ColumnSummary {
+    let values: Vec<&str> = rows
+        .iter()
+        .filter_map(|&r| data.rows.get(r)?.get(col))
+        .map(|s| s.as_str())
+        .filter(|s| !is_null(s))
+        .collect();
+
+    let numeric = numeric_values(data.column_type(col), &values).and_then(|nums| {
+        let first = *nums.first()?;
+        let sum: f64 = nums.iter().sum();
+        let (min, max) = nums
+            .iter()
+            .fold((first, first), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        Some(NumericSummary {
+            sum,
+            avg: sum / nums.len() as f64,
+            min,
+            max,
+        })
+    });
+
+    ColumnSummary {
+        count: values.len(),
+        numeric,
+    }
+}
+
+/// Parse non-null values of a column as numbers.
+/// Number columns skip unparsable values; untyped (Text) columns count as
+/// numeric only when every value parses.
+fn numeric_values(column_type: ColumnType, values: &[&str]) -> Option<Vec<f64>> {
+    let parsed = values.iter().map(|v| v.trim().parse::<f64>().ok());
+    match column_type {
+        ColumnType::Number => Some(parsed.flatten().collect()),
+        ColumnType::Text if !values.is_empty() => parsed.collect(),
+        _ => None,
+    }
+}
+
+/// Collapse rows by the values of `group_columns`.
+///
+/// Returns the aggregated result and, per aggregated row, the source row
+/// indices of the group. Groups appear in order of first occurrence in
+/// `rows`. The aggregated result has the group columns, a `Count` column and
+/// `sum(...)` / `avg(...)` columns for the other numeric columns.
+pub fn group_rows(
+    data: &QueryResultData,
+    rows: &[usize],
+    group_columns: &[usize],
+) -> (QueryResultData, Vec<Vec<usize>>) {
+    let mut index: HashMap<Vec<&str>, usize> = HashMap::new();
+    let mut keys: Vec<Vec<&str>> = Vec::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for &r in rows {
+        let Some(row) = data.rows.get(r) else {
+            continue;
+        };
+        let key: Vec<&str> = group_columns
+            .iter()
+            .map(|&c| row.get(c).map(|s| s.as_str()).unwrap_or(""))
+            .collect();
+        let slot = *index.entry(key.clone()).or_insert_with(|| {
+            keys.push(key);
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[slot].push(r);
+    }
+
+    // Aggregate the numeric columns that are not grouped on
+    let measures: Vec<usize> = (0..data.columns.len())
+        .filter(|c| !group_columns.contains(c))
+        .filter(|&c| summarize_column(data, rows, c).numeric.is_some())
+        .collect();
+
+    let mut columns: Vec<String> = group_columns
+        .iter()
+        .map(|&c| data.columns.get(c).cloned().unwrap_or_default())
+        .collect();
+    let mut column_types: Vec<ColumnType> =
+        group_columns.iter().map(|&c| data.column_type(c)).collect();
+    columns.push("Count".to_string());
+    column_types.push(ColumnType::Number);
+    for &c in &measures {
+        columns.push(format!("sum({})", data.columns[c]));
+        columns.push(format!("avg({})", data.columns[c]));
+        column_types.extend([ColumnType::Number, ColumnType::Number]);
+    }
+
+    let agg_rows = keys
+        .iter()
+        .zip(&groups)
+        .map(|(key, members)| {
+            let mut row: Vec<String> = key.iter().map(|s| s.to_string()).collect();
+            row.push(members.len().to_string());
+            for &c in &measures {
+                match summarize_column(data, members, c).numeric {
+                    Some(stats) => {
+                        row.push(format_number(stats.sum));
+                        row.push(format_number(stats.avg));
+                    }
+                    None => row.extend(["—".to_string(), "—".to_string()]),
+                }
+            }
+            row
+        })
+        .collect();
+
+    let names: Vec<&str> = group_columns
+        .iter()
+        .filter_map(|&c| data.columns.get(c).map(|s| s.as_str()))
+        .collect();
+    let aggregated = QueryResultData {
+        question_id: data.question_id,
+        question_name: format!("{} · by {}", data.question_name, names.join(", ")),
+        columns,
+        rows: agg_rows,
+        column_types,
+        column_keys: Vec::new(),
+    };
+    (aggregated, groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sales() -> QueryResultData {
+        let row = |c: &str, r: &str, v: &str| vec![c.to_string(), r.to_string(), v.to_string()];
+        QueryResultData {
+            question_id: 7,
+            question_name: "Sales".to_string(),
+            columns: vec!["Country".into(), "Region".into(), "Revenue".into()],
+            rows: vec![
+                row("JP", "Kanto", "10"),
+                row("US", "West", "5.5"),
+                row("JP", "Kansai", "—"),
+                row("JP", "Kanto", "20"),
+            ],
+            column_types: vec![ColumnType::Text, ColumnType::Text, ColumnType::Number],
+            column_keys: vec![],
+        }
+    }
+
+    #[test]
+    fn test_summarize_numeric_and_text_columns() {
+        let data = sales();
+        let summary = summarize(&data, &[0, 1, 2, 3]);
+        assert_eq!(summary[0].count, 4);
+        assert_eq!(summary[0].numeric, None);
+        assert_eq!(summary[2].count, 3);
+        let stats = summary[2].numeric.unwrap();
+        assert_eq!(format_number(stats.sum), "35.5");
+        assert_eq!(format_number(stats.avg), "11.83");
+        assert_eq!((stats.min, stats.max), (5.5, 20.0));
+
+        // Only the filtered rows are summarized
+        let summary = summarize(&data, &[1]);
+        assert_eq!(summary[2].numeric.unwrap().sum, 5.5);
+    }
+
+    #[test]
+    fn test_group_rows() {
+        let data = sales();
+        let (agg, groups) = group_rows(&data, &[0, 1, 2, 3], &[0]);
+        assert_eq!(
+            agg.columns,
+            vec!["Country", "Count", "sum(Revenue)", "avg(Revenue)"]
+        );
+        assert_eq!(agg.rows[0], vec!["JP", "3", "30", "15"]);
+        assert_eq!(agg.rows[1], vec!["US", "1", "5.5", "5.5"]);
+        assert_eq!(groups, vec![vec![0, 2, 3], vec![1]]);
+
+        let (agg, groups) = group_rows(&data, &[0, 1, 2, 3], &[0, 1]);
+        assert_eq!(agg.rows.len(), 3);
+        assert_eq!(agg.rows[0][..3], ["JP", "Kanto", "2"]);
+        assert_eq!(groups[0], vec![0, 3]);
+        assert_eq!(agg.question_name, "Sales · by Country, Region");
+    }
+}
@@ -0,0 +1,384 @@
+//! Result buffers: several open query results.
+//!
+//! Every executed question gets a buffer; running it again replaces the
+//! buffer's result. One buffer is live: its result and view state are the
+//! panel's result fields. The others are parked with their view state (sort,
+//! filter, search, layout, grouping), or hold a result that finished in the
+//! background and has not been shown yet.
+
+use mbr_core::storage::tui_state::{ColumnLayout as SavedColumnLayout, ResultView};
+use ratatui::widgets::TableState;
+
+use super::ContentPanel;
+use super::columns::ColumnLayout;
+use super::group::{GroupState, ResultViewState};
+use super::types::{ContentView, QueryResultData};
+
+/// An open query result.
+#[derive(Debug)]
+pub struct ResultBuffer {
+    question_id: u32,
+    name: String,
+    rows: usize,
+    content: BufferContent,
+}
+
+#[derive(Debug)]
+enum BufferContent {
+    /// Shown in the panel
+    Live,
+    /// Not shown yet, with the settings saved for the question
+    Loaded(Box<LoadedResult>),
+    /// Shown before, with its view state
+    Parked(Box<ParkedResult>),
+}
+
+#[derive(Debug)]
+struct LoadedResult {
+    data: QueryResultData,
+    layout: Option<SavedColumnLayout>,
+    view: Option<ResultView>,
+}
+
+#[derive(Debug)]
+struct ParkedResult {
+    view: ResultViewState,
+    group: Option<GroupState>,
+}
+
+/// Summary of a buffer for the buffer list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferInfo {
+    pub name: String,
+    pub rows: usize,
+    /// Shown in the panel
+    pub live: bool,
+    /// Finished in the background and not shown yet
+    pub unseen: bool,
+}
+
+impl ContentPanel {
+    /// Number of open buffers.
+    pub fn buffer_count(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Index of the live buffer.
+    pub fn active_buffer(&self) -> Option<usize> {
+        self.active_buffer
+    }
+
+    /// Question of the live buffer.
+    pub fn active_buffer_question(&self) -> Option<u32> {
+        self.active_buffer
+            .and_then(|i| self.buffers.get(i))
+            .map(|b| b.question_id)
+    }
+
+    /// Buffers in list order.
+    pub fn buffer_infos(&self) -> Vec<BufferInfo> {
+        self.buffers
+            .iter()
+            .map(|b| BufferInfo {
+                name: b.name.clone(),
+                rows: b.rows,
+                live: matches!(b.content, BufferContent::Live),
+                unseen: matches!(b.content, BufferContent::Loaded(_)),
+            })
+            .collect()
+    }
+
+    /// Store a result in its question's buffer (a new one if the question
+    /// has none) and return the buffer index. The saved layout and view are
+    /// applied when the buffer is first shown. A result for the live buffer
+    /// replaces it in place; others are not shown.
+    pub fn store_result(
+        &mut self,
+        data: QueryResultData,
+        layout: Option<SavedColumnLayout>,
+        view: Option<ResultView>,
+    ) -> usize {
+        let question_id = data.question_id;
+        let name = data.question_name.clone();
+        let rows = data.rows.len();
+        let content = BufferContent::Loaded(Box::new(LoadedResult { data, layout, view }));
+
+        let Some(index) = self
+            .buffers
+            .iter()
+            .position(|b| b.question_id == question_id)
+        else {
+            self.buffers.push(ResultBuffer {
+                question_id,
+                name,
+                rows,
+                content,
+            });
+            return self.buffers.len() - 1;
+        };
+        let buffer = &mut self.buffers[index];
+        buffer.name = name;
+        buffer.rows = rows;
+        buffer.content = content;
+        if self.active_buffer == Some(index) {
+            self.discard_live();
+            self.show_buffer(index);
+        }
+        index
+    }
+
+    /// Show a buffer in the result view, parking the live one.
+    /// Returns false if there is no such buffer.
+    pub fn show_buffer(&mut self, index: usize) -> bool {
+        if index >= self.buffers.len() {
+            return false;
+        }
+        if self.active_buffer != Some(index) {
+            self.park_live();
+            let content = std::mem::replace(&mut self.buffers[index].content, BufferContent::Live);
+            // show_result_data keeps the grouping of the result it replaces
+            self.group = None;
+            match content {
+                BufferContent::Loaded(loaded) => {
+                    self.show_result_data(loaded.data);
+                    if let Some(ref layout) = loaded.layout {
+                        self.apply_saved_column_layout(layout);
+                    }
+                    if let Some(ref view) = loaded.view {
+                        self.apply_saved_result_view(view);
+                    }
+                }
+                BufferContent::Parked(parked) => {
+                    self.restore_view_state(parked.view);
+                    self.group = parked.group;
+                }
+                BufferContent::Live => {}
+            }
+            self.active_buffer = Some(index);
+            if self.split == Some(index) {
+                self.split = None;
+            }
+        }
+        if self.view != ContentView::QueryResult {
+            self.push_view(ContentView::QueryResult);
+        }
+        true
+    }
+
+    /// Show the next (or previous) buffer. Outside the result view this
+    /// returns to the buffer shown last. Returns the shown buffer index.
+    pub fn cycle_buffer(&mut self, forward: bool) -> Option<usize> {
+        let count = self.buffers.len();
+        if count == 0 {
+            return None;
+        }
+        let index = match self.active_buffer {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None => self
+                .previous_buffer
+                .filter(|&i| i < count)
+                .unwrap_or(count - 1),
+        };
+        self.show_buffer(index).then_some(index)
+    }
+
+    /// Close a buffer. Closing the live buffer shows a neighbouring one, or
+    /// leaves the result view when it was the last.
+    pub fn close_buffer(&mut self, index: usize) -> bool {
+        if index >= self.buffers.len() {
+            return false;
+        }
+        let was_live = self.active_buffer == Some(index);
+        if was_live {
+            self.discard_live();
+        }
+        self.buffers.remove(index);
+        let shift = |slot: Option<usize>| match slot {
+            Some(i) if i == index => None,
+            Some(i) if i > index => Some(i - 1),
+            other => other,
+        };
+        self.active_buffer = shift(self.active_buffer);
+        self.previous_buffer = shift(self.previous_buffer);
+        self.split = shift(self.split);
+
+        if was_live {
+            if self.buffers.is_empty() {
+                self.back_to_questions();
+            } else {
+                self.show_buffer(index.min(self.buffers.len() - 1));
+            }
+        }
+        true
+    }
+
+    /// Park the live buffer with its view state.
+    pub(super) fn park_live(&mut self) {
+        let Some(index) = self.active_buffer.take() else {
+            return;
+        };
+        self.unwind_links();
+        let group = self.group.take();
+        if let Some(view) = self.take_view_state() {
+            self.buffers[index].content =
+                BufferContent::Parked(Box::new(ParkedResult { view, group }));
+            self.previous_buffer = Some(index);
+        }
+        self.clear_result_state();
+    }
+
+    /// Drop the live result without parking it.
+    fn discard_live(&mut self) {
+        self.unwind_links();
+        self.active_buffer = None;
+        self.query_result = None;
+        self.clear_result_state();
+    }
+
+    /// Reset the result fields after the result was taken away.
+    pub(super) fn clear_result_state(&mut self) {
+        self.sort_indices = None;
+        self.filter_indices = None;
+        self.result_table_state = TableState::default();
+        self.result_page = 0;
+        self.scroll_x = 0;
+        self.reset_sort_filter_state();
+        self.column_layout = ColumnLayout::default();
+        self.column_manager_active = false;
+        self.split = None;
+    }
+
+    /// Pop views until one that can be shown; result views whose result was
+    /// parked or dropped are skipped. Returns false if the stack ran out.
+    pub(super) fn pop_to_available_view(&mut self) -> bool {
+        loop {
+            if self.pop_view().is_none() {
+                return false;
+            }
+            if !(self.is_result_view() && self.query_result.is_none()) {
+                return true;
+            }
+        }
+    }
+
+    // === Split view ===
+
+    /// Buffer compared with the live result, if the split view is shown.
+    pub fn split_buffer(&self) -> Option<usize> {
+        self.split
+    }
+
+    /// Buffer to compare with when none is named: the one shown before the
+    /// live buffer, else the first other buffer.
+    pub fn alternate_buffer(&self) -> Option<usize> {
+        let active = self.active_buffer?;
+        self.previous_buffer
+            .filter(|&i| i != active && i < self.buffers.len())
+            .or_else(|| (0..self.buffers.len()).find(|&i| i != active))
+    }
+
+    /// Compare the live result with another buffer, or stop comparing (None).
+    /// Returns false if the buffer cannot be compared.
+    pub fn set_split(&mut self, index: Option<usize>) -> bool {
+        match index {
+            None => {
+                self.split = None;
+                true
+            }
+            Some(i) if i < self.buffers.len() && self.active_buffer.is_some_and(|a| a != i) => {
+                self.split = Some(i);
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    /// Result of a buffer that is not live.
+    pub(super) fn buffer_data(&self, index: usize) -> Option<&QueryResultData> {
+        match self.buffers.get(index)?.content {
+            BufferContent::Loaded(ref loaded) => Some(&loaded.data),
+            BufferContent::Parked(ref parked) => Some(parked.view.data()),
+            BufferContent::Live => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::ColumnType;
+    use crate::components::content::filter_expr::{FilterCondition, FilterJoin, FilterOp};
+
+    fn data(question_id: u32, rows: &[&str]) -> QueryResultData {
+        QueryResultData {
+            question_id,
+            question_name: format!("Q{}", question_id),
+            columns: vec!["ID".to_string()],
+            rows: rows.iter().map(|r| vec![r.to_string()]).collect(),
+            column_types: vec![ColumnType::Number],
+            column_keys: vec![],
+        }
+    }
+
+    fn shown_rows(panel: &ContentPanel) -> usize {
+        panel.query_result.as_ref().map_or(0, |r| r.rows.len())
+    }
+
+    #[test]
+    fn test_buffers_keep_view_state() {
+        let mut panel = ContentPanel::new();
+        panel.set_view(ContentView::Questions);
+        let first = panel.store_result(data(1, &["1", "2", "3"]), None, None);
+        assert!(panel.show_buffer(first));
+        panel.set_filters(vec![
+            FilterCondition::new(FilterJoin::And, 0, FilterOp::Ge, "2").unwrap(),
+        ]);
+
+        // A background result does not replace the live one
+        let second = panel.store_result(data(2, &["9"]), None, None);
+        assert_eq!(panel.active_buffer(), Some(first));
+        assert!(panel.buffer_infos()[second].unseen);
+
+        assert_eq!(panel.cycle_buffer(true), Some(second));
+        assert_eq!(shown_rows(&panel), 1);
+        assert_eq!(panel.alternate_buffer(), Some(first));
+
+        // The parked buffer comes back with its filter
+        assert!(panel.show_buffer(first));
+        assert_eq!(panel.filter_indices, Some(vec![1, 2]));
+        assert_eq!(panel.navigation_depth(), 1);
+
+        // Leaving the result view parks the buffer
+        panel.back_to_questions();
+        assert_eq!(panel.current_view(), ContentView::Questions);
+        assert_eq!(panel.cycle_buffer(true), Some(first));
+        assert_eq!(panel.filters().len(), 1);
+    }
+
+    #[test]
+    fn test_rerun_and_close_buffers() {
+        let mut panel = ContentPanel::new();
+        panel.set_view(ContentView::Questions);
+        let first = panel.store_result(data(1, &["1"]), None, None);
+        panel.show_buffer(first);
+        let second = panel.store_result(data(2, &["2"]), None, None);
+
+        // Re-running the live question refreshes it in place
+        assert_eq!(panel.store_result(data(1, &["1", "2"]), None, None), first);
+        assert_eq!(panel.active_buffer(), Some(first));
+        assert_eq!(shown_rows(&panel), 2);
+
+        assert!(panel.set_split(Some(second)));
+        assert!(!panel.set_split(Some(first)));
+
+        // Closing the live buffer shows the remaining one
+        assert!(panel.close_buffer(first));
+        assert_eq!(panel.buffer_count(), 1);
+        assert_eq!(panel.active_buffer(), Some(0));
+        assert_eq!(panel.split_buffer(), None);
+        assert!(panel.close_buffer(0));
+        assert_eq!(panel.current_view(), ContentView::Questions);
+        assert!(panel.query_result.is_none());
+    }
+}
@@ -0,0 +1,555 @@
+//! Collection tree state for the Collections view.
+//!
+//! Nodes are stored in a flat arena with parent links. Children of a
+//! collection are fetched lazily from `/api/collection/:id/items` the first
+//! time the node is expanded. Personal collections are grouped under a
+//! separate top-level node so they don't clutter "Our analytics".
+
+use mbr_core::api::models::{CollectionEntry, CollectionEntryKind, CollectionItem};
+
+use crate::service::LoadState;
+
+/// Label of the root collection node.
+pub const ROOT_COLLECTION_NAME: &str = "Our analytics";
+
+/// Label of the personal collections group node.
+pub const PERSONAL_GROUP_NAME: &str = "Personal collections";
+
+/// Kind of a node in the collection tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeNodeKind {
+    /// Synthetic grouping node (e.g., personal collections)
+    Group,
+    Collection,
+    Dashboard,
+    Model,
+    Question,
+}
+
+impl TreeNodeKind {
+    /// Short label shown in the Type column.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TreeNodeKind::Group => "Group",
+            TreeNodeKind::Collection => "Collection",
+            TreeNodeKind::Dashboard => "Dashboard",
+            TreeNodeKind::Model => "Model",
+            TreeNodeKind::Question => "Question",
+        }
+    }
+
+    /// Whether nodes of this kind can have children.
+    pub fn is_container(&self) -> bool {
+        matches!(self, TreeNodeKind::Group | TreeNodeKind::Collection)
+    }
+
+    /// Whether nodes of this kind can be executed as a query.
+    pub fn is_runnable(&self) -> bool {
+        matches!(self, TreeNodeKind::Model | TreeNodeKind::Question)
+    }
+}
+
+/// A single node in the collection tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    pub kind: TreeNodeKind,
+    /// Metabase ID (None for the root collection and synthetic groups)
+    pub id: Option<u32>,
+    pub name: String,
+    pub description: Option<String>,
+    /// Indentation level (top-level nodes are 0)
+    pub depth: usize,
+    /// Arena index of the parent node
+    pub parent: Option<usize>,
+    /// Arena indices of children (Idle = not fetched yet)
+    pub children: LoadState<Vec<usize>>,
+    pub expanded: bool,
+}
+
+/// Selected tree node summary handed to the App for Enter handling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeSelection {
+    pub kind: TreeNodeKind,
+    pub id: Option<u32>,
+    pub name: String,
+}
+
+/// Collection tree with lazily loaded children.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionTree {
+    nodes: Vec<TreeNode>,
+    roots: Vec<usize>,
+}
+
+impl CollectionTree {
+    /// Build the top level of the tree from the `/api/collection` list.
+    ///
+    /// The root collection starts expanded with its children not yet fetched;
+    /// top-level personal collections are placed under a collapsed group.
+    pub fn from_collections(collections: &[CollectionItem]) -> Self {
+        let mut tree = Self::default();
+
+        let root = tree.push_node(TreeNode {
+            kind: TreeNodeKind::Collection,
+            id: None,
+            name: ROOT_COLLECTION_NAME.to_string(),
+            description: None,
+            depth: 0,
+            parent: None,
+            children: LoadState::Idle,
+            expanded: true,
+        });
+        tree.roots.push(root);
+
+        let personal: Vec<&CollectionItem> = collections
+            .iter()
+            .filter(|c| c.personal_owner_id.is_some() && c.id.is_some())
+            .collect();
+
+        if !personal.is_empty() {
+            let group = tree.push_node(TreeNode {
+                kind: TreeNodeKind::Group,
+                id: None,
+                name: PERSONAL_GROUP_NAME.to_string(),
+                description: None,
+                depth: 0,
+                parent: None,
+                children: LoadState::Loaded(Vec::new()),
+                expanded: false,
+            });
+            tree.roots.push(group);
+
+            let children: Vec<usize> = personal
+                .into_iter()
+                .map(|c| {
+                    tree.push_node(TreeNode {
+                        kind: TreeNodeKind::Collection,
+                        id: c.id,
+                        name: c.name.clone(),
+                        description: c.description.clone(),
+                        depth: 1,
+                        parent: Some(group),
+                        children: LoadState::Idle,
+                        expanded: false,
+                    })
+                })
+                .collect();
+            tree.nodes[group].children = LoadState::Loaded(children);
+        }
+
+        tree
+    }
+
+    fn push_node(&mut self, node: TreeNode) -> usize {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    /// Get a node by arena index.
+    pub fn node(&self, index: usize) -> Option<&TreeNode> {
+        self.nodes.get(index)
+    }
+
+    /// Whether the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Arena indices of visible nodes in display (depth-first) order.
+    pub fn visible(&self) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(idx) = stack.pop() {
+            out.push(idx);
+            let node = &self.nodes[idx];
+            if node.expanded
+                && let LoadState::Loaded(children) = &node.children
+            {
+                stack.extend(children.iter().rev());
+            }
+        }
+        out
+    }
+
+    /// Number of visible nodes.
+    pub fn visible_len(&self) -> usize {
+        self.visible().len()
+    }
+
+    /// Kinds and IDs from the top-level node down to the given node, which
+    /// identify it across re-fetches (arena indices do not).
+    pub fn node_key(&self, index: usize) -> Vec<(TreeNodeKind, Option<u32>)> {
+        let mut key = Vec::new();
+        let mut current = Some(index);
+        while let Some(node) = current.and_then(|idx| self.nodes.get(idx)) {
+            key.push((node.kind, node.id));
+            current = node.parent;
+        }
+        key.reverse();
+        key
+    }
+
+    /// Arena index of the node with the given key, if it is still loaded.
+    pub fn find_key(&self, key: &[(TreeNodeKind, Option<u32>)]) -> Option<usize> {
+        let matches = |idx: &usize, (kind, id): &(TreeNodeKind, Option<u32>)| {
+            self.nodes[*idx].kind == *kind && self.nodes[*idx].id == *id
+        };
+        let (first, rest) = key.split_first()?;
+        let mut current = *self.roots.iter().find(|idx| matches(idx, first))?;
+        for step in rest {
+            let LoadState::Loaded(children) = &self.nodes[current].children else {
+                return None;
+            };
+            current = *children.iter().find(|idx| matches(idx, step))?;
+        }
+        Some(current)
+    }
+
+    /// Arena index of the node at a visible row.
+    pub fn visible_index(&self, row: usize) -> Option<usize> {
+        self.visible().get(row).copied()
+    }
+
+    /// Visible row of the node with the given arena index.
+    pub fn row_of(&self, index: usize) -> Option<usize> {
+        self.visible().iter().position(|&i| i == index)
+    }
+
+    /// Expand a container node.
+    /// Returns the collection to fetch when its children have not been loaded yet
+    /// (`Some(None)` means the root collection).
+    pub fn expand(&mut self, index: usize) -> Option<Option<u32>> {
+        let node = self.nodes.get_mut(index)?;
+        if !node.kind.is_container() {
+            return None;
+        }
+        node.expanded = true;
+        if node.kind == TreeNodeKind::Collection
+            && matches!(node.children, LoadState::Idle | LoadState::Error(_))
+        {
+            node.children = LoadState::Loading;
+            return Some(node.id);
+        }
+        None
+    }
+
+    /// Collapse a container node. Returns true if the node was expanded.
+    pub fn collapse(&mut self, index: usize) -> bool {
+        match self.nodes.get_mut(index) {
+            Some(node) if node.expanded => {
+                node.expanded = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark the children of a collection as loading (used for the initial root fetch).
+    pub fn mark_loading(&mut self, collection_id: Option<u32>) {
+        for idx in self.clear_children(collection_id) {
+            self.nodes[idx].children = LoadState::Loading;
+        }
+    }
+
+    /// Attach fetched items as children of the matching collection node(s).
+    /// Personal collections are skipped at the root since they have their own group.
+    pub fn set_items(&mut self, collection_id: Option<u32>, entries: &[CollectionEntry]) {
+        for parent in self.clear_children(collection_id) {
+            let depth = self.nodes[parent].depth + 1;
+            let children: Vec<usize> = entries
+                .iter()
+                .filter(|e| !(collection_id.is_none() && e.personal_owner_id.is_some()))
+                .filter_map(|e| {
+                    let kind = match e.kind() {
+                        CollectionEntryKind::Collection => TreeNodeKind::Collection,
+                        CollectionEntryKind::Dashboard => TreeNodeKind::Dashboard,
+                        CollectionEntryKind::Model => TreeNodeKind::Model,
+                        CollectionEntryKind::Question => TreeNodeKind::Question,
+                        CollectionEntryKind::Other => return None,
+                    };
+                    let children = if kind == TreeNodeKind::Collection {
+                        LoadState::Idle
+                    } else {
+                        LoadState::Loaded(Vec::new())
+                    };
+                    Some(self.push_node(TreeNode {
+                        kind,
+                        id: Some(e.id),
+                        name: e.name.clone(),
+                        description: e.description.clone(),
+                        depth,
+                        parent: Some(parent),
+                        children,
+                        expanded: false,
+                    }))
+                })
+                .collect();
+            self.nodes[parent].children = LoadState::Loaded(children);
+        }
+    }
+
+    /// Record a failed fetch on the matching collection node(s).
+    pub fn set_error(&mut self, collection_id: Option<u32>, error: String) {
+        for idx in self.clear_children(collection_id) {
+            self.nodes[idx].children = LoadState::Error(error.clone());
+        }
+    }
+
+    /// Remove the loaded subtrees of the matching collection node(s) from the
+    /// arena, so that a re-fetch leaves no unreachable nodes behind, and
+    /// return the (renumbered) indices of those nodes.
+    fn clear_children(&mut self, collection_id: Option<u32>) -> Vec<usize> {
+        let mut removed = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = Vec::new();
+        for parent in self.find_collections(collection_id) {
+            if let LoadState::Loaded(children) = &self.nodes[parent].children {
+                stack.extend(children);
+            }
+        }
+        while let Some(idx) = stack.pop() {
+            removed[idx] = true;
+            if let LoadState::Loaded(children) = &self.nodes[idx].children {
+                stack.extend(children);
+            }
+        }
+
+        if removed.contains(&true) {
+            let mut renumbered = Vec::with_capacity(removed.len());
+            let mut next = 0;
+            for &gone in &removed {
+                renumbered.push(next);
+                if !gone {
+                    next += 1;
+                }
+            }
+            let mut idx = 0;
+            self.nodes.retain(|_| {
+                idx += 1;
+                !removed[idx - 1]
+            });
+            for node in &mut self.nodes {
+                node.parent = node.parent.map(|p| renumbered[p]);
+                if let LoadState::Loaded(children) = &mut node.children {
+                    // Only the cleared parents lose children; callers set their new state
+                    children.retain(|&c| !removed[c]);
+                    children.iter_mut().for_each(|c| *c = renumbered[*c]);
+                }
+            }
+            self.roots.iter_mut().for_each(|r| *r = renumbered[*r]);
+        }
+        self.find_collections(collection_id)
+    }
+
+    fn find_collections(&self, collection_id: Option<u32>) -> Vec<usize> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.kind == TreeNodeKind::Collection && n.id == collection_id)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Names from the top-level node down to the given node.
+    pub fn path(&self, index: usize) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut current = Some(index);
+        while let Some(idx) = current {
+            let Some(node) = self.nodes.get(idx) else {
+                break;
+            };
+            path.push(node.name.clone());
+            current = node.parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Find the name of a loaded question or model by its card ID.
+    pub fn find_card_name(&self, card_id: u32) -> Option<String> {
+        self.nodes
+            .iter()
+            .find(|n| n.kind.is_runnable() && n.id == Some(card_id))
+            .map(|n| n.name.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection(id: u32, name: &str, personal_owner_id: Option<u32>) -> CollectionItem {
+        CollectionItem {
+            id: Some(id),
+            name: name.to_string(),
+            description: None,
+            location: Some("/".to_string()),
+            personal_owner_id,
+            archived: false,
+        }
+    }
+
+    fn entry(id: u32, name: &str, model: &str) -> CollectionEntry {
+        CollectionEntry {
+            id,
+            name: name.to_string(),
+            description: None,
+            model: model.to_string(),
+            personal_owner_id: None,
+        }
+    }
+
+    #[test]
+    fn test_from_collections_groups_personal() {
+        let tree = CollectionTree::from_collections(&[
+            collection(1, "Sales", None),
+            collection(2, "Alice's Personal Collection", Some(7)),
+        ]);
+
+        // Root is expanded but has no children yet; personal group is collapsed
+        let visible = tree.visible();
+        assert_eq!(visible.len(), 2);
+        assert_eq!(tree.node(visible[0]).unwrap().name, ROOT_COLLECTION_NAME);
+        assert_eq!(tree.node(visible[1]).unwrap().name, PERSONAL_GROUP_NAME);
+    }
+
+    #[test]
+    fn test_from_collections_without_personal() {
+        let tree = CollectionTree::from_collections(&[collection(1, "Sales", None)]);
+        assert_eq!(tree.visible().len(), 1);
+    }
+
+    #[test]
+    fn test_expand_requests_load_once() {
+        let mut tree = CollectionTree::from_collections(&[]);
+        let root = tree.visible_index(0).unwrap();
+
+        assert_eq!(tree.expand(root), Some(None));
+        // Already loading: no second request
+        assert_eq!(tree.expand(root), None);
+
+        tree.set_items(None, &[entry(10, "Sales", "collection")]);
+        let sales = tree.visible_index(1).unwrap();
+        assert_eq!(tree.expand(sales), Some(Some(10)));
+    }
+
+    #[test]
+    fn test_set_items_and_visibility() {
+        let mut tree = CollectionTree::from_collections(&[]);
+        tree.mark_loading(None);
+        tree.set_items(
+            None,
+            &[
+                entry(10, "Sales", "collection"),
+                entry(20, "KPIs", "dashboard"),
+                entry(30, "Orders", "dataset"),
+                entry(40, "Revenue", "card"),
+                entry(50, "Weekly", "pulse"),
+            ],
+        );
+
+        let visible = tree.visible();
+        // Root + 4 children (pulse is skipped)
+        assert_eq!(visible.len(), 5);
+        let kinds: Vec<TreeNodeKind> = visible
+            .iter()
+            .skip(1)
+            .map(|&i| tree.node(i).unwrap().kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TreeNodeKind::Collection,
+                TreeNodeKind::Dashboard,
+                TreeNodeKind::Model,
+                TreeNodeKind::Question,
+            ]
+        );
+
+        // Nested load and path
+        let sales = visible[1];
+        tree.expand(sales);
+        tree.set_items(Some(10), &[entry(41, "Q3 Revenue", "card")]);
+        let q3 = tree.visible_index(2).unwrap();
+        assert_eq!(tree.node(q3).unwrap().depth, 2);
+        assert_eq!(
+            tree.path(q3),
+            vec![
+                ROOT_COLLECTION_NAME.to_string(),
+                "Sales".to_string(),
+                "Q3 Revenue".to_string()
+            ]
+        );
+        assert_eq!(tree.find_card_name(41), Some("Q3 Revenue".to_string()));
+
+        // Collapse hides the subtree
+        assert!(tree.collapse(sales));
+        assert!(!tree.collapse(sales));
+        assert_eq!(tree.visible().len(), 5);
+    }
+
+    #[test]
+    fn test_refetch_replaces_subtree() {
+        let mut tree = CollectionTree::from_collections(&[collection(7, "Mine", Some(1))]);
+        tree.set_items(
+            None,
+            &[
+                entry(10, "Sales", "collection"),
+                entry(40, "Revenue", "card"),
+            ],
+        );
+        let sales = tree.visible_index(1).unwrap();
+        tree.expand(sales);
+        tree.set_items(Some(10), &[entry(41, "Q3 Revenue", "card")]);
+        assert_eq!(tree.nodes.len(), 6);
+
+        // Re-fetching the root drops Sales and its loaded children
+        tree.mark_loading(None);
+        assert_eq!(tree.nodes.len(), 3);
+        tree.set_items(None, &[entry(40, "Revenue (renamed)", "card")]);
+        assert_eq!(tree.nodes.len(), 4);
+        assert!(tree.find_collections(Some(10)).is_empty());
+        assert_eq!(tree.find_card_name(41), None);
+        assert_eq!(
+            tree.find_card_name(40),
+            Some("Revenue (renamed)".to_string())
+        );
+
+        // Indices stay consistent after the arena is compacted
+        let names: Vec<Vec<String>> = tree.visible().into_iter().map(|i| tree.path(i)).collect();
+        assert_eq!(
+            names,
+            vec![
+                vec![ROOT_COLLECTION_NAME.to_string()],
+                vec![
+                    ROOT_COLLECTION_NAME.to_string(),
+                    "Revenue (renamed)".to_string()
+                ],
+                vec![PERSONAL_GROUP_NAME.to_string()],
+            ]
+        );
+        let group = tree.visible_index(2).unwrap();
+        tree.expand(group);
+        let mine = tree.visible_index(3).unwrap();
+        assert_eq!(tree.node(mine).unwrap().parent, Some(group));
+        assert_eq!(tree.expand(mine), Some(Some(7)));
+    }
+
+    #[test]
+    fn test_root_items_skip_personal_collections() {
+        let mut tree = CollectionTree::from_collections(&[]);
+        let mut personal = entry(99, "My Collection", "collection");
+        personal.personal_owner_id = Some(1);
+        tree.set_items(None, &[entry(10, "Sales", "collection"), personal]);
+        assert_eq!(tree.visible().len(), 2);
+    }
+
+    #[test]
+    fn test_set_error_allows_retry() {
+        let mut tree = CollectionTree::from_collections(&[]);
+        let root = tree.visible_index(0).unwrap();
+        tree.expand(root);
+        tree.set_error(None, "boom".to_string());
+        assert!(tree.node(root).unwrap().children.is_error());
+        assert_eq!(tree.expand(root), Some(None));
+    }
+}
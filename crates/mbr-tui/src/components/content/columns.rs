@@ -0,0 +1,412 @@
+//! Column management for query results.
+//!
+//! Tracks display order, visibility, pinned (frozen) columns and width
+//! overrides of the results table, plus the column manager modal.
+//! Layouts convert to and from `mbr_core::storage::tui_state::ColumnLayout`
+//! so they can be remembered per question.
+
+use mbr_core::storage::tui_state::{ColumnLayout as SavedColumnLayout, ColumnSetting};
+
+use super::ContentPanel;
+use super::types::ContentView;
+
+/// Narrowest width a column can be resized to.
+const MIN_COLUMN_WIDTH: u16 = 4;
+/// Widest width a column can be resized to.
+const MAX_COLUMN_WIDTH: u16 = 200;
+/// Width change per resize step.
+const RESIZE_STEP: i16 = 2;
+
+/// Display layout of the result columns.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnLayout {
+    /// Column indices (into `QueryResultData::columns`) in display order
+    order: Vec<usize>,
+    /// Hidden flag per column index
+    hidden: Vec<bool>,
+    /// Width override per column index (None = automatic)
+    widths: Vec<Option<u16>>,
+    /// Number of leading entries of `order` frozen while scrolling horizontally
+    pinned: usize,
+}
+
+impl ColumnLayout {
+    /// Default layout: API order, all visible, nothing pinned.
+    pub fn new(column_count: usize) -> Self {
+        Self {
+            order: (0..column_count).collect(),
+            hidden: vec![false; column_count],
+            widths: vec![None; column_count],
+            pinned: 0,
+        }
+    }
+
+    /// Restore a saved layout, matching columns by name.
+    /// Columns missing from the saved layout are appended in API order.
+    pub fn from_saved(columns: &[String], saved: &SavedColumnLayout) -> Self {
+        let mut layout = Self::new(columns.len());
+        let mut order = Vec::with_capacity(columns.len());
+        let mut used = vec![false; columns.len()];
+
+        for setting in &saved.columns {
+            let Some(idx) = (0..columns.len()).find(|&i| !used[i] && columns[i] == setting.name)
+            else {
+                continue;
+            };
+            used[idx] = true;
+            order.push(idx);
+            layout.hidden[idx] = setting.hidden;
+            layout.widths[idx] = setting
+                .width
+                .map(|w| w.clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH));
+        }
+        let matched = order.len();
+        order.extend((0..columns.len()).filter(|&i| !used[i]));
+
+        layout.order = order;
+        layout.pinned = saved.pinned.min(matched);
+        if layout.hidden.iter().all(|&h| h) {
+            // Never restore a layout that hides everything
+            layout.hidden.fill(false);
+        }
+        layout
+    }
+
+    /// Convert to the persisted form.
+    pub fn to_saved(&self, columns: &[String]) -> SavedColumnLayout {
+        SavedColumnLayout {
+            columns: self
+                .order
+                .iter()
+                .filter_map(|&idx| {
+                    Some(ColumnSetting {
+                        name: columns.get(idx)?.clone(),
+                        hidden: self.hidden[idx],
+                        width: self.widths[idx],
+                    })
+                })
+                .collect(),
+            pinned: self.pinned,
+        }
+    }
+
+    /// Whether this is the default layout (nothing worth persisting).
+    pub fn is_default(&self) -> bool {
+        self.pinned == 0
+            && self.order.iter().enumerate().all(|(pos, &idx)| pos == idx)
+            && self.hidden.iter().all(|&h| !h)
+            && self.widths.iter().all(Option::is_none)
+    }
+
+    /// Number of columns (visible or not).
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether there are no columns.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Column index at a display position.
+    pub fn column_at(&self, position: usize) -> Option<usize> {
+        self.order.get(position).copied()
+    }
+
+    /// Whether a column is hidden.
+    pub fn is_hidden(&self, column: usize) -> bool {
+        self.hidden.get(column).copied().unwrap_or(false)
+    }
+
+    /// Width override of a column.
+    pub fn width(&self, column: usize) -> Option<u16> {
+        self.widths.get(column).copied().flatten()
+    }
+
+    /// Number of pinned positions.
+    pub fn pinned(&self) -> usize {
+        self.pinned
+    }
+
+    /// Visible pinned columns, then visible scrollable columns, in display order.
+    pub fn visible_columns(&self) -> (Vec<usize>, Vec<usize>) {
+        let (pinned, scrollable): (Vec<_>, Vec<_>) = self
+            .order
+            .iter()
+            .enumerate()
+            .filter(|&(_, &idx)| !self.hidden[idx])
+            .partition(|&(pos, _)| pos < self.pinned);
+        (
+            pinned.into_iter().map(|(_, &idx)| idx).collect(),
+            scrollable.into_iter().map(|(_, &idx)| idx).collect(),
+        )
+    }
+
+    /// Toggle visibility of the column at a position.
+    /// The last visible column cannot be hidden.
+    pub fn toggle_hidden(&mut self, position: usize) {
+        let Some(idx) = self.column_at(position) else {
+            return;
+        };
+        let visible = self.hidden.iter().filter(|&&h| !h).count();
+        if !self.hidden[idx] && visible <= 1 {
+            return;
+        }
+        self.hidden[idx] = !self.hidden[idx];
+    }
+
+    /// Move the column at a position one step left (up in the manager).
+    /// Returns the new position.
+    pub fn move_up(&mut self, position: usize) -> usize {
+        if position == 0 || position >= self.order.len() {
+            return position;
+        }
+        self.order.swap(position, position - 1);
+        position - 1
+    }
+
+    /// Move the column at a position one step right (down in the manager).
+    /// Returns the new position.
+    pub fn move_down(&mut self, position: usize) -> usize {
+        if position + 1 >= self.order.len() {
+            return position;
+        }
+        self.order.swap(position, position + 1);
+        position + 1
+    }
+
+    /// Pin every column up to and including a position.
+    /// Pinning the last pinned column again unpins all columns.
+    pub fn toggle_pin(&mut self, position: usize) {
+        if position >= self.order.len() {
+            return;
+        }
+        self.pinned = if self.pinned == position + 1 {
+            0
+        } else {
+            position + 1
+        };
+    }
+
+    /// Widen (positive) or narrow (negative) the column at a position.
+    /// `current` is the automatic width used when no override is set.
+    pub fn resize(&mut self, position: usize, delta: i16, current: u16) {
+        let Some(idx) = self.column_at(position) else {
+            return;
+        };
+        let base = self.widths[idx].unwrap_or(current) as i16;
+        let width = (base + delta).clamp(MIN_COLUMN_WIDTH as i16, MAX_COLUMN_WIDTH as i16);
+        self.widths[idx] = Some(width as u16);
+    }
+
+    /// Return the column at a position to automatic width.
+    pub fn reset_width(&mut self, position: usize) {
+        if let Some(idx) = self.column_at(position) {
+            self.widths[idx] = None;
+        }
+    }
+}
+
+impl ContentPanel {
+    /// Check if column manager modal is active.
+    pub fn is_column_manager_active(&self) -> bool {
+        self.column_manager_active
+    }
+
+    /// Open the column manager modal.
+    pub fn open_column_manager(&mut self) {
+        if !self.column_layout.is_empty() {
+            self.column_manager_active = true;
+            self.column_manager_selection = 0;
+        }
+    }
+
+    /// Close the column manager modal.
+    pub fn close_column_manager(&mut self) {
+        self.column_manager_active = false;
+    }
+
+    /// Move selection up in column manager.
+    pub fn column_manager_up(&mut self) {
+        self.column_manager_selection = self.column_manager_selection.saturating_sub(1);
+    }
+
+    /// Move selection down in column manager.
+    pub fn column_manager_down(&mut self) {
+        if self.column_manager_selection + 1 < self.column_layout.len() {
+            self.column_manager_selection += 1;
+        }
+    }
+
+    /// Show or hide the selected column.
+    pub fn column_manager_toggle_hidden(&mut self) {
+        self.column_layout
+            .toggle_hidden(self.column_manager_selection);
+        self.column_layout_changed();
+    }
+
+    /// Move the selected column up (left in the table).
+    pub fn column_manager_move_up(&mut self) {
+        self.column_manager_selection = self.column_layout.move_up(self.column_manager_selection);
+        self.column_layout_changed();
+    }
+
+    /// Move the selected column down (right in the table).
+    pub fn column_manager_move_down(&mut self) {
+        self.column_manager_selection = self.column_layout.move_down(self.column_manager_selection);
+        self.column_layout_changed();
+    }
+
+    /// Pin columns up to the selected one (or unpin).
+    pub fn column_manager_toggle_pin(&mut self) {
+        self.column_layout.toggle_pin(self.column_manager_selection);
+        self.column_layout_changed();
+    }
+
+    /// Widen or narrow the selected column.
+    pub fn column_manager_resize(&mut self, wider: bool) {
+        let position = self.column_manager_selection;
+        let Some(idx) = self.column_layout.column_at(position) else {
+            return;
+        };
+        let current = self.auto_column_width(idx);
+        let delta = if wider { RESIZE_STEP } else { -RESIZE_STEP };
+        self.column_layout.resize(position, delta, current);
+        self.column_layout_changed();
+    }
+
+    /// Return the selected column to automatic width.
+    pub fn column_manager_reset_width(&mut self) {
+        self.column_layout
+            .reset_width(self.column_manager_selection);
+        self.column_layout_changed();
+    }
+
+    /// Restore the default layout for the current result.
+    pub fn reset_column_layout(&mut self) {
+        self.column_layout = ColumnLayout::new(self.column_layout.len());
+        self.column_manager_selection = 0;
+        self.column_layout_changed();
+    }
+
+    /// Apply a saved layout to the current result.
+    pub fn apply_saved_column_layout(&mut self, saved: &SavedColumnLayout) {
+        if let Some(ref result) = self.query_result {
+            self.column_layout = ColumnLayout::from_saved(&result.columns, saved);
+            self.scroll_x = 0;
+        }
+    }
+
+    /// Column in focus: the leftmost scrolled column, or the first visible
+    /// one if all are pinned.
+    pub fn focus_column(&self) -> Option<usize> {
+        let (pinned, scrollable) = self.column_layout.visible_columns();
+        scrollable
+            .get(self.scroll_x.min(scrollable.len().saturating_sub(1)))
+            .or(pinned.first())
+            .copied()
+    }
+
+    /// Take a pending layout change of a question result for persistence.
+    /// Returns (question id, layout), where None means the default layout.
+    pub fn take_column_layout_change(&mut self) -> Option<(u32, Option<SavedColumnLayout>)> {
+        // Layouts of grouped views are not saved
+        if !std::mem::take(&mut self.column_layout_dirty)
+            || self.view != ContentView::QueryResult
+            || self.is_grouped()
+        {
+            return None;
+        }
+        let result = self.query_result.as_ref()?;
+        let saved = (!self.column_layout.is_default())
+            .then(|| self.column_layout.to_saved(&result.columns));
+        Some((result.question_id, saved))
+    }
+
+    /// Automatic display width of a column (before padding).
+    pub(super) fn auto_column_width(&self, column: usize) -> u16 {
+        self.cached_column_widths
+            .as_ref()
+            .and_then(|widths| widths.get(column).copied())
+            .unwrap_or(15)
+            .max(8)
+    }
+
+    /// Mark the layout as changed and keep horizontal scroll in range.
+    fn column_layout_changed(&mut self) {
+        self.column_layout_dirty = true;
+        let (_, scrollable) = self.column_layout.visible_columns();
+        self.scroll_x = self.scroll_x.min(scrollable.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_column_layout_edit_and_visible_columns() {
+        let mut layout = ColumnLayout::new(4);
+        assert!(layout.is_default());
+
+        // Move column 2 to the front and pin it
+        let pos = layout.move_up(2);
+        let pos = layout.move_up(pos);
+        assert_eq!(pos, 0);
+        layout.toggle_pin(0);
+        layout.toggle_hidden(3);
+
+        let (pinned, scrollable) = layout.visible_columns();
+        assert_eq!(pinned, vec![2]);
+        assert_eq!(scrollable, vec![0, 1]);
+        assert!(!layout.is_default());
+
+        // Pinning the same column again unpins
+        layout.toggle_pin(0);
+        assert_eq!(layout.pinned(), 0);
+
+        // Resize from the automatic width, then reset
+        layout.resize(1, 2, 10);
+        assert_eq!(layout.width(0), Some(12));
+        layout.reset_width(1);
+        assert_eq!(layout.width(0), None);
+    }
+
+    #[test]
+    fn test_column_layout_never_hides_last_column() {
+        let mut layout = ColumnLayout::new(2);
+        layout.toggle_hidden(0);
+        layout.toggle_hidden(1);
+        assert!(layout.is_hidden(0));
+        assert!(!layout.is_hidden(1));
+    }
+
+    #[test]
+    fn test_column_layout_saved_round_trip_by_name() {
+        let columns = names(&["id", "name", "total"]);
+        let mut layout = ColumnLayout::new(3);
+        layout.move_down(0);
+        layout.toggle_pin(0);
+        layout.toggle_hidden(2);
+        layout.resize(1, 4, 8);
+
+        let saved = layout.to_saved(&columns);
+        assert_eq!(saved.pinned, 1);
+        assert_eq!(saved.columns[0].name, "name");
+        assert_eq!(saved.columns[1].width, Some(12));
+        assert!(saved.columns[2].hidden);
+        assert_eq!(ColumnLayout::from_saved(&columns, &saved), layout);
+
+        // New columns are appended, vanished ones are dropped
+        let changed = names(&["name", "created_at", "id"]);
+        let restored = ColumnLayout::from_saved(&changed, &saved);
+        assert_eq!(restored.column_at(0), Some(0));
+        assert_eq!(restored.column_at(1), Some(2));
+        assert_eq!(restored.column_at(2), Some(1));
+        assert_eq!(restored.width(2), Some(12));
+    }
+}
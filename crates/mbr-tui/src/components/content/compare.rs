@@ -0,0 +1,174 @@
+//! Row alignment and differences of two results (split view).
+//!
+//! The compared result follows the rows of the live one. When both results
+//! have a key column (a primary key, or an `id` column in both) rows are
+//! matched by key and differing cells are marked; otherwise rows are
+//! matched by position.
+
+use std::collections::{HashMap, HashSet};
+
+use super::column_position;
+use super::types::{ColumnKey, QueryResultData};
+
+/// Rows of the compared result aligned with rows of the live result.
+#[derive(Debug, Default, PartialEq)]
+pub struct Alignment {
+    /// Key column names (live, compared), if rows are matched by key
+    pub key: Option<(String, String)>,
+    /// Per live row: the matching compared row and its differing columns
+    pub rows: Vec<Option<(usize, Vec<usize>)>>,
+    /// Live rows with at least one differing cell
+    pub differing: usize,
+    /// Compared rows with a key that no live row has
+    pub only_compared: usize,
+}
+
+/// Key columns shared by both results: the first column of `left` that
+/// `right` also has and that is a primary key in either, else `id`.
+pub fn key_columns(left: &QueryResultData, right: &QueryResultData) -> Option<(usize, usize)> {
+    let is_primary = |data: &QueryResultData, col: usize| {
+        matches!(data.column_key(col), ColumnKey::Primary { .. })
+    };
+    let shared = |name: &str| {
+        Some((
+            column_position(&left.columns, name)?,
+            column_position(&right.columns, name)?,
+        ))
+    };
+    left.columns
+        .iter()
+        .filter_map(|name| shared(name))
+        .find(|&(l, r)| is_primary(left, l) || is_primary(right, r))
+        .or_else(|| shared("id"))
+}
+
+/// Align `right` with the given rows of `left` (in display order).
+pub fn align_rows(
+    left: &QueryResultData,
+    left_rows: &[usize],
+    right: &QueryResultData,
+) -> Alignment {
+    let Some((left_key, right_key)) = key_columns(left, right) else {
+        // Without a key, rows are compared by position only
+        let rows = (0..left_rows.len())
+            .map(|i| (i < right.rows.len()).then(|| (i, Vec::new())))
+            .collect();
+        return Alignment {
+            rows,
+            ..Alignment::default()
+        };
+    };
+
+    let index: HashMap<&str, usize> = right
+        .rows
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(|(i, row)| Some((row.get(right_key)?.as_str(), i)))
+        .collect();
+    // Compared columns and their position in the live result
+    let shared: Vec<(usize, usize)> = right
+        .columns
+        .iter()
+        .enumerate()
+        .filter_map(|(r, name)| Some((r, column_position(&left.columns, name)?)))
+        .collect();
+
+    let mut alignment = Alignment {
+        key: Some((
+            left.columns[left_key].clone(),
+            right.columns[right_key].clone(),
+        )),
+        ..Alignment::default()
+    };
+    let mut seen = vec![false; right.rows.len()];
+    for &l in left_rows {
+        let Some(left_row) = left.rows.get(l) else {
+            alignment.rows.push(None);
+            continue;
+        };
+        let matched = left_row
+            .get(left_key)
+            .and_then(|key| index.get(key.as_str()))
+            .map(|&r| {
+                seen[r] = true;
+                let right_row = &right.rows[r];
+                let diff: Vec<usize> = shared
+                    .iter()
+                    .filter(|&&(rc, lc)| right_row.get(rc) != left_row.get(lc))
+                    .map(|&(rc, _)| rc)
+                    .collect();
+                (r, diff)
+            });
+        if matched.as_ref().is_some_and(|(_, diff)| !diff.is_empty()) {
+            alignment.differing += 1;
+        }
+        alignment.rows.push(matched);
+    }
+    // Rows no live row matched (the live result may be filtered)
+    let left_keys: HashSet<&str> = left
+        .rows
+        .iter()
+        .filter_map(|row| row.get(left_key).map(|s| s.as_str()))
+        .collect();
+    alignment.only_compared = right
+        .rows
+        .iter()
+        .enumerate()
+        .filter(|(i, row)| {
+            !seen[*i]
+                && row
+                    .get(right_key)
+                    .is_some_and(|k| !left_keys.contains(k.as_str()))
+        })
+        .count();
+    alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::ColumnType;
+
+    fn data(columns: &[&str], rows: &[&[&str]]) -> QueryResultData {
+        QueryResultData {
+            question_id: 1,
+            question_name: "Q".to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: rows
+                .iter()
+                .map(|r| r.iter().map(|c| c.to_string()).collect())
+                .collect(),
+            column_types: vec![ColumnType::Text; columns.len()],
+            column_keys: vec![],
+        }
+    }
+
+    #[test]
+    fn test_align_by_key_marks_differences() {
+        let left = data(
+            &["ID", "Name", "Total"],
+            &[&["1", "a", "10"], &["2", "b", "20"], &["3", "c", "30"]],
+        );
+        let right = data(
+            &["Total", "id"],
+            &[&["20", "2"], &["11", "1"], &["40", "4"]],
+        );
+        let alignment = align_rows(&left, &[2, 0, 1], &right);
+        assert_eq!(alignment.key, Some(("ID".to_string(), "id".to_string())));
+        assert_eq!(
+            alignment.rows,
+            vec![None, Some((1, vec![0])), Some((0, vec![]))]
+        );
+        assert_eq!((alignment.differing, alignment.only_compared), (1, 1));
+    }
+
+    #[test]
+    fn test_align_by_position_without_key() {
+        let left = data(&["Name"], &[&["a"], &["b"]]);
+        let right = data(&["Other"], &[&["x"]]);
+        let alignment = align_rows(&left, &[1, 0], &right);
+        assert_eq!(alignment.key, None);
+        assert_eq!(alignment.rows, vec![Some((0, vec![])), None]);
+    }
+}
@@ -1,10 +1,12 @@
 //! Filter functionality for query results.
 //!
-//! Provides filter modal handling with two-step workflow (column selection → text input),
-//! case-insensitive contains matching, and integration with search results.
+//! Provides the filter modal (condition list → column → operator → value),
+//! stacked conditions combined with AND/OR (see `filter_expr`), and
+//! integration with search results.
 
 use super::ContentPanel;
-use super::types::SortOrder;
+use super::filter_expr::{FilterCondition, FilterJoin, FilterOp, row_matches};
+use super::types::FilterStep;
 
 impl ContentPanel {
     /// Check if filter modal is active.
@@ -12,143 +14,271 @@ impl ContentPanel {
         self.filter_mode_active
     }
 
-    /// Open filter column selection modal.
+    /// Open the filter modal.
+    /// Starts at the condition list when filters exist, otherwise at column selection.
     pub fn open_filter_modal(&mut self) {
         if let Some(ref result) = self.query_result
             && !result.columns.is_empty()
         {
             self.filter_mode_active = true;
-            self.filter_modal_step = 0; // Start at column selection
-            // Start at currently filtered column or first column
-            self.filter_modal_selection = self.filter_column_index.unwrap_or(0);
+            self.filter_error = None;
+            if self.filters.is_empty() {
+                self.start_new_condition();
+            } else {
+                self.filter_modal_step = FilterStep::Conditions;
+                self.filter_condition_selection = 0;
+            }
         }
     }
 
     /// Close filter modal without applying.
     pub fn close_filter_modal(&mut self) {
         self.filter_mode_active = false;
-        self.filter_modal_step = 0;
-        // Don't clear filter_text here to preserve it for re-editing
+        self.filter_modal_step = FilterStep::default();
+        self.filter_editing = None;
+        self.filter_error = None;
     }
 
-    /// Move selection up in filter modal (column selection step).
+    /// Move selection up in the current filter modal list.
     pub fn filter_modal_up(&mut self) {
-        if self.filter_modal_step == 0 && self.filter_modal_selection > 0 {
-            self.filter_modal_selection -= 1;
+        match self.filter_modal_step {
+            FilterStep::Conditions => {
+                self.filter_condition_selection = self.filter_condition_selection.saturating_sub(1);
+            }
+            FilterStep::Column => {
+                self.filter_modal_selection = self.filter_modal_selection.saturating_sub(1);
+            }
+            FilterStep::Operator => {
+                self.filter_op_selection = self.filter_op_selection.saturating_sub(1);
+            }
+            FilterStep::Value => {}
         }
     }
 
-    /// Move selection down in filter modal (column selection step).
+    /// Move selection down in the current filter modal list.
     pub fn filter_modal_down(&mut self) {
-        if self.filter_modal_step == 0
-            && let Some(ref result) = self.query_result
-            && self.filter_modal_selection < result.columns.len().saturating_sub(1)
-        {
-            self.filter_modal_selection += 1;
+        match self.filter_modal_step {
+            FilterStep::Conditions => {
+                // Last entry is "add condition"
+                if self.filter_condition_selection < self.filters.len() {
+                    self.filter_condition_selection += 1;
+                }
+            }
+            FilterStep::Column => {
+                if let Some(ref result) = self.query_result
+                    && self.filter_modal_selection < result.columns.len().saturating_sub(1)
+                {
+                    self.filter_modal_selection += 1;
+                }
+            }
+            FilterStep::Operator => {
+                if self.filter_op_selection < FilterOp::ALL.len() - 1 {
+                    self.filter_op_selection += 1;
+                }
+            }
+            FilterStep::Value => {}
         }
     }
 
-    /// Move to next step in filter modal (column selection → text input).
+    /// Move to the next step in filter modal (Enter).
     pub fn filter_modal_next_step(&mut self) {
-        if self.filter_modal_step == 0 {
-            self.filter_modal_step = 1;
-            // Pre-fill with existing filter text if same column
-            if self.filter_column_index != Some(self.filter_modal_selection) {
-                self.filter_text.clear();
+        self.filter_error = None;
+        match self.filter_modal_step {
+            FilterStep::Conditions => {
+                if self.filter_condition_selection < self.filters.len() {
+                    self.edit_condition(self.filter_condition_selection);
+                } else {
+                    self.start_new_condition();
+                }
+            }
+            FilterStep::Column => {
+                self.filter_modal_step = FilterStep::Operator;
+            }
+            FilterStep::Operator => {
+                if self.selected_filter_op().needs_value() {
+                    self.filter_modal_step = FilterStep::Value;
+                } else {
+                    self.apply_filter();
+                }
             }
+            FilterStep::Value => self.apply_filter(),
         }
     }
 
-    /// Move to previous step in filter modal (text input → column selection).
+    /// Move to the previous step in filter modal (Esc); closes from the first step.
     pub fn filter_modal_prev_step(&mut self) {
-        if self.filter_modal_step == 1 {
-            self.filter_modal_step = 0;
+        self.filter_error = None;
+        match self.filter_modal_step {
+            FilterStep::Value => self.filter_modal_step = FilterStep::Operator,
+            FilterStep::Operator => self.filter_modal_step = FilterStep::Column,
+            FilterStep::Column if !self.filters.is_empty() => {
+                self.filter_modal_step = FilterStep::Conditions;
+                self.filter_editing = None;
+            }
+            FilterStep::Column | FilterStep::Conditions => self.close_filter_modal(),
         }
     }
 
-    /// Handle character input in filter modal (text input step).
+    /// Handle character input in filter modal (value step).
     pub fn filter_modal_input_char(&mut self, c: char) {
-        if self.filter_modal_step == 1 {
-            self.filter_text.push(c);
-            // Invalidate cached lowercase text
-            self.cached_filter_lower = Some(self.filter_text.to_lowercase());
+        if self.filter_modal_step == FilterStep::Value {
+            self.filter_input.push(c);
+            self.filter_error = None;
         }
     }
 
-    /// Handle backspace in filter modal (text input step).
+    /// Handle backspace in filter modal (value step).
     pub fn filter_modal_delete_char(&mut self) {
-        if self.filter_modal_step == 1 {
-            self.filter_text.pop();
-            // Invalidate cached lowercase text
-            self.cached_filter_lower = Some(self.filter_text.to_lowercase());
+        if self.filter_modal_step == FilterStep::Value {
+            self.filter_input.pop();
+            self.filter_error = None;
         }
     }
 
-    /// Apply filter on selected column with current filter text.
-    pub fn apply_filter(&mut self) {
-        let selected_col = self.filter_modal_selection;
-
-        if self.filter_text.is_empty() {
-            // Empty filter text - clear filter
-            self.clear_filter();
+    /// Toggle AND/OR: for the selected condition in the list, or for the condition being added.
+    pub fn filter_modal_toggle_join(&mut self) {
+        if self.filter_modal_step == FilterStep::Conditions {
+            let idx = self.filter_condition_selection;
+            if idx > 0
+                && let Some(condition) = self.filters.get_mut(idx)
+            {
+                condition.join = condition.join.toggled();
+                self.filters_changed();
+            }
         } else {
-            // Apply filter
-            self.filter_column_index = Some(selected_col);
-            self.update_filter_indices();
-            // Re-apply sort on filtered data
-            if self.sort_order != SortOrder::None {
-                self.update_sort_indices();
+            self.filter_join = self.filter_join.toggled();
+        }
+    }
+
+    /// Remove the selected condition in the condition list.
+    pub fn filter_modal_remove_selected(&mut self) {
+        if self.filter_modal_step == FilterStep::Conditions
+            && self.filter_condition_selection < self.filters.len()
+        {
+            self.remove_filter(self.filter_condition_selection);
+            if self.filters.is_empty() {
+                self.close_filter_modal();
             }
         }
+    }
 
-        // Close modal
-        self.filter_mode_active = false;
-        self.filter_modal_step = 0;
+    /// Apply the condition built in the filter modal (adds or replaces).
+    /// Keeps the modal open with an error message when the value is invalid.
+    pub fn apply_filter(&mut self) {
+        let op = self.selected_filter_op();
+        let join = match self.filter_editing {
+            Some(idx) => self.filters.get(idx).map(|c| c.join).unwrap_or_default(),
+            None => self.filter_join,
+        };
 
-        // Reset to first page and first row after filter
-        self.result_page = 0;
-        self.result_table_state.select(Some(0));
+        let condition =
+            match FilterCondition::new(join, self.filter_modal_selection, op, &self.filter_input) {
+                Ok(condition) => condition,
+                Err(e) => {
+                    self.filter_error = Some(e);
+                    return;
+                }
+            };
+
+        match self.filter_editing {
+            Some(idx) if idx < self.filters.len() => self.filters[idx] = condition,
+            _ => self.filters.push(condition),
+        }
+        self.filters_changed();
+        self.close_filter_modal();
+    }
+
+    /// Replace all conditions (used by the `:filter` command).
+    pub fn set_filters(&mut self, conditions: Vec<FilterCondition>) {
+        self.filters = conditions;
+        self.filters_changed();
+    }
+
+    /// Append a condition (used by the `:and` / `:or` commands).
+    pub fn add_filter(&mut self, condition: FilterCondition) {
+        self.filters.push(condition);
+        self.filters_changed();
+    }
+
+    /// Remove a single condition by index.
+    pub fn remove_filter(&mut self, index: usize) {
+        if index < self.filters.len() {
+            self.filters.remove(index);
+            self.filter_condition_selection =
+                self.filter_condition_selection.min(self.filters.len());
+            self.filters_changed();
+        }
     }
 
     /// Clear filter and restore all rows.
     pub fn clear_filter(&mut self) {
-        self.filter_column_index = None;
-        self.filter_text.clear();
+        self.filters.clear();
         self.filter_indices = None;
-        self.cached_filter_lower = None;
         // Re-apply sort on full data
-        if self.sort_order != SortOrder::None {
+        if !self.sort_keys.is_empty() {
+            self.update_sort_indices();
+        }
+    }
+
+    /// Active filter conditions.
+    pub fn filters(&self) -> &[FilterCondition] {
+        &self.filters
+    }
+
+    /// Operator currently selected in the filter modal.
+    pub(super) fn selected_filter_op(&self) -> FilterOp {
+        FilterOp::ALL
+            .get(self.filter_op_selection)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Start building a new condition at the column step.
+    pub(super) fn start_new_condition(&mut self) {
+        self.filter_modal_step = FilterStep::Column;
+        self.filter_editing = None;
+        self.filter_input.clear();
+        self.filter_op_selection = 0;
+        self.filter_join = FilterJoin::And;
+        self.filter_modal_selection = self.filters.last().map(|c| c.column).unwrap_or(0);
+    }
+
+    /// Load an existing condition into the modal for editing.
+    fn edit_condition(&mut self, index: usize) {
+        if let Some(condition) = self.filters.get(index) {
+            self.filter_editing = Some(index);
+            self.filter_modal_selection = condition.column;
+            self.filter_op_selection = FilterOp::ALL
+                .iter()
+                .position(|op| *op == condition.op)
+                .unwrap_or(0);
+            self.filter_input = condition.value.clone();
+            self.filter_modal_step = FilterStep::Column;
+        }
+    }
+
+    /// Recompute indices after the conditions changed.
+    fn filters_changed(&mut self) {
+        self.update_filter_indices();
+        // Re-apply sort on filtered data
+        if !self.sort_keys.is_empty() {
             self.update_sort_indices();
         }
+        // Reset to first page and first row after filter
+        self.result_page = 0;
+        self.result_table_state.select(Some(0));
     }
 
-    /// Update filter indices based on current filter column and text.
+    /// Update filter indices based on the current conditions.
     /// If search is active, filters within search results.
     pub(super) fn update_filter_indices(&mut self) {
-        let col_idx = match self.filter_column_index {
-            Some(idx) => idx,
-            None => {
-                self.filter_indices = None;
-                return;
-            }
-        };
-
-        if self.filter_text.is_empty() {
+        if self.filters.is_empty() {
             self.filter_indices = None;
             return;
         }
 
         if let Some(ref result) = self.query_result {
-            if col_idx >= result.columns.len() {
-                self.filter_indices = None;
-                return;
-            }
-
-            // Use cached lowercase text to avoid recomputing on each call
-            let filter_lower = self
-                .cached_filter_lower
-                .get_or_insert_with(|| self.filter_text.to_lowercase())
-                .clone();
+            let conditions = &self.filters;
 
             // Determine the base set of indices to filter from
             let base_indices: Box<dyn Iterator<Item = usize>> =
@@ -165,9 +295,7 @@ impl ContentPanel {
                     result
                         .rows
                         .get(i)
-                        .and_then(|row| row.get(col_idx))
-                        .map(|cell| cell.to_lowercase().contains(&filter_lower))
-                        .unwrap_or(false)
+                        .is_some_and(|row| row_matches(conditions, row, |c| result.column_type(c)))
                 })
                 .collect();
 
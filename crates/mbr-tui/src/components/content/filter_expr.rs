@@ -0,0 +1,360 @@
+//! Filter conditions for query results.
+//!
+//! A filter is a list of conditions combined left to right, where AND binds
+//! tighter than OR (`a AND b OR c` = `(a AND b) OR c`). Comparisons are
+//! numeric or date-aware depending on the column type.
+
+use std::cmp::Ordering;
+
+use regex::Regex;
+
+use super::types::ColumnType;
+
+/// Text shown for NULL cells (see `transform_query_result`).
+const NULL_DISPLAY: &str = "—";
+
+/// Comparison operator for column filters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterOp {
+    /// Case-insensitive substring match (filter modal default)
+    #[default]
+    Contains,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    /// Inclusive range, value written as `low..high`
+    Between,
+    /// Any of a comma-separated list
+    In,
+    IsNull,
+    NotNull,
+    /// Case-insensitive prefix match
+    StartsWith,
+    /// Regular expression match
+    Regex,
+}
+
+impl FilterOp {
+    /// All operators in filter modal order.
+    pub const ALL: [FilterOp; 13] = [
+        FilterOp::Contains,
+        FilterOp::Eq,
+        FilterOp::Ne,
+        FilterOp::Gt,
+        FilterOp::Ge,
+        FilterOp::Lt,
+        FilterOp::Le,
+        FilterOp::Between,
+        FilterOp::In,
+        FilterOp::StartsWith,
+        FilterOp::Regex,
+        FilterOp::IsNull,
+        FilterOp::NotNull,
+    ];
+
+    /// Operator as typed on the command line and shown in filter chips.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            FilterOp::Contains => "~",
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "!=",
+            FilterOp::Gt => ">",
+            FilterOp::Ge => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Le => "<=",
+            FilterOp::Between => "between",
+            FilterOp::In => "in",
+            FilterOp::IsNull => "is null",
+            FilterOp::NotNull => "is not null",
+            FilterOp::StartsWith => "starts-with",
+            FilterOp::Regex => "=~",
+        }
+    }
+
+    /// Look up an operator by its symbol.
+    pub fn from_symbol(symbol: &str) -> Option<FilterOp> {
+        Self::ALL.into_iter().find(|op| op.symbol() == symbol)
+    }
+
+    /// Description shown in the filter modal.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterOp::Contains => "contains",
+            FilterOp::Eq => "equals",
+            FilterOp::Ne => "not equal",
+            FilterOp::Gt => "greater than",
+            FilterOp::Ge => "greater or equal",
+            FilterOp::Lt => "less than",
+            FilterOp::Le => "less or equal",
+            FilterOp::Between => "between (low..high)",
+            FilterOp::In => "in (a, b, c)",
+            FilterOp::IsNull => "is null",
+            FilterOp::NotNull => "is not null",
+            FilterOp::StartsWith => "starts with",
+            FilterOp::Regex => "matches regex",
+        }
+    }
+
+    /// Whether the operator takes a value.
+    pub fn needs_value(&self) -> bool {
+        !matches!(self, FilterOp::IsNull | FilterOp::NotNull)
+    }
+}
+
+/// How a condition combines with the conditions before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterJoin {
+    #[default]
+    And,
+    Or,
+}
+
+impl FilterJoin {
+    /// Keyword shown between filter chips.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterJoin::And => "AND",
+            FilterJoin::Or => "OR",
+        }
+    }
+
+    /// The other join.
+    pub fn toggled(self) -> Self {
+        match self {
+            FilterJoin::And => FilterJoin::Or,
+            FilterJoin::Or => FilterJoin::And,
+        }
+    }
+}
+
+/// A condition referring to a column by name (parsed from `:filter`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterClause {
+    pub join: FilterJoin,
+    pub column: String,
+    pub op: FilterOp,
+    pub value: String,
+}
+
+/// A validated filter condition on a result column.
+#[derive(Debug, Clone)]
+pub struct FilterCondition {
+    /// Join with the previous condition (ignored for the first one)
+    pub join: FilterJoin,
+    /// Column index in the result
+    pub column: usize,
+    pub op: FilterOp,
+    /// Value as entered (empty for null checks)
+    pub value: String,
+    /// Compiled pattern for `FilterOp::Regex`
+    regex: Option<Regex>,
+}
+
+impl FilterCondition {
+    /// Build a condition, validating the value for the operator.
+    pub fn new(join: FilterJoin, column: usize, op: FilterOp, value: &str) -> Result<Self, String> {
+        let value = if op.needs_value() { value.trim() } else { "" };
+        if op.needs_value() && value.is_empty() {
+            return Err(format!("'{}' needs a value", op.symbol()));
+        }
+
+        let regex = match op {
+            FilterOp::Regex => {
+                Some(Regex::new(value).map_err(|e| format!("Invalid regex: {}", e))?)
+            }
+            FilterOp::Between if split_range(value).is_none() => {
+                return Err("'between' needs a range like 10..20".to_string());
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            join,
+            column,
+            op,
+            value: value.to_string(),
+            regex,
+        })
+    }
+
+    /// Check whether a cell satisfies this condition.
+    pub fn matches(&self, cell: &str, column_type: ColumnType) -> bool {
+        let is_null = is_null(cell);
+        match self.op {
+            FilterOp::IsNull => return is_null,
+            FilterOp::NotNull => return !is_null,
+            // NULL never matches a comparison
+            _ if is_null => return false,
+            _ => {}
+        }
+
+        let cmp = |value: &str| compare(cell, value, column_type);
+        match self.op {
+            FilterOp::Contains => cell.to_lowercase().contains(&self.value.to_lowercase()),
+            FilterOp::StartsWith => cell.to_lowercase().starts_with(&self.value.to_lowercase()),
+            FilterOp::Regex => self.regex.as_ref().is_some_and(|re| re.is_match(cell)),
+            FilterOp::Eq => cmp(&self.value).is_some_and(Ordering::is_eq),
+            FilterOp::Ne => cmp(&self.value).is_some_and(Ordering::is_ne),
+            FilterOp::Gt => cmp(&self.value).is_some_and(Ordering::is_gt),
+            FilterOp::Ge => cmp(&self.value).is_some_and(Ordering::is_ge),
+            FilterOp::Lt => cmp(&self.value).is_some_and(Ordering::is_lt),
+            FilterOp::Le => cmp(&self.value).is_some_and(Ordering::is_le),
+            FilterOp::Between => split_range(&self.value).is_some_and(|(low, high)| {
+                cmp(low).is_some_and(Ordering::is_ge) && cmp(high).is_some_and(Ordering::is_le)
+            }),
+            FilterOp::In => self
+                .value
+                .split(',')
+                .map(str::trim)
+                .any(|v| cmp(v).is_some_and(Ordering::is_eq)),
+            FilterOp::IsNull | FilterOp::NotNull => unreachable!("handled above"),
+        }
+    }
+
+    /// Short form shown in the filter chip bar, e.g. `price > 10`.
+    pub fn describe(&self, column_name: &str) -> String {
+        if self.op.needs_value() {
+            format!("{} {} {}", column_name, self.op.symbol(), self.value)
+        } else {
+            format!("{} {}", column_name, self.op.symbol())
+        }
+    }
+}
+
+/// Whether a cell holds NULL (rendered as `—`) or nothing.
+pub(super) fn is_null(cell: &str) -> bool {
+    cell.is_empty() || cell == NULL_DISPLAY
+}
+
+/// Evaluate conditions against a row: OR of AND-groups, left to right.
+/// An empty list matches every row.
+pub fn row_matches(
+    conditions: &[FilterCondition],
+    row: &[String],
+    column_type: impl Fn(usize) -> ColumnType,
+) -> bool {
+    if conditions.is_empty() {
+        return true;
+    }
+
+    let mut any_group = false;
+    let mut group = true;
+    for (i, condition) in conditions.iter().enumerate() {
+        if i > 0 && condition.join == FilterJoin::Or {
+            any_group |= group;
+            group = true;
+        }
+        if group {
+            group = row
+                .get(condition.column)
+                .is_some_and(|cell| condition.matches(cell, column_type(condition.column)));
+        }
+    }
+    any_group || group
+}
+
+/// Compare a cell with a filter value according to the column type.
+///
+/// Numbers compare numerically. Dates compare on the precision of the value,
+/// so `2024-01` matches any day in January 2024. Text compares numerically
+/// when both sides are numbers, otherwise case-insensitively.
+fn compare(cell: &str, value: &str, column_type: ColumnType) -> Option<Ordering> {
+    let cell = cell.trim();
+    let value = value.trim();
+    let numeric = || match (cell.parse::<f64>(), value.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b),
+        _ => None,
+    };
+
+    match column_type {
+        ColumnType::Number => numeric(),
+        ColumnType::Date => {
+            let cell = cell.replace(' ', "T");
+            let value = value.replace(' ', "T");
+            let prefix = cell.get(..value.len().min(cell.len()))?;
+            Some(prefix.cmp(value.as_str()))
+        }
+        ColumnType::Text | ColumnType::Boolean => {
+            numeric().or_else(|| Some(cell.to_lowercase().cmp(&value.to_lowercase())))
+        }
+    }
+}
+
+/// Split a `low..high` range value.
+fn split_range(value: &str) -> Option<(&str, &str)> {
+    let (low, high) = value.split_once("..")?;
+    let (low, high) = (low.trim(), high.trim());
+    (!low.is_empty() && !high.is_empty()).then_some((low, high))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cond(join: FilterJoin, column: usize, op: FilterOp, value: &str) -> FilterCondition {
+        FilterCondition::new(join, column, op, value).unwrap()
+    }
+
+    #[test]
+    fn test_filter_condition_operators() {
+        let text = ColumnType::Text;
+        let num = ColumnType::Number;
+        let date = ColumnType::Date;
+
+        assert!(cond(FilterJoin::And, 0, FilterOp::Between, "10..20").matches("15", num));
+        assert!(!cond(FilterJoin::And, 0, FilterOp::Between, "10..20").matches("25", num));
+        assert!(cond(FilterJoin::And, 0, FilterOp::In, "JP, us").matches("US", text));
+        assert!(cond(FilterJoin::And, 0, FilterOp::StartsWith, "ja").matches("Japan", text));
+        assert!(cond(FilterJoin::And, 0, FilterOp::Regex, "^a.c$").matches("abc", text));
+        assert!(cond(FilterJoin::And, 0, FilterOp::IsNull, "").matches("—", text));
+        assert!(!cond(FilterJoin::And, 0, FilterOp::Gt, "1").matches("—", num));
+        assert!(cond(FilterJoin::And, 0, FilterOp::NotNull, "").matches("x", text));
+
+        // Number columns compare numerically ("9" < "10")
+        assert!(cond(FilterJoin::And, 0, FilterOp::Lt, "10").matches("9", num));
+        // Dates compare on the precision of the value
+        assert!(
+            cond(FilterJoin::And, 0, FilterOp::Eq, "2024-01").matches("2024-01-15T10:00:00Z", date)
+        );
+        assert!(
+            cond(FilterJoin::And, 0, FilterOp::Gt, "2024-01-15").matches("2024-01-16 08:00", date)
+        );
+        assert!(
+            !cond(FilterJoin::And, 0, FilterOp::Gt, "2024-01-15").matches("2024-01-15T23:59", date)
+        );
+    }
+
+    #[test]
+    fn test_filter_condition_validation() {
+        assert!(FilterCondition::new(FilterJoin::And, 0, FilterOp::Regex, "(").is_err());
+        assert!(FilterCondition::new(FilterJoin::And, 0, FilterOp::Between, "10").is_err());
+        assert!(FilterCondition::new(FilterJoin::And, 0, FilterOp::Eq, " ").is_err());
+        assert!(FilterCondition::new(FilterJoin::And, 0, FilterOp::IsNull, "").is_ok());
+    }
+
+    #[test]
+    fn test_row_matches_and_binds_tighter_than_or() {
+        let row = vec!["JP".to_string(), "5".to_string()];
+        let types = |_| ColumnType::Text;
+
+        // country = US AND n > 1 OR n < 10  →  (false AND true) OR true
+        let conditions = vec![
+            cond(FilterJoin::And, 0, FilterOp::Eq, "US"),
+            cond(FilterJoin::And, 1, FilterOp::Gt, "1"),
+            cond(FilterJoin::Or, 1, FilterOp::Lt, "10"),
+        ];
+        assert!(row_matches(&conditions, &row, types));
+
+        // country = US OR n > 1 AND n > 7  →  false OR (true AND false)
+        let conditions = vec![
+            cond(FilterJoin::And, 0, FilterOp::Eq, "US"),
+            cond(FilterJoin::Or, 1, FilterOp::Gt, "1"),
+            cond(FilterJoin::And, 1, FilterOp::Gt, "7"),
+        ];
+        assert!(!row_matches(&conditions, &row, types));
+        assert!(row_matches(&[], &row, types));
+    }
+}
@@ -0,0 +1,375 @@
+//! Summary footer and group-by for query results.
+//!
+//! Grouping swaps the displayed result for an aggregated one (see
+//! `aggregate::group_rows`), so sort, filter, search and export work on
+//! groups unchanged. Expanding a group shows its detail rows; collapsing
+//! and ungrouping restore the previous view state.
+
+use super::ContentPanel;
+use super::aggregate::{ColumnSummary, group_rows, summarize};
+use super::columns::ColumnLayout;
+use super::filter_expr::FilterCondition;
+use super::types::{QueryResultData, SortKey};
+
+/// Saved state of a results view (data plus sort, filter, search and layout).
+#[derive(Debug)]
+pub(super) struct ResultViewState {
+    data: QueryResultData,
+    column_layout: ColumnLayout,
+    cached_column_widths: Option<Vec<u16>>,
+    sort_keys: Vec<SortKey>,
+    filters: Vec<FilterCondition>,
+    search_text: String,
+    page: usize,
+    selected: Option<usize>,
+    scroll_x: usize,
+}
+
+impl ResultViewState {
+    /// Question (or table) the saved result belongs to.
+    pub(super) fn question_id(&self) -> u32 {
+        self.data.question_id
+    }
+
+    /// The saved result.
+    pub(super) fn data(&self) -> &QueryResultData {
+        &self.data
+    }
+}
+
+/// Active group-by: the grouped source view and the aggregated view.
+#[derive(Debug)]
+pub struct GroupState {
+    /// Group-by column indices in the source result
+    columns: Vec<usize>,
+    /// Source row indices per aggregated row
+    groups: Vec<Vec<usize>>,
+    /// Source view, restored when ungrouping
+    source: ResultViewState,
+    /// Aggregated view, kept while a group is expanded
+    aggregated: Option<ResultViewState>,
+}
+
+impl ContentPanel {
+    // === Summary footer ===
+
+    /// Show or hide the summary footer.
+    pub fn toggle_summary(&mut self) {
+        self.summary_visible = !self.summary_visible;
+    }
+
+    /// Whether the summary footer is shown.
+    pub fn is_summary_visible(&self) -> bool {
+        self.summary_visible
+    }
+
+    /// Summaries of all columns over the visible (searched and filtered) rows.
+    pub(super) fn visible_summary(&self) -> Vec<ColumnSummary> {
+        let Some(ref result) = self.query_result else {
+            return Vec::new();
+        };
+        let rows: Vec<usize> = (0..self.visible_row_count())
+            .filter_map(|i| self.visible_row_index(i))
+            .collect();
+        summarize(result, &rows)
+    }
+
+    // === Group-by modal ===
+
+    /// Check if the group-by modal is active.
+    pub fn is_group_modal_active(&self) -> bool {
+        self.group_modal_active
+    }
+
+    /// Open the group-by column selection, pre-checking the current grouping.
+    pub fn open_group_modal(&mut self) {
+        let columns = match self.group {
+            Some(ref group) => {
+                self.group_modal_checked = group.columns.clone();
+                group.source.data.columns.len()
+            }
+            None => {
+                self.group_modal_checked.clear();
+                self.query_result.as_ref().map_or(0, |r| r.columns.len())
+            }
+        };
+        if columns > 0 {
+            self.group_modal_active = true;
+            self.group_modal_selection = self.group_modal_checked.first().copied().unwrap_or(0);
+        }
+    }
+
+    /// Close the group-by modal without applying.
+    pub fn close_group_modal(&mut self) {
+        self.group_modal_active = false;
+    }
+
+    /// Move selection up in the group-by modal.
+    pub fn group_modal_up(&mut self) {
+        self.group_modal_selection = self.group_modal_selection.saturating_sub(1);
+    }
+
+    /// Move selection down in the group-by modal.
+    pub fn group_modal_down(&mut self) {
+        if self.group_modal_selection + 1 < self.group_source_columns().len() {
+            self.group_modal_selection += 1;
+        }
+    }
+
+    /// Check or uncheck the selected column (checked order is the group order).
+    pub fn group_modal_toggle(&mut self) {
+        let col = self.group_modal_selection;
+        match self.group_modal_checked.iter().position(|&c| c == col) {
+            Some(pos) => {
+                self.group_modal_checked.remove(pos);
+            }
+            None => self.group_modal_checked.push(col),
+        }
+    }
+
+    /// Apply the checked columns; with none checked, removes the grouping.
+    pub fn apply_group_modal(&mut self) {
+        self.group_modal_active = false;
+        let columns = std::mem::take(&mut self.group_modal_checked);
+        if columns.is_empty() {
+            self.ungroup();
+        } else {
+            self.group_by(columns);
+        }
+    }
+
+    /// Columns offered in the group-by modal (those of the ungrouped result).
+    pub(super) fn group_source_columns(&self) -> &[String] {
+        match self.group {
+            Some(ref group) => &group.source.data.columns,
+            None => self.query_result.as_ref().map_or(&[], |r| &r.columns),
+        }
+    }
+
+    // === Grouping ===
+
+    /// Whether the result is grouped (aggregated or showing a group's rows).
+    pub fn is_grouped(&self) -> bool {
+        self.group.is_some()
+    }
+
+    /// Group the result by columns of the ungrouped result.
+    /// Groups are built from the rows visible before grouping.
+    pub fn group_by(&mut self, columns: Vec<usize>) {
+        // Regrouping starts again from the source view
+        self.ungroup();
+        let Some(ref result) = self.query_result else {
+            return;
+        };
+        if columns.is_empty() || columns.iter().any(|&c| c >= result.columns.len()) {
+            return;
+        }
+
+        let rows: Vec<usize> = (0..self.visible_row_count())
+            .filter_map(|i| self.visible_row_index(i))
+            .collect();
+        let (aggregated, groups) = group_rows(result, &rows, &columns);
+
+        let Some(source) = self.take_view_state() else {
+            return;
+        };
+        self.show_result_data(aggregated);
+        self.group = Some(GroupState {
+            columns,
+            groups,
+            source,
+            aggregated: None,
+        });
+    }
+
+    /// Remove the grouping and restore the source view.
+    pub fn ungroup(&mut self) {
+        if let Some(group) = self.group.take() {
+            self.restore_view_state(group.source);
+        }
+    }
+
+    /// Show the detail rows of the selected group.
+    /// Returns false if the aggregated view is not shown.
+    pub fn expand_selected_group(&mut self) -> bool {
+        let Some(index) = self.selected_row_index() else {
+            return false;
+        };
+        let Some(ref group) = self.group else {
+            return false;
+        };
+        if group.aggregated.is_some() {
+            return false;
+        }
+        let Some(members) = group.groups.get(index) else {
+            return false;
+        };
+
+        let source = &group.source.data;
+        let key: Vec<&str> = group
+            .columns
+            .iter()
+            .filter_map(|&c| source.rows.get(members[0])?.get(c).map(|s| s.as_str()))
+            .collect();
+        let detail = QueryResultData {
+            question_id: source.question_id,
+            question_name: format!("{} · {}", source.question_name, key.join(", ")),
+            columns: source.columns.clone(),
+            rows: members.iter().map(|&r| source.rows[r].clone()).collect(),
+            column_types: source.column_types.clone(),
+            column_keys: source.column_keys.clone(),
+        };
+        let layout = group.source.column_layout.clone();
+        let widths = group.source.cached_column_widths.clone();
+
+        let aggregated = self.take_view_state();
+        self.show_result_data(detail);
+        self.column_layout = layout;
+        self.cached_column_widths = widths;
+        if let Some(ref mut group) = self.group {
+            group.aggregated = aggregated;
+        }
+        true
+    }
+
+    /// Step back out of grouping: from a group's rows to the aggregated view,
+    /// or from the aggregated view to the ungrouped result.
+    /// Returns false if the result is not grouped.
+    pub fn collapse_group(&mut self) -> bool {
+        let Some(ref mut group) = self.group else {
+            return false;
+        };
+        match group.aggregated.take() {
+            Some(aggregated) => self.restore_view_state(aggregated),
+            None => self.ungroup(),
+        }
+        true
+    }
+
+    /// Actual row index of the selected row in the current result.
+    fn selected_row_index(&self) -> Option<usize> {
+        let selected = self.result_table_state.selected()?;
+        self.visible_row_index(self.result_page * self.rows_per_page + selected)
+    }
+
+    /// Replace the displayed result, resetting sort, filter, search and layout.
+    /// Keeps the view and the navigation stack.
+    pub(super) fn show_result_data(&mut self, data: QueryResultData) {
+        let group = self.group.take();
+        let has_rows = !data.rows.is_empty();
+        self.query_result = Some(data);
+        self.sort_indices = None;
+        self.filter_indices = None;
+        self.result_page = 0;
+        self.scroll_x = 0;
+        self.clear_selection();
+        self.reset_sort_filter_state();
+        self.reset_column_layout_for_result();
+        self.result_table_state.select(has_rows.then_some(0));
+        self.group = group;
+    }
+
+    /// Take the displayed result and its view state.
+    pub(super) fn take_view_state(&mut self) -> Option<ResultViewState> {
+        Some(ResultViewState {
+            data: self.query_result.take()?,
+            column_layout: std::mem::take(&mut self.column_layout),
+            cached_column_widths: self.cached_column_widths.take(),
+            sort_keys: std::mem::take(&mut self.sort_keys),
+            filters: std::mem::take(&mut self.filters),
+            search_text: std::mem::take(&mut self.result_search_text),
+            page: self.result_page,
+            selected: self.result_table_state.selected(),
+            scroll_x: self.scroll_x,
+        })
+    }
+
+    /// Restore a result and its view state, recomputing search, filter and sort.
+    pub(super) fn restore_view_state(&mut self, state: ResultViewState) {
+        self.show_result_data(state.data);
+        self.column_layout = state.column_layout;
+        self.cached_column_widths = state.cached_column_widths;
+        self.sort_keys = state.sort_keys;
+        self.filters = state.filters;
+        self.result_search_text = state.search_text;
+        self.update_result_search_indices();
+        self.update_filter_indices();
+        self.update_sort_indices();
+        self.result_page = state.page;
+        self.result_table_state.select(state.selected);
+        self.scroll_x = state.scroll_x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::{ColumnType, FilterJoin, FilterOp};
+
+    fn panel() -> ContentPanel {
+        let row = |c: &str, v: &str| vec![c.to_string(), v.to_string()];
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(QueryResultData {
+            question_id: 1,
+            question_name: "Sales".to_string(),
+            columns: vec!["Country".to_string(), "Revenue".to_string()],
+            rows: vec![
+                row("JP", "10"),
+                row("US", "5"),
+                row("JP", "20"),
+                row("FR", "1"),
+            ],
+            column_types: vec![ColumnType::Text, ColumnType::Number],
+            column_keys: vec![],
+        });
+        panel
+    }
+
+    #[test]
+    fn test_group_expand_collapse_restores_view() {
+        let mut panel = panel();
+        panel.set_filters(vec![
+            FilterCondition::new(FilterJoin::And, 1, FilterOp::Ge, "5").unwrap(),
+        ]);
+
+        panel.group_by(vec![0]);
+        assert!(panel.is_grouped());
+        let agg = panel.query_result.as_ref().unwrap();
+        // FR is filtered out before grouping
+        assert_eq!(agg.rows.len(), 2);
+        assert_eq!(agg.rows[0], vec!["JP", "2", "30", "15"]);
+
+        // Expand JP
+        assert!(panel.expand_selected_group());
+        assert_eq!(panel.query_result.as_ref().unwrap().rows.len(), 2);
+        assert!(!panel.expand_selected_group());
+
+        // Back to the aggregated view, then to the filtered source
+        assert!(panel.collapse_group());
+        assert_eq!(panel.query_result.as_ref().unwrap().columns[1], "Count");
+        assert!(panel.collapse_group());
+        assert!(!panel.is_grouped());
+        assert_eq!(panel.filters.len(), 1);
+        assert_eq!(panel.filter_indices, Some(vec![0, 1, 2]));
+        assert!(!panel.collapse_group());
+    }
+
+    #[test]
+    fn test_group_modal_and_summary() {
+        let mut panel = panel();
+        panel.open_group_modal();
+        panel.group_modal_toggle();
+        panel.apply_group_modal();
+        assert_eq!(panel.query_result.as_ref().unwrap().rows.len(), 3);
+
+        // Reopening pre-checks the grouping; unchecking all ungroups
+        panel.open_group_modal();
+        assert_eq!(panel.group_modal_checked, vec![0]);
+        panel.group_modal_toggle();
+        panel.apply_group_modal();
+        assert!(!panel.is_grouped());
+
+        let summary = panel.visible_summary();
+        assert_eq!(summary[1].numeric.unwrap().sum, 36.0);
+    }
+}
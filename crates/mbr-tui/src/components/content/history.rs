@@ -0,0 +1,121 @@
+//! History view of executed questions.
+//!
+//! Lists the executions recorded in `TuiState` (newest first) so that past
+//! questions can be re-run. The view is pushed on the navigation stack and
+//! returns to the view it was opened from.
+
+use mbr_core::storage::tui_state::HistoryEntry;
+use ratatui::widgets::TableState;
+
+use super::ContentPanel;
+use super::types::ContentView;
+
+impl ContentPanel {
+    /// Check if current view is History.
+    pub fn is_history_view(&self) -> bool {
+        self.view == ContentView::History
+    }
+
+    /// Show the history view with the given entries.
+    pub fn enter_history(&mut self, entries: Vec<HistoryEntry>) {
+        if !self.is_history_view() {
+            self.push_view(ContentView::History);
+        }
+        self.history_table_state = TableState::default();
+        self.update_history(entries);
+    }
+
+    /// Leave the history view (to Questions if it was opened without a parent).
+    pub fn exit_history(&mut self) {
+        if !self.pop_to_available_view() {
+            self.view = ContentView::Questions;
+        }
+    }
+
+    /// Replace the history entries, keeping the selection in range.
+    pub fn update_history(&mut self, entries: Vec<HistoryEntry>) {
+        self.history = entries;
+        let selected = match self.history_table_state.selected() {
+            _ if self.history.is_empty() => None,
+            Some(i) => Some(i.min(self.history.len() - 1)),
+            None => Some(0),
+        };
+        self.history_table_state.select(selected);
+    }
+
+    /// Question id of the selected history entry.
+    pub fn get_selected_history_question(&self) -> Option<u32> {
+        if !self.is_history_view() {
+            return None;
+        }
+        let selected = self.history_table_state.selected()?;
+        self.history.get(selected).map(|e| e.question_id)
+    }
+
+    // === History view navigation ===
+
+    /// Select next history entry.
+    pub(super) fn select_history_next(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let current = self.history_table_state.selected().unwrap_or(0);
+        let next = (current + 1).min(self.history.len() - 1);
+        self.history_table_state.select(Some(next));
+    }
+
+    /// Select previous history entry.
+    pub(super) fn select_history_previous(&mut self) {
+        let current = self.history_table_state.selected().unwrap_or(0);
+        self.history_table_state
+            .select(Some(current.saturating_sub(1)));
+    }
+
+    /// Select first history entry.
+    pub(super) fn select_history_first(&mut self) {
+        if !self.history.is_empty() {
+            self.history_table_state.select(Some(0));
+        }
+    }
+
+    /// Select last history entry.
+    pub(super) fn select_history_last(&mut self) {
+        if !self.history.is_empty() {
+            self.history_table_state
+                .select(Some(self.history.len() - 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(question_id: u32) -> HistoryEntry {
+        HistoryEntry {
+            question_id,
+            name: format!("Q{}", question_id),
+            executed_at: "2026-10-18T09:30:00Z".to_string(),
+            rows: 3,
+            duration_ms: 120,
+        }
+    }
+
+    #[test]
+    fn test_history_view_navigation() {
+        let mut panel = ContentPanel::new();
+        panel.set_view(ContentView::Collections);
+        panel.enter_history(vec![entry(5), entry(7)]);
+        assert!(panel.is_history_view());
+        assert_eq!(panel.get_selected_history_question(), Some(5));
+
+        panel.select_history_last();
+        assert_eq!(panel.get_selected_history_question(), Some(7));
+        // Selection stays in range when entries shrink
+        panel.update_history(vec![entry(1)]);
+        assert_eq!(panel.get_selected_history_question(), Some(1));
+
+        panel.exit_history();
+        assert_eq!(panel.current_view(), ContentView::Collections);
+    }
+}
@@ -2,11 +2,14 @@
 //!
 //! Implements the Component trait's handle_key method with view-specific
 //! key bindings for navigation, search, sort, filter, and modal interactions.
+//! Navigation keys are resolved through the configurable `Keymap`; text inputs
+//! and modal Enter/Esc keys are fixed.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::ContentPanel;
-use super::types::{ContentView, InputMode};
+use super::types::{ContentView, FilterStep, InputMode};
+use crate::keymap::KeyAction;
 
 impl ContentPanel {
     /// Handle key events for the content panel.
@@ -22,11 +25,13 @@ impl ContentPanel {
             ContentView::Questions => self.handle_questions_key(key),
             ContentView::Collections => self.handle_collections_key(key),
             ContentView::Databases => self.handle_databases_key(key),
-            ContentView::QueryResult => self.handle_query_result_key(key),
-            ContentView::CollectionQuestions { .. } => self.handle_collection_questions_key(key),
+            ContentView::QueryResult | ContentView::LinkedRows { .. } => {
+                self.handle_query_result_key(key)
+            }
             ContentView::DatabaseSchemas { .. } => self.handle_database_schemas_key(key),
             ContentView::SchemaTables { .. } => self.handle_schema_tables_key(key),
             ContentView::TablePreview { .. } => self.handle_table_preview_key(key),
+            ContentView::History => self.handle_history_key(key),
             ContentView::Welcome => self.handle_welcome_key(key),
         }
     }
@@ -47,27 +52,32 @@ impl ContentPanel {
         }
     }
 
+    /// Resolve a key event through the active keymap.
+    fn resolve_key(&self, key: KeyEvent) -> Option<KeyAction> {
+        self.keymap.resolve(key.code, key.modifiers)
+    }
+
     /// Handle keys in Questions view.
     /// Note: 'n'/'p' for pagination are handled by App (need to send actions).
     fn handle_questions_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
                 self.select_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 self.select_next();
                 true
             }
-            KeyCode::Home | KeyCode::Char('g') => {
+            Some(KeyAction::First) => {
                 self.select_first();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            Some(KeyAction::Last) => {
                 self.select_last();
                 true
             }
-            KeyCode::Char('/') => {
+            Some(KeyAction::Search) => {
                 self.enter_search_mode();
                 true
             }
@@ -78,20 +88,20 @@ impl ContentPanel {
 
     /// Handle keys in Collections view.
     fn handle_collections_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
                 self.select_collections_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 self.select_collections_next();
                 true
             }
-            KeyCode::Home | KeyCode::Char('g') => {
+            Some(KeyAction::First) => {
                 self.select_collections_first();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            Some(KeyAction::Last) => {
                 self.select_collections_last();
                 true
             }
@@ -101,20 +111,20 @@ impl ContentPanel {
 
     /// Handle keys in Databases view.
     fn handle_databases_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
                 self.select_databases_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 self.select_databases_next();
                 true
             }
-            KeyCode::Home | KeyCode::Char('g') => {
+            Some(KeyAction::First) => {
                 self.select_databases_first();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            Some(KeyAction::Last) => {
                 self.select_databases_last();
                 true
             }
@@ -122,24 +132,24 @@ impl ContentPanel {
         }
     }
 
-    /// Handle keys in CollectionQuestions view.
-    fn handle_collection_questions_key(&mut self, key: KeyEvent) -> bool {
-        // Same navigation as Questions, Enter/Esc handled by App
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.select_previous();
+    /// Handle keys in History view.
+    /// Note: Enter (re-run) and Esc (back) are handled by App.
+    fn handle_history_key(&mut self, key: KeyEvent) -> bool {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
+                self.select_history_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.select_next();
+            Some(KeyAction::Down) => {
+                self.select_history_next();
                 true
             }
-            KeyCode::Home | KeyCode::Char('g') => {
-                self.select_first();
+            Some(KeyAction::First) => {
+                self.select_history_first();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
-                self.select_last();
+            Some(KeyAction::Last) => {
+                self.select_history_last();
                 true
             }
             _ => false,
@@ -148,20 +158,20 @@ impl ContentPanel {
 
     /// Handle keys in DatabaseSchemas view.
     fn handle_database_schemas_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
                 self.select_schemas_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 self.select_schemas_next();
                 true
             }
-            KeyCode::Home | KeyCode::Char('g') => {
+            Some(KeyAction::First) => {
                 self.select_schemas_first();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            Some(KeyAction::Last) => {
                 self.select_schemas_last();
                 true
             }
@@ -171,20 +181,20 @@ impl ContentPanel {
 
     /// Handle keys in SchemaTables view.
     fn handle_schema_tables_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
                 self.select_tables_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 self.select_tables_next();
                 true
             }
-            KeyCode::Home | KeyCode::Char('g') => {
+            Some(KeyAction::First) => {
                 self.select_tables_first();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            Some(KeyAction::Last) => {
                 self.select_tables_last();
                 true
             }
@@ -201,6 +211,15 @@ impl ContentPanel {
         if self.sort_mode_active {
             return self.handle_sort_modal_key(key);
         }
+        if self.column_manager_active {
+            return self.handle_column_manager_key(key);
+        }
+        if self.group_modal_active {
+            return self.handle_group_modal_key(key);
+        }
+        if self.column_profile.is_some() {
+            return self.handle_column_profile_key(key);
+        }
         if self.result_search_active {
             return self.handle_result_search_key(key);
         }
@@ -218,6 +237,15 @@ impl ContentPanel {
         if self.sort_mode_active {
             return self.handle_sort_modal_key(key);
         }
+        if self.column_manager_active {
+            return self.handle_column_manager_key(key);
+        }
+        if self.group_modal_active {
+            return self.handle_group_modal_key(key);
+        }
+        if self.column_profile.is_some() {
+            return self.handle_column_profile_key(key);
+        }
         if self.result_search_active {
             return self.handle_result_search_key(key);
         }
@@ -228,22 +256,22 @@ impl ContentPanel {
 
     /// Handle keys in Welcome view.
     fn handle_welcome_key(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
+        match self.resolve_key(key) {
+            Some(KeyAction::Up) => {
                 self.scroll.scroll_up();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 self.scroll.scroll_down();
                 true
             }
-            KeyCode::PageUp => {
+            Some(KeyAction::PageUp) => {
                 for _ in 0..self.scroll.visible {
                     self.scroll.scroll_up();
                 }
                 true
             }
-            KeyCode::PageDown => {
+            Some(KeyAction::PageDown) => {
                 for _ in 0..self.scroll.visible {
                     self.scroll.scroll_down();
                 }
@@ -255,72 +283,221 @@ impl ContentPanel {
 
     /// Handle keys in filter modal.
     fn handle_filter_modal_key(&mut self, key: KeyEvent) -> bool {
+        let step = self.filter_modal_step;
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.filter_modal_up();
+            KeyCode::Enter => {
+                // Condition list → edit/add, column → operator → value → apply
+                self.filter_modal_next_step();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.filter_modal_down();
+            KeyCode::Esc => {
+                // Back one step; closes from the first step
+                self.filter_modal_prev_step();
                 true
             }
-            KeyCode::Enter => {
-                if self.filter_modal_step == 0 {
-                    // Column selection → text input
-                    self.filter_modal_next_step();
-                } else {
-                    // Text input → apply filter
-                    self.apply_filter();
-                }
+            KeyCode::Backspace if step == FilterStep::Value => {
+                self.filter_modal_delete_char();
                 true
             }
-            KeyCode::Esc => {
-                if self.filter_modal_step == 1 {
-                    // Text input → column selection
-                    self.filter_modal_prev_step();
-                } else {
-                    // Column selection → close modal
-                    self.close_filter_modal();
-                }
+            KeyCode::Tab if step != FilterStep::Conditions => {
+                self.filter_modal_toggle_join();
                 true
             }
-            KeyCode::Char('f') if self.filter_modal_step == 0 => {
-                // Close modal if 'f' pressed in column selection
-                self.close_filter_modal();
+            KeyCode::Char(c) if step == FilterStep::Value => {
+                self.filter_modal_input_char(c);
                 true
             }
-            KeyCode::Backspace => {
-                self.filter_modal_delete_char();
+            KeyCode::Delete | KeyCode::Char('d') if step == FilterStep::Conditions => {
+                self.filter_modal_remove_selected();
                 true
             }
-            KeyCode::Char(c) if self.filter_modal_step == 1 => {
-                self.filter_modal_input_char(c);
+            KeyCode::Char(' ') | KeyCode::Char('o') if step == FilterStep::Conditions => {
+                self.filter_modal_toggle_join();
                 true
             }
-            _ => false,
+            KeyCode::Char('a') if step == FilterStep::Conditions => {
+                self.start_new_condition();
+                true
+            }
+            // List steps: navigation via keymap
+            _ => match self.resolve_key(key) {
+                Some(KeyAction::Up) => {
+                    self.filter_modal_up();
+                    true
+                }
+                Some(KeyAction::Down) => {
+                    self.filter_modal_down();
+                    true
+                }
+                Some(KeyAction::Filter) if step != FilterStep::Value => {
+                    // Close modal if the filter key is pressed in a list step
+                    self.close_filter_modal();
+                    true
+                }
+                _ => false,
+            },
         }
     }
 
     /// Handle keys in sort modal.
     fn handle_sort_modal_key(&mut self, key: KeyEvent) -> bool {
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.sort_modal_up();
+            // Shift+Enter (where the terminal reports it) or Space adds a sort key
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.apply_sort_key();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.sort_modal_down();
+            KeyCode::Char(' ') => {
+                self.apply_sort_key();
                 true
             }
             KeyCode::Enter => {
                 self.apply_sort();
                 true
             }
-            KeyCode::Esc | KeyCode::Char('s') => {
+            KeyCode::Esc => {
                 self.close_sort_modal();
                 true
             }
-            _ => false,
+            _ => match self.resolve_key(key) {
+                Some(KeyAction::Up) => {
+                    self.sort_modal_up();
+                    true
+                }
+                Some(KeyAction::Down) => {
+                    self.sort_modal_down();
+                    true
+                }
+                Some(KeyAction::Sort) => {
+                    self.close_sort_modal();
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Handle keys in column manager modal.
+    fn handle_column_manager_key(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                self.close_column_manager();
+                true
+            }
+            KeyCode::Char(' ') => {
+                self.column_manager_toggle_hidden();
+                true
+            }
+            KeyCode::Up if shift => {
+                self.column_manager_move_up();
+                true
+            }
+            KeyCode::Down if shift => {
+                self.column_manager_move_down();
+                true
+            }
+            KeyCode::Char('K') => {
+                self.column_manager_move_up();
+                true
+            }
+            KeyCode::Char('J') => {
+                self.column_manager_move_down();
+                true
+            }
+            KeyCode::Char('p') => {
+                self.column_manager_toggle_pin();
+                true
+            }
+            KeyCode::Char('+') | KeyCode::Char('>') => {
+                self.column_manager_resize(true);
+                true
+            }
+            KeyCode::Char('-') | KeyCode::Char('<') => {
+                self.column_manager_resize(false);
+                true
+            }
+            KeyCode::Char('=') => {
+                self.column_manager_reset_width();
+                true
+            }
+            KeyCode::Char('R') => {
+                self.reset_column_layout();
+                true
+            }
+            _ => match self.resolve_key(key) {
+                Some(KeyAction::Up) => {
+                    self.column_manager_up();
+                    true
+                }
+                Some(KeyAction::Down) => {
+                    self.column_manager_down();
+                    true
+                }
+                Some(KeyAction::Columns) => {
+                    self.close_column_manager();
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Handle keys in group-by modal.
+    fn handle_group_modal_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Enter => {
+                self.apply_group_modal();
+                true
+            }
+            KeyCode::Esc => {
+                self.close_group_modal();
+                true
+            }
+            KeyCode::Char(' ') => {
+                self.group_modal_toggle();
+                true
+            }
+            _ => match self.resolve_key(key) {
+                Some(KeyAction::Up) => {
+                    self.group_modal_up();
+                    true
+                }
+                Some(KeyAction::Down) => {
+                    self.group_modal_down();
+                    true
+                }
+                Some(KeyAction::GroupBy) => {
+                    self.close_group_modal();
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Handle keys in the column profile popup.
+    fn handle_column_profile_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => {
+                self.close_column_profile();
+                true
+            }
+            _ => match self.resolve_key(key) {
+                Some(KeyAction::Left) | Some(KeyAction::Up) => {
+                    self.column_profile_step(false);
+                    true
+                }
+                Some(KeyAction::Right) | Some(KeyAction::Down) => {
+                    self.column_profile_step(true);
+                    true
+                }
+                Some(KeyAction::Profile) => {
+                    self.close_column_profile();
+                    true
+                }
+                _ => false,
+            },
         }
     }
 
@@ -349,18 +526,8 @@ impl ContentPanel {
 
     /// Handle result table navigation keys.
     fn handle_result_navigation_key(&mut self, key: KeyEvent) -> bool {
-        // Check for modifier keys first
-        let has_shift = key.modifiers.contains(KeyModifiers::SHIFT);
-        let has_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
-
-        // Multi-select: Ctrl+A for select all
-        if has_ctrl && key.code == KeyCode::Char('a') {
-            self.select_all_rows();
-            return true;
-        }
-
-        // Range selection: Shift+Arrow/Home/End/PageUp/PageDown
-        if has_shift {
+        // Range selection: Shift+Arrow/Home/End/PageUp/PageDown (fixed keys)
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
             match key.code {
                 KeyCode::Up => {
                     // Move cursor up and extend selection
@@ -439,9 +606,9 @@ impl ContentPanel {
             }
         }
 
-        match key.code {
-            // Multi-select: Space to toggle selection
-            KeyCode::Char(' ') => {
+        match self.resolve_key(key) {
+            // Multi-select: toggle selection
+            Some(KeyAction::ToggleSelect) => {
                 if let Some(row_idx) = self.get_current_row_index() {
                     self.toggle_row_selection(row_idx);
                     // Clear anchor when using space toggle
@@ -449,86 +616,110 @@ impl ContentPanel {
                 }
                 true
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            Some(KeyAction::SelectAll) => {
+                self.select_all_rows();
+                true
+            }
+            Some(KeyAction::Up) => {
                 // Clear anchor on normal navigation
                 self.selection_anchor = None;
                 self.select_result_previous();
                 true
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Some(KeyAction::Down) => {
                 // Clear anchor on normal navigation
                 self.selection_anchor = None;
                 self.select_result_next();
                 true
             }
-            // Horizontal scroll with h/l or Left/Right arrows
-            KeyCode::Left | KeyCode::Char('h') => {
+            // Horizontal scroll
+            Some(KeyAction::Left) => {
                 self.scroll_left();
                 true
             }
-            KeyCode::Right | KeyCode::Char('l') => {
+            Some(KeyAction::Right) => {
                 self.scroll_right();
                 true
             }
-            // Pagination: n for next page, p for previous page (matches CLI)
-            KeyCode::Char('n') => {
+            // Pagination (n/p by default, matches CLI)
+            Some(KeyAction::NextPage) => {
                 self.next_page();
                 true
             }
-            KeyCode::Char('p') => {
+            Some(KeyAction::PrevPage) => {
                 self.prev_page();
                 true
             }
-            // PageUp/PageDown for scrolling within page (matches CLI)
-            KeyCode::PageUp => {
+            // Scrolling within page (matches CLI)
+            Some(KeyAction::PageUp) => {
                 self.scroll_result_page_up();
                 true
             }
-            KeyCode::PageDown => {
+            Some(KeyAction::PageDown) => {
                 self.scroll_result_page_down();
                 true
             }
-            // First/Last page with g/G
-            KeyCode::Home | KeyCode::Char('g') => {
+            // First/Last page
+            Some(KeyAction::First) => {
                 self.first_page();
                 true
             }
-            KeyCode::End | KeyCode::Char('G') => {
+            Some(KeyAction::Last) => {
                 self.last_page();
                 true
             }
-            // Sort: s to open sort modal
-            KeyCode::Char('s') => {
+            // Sort modal
+            Some(KeyAction::Sort) => {
                 self.open_sort_modal();
                 true
             }
-            // Filter: f to open filter modal
-            KeyCode::Char('f') => {
+            // Column manager modal
+            Some(KeyAction::Columns) => {
+                self.open_column_manager();
+                true
+            }
+            // Summary footer
+            Some(KeyAction::Summary) => {
+                self.toggle_summary();
+                true
+            }
+            // Group-by modal
+            Some(KeyAction::GroupBy) => {
+                self.open_group_modal();
+                true
+            }
+            // Column profile popup
+            Some(KeyAction::Profile) => {
+                self.open_column_profile();
+                true
+            }
+            // Filter modal
+            Some(KeyAction::Filter) => {
                 self.open_filter_modal();
                 true
             }
-            // Clear filter: F (shift+f) to clear filter
-            KeyCode::Char('F') => {
+            // Clear filter
+            Some(KeyAction::ClearFilter) => {
                 self.clear_filter();
                 // Reset to first page after clearing filter
                 self.result_page = 0;
                 self.result_table_state.select(Some(0));
                 true
             }
-            // Search: / to open search input
-            KeyCode::Char('/') => {
+            // Search input
+            Some(KeyAction::Search) => {
                 self.open_result_search();
                 true
             }
-            // Clear search: Shift+S to clear search
-            KeyCode::Char('S') => {
+            // Clear search
+            Some(KeyAction::ClearSearch) => {
                 self.clear_result_search();
                 // Reset to first page after clearing search
                 self.result_page = 0;
                 self.result_table_state.select(Some(0));
                 true
             }
-            // Note: Esc is handled in App for returning to previous view
+            // Note: Back (Esc) is handled in App for returning to previous view
             _ => false,
         }
     }
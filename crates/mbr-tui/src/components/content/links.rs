@@ -0,0 +1,159 @@
+//! Foreign-key navigation between result rows.
+//!
+//! Following a key from a record shows the linked rows in a `LinkedRows`
+//! view. The result it was opened from (data, sort, filter, grouping) is kept
+//! on a stack and restored on the way back, like drill-down navigation.
+
+use serde_json::Value;
+
+use super::ContentPanel;
+use super::filter_expr::is_null;
+use super::group::{GroupState, ResultViewState};
+use super::types::{ColumnKey, ColumnType, ContentView, QueryResultData};
+
+/// Result view that linked rows were opened from.
+#[derive(Debug)]
+pub struct LinkOrigin {
+    view: ResultViewState,
+    group: Option<GroupState>,
+}
+
+impl LinkOrigin {
+    /// Question (or table) of the result the links were followed from.
+    pub(super) fn question_id(&self) -> u32 {
+        self.view.question_id()
+    }
+}
+
+impl ContentPanel {
+    /// Check if current view shows rows reached through a key.
+    pub fn is_linked_rows_view(&self) -> bool {
+        matches!(self.view, ContentView::LinkedRows { .. })
+    }
+
+    /// Show linked rows, keeping the current result for the way back.
+    pub fn enter_linked_rows(&mut self, data: QueryResultData) {
+        let group = self.group.take();
+        if let Some(view) = self.take_view_state() {
+            self.link_stack.push(LinkOrigin { view, group });
+        }
+        self.push_view(ContentView::LinkedRows {
+            title: data.question_name.clone(),
+        });
+        self.show_result_data(data);
+    }
+
+    /// Return to the result the linked rows were opened from.
+    pub fn exit_linked_rows(&mut self) {
+        self.pop_view();
+        match self.link_stack.pop() {
+            Some(origin) => {
+                self.restore_view_state(origin.view);
+                self.group = origin.group;
+            }
+            // Without an origin there is nothing left to show
+            None => self.back_to_questions(),
+        }
+    }
+
+    /// Drop all linked rows and show the result they were first opened from.
+    pub(super) fn unwind_links(&mut self) {
+        let Some(origin) = self.link_stack.drain(..).next() else {
+            return;
+        };
+        while self.is_linked_rows_view() && self.pop_view().is_some() {}
+        self.restore_view_state(origin.view);
+        self.group = origin.group;
+    }
+
+    /// Key roles of the current result's columns.
+    pub fn column_keys(&self) -> Vec<ColumnKey> {
+        self.query_result
+            .as_ref()
+            .map(|r| (0..r.columns.len()).map(|c| r.column_key(c)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Key value of a cell for a lookup query: numbers for numeric columns,
+    /// strings otherwise. None for NULL.
+    pub fn key_value(&self, column: usize, cell: &str) -> Option<Value> {
+        if is_null(cell) {
+            return None;
+        }
+        let column_type = self
+            .query_result
+            .as_ref()
+            .map_or(ColumnType::Text, |r| r.column_type(column));
+        let number = match column_type {
+            ColumnType::Number => cell
+                .parse::<i64>()
+                .map(Value::from)
+                .ok()
+                .or_else(|| cell.parse::<f64>().ok().map(Value::from)),
+            _ => None,
+        };
+        Some(number.unwrap_or_else(|| Value::String(cell.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::FilterCondition;
+    use crate::components::content::filter_expr::{FilterJoin, FilterOp};
+
+    fn data(name: &str, rows: &[&str]) -> QueryResultData {
+        QueryResultData {
+            question_id: 1,
+            question_name: name.to_string(),
+            columns: vec!["ID".to_string()],
+            rows: rows.iter().map(|r| vec![r.to_string()]).collect(),
+            column_types: vec![ColumnType::Number],
+            column_keys: vec![ColumnKey::Primary {
+                table_id: 2,
+                field_id: 10,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_linked_rows_restore_origin_view() {
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(data("Users", &["1", "2", "3"]));
+        panel.set_filters(vec![
+            FilterCondition::new(FilterJoin::And, 0, FilterOp::Ge, "2").unwrap(),
+        ]);
+
+        panel.enter_linked_rows(data("Orders · User ID = 2", &["7"]));
+        assert!(panel.is_linked_rows_view());
+        assert!(panel.filters().is_empty());
+        assert_eq!(panel.query_result.as_ref().unwrap().rows.len(), 1);
+
+        // Nested links unwind one level at a time
+        panel.enter_linked_rows(data("Items", &["8", "9"]));
+        panel.exit_linked_rows();
+        assert_eq!(
+            panel.query_result.as_ref().unwrap().question_name,
+            "Orders · User ID = 2"
+        );
+        panel.exit_linked_rows();
+        assert_eq!(panel.current_view(), ContentView::QueryResult);
+        assert_eq!(panel.filters().len(), 1);
+        assert_eq!(panel.filter_indices, Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_key_value_typing() {
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(data("Users", &["1"]));
+        assert_eq!(panel.key_value(0, "42"), Some(Value::from(42)));
+        assert_eq!(panel.key_value(0, "—"), None);
+        assert_eq!(
+            panel.column_keys(),
+            vec![ColumnKey::Primary {
+                table_id: 2,
+                field_id: 10
+            }]
+        );
+    }
+}
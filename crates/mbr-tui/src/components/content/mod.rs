@@ -4,6 +4,7 @@
 //!
 //! This module is split into submodules for better organization:
 //! - `types`: View types, input modes, and data structures
+//! - `collection_tree`: Lazily loaded collection tree for the Collections view
 //! - `views`: View rendering functions (Welcome, Questions, Collections, etc.)
 //! - `modals`: Modal overlay rendering (Sort, Filter, Search)
 //! - `navigation`: Navigation stack and view transitions
@@ -11,18 +12,41 @@
 //! - `search`: Search mode handling
 //! - `sort`: Sort functionality for query results
 //! - `filter`: Filter functionality for query results
+//! - `filter_expr`: Filter conditions (operators, AND/OR, type-aware comparison)
+//! - `aggregate`: Column summaries and group-by aggregation
+//! - `group`: Summary footer and group-by view state
+//! - `profile`: Column profile popup (distribution statistics)
+//! - `links`: Foreign-key navigation between result rows
+//! - `buffers`: Open result buffers and the split view
+//! - `compare`: Row alignment and differences of two results
+//! - `history`: History view of executed questions
+//! - `session`: Saving and restoring navigation and result view state
+//! - `columns`: Column layout (hide, reorder, pin, resize) and column manager
 //! - `result_search`: All-column search in results
 //! - `pagination`: Pagination and scrolling
 //! - `key_handler`: Key event handling
+//! - `mouse`: Mouse hit-testing and wheel scrolling
 
+mod aggregate;
+mod buffers;
+mod collection_tree;
+mod columns;
+mod compare;
 mod filter;
+mod filter_expr;
+mod group;
+mod history;
 mod key_handler;
+mod links;
 mod modals;
+mod mouse;
 mod navigation;
 mod pagination;
+mod profile;
 mod result_search;
 mod search;
 mod selection;
+mod session;
 mod sort;
 pub mod types;
 mod views;
@@ -30,15 +54,34 @@ mod views;
 use std::collections::HashSet;
 
 use crossterm::event::KeyEvent;
-use ratatui::{Frame, layout::Rect, widgets::TableState};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    widgets::TableState,
+};
 
 use mbr_core::api::models::{CollectionItem, Database, Question, TableInfo};
-
-pub use types::{ContentView, InputMode, QueryResultData, SortOrder};
+use mbr_core::core::profile::ColumnProfile;
+use mbr_core::storage::tui_state::HistoryEntry;
+
+pub use buffers::BufferInfo;
+pub use collection_tree::TreeNodeKind;
+pub use columns::ColumnLayout;
+pub use filter_expr::{FilterClause, FilterCondition, FilterJoin, FilterOp};
+pub use mouse::ContentClick;
+pub use types::{
+    ColumnKey, ColumnType, ContentView, InputMode, QueryResultData, RecordLink, SortKey, SortOrder,
+};
 
 use super::{Component, ScrollState};
+use crate::keymap::Keymap;
 use crate::service::LoadState;
-use types::DEFAULT_ROWS_PER_PAGE;
+use buffers::ResultBuffer;
+use collection_tree::CollectionTree;
+use group::GroupState;
+use links::LinkOrigin;
+use mouse::TableGeometry;
+use types::{DEFAULT_ROWS_PER_PAGE, FilterStep};
 
 /// Content panel showing main content.
 pub struct ContentPanel {
@@ -52,8 +95,10 @@ pub struct ContentPanel {
     pub(super) table_state: TableState,
     /// Collections data for the Collections view
     pub(super) collections: LoadState<Vec<CollectionItem>>,
-    /// Table state for Collections view
+    /// Table state for Collections view (selection indexes visible tree rows)
     pub(super) collections_table_state: TableState,
+    /// Collection tree (sub-collections, dashboards, models, questions)
+    pub(super) collection_tree: CollectionTree,
     /// Databases data for the Databases view
     pub(super) databases: LoadState<Vec<Database>>,
     /// Table state for Databases view
@@ -88,10 +133,8 @@ pub struct ContentPanel {
     /// Used for: Databases → Schemas → Tables → Preview
     ///           Collections → Questions → QueryResult
     pub(super) navigation_stack: Vec<ContentView>,
-    /// Sort order for query results
-    pub(super) sort_order: SortOrder,
-    /// Column index currently being sorted (None = no sort)
-    pub(super) sort_column_index: Option<usize>,
+    /// Sort keys for query results, highest priority first (empty = no sort)
+    pub(super) sort_keys: Vec<SortKey>,
     /// Whether sort column selection modal is active
     pub(super) sort_mode_active: bool,
     /// Selected column index in sort modal
@@ -99,16 +142,26 @@ pub struct ContentPanel {
     // === Filter state ===
     /// Filtered row indices (None = no filter, Some = filtered indices)
     pub(super) filter_indices: Option<Vec<usize>>,
-    /// Column index currently being filtered (None = no filter)
-    pub(super) filter_column_index: Option<usize>,
-    /// Filter text (case-insensitive contains match)
-    pub(super) filter_text: String,
+    /// Active filter conditions (empty = no filter)
+    pub(super) filters: Vec<FilterCondition>,
     /// Whether filter modal is active
     pub(super) filter_mode_active: bool,
-    /// Current step in filter modal (0 = column selection, 1 = text input)
-    pub(super) filter_modal_step: usize,
+    /// Current step in filter modal
+    pub(super) filter_modal_step: FilterStep,
     /// Selected column index in filter modal
     pub(super) filter_modal_selection: usize,
+    /// Selected operator index (into `FilterOp::ALL`) in filter modal
+    pub(super) filter_op_selection: usize,
+    /// Selected condition in the filter modal list (== len for "add")
+    pub(super) filter_condition_selection: usize,
+    /// Value being typed in filter modal
+    pub(super) filter_input: String,
+    /// Join for the condition being added
+    pub(super) filter_join: FilterJoin,
+    /// Index of the condition being edited (None = adding a new one)
+    pub(super) filter_editing: Option<usize>,
+    /// Validation error shown in the filter modal
+    pub(super) filter_error: Option<String>,
     // === Result Search state (all-column search) ===
     /// Whether result search mode is active
     pub(super) result_search_active: bool,
@@ -130,8 +183,6 @@ pub struct ContentPanel {
     pub(super) filter_dirty: bool,
     /// Whether search indices need to be recomputed before next render
     pub(super) search_dirty: bool,
-    /// Cached lowercase filter text (recomputed only when filter text changes)
-    pub(super) cached_filter_lower: Option<String>,
     /// Pre-computed column widths for query results (computed on data load)
     pub(super) cached_column_widths: Option<Vec<u16>>,
     // === Questions list pagination state ===
@@ -141,6 +192,49 @@ pub struct ContentPanel {
     pub(super) questions_offset: u32,
     /// Page size for questions list pagination
     pub(super) questions_page_size: u32,
+    /// Active key bindings (set by App from config)
+    pub(super) keymap: Keymap,
+    /// Table geometry from the last frame (for mouse hit-testing)
+    pub(super) table_geometry: Option<TableGeometry>,
+    // === Column management state ===
+    /// Column order, visibility, pinning and widths of the result table
+    pub(super) column_layout: ColumnLayout,
+    /// Whether column manager modal is active
+    pub(super) column_manager_active: bool,
+    /// Selected position in column manager
+    pub(super) column_manager_selection: usize,
+    /// Whether the layout changed since it was last persisted
+    pub(super) column_layout_dirty: bool,
+    // === Summary and group-by state ===
+    /// Whether the summary footer is shown below the result table
+    pub(super) summary_visible: bool,
+    /// Active grouping (None = ungrouped)
+    pub(super) group: Option<GroupState>,
+    /// Whether the group-by modal is active
+    pub(super) group_modal_active: bool,
+    /// Selected column in the group-by modal
+    pub(super) group_modal_selection: usize,
+    /// Checked columns in the group-by modal, in group order
+    pub(super) group_modal_checked: Vec<usize>,
+    /// Column profile popup: profiled column index and its profile
+    pub(super) column_profile: Option<(usize, ColumnProfile)>,
+    // === Foreign-key navigation ===
+    /// Results that linked rows were opened from, restored on the way back
+    pub(super) link_stack: Vec<LinkOrigin>,
+    // === Result buffers ===
+    /// Open query results (the live one is shown in the result fields)
+    pub(super) buffers: Vec<ResultBuffer>,
+    /// Buffer shown in the result view
+    pub(super) active_buffer: Option<usize>,
+    /// Buffer shown before the active one
+    pub(super) previous_buffer: Option<usize>,
+    /// Buffer compared side by side with the active one
+    pub(super) split: Option<usize>,
+    // === History view ===
+    /// Executed questions shown in the History view, newest first
+    pub(super) history: Vec<HistoryEntry>,
+    /// Table state for History view
+    pub(super) history_table_state: TableState,
 }
 
 impl Default for ContentPanel {
@@ -160,6 +254,7 @@ impl ContentPanel {
             table_state: TableState::default(),
             collections: LoadState::default(),
             collections_table_state: TableState::default(),
+            collection_tree: CollectionTree::default(),
             databases: LoadState::default(),
             databases_table_state: TableState::default(),
             schemas: LoadState::default(),
@@ -175,16 +270,20 @@ impl ContentPanel {
             search_query: String::new(),
             active_search: None,
             navigation_stack: Vec::new(),
-            sort_order: SortOrder::None,
-            sort_column_index: None,
+            sort_keys: Vec::new(),
             sort_mode_active: false,
             sort_modal_selection: 0,
             filter_indices: None,
-            filter_column_index: None,
-            filter_text: String::new(),
+            filters: Vec::new(),
             filter_mode_active: false,
-            filter_modal_step: 0,
+            filter_modal_step: FilterStep::default(),
             filter_modal_selection: 0,
+            filter_op_selection: 0,
+            filter_condition_selection: 0,
+            filter_input: String::new(),
+            filter_join: FilterJoin::default(),
+            filter_editing: None,
+            filter_error: None,
             result_search_active: false,
             result_search_text: String::new(),
             result_search_indices: None,
@@ -193,23 +292,49 @@ impl ContentPanel {
             sort_dirty: false,
             filter_dirty: false,
             search_dirty: false,
-            cached_filter_lower: None,
             cached_column_widths: None,
             questions_total: None,
             questions_offset: 0,
             questions_page_size: 100,
+            keymap: Keymap::default(),
+            table_geometry: None,
+            column_layout: ColumnLayout::default(),
+            column_manager_active: false,
+            column_manager_selection: 0,
+            column_layout_dirty: false,
+            summary_visible: false,
+            group: None,
+            group_modal_active: false,
+            group_modal_selection: 0,
+            group_modal_checked: Vec::new(),
+            column_profile: None,
+            link_stack: Vec::new(),
+            buffers: Vec::new(),
+            active_buffer: None,
+            previous_buffer: None,
+            split: None,
+            history: Vec::new(),
+            history_table_state: TableState::default(),
         }
     }
 
+    /// Replace the key bindings used for view navigation.
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
     /// Set the current view (used for tab switching).
     /// Clears navigation stack since tab changes reset the navigation context.
     pub fn set_view(&mut self, view: ContentView) {
+        // Keep the shown result in its buffer
+        self.park_live();
         self.view = view;
         self.scroll = ScrollState::default();
         self.scroll_x = 0;
         self.table_state = TableState::default();
         // Clear navigation stack on tab switch
         self.clear_navigation_stack();
+        self.link_stack.clear();
     }
 
     /// Get the current view (cloned since ContentView now contains data).
@@ -217,17 +342,14 @@ impl ContentPanel {
         self.view.clone()
     }
 
-    /// Check if current view is Questions or CollectionQuestions.
+    /// Check if current view is Questions.
     pub fn is_questions_view(&self) -> bool {
-        matches!(
-            self.view,
-            ContentView::Questions | ContentView::CollectionQuestions { .. }
-        )
+        self.view == ContentView::Questions
     }
 
-    /// Check if current view is CollectionQuestions.
-    pub fn is_collection_questions_view(&self) -> bool {
-        matches!(self.view, ContentView::CollectionQuestions { .. })
+    /// Check if current view is Collections (tree navigator).
+    pub fn is_collections_view(&self) -> bool {
+        self.view == ContentView::Collections
     }
 
     /// Check if current view is DatabaseSchemas.
@@ -245,11 +367,13 @@ impl ContentPanel {
         matches!(self.view, ContentView::TablePreview { .. })
     }
 
-    /// Check if current view is QueryResult or TablePreview (both show query results).
+    /// Check if current view shows query results (QueryResult, TablePreview or LinkedRows).
     pub fn is_result_view(&self) -> bool {
         matches!(
             self.view,
-            ContentView::QueryResult | ContentView::TablePreview { .. }
+            ContentView::QueryResult
+                | ContentView::TablePreview { .. }
+                | ContentView::LinkedRows { .. }
         )
     }
 
@@ -302,7 +426,7 @@ impl ContentPanel {
             .filter(|&t| t > self.questions_page_size)
             .map(|total| {
                 let current_page = self.questions_offset / self.questions_page_size + 1;
-                let total_pages = (total + self.questions_page_size - 1) / self.questions_page_size;
+                let total_pages = total.div_ceil(self.questions_page_size);
                 (current_page, total_pages, total)
             })
     }
@@ -448,6 +572,34 @@ impl ContentPanel {
         self.get_original_row_index(global_display_idx)
     }
 
+    /// Get the name of the current query result (question or table name).
+    pub fn get_query_result_name(&self) -> Option<String> {
+        self.query_result.as_ref().map(|r| r.question_name.clone())
+    }
+
+    /// Get all visible rows (after search/filter/sort) as (columns, values) pairs.
+    pub fn get_visible_records(&self) -> Vec<(Vec<String>, Vec<String>)> {
+        let result = match &self.query_result {
+            Some(r) => r,
+            None => return vec![],
+        };
+
+        (0..self.visible_row_count())
+            .filter_map(|idx| self.get_visible_row(idx))
+            .map(|row| (result.columns.clone(), row.clone()))
+            .collect()
+    }
+
+    /// Find a result column by name (case-insensitive, `_` and space are interchangeable).
+    pub fn find_column_index(&self, name: &str) -> Option<usize> {
+        column_position(&self.query_result.as_ref()?.columns, name)
+    }
+
+    /// Find a column of the ungrouped result by name (for `:group`).
+    pub fn find_group_column_index(&self, name: &str) -> Option<usize> {
+        column_position(self.group_source_columns(), name)
+    }
+
     /// Get selected records as (columns, values) pairs.
     pub fn get_selected_records(&self) -> Vec<(Vec<String>, Vec<String>)> {
         let result = match &self.query_result {
@@ -470,6 +622,9 @@ impl ContentPanel {
 
 impl Component for ContentPanel {
     fn draw(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        // Table renderers record fresh geometry for mouse hit-testing
+        self.table_geometry = None;
+
         // Table-based views render directly (uses stateful Table widget)
         match self.view {
             ContentView::Questions => {
@@ -484,12 +639,15 @@ impl Component for ContentPanel {
                 self.render_databases(area, frame, focused);
                 return;
             }
-            ContentView::QueryResult => {
-                self.render_query_result(area, frame, focused);
+            // Split view: the live result and the compared buffer side by side
+            ContentView::QueryResult if self.split.is_some() => {
+                let [left, right] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(area);
+                self.render_query_result(left, frame, focused);
+                self.render_compare_pane(frame, right);
                 return;
             }
-            ContentView::CollectionQuestions { .. } => {
-                self.render_collection_questions(area, frame, focused);
+            ContentView::QueryResult | ContentView::LinkedRows { .. } => {
+                self.render_query_result(area, frame, focused);
                 return;
             }
             ContentView::DatabaseSchemas { .. } => {
@@ -504,6 +662,10 @@ impl Component for ContentPanel {
                 self.render_table_preview(area, frame, focused);
                 return;
             }
+            ContentView::History => {
+                self.render_history(area, frame, focused);
+                return;
+            }
             ContentView::Welcome => {}
         }
 
@@ -517,9 +679,17 @@ impl Component for ContentPanel {
     }
 }
 
+/// Position of a column by name (case-insensitive, `_` and space are interchangeable).
+fn column_position(columns: &[String], name: &str) -> Option<usize> {
+    let normalize = |s: &str| s.trim().to_lowercase().replace(' ', "_");
+    let target = normalize(name);
+    columns.iter().position(|c| normalize(c) == target)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mbr_core::api::models::CollectionEntry;
 
     // === Navigation Stack Tests ===
 
@@ -711,6 +881,8 @@ mod tests {
             question_id: 1,
             question_name: "Test".to_string(),
             columns: vec!["Col1".to_string(), "Col2".to_string()],
+            column_types: vec![],
+            column_keys: vec![],
             rows: vec![vec!["a".to_string(), "b".to_string()]],
         };
         panel.set_query_result(data);
@@ -732,6 +904,8 @@ mod tests {
             question_id: 1,
             question_name: "Test".to_string(),
             columns: vec!["Col1".to_string(), "Col2".to_string()],
+            column_types: vec![],
+            column_keys: vec![],
             rows: vec![vec!["a".to_string(), "b".to_string()]],
         };
         panel.set_query_result(data);
@@ -852,32 +1026,126 @@ mod tests {
         panel.select_last();
     }
 
-    // === Collection Questions Context Tests ===
+    // === Collection Tree Tests ===
+
+    fn collection_entry(id: u32, name: &str, model: &str) -> CollectionEntry {
+        CollectionEntry {
+            id,
+            name: name.to_string(),
+            description: None,
+            model: model.to_string(),
+            personal_owner_id: None,
+        }
+    }
 
     #[test]
-    fn test_enter_collection_questions_sets_context() {
+    fn test_collection_tree_expand_collapse() {
         let mut panel = ContentPanel::new();
         panel.set_view(ContentView::Collections);
+        panel.update_collections(&LoadState::Loaded(vec![]));
+        panel.mark_collection_items_loading(None);
+        panel.set_collection_items(
+            None,
+            &[
+                collection_entry(10, "Sales", "collection"),
+                collection_entry(20, "Revenue", "card"),
+            ],
+        );
 
-        panel.enter_collection_questions(42, "Test Collection".to_string());
+        // Root is selected and already expanded: l moves to first child
+        assert_eq!(panel.expand_selected_collection(), None);
+        assert_eq!(panel.collections_table_state.selected(), Some(1));
 
-        assert!(panel.is_collection_questions_view());
+        // Expanding "Sales" requests its items
+        assert_eq!(panel.expand_selected_collection(), Some(Some(10)));
+        panel.set_collection_items(Some(10), &[collection_entry(30, "Q3", "card")]);
         assert_eq!(
-            panel.get_collection_context(),
-            Some((42_u32, "Test Collection".to_string()))
+            panel.collection_breadcrumb(),
+            vec!["Our analytics".to_string(), "Sales".to_string()]
         );
+
+        // h collapses, then a second h moves to the parent
+        panel.collapse_selected_collection();
+        panel.collapse_selected_collection();
+        assert_eq!(panel.collections_table_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_exit_collection_questions_clears_context() {
+    fn test_collection_refetch_keeps_selection() {
         let mut panel = ContentPanel::new();
         panel.set_view(ContentView::Collections);
-        panel.enter_collection_questions(42, "Test Collection".to_string());
+        panel.update_collections(&LoadState::Loaded(vec![]));
+        panel.set_collection_items(
+            None,
+            &[
+                collection_entry(10, "Sales", "collection"),
+                collection_entry(20, "Revenue", "card"),
+            ],
+        );
+        panel.select_collections_next();
+        assert_eq!(panel.expand_selected_collection(), Some(Some(10)));
+        panel.set_collection_items(
+            Some(10),
+            &[
+                collection_entry(30, "Q3", "card"),
+                collection_entry(31, "Q4", "card"),
+            ],
+        );
+        panel.select_collections_last();
+        panel.select_collections_previous();
+        let q4 = vec![
+            "Our analytics".to_string(),
+            "Sales".to_string(),
+            "Q4".to_string(),
+        ];
+        assert_eq!(panel.collection_breadcrumb(), q4);
+
+        // Refreshing Sales finds Q4 again after the arena is rebuilt
+        panel.set_collection_items(
+            Some(10),
+            &[
+                collection_entry(31, "Q4", "card"),
+                collection_entry(30, "Q3", "card"),
+            ],
+        );
+        assert_eq!(panel.collection_breadcrumb(), q4);
 
-        panel.exit_collection_questions();
+        // Once Q4 is gone, Sales (still expanded) is selected
+        panel.mark_collection_items_loading(Some(10));
+        panel.set_collection_items(Some(10), &[collection_entry(30, "Q3", "card")]);
+        assert_eq!(
+            panel.collection_breadcrumb(),
+            vec!["Our analytics".to_string(), "Sales".to_string()]
+        );
 
-        assert_eq!(panel.current_view(), ContentView::Collections);
-        assert_eq!(panel.get_collection_context(), None);
+        // Re-fetching the root without Sales falls back to the root node
+        panel.select_collections_last();
+        panel.select_collections_previous();
+        assert_eq!(panel.collection_breadcrumb().last().unwrap(), "Q3");
+        panel.set_collection_items(None, &[collection_entry(20, "Revenue", "card")]);
+        assert_eq!(panel.collection_tree.visible_len(), 2);
+        assert_eq!(panel.collections_table_state.selected(), Some(0));
+        assert_eq!(
+            panel.collection_breadcrumb(),
+            vec!["Our analytics".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_selected_collection_node() {
+        let mut panel = ContentPanel::new();
+        panel.set_view(ContentView::Collections);
+        panel.update_collections(&LoadState::Loaded(vec![]));
+        panel.set_collection_items(None, &[collection_entry(20, "Revenue", "card")]);
+
+        panel.select_collections_next();
+        let selection = panel.get_selected_collection_node().unwrap();
+        assert_eq!(selection.kind, TreeNodeKind::Question);
+        assert_eq!(selection.id, Some(20));
+        assert_eq!(
+            panel.find_collection_card_name(20),
+            Some("Revenue".to_string())
+        );
     }
 
     // === Database Drill-down Context Tests ===
@@ -932,6 +1200,8 @@ mod tests {
             question_id: 1,
             question_name: "Test Query".to_string(),
             columns: vec!["ID".to_string(), "Name".to_string()],
+            column_types: vec![],
+            column_keys: vec![],
             rows: vec![
                 vec!["1".to_string(), "Alice".to_string()],
                 vec!["2".to_string(), "Bob".to_string()],
@@ -953,6 +1223,8 @@ mod tests {
             question_id: 1,
             question_name: "Test Query".to_string(),
             columns: vec!["ID".to_string()],
+            column_types: vec![],
+            column_keys: vec![],
             rows: vec![vec!["1".to_string()]],
         };
 
@@ -964,6 +1236,91 @@ mod tests {
         assert_eq!(panel.current_view(), ContentView::Questions);
     }
 
+    #[test]
+    fn test_set_filter_with_comparison_operator() {
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(QueryResultData {
+            question_id: 1,
+            question_name: "Revenue".to_string(),
+            columns: vec!["Region".to_string(), "Total Revenue".to_string()],
+            column_types: vec![ColumnType::Text, ColumnType::Number],
+            column_keys: vec![],
+            rows: vec![
+                vec!["east".to_string(), "50".to_string()],
+                vec!["west".to_string(), "150".to_string()],
+                vec!["north".to_string(), "1000".to_string()],
+            ],
+        });
+
+        let col = panel.find_column_index("total_revenue").unwrap();
+        let condition = FilterCondition::new(FilterJoin::And, col, FilterOp::Gt, "100").unwrap();
+        panel.set_filters(vec![condition]);
+        assert_eq!(panel.filter_indices, Some(vec![1, 2]));
+
+        panel.set_sort_keys(vec![SortKey {
+            column: col,
+            order: SortOrder::Descending,
+        }]);
+        let visible: Vec<String> = panel
+            .get_visible_records()
+            .into_iter()
+            .map(|(_, row)| row[0].clone())
+            .collect();
+        assert_eq!(visible, vec!["north".to_string(), "west".to_string()]);
+
+        panel.clear_filter();
+        assert_eq!(panel.filter_indices, None);
+    }
+
+    #[test]
+    fn test_stacked_filters_edit_and_remove() {
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(QueryResultData {
+            question_id: 1,
+            question_name: "Revenue".to_string(),
+            columns: vec!["Region".to_string(), "Total Revenue".to_string()],
+            column_types: vec![ColumnType::Text, ColumnType::Number],
+            column_keys: vec![],
+            rows: vec![
+                vec!["east".to_string(), "50".to_string()],
+                vec!["west".to_string(), "150".to_string()],
+                vec!["north".to_string(), "1000".to_string()],
+            ],
+        });
+
+        let cond = |join, col, op, value| FilterCondition::new(join, col, op, value).unwrap();
+        panel.set_filters(vec![
+            cond(FilterJoin::And, 1, FilterOp::Ge, "100"),
+            cond(FilterJoin::And, 0, FilterOp::StartsWith, "w"),
+        ]);
+        assert_eq!(panel.filter_indices, Some(vec![1]));
+
+        // Toggle the second condition to OR from the condition list
+        panel.open_filter_modal();
+        panel.filter_modal_down();
+        panel.filter_modal_toggle_join();
+        assert_eq!(panel.filter_indices, Some(vec![1, 2]));
+
+        // Edit the first condition's value through the modal steps
+        panel.filter_modal_up();
+        panel.filter_modal_next_step(); // edit → column
+        panel.filter_modal_next_step(); // column → operator
+        panel.filter_modal_next_step(); // operator → value
+        panel.filter_modal_delete_char();
+        panel.filter_modal_delete_char();
+        panel.filter_modal_delete_char();
+        panel.filter_modal_input_char('9');
+        panel.filter_modal_input_char('9');
+        panel.filter_modal_input_char('9');
+        panel.filter_modal_next_step();
+        assert!(!panel.is_filter_mode_active());
+        assert_eq!(panel.filter_indices, Some(vec![1, 2]));
+        assert_eq!(panel.filters()[0].value, "999");
+
+        panel.remove_filter(1);
+        assert_eq!(panel.filter_indices, Some(vec![2]));
+    }
+
     // === Load State Tests ===
 
     #[test]
@@ -2,18 +2,29 @@
 //!
 //! This module contains rendering functions for modal overlays:
 //! - Sort column selection modal
-//! - Filter column/text input modal
+//! - Filter modal (condition list, column, operator, value)
 //! - Result search bar overlay
+//! - Column manager modal
+//! - Group-by column selection modal
+//! - Column profile popup
 
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use super::{ContentPanel, QueryResultData, SortOrder};
+use mbr_core::core::profile::sparkline;
+use mbr_core::utils::text::{pad_to_width, truncate_text_unicode};
+
+use super::types::FilterStep;
+use super::{ContentPanel, FilterOp, QueryResultData};
+use crate::components::styles::{
+    popup_style, text_accent, text_dim, text_error, text_key, text_normal, text_success,
+};
+use crate::components::theme::theme;
 
 impl ContentPanel {
     /// Render result search bar as an overlay at the bottom.
@@ -48,17 +59,17 @@ impl ContentPanel {
 
         let lines = vec![
             Line::from(vec![
-                Span::styled(" > ", Style::default().fg(Color::Yellow)),
+                Span::styled(" > ", text_key()),
                 Span::styled(
                     input_display,
                     Style::default()
-                        .fg(Color::White)
+                        .fg(theme().text)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(Span::styled(
                 format!(" Found: {} / {} rows", matched, total),
-                Style::default().fg(Color::DarkGray),
+                text_dim(),
             )),
         ];
 
@@ -68,43 +79,43 @@ impl ContentPanel {
                     .title(title)
                     .title_style(
                         Style::default()
-                            .fg(Color::Green)
+                            .fg(theme().success)
                             .add_modifier(Modifier::BOLD),
                     )
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Green)),
+                    .border_style(text_success()),
             )
-            .style(Style::default().bg(Color::Black));
+            .style(popup_style());
 
         frame.render_widget(paragraph, bar_area);
     }
 
-    /// Render filter column/text input modal as an overlay.
+    /// Render the filter modal for the current step as an overlay.
     pub(super) fn render_filter_modal(&self, frame: &mut Frame, area: Rect) {
         let result = match &self.query_result {
             Some(r) => r,
             None => return,
         };
 
-        if self.filter_modal_step == 0 {
-            // Step 0: Column selection (similar to sort modal)
-            self.render_filter_column_selection(frame, area, result);
-        } else {
-            // Step 1: Text input
-            self.render_filter_text_input(frame, area, result);
+        match self.filter_modal_step {
+            FilterStep::Conditions => self.render_filter_conditions(frame, area, result),
+            FilterStep::Column => self.render_filter_column_selection(frame, area, result),
+            FilterStep::Operator => self.render_filter_operator_selection(frame, area, result),
+            FilterStep::Value => self.render_filter_text_input(frame, area, result),
         }
     }
 
-    /// Render filter column selection modal (step 0).
-    fn render_filter_column_selection(
-        &self,
+    /// Render a filter modal list step with a footer hint.
+    fn render_filter_list(
         frame: &mut Frame,
         area: Rect,
-        result: &QueryResultData,
+        title: &str,
+        items: Vec<Line>,
+        hint: Vec<Span>,
     ) {
         // Calculate modal dimensions (40% width, centered)
-        let modal_width = (area.width as f32 * 0.4).clamp(30.0, 60.0) as u16;
-        let max_height = (result.columns.len() + 4).min(20) as u16;
+        let modal_width = (area.width as f32 * 0.4).clamp(36.0, 64.0) as u16;
+        let max_height = (items.len() + 4).min(20) as u16;
         let modal_height = max_height.min(area.height.saturating_sub(4));
 
         let modal_x = (area.width.saturating_sub(modal_width)) / 2;
@@ -120,48 +131,19 @@ impl ContentPanel {
         // Clear background for better visibility
         frame.render_widget(Clear, modal_area);
 
-        // Build column list items
-        let items: Vec<Line> = result
-            .columns
-            .iter()
-            .enumerate()
-            .map(|(i, col)| {
-                let is_selected = i == self.filter_modal_selection;
-                let is_filtered = self.filter_column_index == Some(i);
-
-                // Build column text with filter indicator
-                let filter_indicator = if is_filtered { " ⚡" } else { "" };
-                let prefix = if is_selected { "► " } else { "  " };
-                let text = format!("{}{}{}", prefix, col, filter_indicator);
-
-                let style = if is_selected {
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Magenta)
-                        .add_modifier(Modifier::BOLD)
-                } else if is_filtered {
-                    Style::default().fg(Color::Magenta)
-                } else {
-                    Style::default().fg(Color::White)
-                };
-
-                Line::from(Span::styled(text, style))
-            })
-            .collect();
-
         let paragraph = Paragraph::new(items)
             .block(
                 Block::default()
-                    .title(" Filter by Column ")
+                    .title(title)
                     .title_style(
                         Style::default()
-                            .fg(Color::Magenta)
+                            .fg(theme().secondary)
                             .add_modifier(Modifier::BOLD),
                     )
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Magenta)),
+                    .border_style(Style::default().fg(theme().secondary)),
             )
-            .style(Style::default().bg(Color::Black));
+            .style(popup_style());
 
         frame.render_widget(paragraph, modal_area);
 
@@ -173,22 +155,159 @@ impl ContentPanel {
                 modal_area.width.saturating_sub(2),
                 1,
             );
-            let hint = Paragraph::new(Line::from(vec![
-                Span::styled("Enter", Style::default().fg(Color::Yellow)),
-                Span::styled(": Next  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Esc", Style::default().fg(Color::Yellow)),
-                Span::styled(": Cancel", Style::default().fg(Color::DarkGray)),
-            ]))
-            .style(Style::default().bg(Color::Black));
+            let hint = Paragraph::new(Line::from(hint)).style(popup_style());
             frame.render_widget(hint, footer_area);
         }
     }
 
-    /// Render filter text input modal (step 1).
+    /// Style of a filter modal list entry.
+    fn filter_item_style(is_selected: bool, is_marked: bool) -> Style {
+        if is_selected {
+            theme()
+                .highlight(theme().secondary)
+                .add_modifier(Modifier::BOLD)
+        } else if is_marked {
+            Style::default().fg(theme().secondary)
+        } else {
+            text_normal()
+        }
+    }
+
+    /// Render the list of active conditions.
+    fn render_filter_conditions(&self, frame: &mut Frame, area: Rect, result: &QueryResultData) {
+        let mut items: Vec<Line> = self
+            .filters
+            .iter()
+            .enumerate()
+            .map(|(i, condition)| {
+                let is_selected = i == self.filter_condition_selection;
+                let prefix = if is_selected { "► " } else { "  " };
+                let join = if i == 0 { "" } else { condition.join.label() };
+                let column = result
+                    .columns
+                    .get(condition.column)
+                    .map(|s| s.as_str())
+                    .unwrap_or("?");
+                let text = format!("{}{:<4}{}", prefix, join, condition.describe(column));
+                Line::from(Span::styled(
+                    text,
+                    Self::filter_item_style(is_selected, false),
+                ))
+            })
+            .collect();
+
+        let add_selected = self.filter_condition_selection >= self.filters.len();
+        items.push(Line::from(Span::styled(
+            format!("{}+ Add condition", if add_selected { "► " } else { "  " }),
+            Self::filter_item_style(add_selected, true),
+        )));
+
+        let hint = vec![
+            Span::styled("Enter", text_key()),
+            Span::styled(": Edit  ", text_dim()),
+            Span::styled("a", text_key()),
+            Span::styled(": Add  ", text_dim()),
+            Span::styled("d", text_key()),
+            Span::styled(": Remove  ", text_dim()),
+            Span::styled("o", text_key()),
+            Span::styled(": AND/OR", text_dim()),
+        ];
+        Self::render_filter_list(frame, area, " Filter Conditions ", items, hint);
+    }
+
+    /// Render filter column selection.
+    fn render_filter_column_selection(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        result: &QueryResultData,
+    ) {
+        // Build column list items
+        let items: Vec<Line> = result
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                let is_selected = i == self.filter_modal_selection;
+                let is_filtered = self.filters.iter().any(|c| c.column == i);
+
+                // Build column text with filter indicator
+                let filter_indicator = if is_filtered { " ⚡" } else { "" };
+                let prefix = if is_selected { "► " } else { "  " };
+                let text = format!("{}{}{}", prefix, col, filter_indicator);
+
+                Line::from(Span::styled(
+                    text,
+                    Self::filter_item_style(is_selected, is_filtered),
+                ))
+            })
+            .collect();
+
+        let title = format!(" {} Filter by Column ", self.filter_step_join_label());
+        let hint = vec![
+            Span::styled("Enter", text_key()),
+            Span::styled(": Next  ", text_dim()),
+            Span::styled("Tab", text_key()),
+            Span::styled(": AND/OR  ", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(": Back", text_dim()),
+        ];
+        Self::render_filter_list(frame, area, &title, items, hint);
+    }
+
+    /// Render filter operator selection.
+    fn render_filter_operator_selection(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        result: &QueryResultData,
+    ) {
+        let items: Vec<Line> = FilterOp::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, op)| {
+                let is_selected = i == self.filter_op_selection;
+                let prefix = if is_selected { "► " } else { "  " };
+                let text = format!("{}{:<12} {}", prefix, op.symbol(), op.label());
+                Line::from(Span::styled(
+                    text,
+                    Self::filter_item_style(is_selected, false),
+                ))
+            })
+            .collect();
+
+        let col_name = result
+            .columns
+            .get(self.filter_modal_selection)
+            .map(|s| s.as_str())
+            .unwrap_or("Column");
+        let title = format!(" {} Filter: {} ", self.filter_step_join_label(), col_name);
+        let hint = vec![
+            Span::styled("Enter", text_key()),
+            Span::styled(": Next  ", text_dim()),
+            Span::styled("Tab", text_key()),
+            Span::styled(": AND/OR  ", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(": Back", text_dim()),
+        ];
+        Self::render_filter_list(frame, area, &title, items, hint);
+    }
+
+    /// Join label for the condition being built ("" for the first condition).
+    fn filter_step_join_label(&self) -> &'static str {
+        match self.filter_editing {
+            Some(0) => "",
+            Some(idx) => self.filters.get(idx).map_or("", |c| c.join.label()),
+            None if self.filters.is_empty() => "",
+            None => self.filter_join.label(),
+        }
+    }
+
+    /// Render filter value input.
     fn render_filter_text_input(&self, frame: &mut Frame, area: Rect, result: &QueryResultData) {
         // Calculate modal dimensions
         let modal_width = (area.width as f32 * 0.5).clamp(40.0, 70.0) as u16;
-        let modal_height = 7_u16; // Fixed height for text input
+        let modal_height = 8_u16; // Fixed height for text input
 
         let modal_x = (area.width.saturating_sub(modal_width)) / 2;
         let modal_y = (area.height.saturating_sub(modal_height)) / 2;
@@ -209,25 +328,43 @@ impl ContentPanel {
             .get(self.filter_modal_selection)
             .map(|s| s.as_str())
             .unwrap_or("Column");
+        let op = self.selected_filter_op();
+
+        let title = format!(
+            " {} Filter: {} {} ",
+            self.filter_step_join_label(),
+            col_name,
+            op.symbol()
+        );
 
-        let title = format!(" Filter: {} ", col_name);
+        let prompt = match op {
+            FilterOp::Between => "  Enter a range (low..high):",
+            FilterOp::In => "  Enter values separated by commas:",
+            FilterOp::Regex => "  Enter a regular expression:",
+            FilterOp::Contains | FilterOp::StartsWith => "  Enter filter text (case-insensitive):",
+            _ => "  Enter a value (numbers and dates compare by type):",
+        };
 
         // Build input display with cursor
-        let input_display = format!("{}_", self.filter_text);
+        let input_display = format!("{}_", self.filter_input);
 
         let lines = vec![
             Line::from(""),
-            Line::from(Span::styled(
-                "  Enter filter text (case-insensitive):",
-                Style::default().fg(Color::DarkGray),
-            )),
+            Line::from(Span::styled(prompt, text_dim())),
             Line::from(""),
             Line::from(Span::styled(
                 format!("  {}", input_display),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             )),
+            Line::from(Span::styled(
+                self.filter_error
+                    .as_deref()
+                    .map(|e| format!("  {}", e))
+                    .unwrap_or_default(),
+                text_error(),
+            )),
         ];
 
         let paragraph = Paragraph::new(lines)
@@ -236,13 +373,13 @@ impl ContentPanel {
                     .title(title)
                     .title_style(
                         Style::default()
-                            .fg(Color::Magenta)
+                            .fg(theme().secondary)
                             .add_modifier(Modifier::BOLD),
                     )
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Magenta)),
+                    .border_style(Style::default().fg(theme().secondary)),
             )
-            .style(Style::default().bg(Color::Black));
+            .style(popup_style());
 
         frame.render_widget(paragraph, modal_area);
 
@@ -255,14 +392,14 @@ impl ContentPanel {
                 1,
             );
             let hint = Paragraph::new(Line::from(vec![
-                Span::styled("Enter", Style::default().fg(Color::Yellow)),
-                Span::styled(": Apply  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Esc", Style::default().fg(Color::Yellow)),
-                Span::styled(": Back  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Backspace", Style::default().fg(Color::Yellow)),
-                Span::styled(": Delete", Style::default().fg(Color::DarkGray)),
+                Span::styled("Enter", text_key()),
+                Span::styled(": Apply  ", text_dim()),
+                Span::styled("Esc", text_key()),
+                Span::styled(": Back  ", text_dim()),
+                Span::styled("Backspace", text_key()),
+                Span::styled(": Delete", text_dim()),
             ]))
-            .style(Style::default().bg(Color::Black));
+            .style(popup_style());
             frame.render_widget(hint, footer_area);
         }
     }
@@ -299,31 +436,21 @@ impl ContentPanel {
             .enumerate()
             .map(|(i, col)| {
                 let is_selected = i == self.sort_modal_selection;
-                let is_sorted = self.sort_column_index == Some(i);
-
-                // Build column text with sort indicator
-                let sort_indicator = if is_sorted {
-                    match self.sort_order {
-                        SortOrder::Ascending => " ↑",
-                        SortOrder::Descending => " ↓",
-                        SortOrder::None => "",
-                    }
-                } else {
-                    ""
-                };
+                let sort_indicator = self.sort_indicator(i);
+                let is_sorted = sort_indicator.is_some();
+                let sort_indicator = sort_indicator.unwrap_or_default();
 
                 let prefix = if is_selected { "► " } else { "  " };
                 let text = format!("{}{}{}", prefix, col, sort_indicator);
 
                 let style = if is_selected {
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Cyan)
+                    theme()
+                        .highlight(theme().accent)
                         .add_modifier(Modifier::BOLD)
                 } else if is_sorted {
-                    Style::default().fg(Color::Yellow)
+                    text_key()
                 } else {
-                    Style::default().fg(Color::White)
+                    text_normal()
                 };
 
                 Line::from(Span::styled(text, style))
@@ -336,13 +463,340 @@ impl ContentPanel {
                     .title(" Sort by Column ")
                     .title_style(
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .borders(Borders::ALL)
+                    .border_style(text_accent()),
+            )
+            .style(popup_style());
+
+        frame.render_widget(paragraph, modal_area);
+
+        // Render footer hint
+        if modal_area.height > 2 {
+            let footer_area = Rect::new(
+                modal_area.x + 1,
+                modal_area.y + modal_area.height.saturating_sub(1),
+                modal_area.width.saturating_sub(2),
+                1,
+            );
+            let hint = Paragraph::new(Line::from(vec![
+                Span::styled("Enter", text_key()),
+                Span::styled(": Sort by  ", text_dim()),
+                Span::styled("Space", text_key()),
+                Span::styled(": Add key  ", text_dim()),
+                Span::styled("Esc", text_key()),
+                Span::styled(": Close", text_dim()),
+            ]))
+            .style(popup_style());
+            frame.render_widget(hint, footer_area);
+        }
+    }
+
+    /// Render column manager modal (visibility, order, pinning, width).
+    pub(super) fn render_column_manager(&self, frame: &mut Frame, area: Rect) {
+        let result = match &self.query_result {
+            Some(r) => r,
+            None => return,
+        };
+        let layout = &self.column_layout;
+
+        // Column list + two hint lines + borders
+        let modal_width = (area.width as f32 * 0.5).clamp(44.0, 72.0) as u16;
+        let max_height = (layout.len() + 5).min(24) as u16;
+        let modal_height = max_height.min(area.height.saturating_sub(4));
+
+        let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+        let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+        let modal_area = Rect::new(
+            area.x + modal_x,
+            area.y + modal_y,
+            modal_width,
+            modal_height,
+        );
+
+        // Clear background for better visibility
+        frame.render_widget(Clear, modal_area);
+
+        // Scroll the list so the selection stays visible
+        let list_height = (modal_height as usize).saturating_sub(5).max(1);
+        let start = self
+            .column_manager_selection
+            .saturating_sub(list_height - 1);
+        let end = (start + list_height).min(layout.len());
+
+        let mut lines: Vec<Line> = (start..end)
+            .filter_map(|pos| {
+                let col_idx = layout.column_at(pos)?;
+                let name = result.columns.get(col_idx)?;
+                let is_selected = pos == self.column_manager_selection;
+
+                let prefix = if is_selected { "► " } else { "  " };
+                let checkbox = if layout.is_hidden(col_idx) {
+                    "[ ]"
+                } else {
+                    "[x]"
+                };
+                let pin = if pos < layout.pinned() { " 📌" } else { "" };
+                let width = match layout.width(col_idx) {
+                    Some(w) => format!("  w:{}", w),
+                    None => String::new(),
+                };
+                let text = format!("{}{} {}{}{}", prefix, checkbox, name, pin, width);
+
+                let style = if is_selected {
+                    theme()
+                        .highlight(theme().accent)
+                        .add_modifier(Modifier::BOLD)
+                } else if layout.is_hidden(col_idx) {
+                    text_dim()
+                } else {
+                    text_normal()
+                };
+                Some(Line::from(Span::styled(text, style)))
+            })
+            .collect();
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Space", text_key()),
+            Span::styled(": Show/Hide  ", text_dim()),
+            Span::styled("Shift+↑/↓ K/J", text_key()),
+            Span::styled(": Move  ", text_dim()),
+            Span::styled("p", text_key()),
+            Span::styled(": Pin", text_dim()),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("+/-", text_key()),
+            Span::styled(": Width  ", text_dim()),
+            Span::styled("=", text_key()),
+            Span::styled(": Auto  ", text_dim()),
+            Span::styled("R", text_key()),
+            Span::styled(": Reset  ", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(": Close", text_dim()),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(" Columns ")
+                    .title_style(
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .borders(Borders::ALL)
+                    .border_style(text_accent()),
+            )
+            .style(popup_style());
+
+        frame.render_widget(paragraph, modal_area);
+    }
+
+    /// Render group-by column selection modal (checked columns in group order).
+    pub(super) fn render_group_modal(&self, frame: &mut Frame, area: Rect) {
+        let columns = self.group_source_columns();
+
+        let modal_width = (area.width as f32 * 0.4).clamp(30.0, 60.0) as u16;
+        let max_height = (columns.len() + 4).min(20) as u16;
+        let modal_height = max_height.min(area.height.saturating_sub(4));
+
+        let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+        let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+        let modal_area = Rect::new(
+            area.x + modal_x,
+            area.y + modal_y,
+            modal_width,
+            modal_height,
+        );
+
+        // Clear background for better visibility
+        frame.render_widget(Clear, modal_area);
+
+        // Scroll the list so the selection stays visible
+        let list_height = (modal_height as usize).saturating_sub(3).max(1);
+        let start = self.group_modal_selection.saturating_sub(list_height - 1);
+
+        let items: Vec<Line> = columns
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(list_height)
+            .map(|(i, col)| {
+                let is_selected = i == self.group_modal_selection;
+                let order = self.group_modal_checked.iter().position(|&c| c == i);
+
+                let prefix = if is_selected { "► " } else { "  " };
+                let checkbox = match order {
+                    Some(n) => format!("[{}]", n + 1),
+                    None => "[ ]".to_string(),
+                };
+                let text = format!("{}{} {}", prefix, checkbox, col);
+
+                let style = if is_selected {
+                    theme()
+                        .highlight(theme().accent)
+                        .add_modifier(Modifier::BOLD)
+                } else if order.is_some() {
+                    text_key()
+                } else {
+                    text_normal()
+                };
+                Line::from(Span::styled(text, style))
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(items)
+            .block(
+                Block::default()
+                    .title(" Group by Columns ")
+                    .title_style(
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .borders(Borders::ALL)
+                    .border_style(text_accent()),
+            )
+            .style(popup_style());
+
+        frame.render_widget(paragraph, modal_area);
+
+        // Render footer hint
+        if modal_area.height > 2 {
+            let footer_area = Rect::new(
+                modal_area.x + 1,
+                modal_area.y + modal_area.height.saturating_sub(1),
+                modal_area.width.saturating_sub(2),
+                1,
+            );
+            let hint = Paragraph::new(Line::from(vec![
+                Span::styled("Space", text_key()),
+                Span::styled(": Check  ", text_dim()),
+                Span::styled("Enter", text_key()),
+                Span::styled(": Group  ", text_dim()),
+                Span::styled("Esc", text_key()),
+                Span::styled(": Cancel", text_dim()),
+            ]))
+            .style(popup_style());
+            frame.render_widget(hint, footer_area);
+        }
+    }
+
+    /// Render the column profile popup.
+    pub(super) fn render_column_profile(&self, frame: &mut Frame, area: Rect) {
+        let Some(profile) = self.column_profile() else {
+            return;
+        };
+
+        let modal_width = (area.width as f32 * 0.5).clamp(40.0, 70.0) as u16;
+        let inner_width = modal_width.saturating_sub(4) as usize;
+        let percent = |n: usize| match profile.count {
+            0 => 0.0,
+            total => n as f64 * 100.0 / total as f64,
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Rows ", text_dim()),
+                Span::styled(profile.count.to_string(), text_normal()),
+                Span::styled("   Nulls ", text_dim()),
+                Span::styled(
+                    format!("{} ({:.1}%)", profile.nulls, percent(profile.nulls)),
+                    text_normal(),
+                ),
+                Span::styled("   Distinct ", text_dim()),
+                Span::styled(profile.distinct.to_string(), text_normal()),
+            ]),
+            Line::from(vec![
+                Span::styled("Type ", text_dim()),
+                Span::styled(format!("{:?}", profile.kind).to_lowercase(), text_normal()),
+            ]),
+        ];
+
+        if let Some(ref stats) = profile.stats {
+            let stat = |label: &str, value: &str| {
+                [
+                    Span::styled(format!("{} ", label), text_dim()),
+                    Span::styled(format!("{}   ", value), text_normal()),
+                ]
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(
+                [
+                    stat("Min", &stats.min),
+                    stat("Max", &stats.max),
+                    stat("Mean", &stats.mean),
+                ]
+                .concat(),
+            ));
+            lines.push(Line::from(
+                [
+                    stat("p25", &stats.p25),
+                    stat("Median", &stats.median),
+                    stat("p75", &stats.p75),
+                    stat("p95", &stats.p95),
+                ]
+                .concat(),
+            ));
+        }
+        if !profile.histogram.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Distribution ", text_dim()),
+                Span::styled(sparkline(&profile.histogram), text_accent()),
+            ]));
+        }
+
+        if !profile.top_values.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Top values", text_key())));
+            let value_width = inner_width.saturating_sub(18).max(8);
+            for top in &profile.top_values {
+                let value = truncate_text_unicode(&top.value, value_width);
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {}", pad_to_width(&value, value_width)),
+                        text_normal(),
+                    ),
+                    Span::styled(
+                        format!("{:>7} {:>5.1}%", top.count, percent(top.count)),
+                        text_dim(),
+                    ),
+                ]));
+            }
+        }
+
+        let modal_height = (lines.len() as u16 + 3).min(area.height.saturating_sub(2));
+        let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+        let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+        let modal_area = Rect::new(
+            area.x + modal_x,
+            area.y + modal_y,
+            modal_width,
+            modal_height,
+        );
+
+        // Clear background for better visibility
+        frame.render_widget(Clear, modal_area);
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!(" Profile: {} ", profile.column))
+                    .title_style(
+                        Style::default()
+                            .fg(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     )
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(text_accent())
+                    .padding(ratatui::widgets::Padding::horizontal(1)),
             )
-            .style(Style::default().bg(Color::Black));
+            .style(popup_style());
 
         frame.render_widget(paragraph, modal_area);
 
@@ -355,12 +809,12 @@ impl ContentPanel {
                 1,
             );
             let hint = Paragraph::new(Line::from(vec![
-                Span::styled("Enter", Style::default().fg(Color::Yellow)),
-                Span::styled(": Select  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Esc", Style::default().fg(Color::Yellow)),
-                Span::styled(": Cancel", Style::default().fg(Color::DarkGray)),
+                Span::styled("←/→", text_key()),
+                Span::styled(": Column  ", text_dim()),
+                Span::styled("Esc", text_key()),
+                Span::styled(": Close", text_dim()),
             ]))
-            .style(Style::default().bg(Color::Black));
+            .style(popup_style());
             frame.render_widget(hint, footer_area);
         }
     }
@@ -0,0 +1,286 @@
+//! Mouse hit-testing for ContentPanel.
+//!
+//! Table renderers record where the table was drawn in the last frame;
+//! clicks are mapped back onto rows and column headers from that geometry,
+//! and wheel events move the selection of the current view.
+
+use ratatui::layout::{Constraint, Flex, Layout, Margin, Rect};
+use ratatui::widgets::TableState;
+
+use super::ContentPanel;
+use super::types::ContentView;
+use crate::components::styles::HIGHLIGHT_SYMBOL;
+use crate::service::LoadState;
+
+/// Screen geometry of the table drawn in the last frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableGeometry {
+    /// Row of the header line
+    header_y: u16,
+    /// Area covered by data rows (below the header and its margin)
+    rows: Rect,
+    /// Header x-ranges as (start, end, column index) for sortable columns
+    columns: Vec<(u16, u16, usize)>,
+}
+
+/// What a left click in the content panel landed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentClick {
+    /// A data row was selected (index within the current page)
+    Row(usize),
+    /// A column header was clicked and the sort order cycled
+    Header(usize),
+}
+
+impl ContentPanel {
+    /// Record the geometry of a bordered table with a one-line header.
+    pub(super) fn record_table_area(&mut self, area: Rect) {
+        let inner = area.inner(Margin::new(1, 1));
+        // Header line plus its bottom margin
+        let rows = Rect {
+            y: inner.y.saturating_add(2),
+            height: inner.height.saturating_sub(2),
+            ..inner
+        };
+        self.table_geometry = Some(TableGeometry {
+            header_y: inner.y,
+            rows,
+            columns: Vec::new(),
+        });
+    }
+
+    /// Exclude a table footer (including its margin) from the clickable rows.
+    pub(super) fn reserve_table_footer(&mut self, height: u16) {
+        if let Some(ref mut geometry) = self.table_geometry {
+            geometry.rows.height = geometry.rows.height.saturating_sub(height);
+        }
+    }
+
+    /// Record header x-ranges of the result table.
+    ///
+    /// Mirrors the column layout of ratatui's `Table`: the highlight symbol
+    /// column comes first, then the column constraints with 1-cell spacing.
+    /// The first constraint is the selection gutter and is not sortable;
+    /// the others map to `columns` (result column indices in display order).
+    pub(super) fn record_result_columns(
+        &mut self,
+        area: Rect,
+        constraints: &[Constraint],
+        columns: &[usize],
+    ) {
+        let inner = area.inner(Margin::new(1, 1));
+        let selection_width = if self.result_table_state.selected().is_some() {
+            HIGHLIGHT_SYMBOL.chars().count() as u16
+        } else {
+            0
+        };
+        let [_, columns_area] =
+            Layout::horizontal([Constraint::Length(selection_width), Constraint::Fill(0)])
+                .areas(inner);
+        let rects = Layout::horizontal(constraints.iter().copied())
+            .flex(Flex::Start)
+            .spacing(1)
+            .split(columns_area);
+
+        if let Some(ref mut geometry) = self.table_geometry {
+            geometry.columns = rects
+                .iter()
+                .skip(1)
+                .zip(columns)
+                .filter(|(rect, _)| rect.width > 0)
+                .map(|(rect, &col_idx)| (rect.x, rect.x + rect.width, col_idx))
+                .collect();
+        }
+    }
+
+    /// Handle a left click at a screen position.
+    /// A header click sorts by that column; with `add_sort_key` (Shift+click)
+    /// the column is added to the existing sort keys instead.
+    /// Returns what was hit, or None if the click missed the table.
+    pub fn click_at(&mut self, column: u16, row: u16, add_sort_key: bool) -> Option<ContentClick> {
+        let geometry = self.table_geometry.clone()?;
+
+        if row == geometry.header_y {
+            let &(_, _, col_idx) = geometry
+                .columns
+                .iter()
+                .find(|(start, end, _)| (*start..*end).contains(&column))?;
+            if add_sort_key {
+                self.cycle_sort_key(col_idx);
+            } else {
+                self.cycle_sort(col_idx);
+            }
+            return Some(ContentClick::Header(col_idx));
+        }
+
+        let rows = geometry.rows;
+        if !(rows.x..rows.x + rows.width).contains(&column)
+            || !(rows.y..rows.y + rows.height).contains(&row)
+        {
+            return None;
+        }
+
+        let len = self.view_row_count();
+        let state = self.view_table_state_mut()?;
+        let index = state.offset() + (row - rows.y) as usize;
+        if index >= len {
+            return None;
+        }
+        state.select(Some(index));
+        if self.is_result_view() {
+            // Clicking moves the cursor like normal navigation
+            self.selection_anchor = None;
+        }
+        Some(ContentClick::Row(index))
+    }
+
+    /// Move the selection of the current view one step (mouse wheel).
+    pub fn scroll_selection(&mut self, down: bool) {
+        match (&self.view, down) {
+            (ContentView::Questions, true) => self.select_next(),
+            (ContentView::Questions, false) => self.select_previous(),
+            (ContentView::Collections, true) => self.select_collections_next(),
+            (ContentView::Collections, false) => self.select_collections_previous(),
+            (ContentView::Databases, true) => self.select_databases_next(),
+            (ContentView::Databases, false) => self.select_databases_previous(),
+            (ContentView::DatabaseSchemas { .. }, true) => self.select_schemas_next(),
+            (ContentView::DatabaseSchemas { .. }, false) => self.select_schemas_previous(),
+            (ContentView::SchemaTables { .. }, true) => self.select_tables_next(),
+            (ContentView::SchemaTables { .. }, false) => self.select_tables_previous(),
+            (ContentView::History, true) => self.select_history_next(),
+            (ContentView::History, false) => self.select_history_previous(),
+            (
+                ContentView::QueryResult
+                | ContentView::TablePreview { .. }
+                | ContentView::LinkedRows { .. },
+                down,
+            ) => {
+                self.selection_anchor = None;
+                if down {
+                    self.select_result_next();
+                } else {
+                    self.select_result_previous();
+                }
+            }
+            (ContentView::Welcome, true) => self.scroll.scroll_down(),
+            (ContentView::Welcome, false) => self.scroll.scroll_up(),
+        }
+    }
+
+    /// Number of selectable rows in the current view (current page for paged views).
+    fn view_row_count(&self) -> usize {
+        match &self.view {
+            ContentView::Questions => self.questions_page_len(),
+            ContentView::Collections => self.collection_tree.visible_len(),
+            ContentView::Databases => loaded_len(&self.databases),
+            ContentView::DatabaseSchemas { .. } => loaded_len(&self.schemas),
+            ContentView::SchemaTables { .. } => loaded_len(&self.tables),
+            ContentView::History => self.history.len(),
+            ContentView::QueryResult
+            | ContentView::TablePreview { .. }
+            | ContentView::LinkedRows { .. } => {
+                let page_start = self.result_page * self.rows_per_page;
+                self.visible_row_count()
+                    .saturating_sub(page_start)
+                    .min(self.rows_per_page)
+            }
+            ContentView::Welcome => 0,
+        }
+    }
+
+    /// Table state backing the current view.
+    fn view_table_state_mut(&mut self) -> Option<&mut TableState> {
+        match &self.view {
+            ContentView::Questions => Some(&mut self.table_state),
+            ContentView::Collections => Some(&mut self.collections_table_state),
+            ContentView::Databases => Some(&mut self.databases_table_state),
+            ContentView::DatabaseSchemas { .. } => Some(&mut self.schemas_table_state),
+            ContentView::SchemaTables { .. } => Some(&mut self.tables_table_state),
+            ContentView::History => Some(&mut self.history_table_state),
+            ContentView::QueryResult
+            | ContentView::TablePreview { .. }
+            | ContentView::LinkedRows { .. } => Some(&mut self.result_table_state),
+            ContentView::Welcome => None,
+        }
+    }
+}
+
+/// Length of a loaded list, or 0 while loading.
+fn loaded_len<T>(state: &LoadState<Vec<T>>) -> usize {
+    match state {
+        LoadState::Loaded(items) => items.len(),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::types::{QueryResultData, SortOrder};
+
+    fn result_panel() -> ContentPanel {
+        let mut panel = ContentPanel::new();
+        panel.view = ContentView::QueryResult;
+        panel.query_result = Some(QueryResultData {
+            question_id: 1,
+            question_name: "Test".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            column_types: vec![],
+            column_keys: vec![],
+            rows: (0..5)
+                .map(|i| vec![i.to_string(), format!("row{}", i)])
+                .collect(),
+        });
+        panel.result_table_state.select(Some(0));
+        panel
+    }
+
+    #[test]
+    fn test_click_row_selects_and_header_cycles_sort() {
+        let mut panel = result_panel();
+        let area = Rect::new(0, 0, 60, 20);
+        let constraints = [
+            Constraint::Length(2),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ];
+        panel.record_table_area(area);
+        panel.record_result_columns(area, &constraints, &[0, 1]);
+
+        // Rows start below border, header and header margin
+        assert_eq!(panel.click_at(10, 5, false), Some(ContentClick::Row(2)));
+        assert_eq!(panel.result_table_state.selected(), Some(2));
+        // Past the last row
+        assert_eq!(panel.click_at(10, 15, false), None);
+
+        // Border (1) + highlight symbol (2) + gutter (2) + spacing (1) = column "id" at x=6
+        assert_eq!(panel.click_at(6, 1, false), Some(ContentClick::Header(0)));
+        assert_eq!(panel.sort_keys[0].order, SortOrder::Ascending);
+        panel.click_at(7, 1, false);
+        assert_eq!(panel.sort_keys[0].order, SortOrder::Descending);
+        // Shift+click on another column adds a secondary key
+        panel.click_at(20, 1, true);
+        assert_eq!(panel.sort_keys.len(), 2);
+        assert_eq!(panel.sort_keys[1].column, 1);
+        // Plain click replaces all keys
+        panel.click_at(7, 1, false);
+        assert_eq!(panel.sort_keys[0].order, SortOrder::Ascending);
+        assert_eq!(panel.sort_keys.len(), 1);
+        panel.click_at(7, 1, false);
+        panel.click_at(7, 1, false);
+        assert!(panel.sort_keys.is_empty());
+
+        // Gutter header is not sortable
+        assert_eq!(panel.click_at(3, 1, false), None);
+    }
+
+    #[test]
+    fn test_scroll_selection_in_results() {
+        let mut panel = result_panel();
+        panel.scroll_selection(true);
+        panel.scroll_selection(true);
+        assert_eq!(panel.result_table_state.selected(), Some(2));
+        panel.scroll_selection(false);
+        assert_eq!(panel.result_table_state.selected(), Some(1));
+    }
+}
@@ -5,7 +5,11 @@
 
 use ratatui::widgets::TableState;
 
+use mbr_core::api::models::CollectionEntry;
+
 use super::ContentPanel;
+use super::collection_tree::CollectionTree;
+use super::columns::ColumnLayout;
 use super::types::{ContentView, QueryResultData};
 use crate::service::LoadState;
 
@@ -13,7 +17,7 @@ impl ContentPanel {
     // === Navigation Stack Methods ===
 
     /// Push current view to stack and navigate to new view.
-    /// Used for drill-down navigation (e.g., Collections → Questions).
+    /// Used for drill-down navigation (e.g., Databases → Schemas).
     pub fn push_view(&mut self, new_view: ContentView) {
         self.navigation_stack.push(self.view.clone());
         self.view = new_view;
@@ -41,41 +45,113 @@ impl ContentPanel {
         self.navigation_stack.clear();
     }
 
-    // === Collection Questions View ===
+    // === Collection Tree Navigation ===
 
-    /// Enter collection questions view to show questions from a specific collection.
-    /// Uses navigation stack for proper back navigation.
-    pub fn enter_collection_questions(&mut self, collection_id: u32, collection_name: String) {
-        // Reset questions state for new load
-        self.questions = LoadState::Idle;
-        self.table_state = TableState::default();
-        // Push new view with embedded context
-        self.push_view(ContentView::CollectionQuestions {
-            id: collection_id,
-            name: collection_name,
-        });
+    /// Expand the selected collection node, or move to its first child if already expanded.
+    /// Returns the collection whose items need to be fetched (`Some(None)` = root).
+    pub fn expand_selected_collection(&mut self) -> Option<Option<u32>> {
+        let selected = self.collections_table_state.selected()?;
+        let index = self.collection_tree.visible_index(selected)?;
+        let node = self.collection_tree.node(index)?;
+
+        if node.expanded {
+            if let LoadState::Loaded(children) = &node.children
+                && !children.is_empty()
+            {
+                self.collections_table_state.select(Some(selected + 1));
+            }
+            return None;
+        }
+        self.collection_tree.expand(index)
     }
 
-    /// Exit collection questions view and return to previous view.
-    /// Uses navigation stack to return to the correct originating view.
-    pub fn exit_collection_questions(&mut self) {
-        // Reset questions state
-        self.questions = LoadState::Idle;
-        self.table_state = TableState::default();
-        // Pop from navigation stack (defaults to Collections if stack is empty)
-        if self.pop_view().is_none() {
-            self.view = ContentView::Collections;
+    /// Collapse the selected collection node, or move to its parent if already collapsed.
+    pub fn collapse_selected_collection(&mut self) {
+        let Some(selected) = self.collections_table_state.selected() else {
+            return;
+        };
+        let Some(index) = self.collection_tree.visible_index(selected) else {
+            return;
+        };
+        if self.collection_tree.collapse(index) {
+            return;
+        }
+        if let Some(parent) = self.collection_tree.node(index).and_then(|n| n.parent)
+            && let Some(row) = self.collection_tree.row_of(parent)
+        {
+            self.collections_table_state.select(Some(row));
         }
     }
 
-    /// Get the current collection context (id, name) for CollectionQuestions view.
-    /// Extracts context from the ContentView variant.
-    #[allow(dead_code)] // Designed for future features
-    pub fn get_collection_context(&self) -> Option<(u32, String)> {
-        match &self.view {
-            ContentView::CollectionQuestions { id, name } => Some((*id, name.clone())),
-            _ => None,
+    /// Toggle the selected collection node (Enter key).
+    /// Returns the collection whose items need to be fetched (`Some(None)` = root).
+    pub fn toggle_selected_collection(&mut self) -> Option<Option<u32>> {
+        let selected = self.collections_table_state.selected()?;
+        let index = self.collection_tree.visible_index(selected)?;
+        if self.collection_tree.collapse(index) {
+            return None;
         }
+        self.collection_tree.expand(index)
+    }
+
+    /// Mark a collection's children as loading before fetching them.
+    pub fn mark_collection_items_loading(&mut self, collection_id: Option<u32>) {
+        self.keep_collection_selection(|tree| tree.mark_loading(collection_id));
+    }
+
+    /// Attach fetched collection items to the tree.
+    pub fn set_collection_items(
+        &mut self,
+        collection_id: Option<u32>,
+        entries: &[CollectionEntry],
+    ) {
+        self.keep_collection_selection(|tree| tree.set_items(collection_id, entries));
+    }
+
+    /// Record a failed collection items fetch in the tree.
+    pub fn set_collection_items_error(&mut self, collection_id: Option<u32>, error: String) {
+        self.keep_collection_selection(|tree| tree.set_error(collection_id, error));
+    }
+
+    /// Change the tree and keep the selected node selected: re-fetched
+    /// subtrees are rebuilt, so the node is found again by its key, or else
+    /// its closest visible ancestor, and the row is kept in range otherwise.
+    fn keep_collection_selection(&mut self, change: impl FnOnce(&mut CollectionTree)) {
+        let key = self
+            .collections_table_state
+            .selected()
+            .and_then(|row| self.collection_tree.visible_index(row))
+            .map(|index| self.collection_tree.node_key(index));
+        change(&mut self.collection_tree);
+
+        let Some(key) = key else {
+            return;
+        };
+        let row = (1..=key.len())
+            .rev()
+            .find_map(|len| {
+                let index = self.collection_tree.find_key(&key[..len])?;
+                self.collection_tree.row_of(index)
+            })
+            .or_else(|| self.collection_tree.visible_len().checked_sub(1));
+        self.collections_table_state.select(row);
+    }
+
+    /// Get the breadcrumb path (top-level → selected node) in the collection tree.
+    pub fn collection_breadcrumb(&self) -> Vec<String> {
+        if self.view != ContentView::Collections {
+            return Vec::new();
+        }
+        self.collections_table_state
+            .selected()
+            .and_then(|row| self.collection_tree.visible_index(row))
+            .map(|index| self.collection_tree.path(index))
+            .unwrap_or_default()
+    }
+
+    /// Find the name of a question or model loaded in the collection tree.
+    pub fn find_collection_card_name(&self, card_id: u32) -> Option<String> {
+        self.collection_tree.find_card_name(card_id)
     }
 
     // === Database Drill-down View ===
@@ -157,6 +233,8 @@ impl ContentPanel {
         self.scroll_x = 0;
         // Reset sort/filter state
         self.reset_sort_filter_state();
+        self.column_layout = ColumnLayout::default();
+        self.column_manager_active = false;
         // Pop from navigation stack (defaults to SchemaTables if stack is empty)
         if self.pop_view().is_none() {
             // Fallback without context - should rarely happen
@@ -170,13 +248,13 @@ impl ContentPanel {
         // Clear sort/filter indices for new data
         self.sort_indices = None;
         self.filter_indices = None;
-        // Pre-compute column widths based on header + first N rows
-        self.cached_column_widths = Some(Self::compute_column_widths(&data));
         self.query_result = Some(data);
         self.result_table_state = TableState::default();
         self.result_page = 0;
+        self.scroll_x = 0;
         // Reset sort/filter state for new data
         self.reset_sort_filter_state();
+        self.reset_column_layout_for_result();
         // Auto-select first row if available
         if self
             .query_result
@@ -244,20 +322,21 @@ impl ContentPanel {
 
     // === Query Result Navigation ===
 
-    /// Set query result data and switch to QueryResult view.
+    /// Set query result data and switch to QueryResult view, outside of the
+    /// result buffers (executed questions go through `store_result`).
     /// Uses navigation stack to enable returning to the originating view.
+    #[cfg(test)]
     pub fn set_query_result(&mut self, data: QueryResultData) {
         // Clear sort/filter indices for new data
         self.sort_indices = None;
         self.filter_indices = None;
-        // Pre-compute column widths based on header + first N rows
-        self.cached_column_widths = Some(Self::compute_column_widths(&data));
         self.query_result = Some(data);
         self.result_table_state = TableState::default();
         self.result_page = 0; // Reset to first page
         self.scroll_x = 0;
         // Reset sort/filter state for new data
         self.reset_sort_filter_state();
+        self.reset_column_layout_for_result();
         // Auto-select first row if available
         if self
             .query_result
@@ -273,20 +352,30 @@ impl ContentPanel {
     /// Clear query result and return to previous view.
     /// Uses navigation stack to return to the correct originating view.
     pub fn back_to_questions(&mut self) {
+        // Keep the result in its buffer
+        self.park_live();
         self.query_result = None;
-        self.sort_indices = None;
-        self.filter_indices = None;
-        self.result_table_state = TableState::default();
-        self.result_page = 0;
-        self.scroll_x = 0;
-        // Reset sort/filter state
-        self.reset_sort_filter_state();
+        self.clear_result_state();
         // Pop from navigation stack (defaults to Questions if stack is empty)
-        if self.pop_view().is_none() {
+        if !self.pop_to_available_view() {
             self.view = ContentView::Questions;
         }
     }
 
+    /// Reset column widths and layout for newly loaded result data.
+    /// Widths are computed after `reset_sort_filter_state`, which clears the cache.
+    pub(super) fn reset_column_layout_for_result(&mut self) {
+        let Some(ref data) = self.query_result else {
+            return;
+        };
+        // Pre-compute column widths based on header + first N rows
+        self.cached_column_widths = Some(Self::compute_column_widths(data));
+        self.column_layout = ColumnLayout::new(data.columns.len());
+        self.column_manager_active = false;
+        self.column_manager_selection = 0;
+        self.column_layout_dirty = false;
+    }
+
     /// Pre-compute column widths based on header + first N rows.
     /// Samples up to 100 rows to estimate optimal column widths.
     /// Caps individual columns at 50 characters.
@@ -3,7 +3,7 @@
 //! Provides page navigation, horizontal scrolling, and row visibility calculations.
 
 use super::ContentPanel;
-use super::types::{ContentView, SortOrder};
+use super::types::{ContentView, FilterStep};
 
 impl ContentPanel {
     /// Get the number of visible rows (after search and filter are applied).
@@ -79,11 +79,10 @@ impl ContentPanel {
     /// Get total number of columns for current view.
     fn get_total_columns(&self) -> usize {
         match self.view {
-            ContentView::QueryResult => self
-                .query_result
-                .as_ref()
-                .map(|r| r.columns.len())
-                .unwrap_or(0),
+            // Pinned and hidden columns do not scroll
+            ContentView::QueryResult
+            | ContentView::TablePreview { .. }
+            | ContentView::LinkedRows { .. } => self.column_layout.visible_columns().1.len(),
             ContentView::Questions => 3, // ID, Name, Collection
             _ => 0,
         }
@@ -105,27 +104,29 @@ impl ContentPanel {
     /// Get row at logical index (respects search, filter, and sort).
     /// Returns the actual row from query_result based on search, filter, and sort indices.
     pub(super) fn get_visible_row(&self, logical_index: usize) -> Option<&Vec<String>> {
-        self.query_result.as_ref().and_then(|result| {
-            // Step 1: Apply sort (if active), get index into visible rows
-            let sorted_index = if let Some(ref sort_idx) = self.sort_indices {
-                *sort_idx.get(logical_index)?
-            } else {
-                logical_index
-            };
-
-            // Step 2: Get actual row index from filter or search indices
-            // Priority: Filter (which may operate on search results) > Search > None
-            let actual_index = match (&self.filter_indices, &self.result_search_indices) {
-                // Filter is active (may be filtering search results)
-                (Some(filter), _) => *filter.get(sorted_index)?,
-                // Only search active
-                (None, Some(search)) => *search.get(sorted_index)?,
-                // Neither: direct index
-                (None, None) => sorted_index,
-            };
-
-            result.rows.get(actual_index)
-        })
+        let actual_index = self.visible_row_index(logical_index)?;
+        self.query_result.as_ref()?.rows.get(actual_index)
+    }
+
+    /// Map a logical (displayed) row index to the index in query_result rows.
+    pub(super) fn visible_row_index(&self, logical_index: usize) -> Option<usize> {
+        // Step 1: Apply sort (if active), get index into visible rows
+        let sorted_index = if let Some(ref sort_idx) = self.sort_indices {
+            *sort_idx.get(logical_index)?
+        } else {
+            logical_index
+        };
+
+        // Step 2: Get actual row index from filter or search indices
+        // Priority: Filter (which may operate on search results) > Search > None
+        match (&self.filter_indices, &self.result_search_indices) {
+            // Filter is active (may be filtering search results)
+            (Some(filter), _) => filter.get(sorted_index).copied(),
+            // Only search active
+            (None, Some(search)) => search.get(sorted_index).copied(),
+            // Neither: direct index
+            (None, None) => Some(sorted_index),
+        }
     }
 
     /// Scroll result table up by multiple rows (PageUp).
@@ -148,14 +149,15 @@ impl ContentPanel {
     /// Reset sort, filter, and search state (helper for view transitions).
     pub(super) fn reset_sort_filter_state(&mut self) {
         // Sort state
-        self.sort_order = SortOrder::None;
-        self.sort_column_index = None;
+        self.sort_keys.clear();
         self.sort_mode_active = false;
         // Filter state
-        self.filter_column_index = None;
-        self.filter_text.clear();
+        self.filters.clear();
+        self.filter_input.clear();
         self.filter_mode_active = false;
-        self.filter_modal_step = 0;
+        self.filter_modal_step = FilterStep::default();
+        self.filter_editing = None;
+        self.filter_error = None;
         // Result search state
         self.result_search_active = false;
         self.result_search_text.clear();
@@ -164,7 +166,10 @@ impl ContentPanel {
         self.sort_dirty = false;
         self.filter_dirty = false;
         self.search_dirty = false;
-        self.cached_filter_lower = None;
         self.cached_column_widths = None;
+        // Grouping state
+        self.group = None;
+        self.group_modal_active = false;
+        self.column_profile = None;
     }
 }
@@ -0,0 +1,107 @@
+//! Column profile popup for query results.
+//!
+//! Profiles one column over the visible (searched and filtered) rows using
+//! `mbr_core::core::profile`, the same statistics as `mbr-cli run --profile`.
+
+use mbr_core::core::profile::{ColumnProfile, profile_column};
+
+use super::ContentPanel;
+use super::filter_expr::is_null;
+
+impl ContentPanel {
+    /// Check if the column profile popup is shown.
+    pub fn is_column_profile_active(&self) -> bool {
+        self.column_profile.is_some()
+    }
+
+    /// Profile the column in focus.
+    pub fn open_column_profile(&mut self) {
+        if let Some(column) = self.focus_column() {
+            self.show_column_profile(column);
+        }
+    }
+
+    /// Close the column profile popup.
+    pub fn close_column_profile(&mut self) {
+        self.column_profile = None;
+    }
+
+    /// Profile the next (or previous) visible column.
+    pub fn column_profile_step(&mut self, forward: bool) {
+        let Some((current, _)) = self.column_profile else {
+            return;
+        };
+        let (pinned, scrollable) = self.column_layout.visible_columns();
+        let columns: Vec<usize> = pinned.into_iter().chain(scrollable).collect();
+        let Some(pos) = columns.iter().position(|&c| c == current) else {
+            return;
+        };
+        let next = if forward {
+            (pos + 1) % columns.len()
+        } else {
+            (pos + columns.len() - 1) % columns.len()
+        };
+        self.show_column_profile(columns[next]);
+    }
+
+    /// Compute and show the profile of a column.
+    pub fn show_column_profile(&mut self, column: usize) {
+        let Some(ref result) = self.query_result else {
+            return;
+        };
+        let Some(name) = result.columns.get(column) else {
+            return;
+        };
+        let values: Vec<Option<&str>> = (0..self.visible_row_count())
+            .filter_map(|i| self.get_visible_row(i))
+            .map(|row| {
+                row.get(column)
+                    .map(|cell| cell.as_str())
+                    .filter(|cell| !is_null(cell))
+            })
+            .collect();
+        let profile = profile_column(name, result.column_type(column).profile_kind(), &values);
+        self.column_profile = Some((column, profile));
+    }
+
+    /// Profile shown in the popup.
+    pub(super) fn column_profile(&self) -> Option<&ColumnProfile> {
+        self.column_profile.as_ref().map(|(_, profile)| profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::{ColumnType, QueryResultData};
+
+    #[test]
+    fn test_column_profile_follows_filter_and_steps_columns() {
+        let row = |c: &str, v: &str| vec![c.to_string(), v.to_string()];
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(QueryResultData {
+            question_id: 1,
+            question_name: "Sales".to_string(),
+            columns: vec!["Country".to_string(), "Revenue".to_string()],
+            rows: vec![row("JP", "10"), row("US", "—"), row("JP", "20")],
+            column_types: vec![ColumnType::Text, ColumnType::Number],
+            column_keys: vec![],
+        });
+
+        panel.open_column_profile();
+        let profile = panel.column_profile().unwrap();
+        assert_eq!(profile.column, "Country");
+        assert_eq!((profile.distinct, profile.top_values[0].count), (2, 2));
+
+        panel.column_profile_step(true);
+        let profile = panel.column_profile().unwrap();
+        assert_eq!((profile.nulls, profile.count), (1, 3));
+        assert_eq!(profile.stats.as_ref().unwrap().mean, "15");
+
+        // Wraps around to the first column
+        panel.column_profile_step(true);
+        assert_eq!(panel.column_profile().unwrap().column, "Country");
+        panel.close_column_profile();
+        assert!(!panel.is_column_profile_active());
+    }
+}
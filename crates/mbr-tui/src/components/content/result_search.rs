@@ -4,7 +4,6 @@
 //! with case-insensitive matching and integration with filter/sort.
 
 use super::ContentPanel;
-use super::types::SortOrder;
 
 impl ContentPanel {
     /// Check if result search mode is active.
@@ -32,10 +31,10 @@ impl ContentPanel {
         if self.result_search_text.len() >= 3 {
             self.search_dirty = true;
             // Cascade: filter and sort also need recomputation
-            if self.filter_column_index.is_some() && !self.filter_text.is_empty() {
+            if !self.filters.is_empty() {
                 self.filter_dirty = true;
             }
-            if self.sort_order != SortOrder::None {
+            if !self.sort_keys.is_empty() {
                 self.sort_dirty = true;
             }
         }
@@ -47,10 +46,10 @@ impl ContentPanel {
         self.result_search_text.pop();
         // Mark dirty for recomputation (including when dropping below 3 chars to clear results)
         self.search_dirty = true;
-        if self.filter_column_index.is_some() && !self.filter_text.is_empty() {
+        if !self.filters.is_empty() {
             self.filter_dirty = true;
         }
-        if self.sort_order != SortOrder::None {
+        if !self.sort_keys.is_empty() {
             self.sort_dirty = true;
         }
     }
@@ -70,10 +69,10 @@ impl ContentPanel {
         self.result_search_active = false;
         self.search_dirty = false; // Already cleared manually
         // Cascade: filter and sort need recomputation on full data
-        if self.filter_column_index.is_some() && !self.filter_text.is_empty() {
+        if !self.filters.is_empty() {
             self.filter_dirty = true;
         }
-        if self.sort_order != SortOrder::None {
+        if !self.sort_keys.is_empty() {
             self.sort_dirty = true;
         }
     }
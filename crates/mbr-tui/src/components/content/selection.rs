@@ -6,6 +6,7 @@
 use mbr_core::api::models::{CollectionItem, Database, Question, TableInfo};
 
 use super::ContentPanel;
+use super::collection_tree::{CollectionTree, TreeSelection};
 use super::types::ContentView;
 use crate::service::LoadState;
 
@@ -13,7 +14,7 @@ impl ContentPanel {
     // === Questions view navigation ===
 
     /// Get the number of questions visible on the current page.
-    fn questions_page_len(&self) -> usize {
+    pub(super) fn questions_page_len(&self) -> usize {
         if let LoadState::Loaded(questions) = &self.questions {
             let offset = self.questions_offset as usize;
             let page_size = self.questions_page_size as usize;
@@ -57,37 +58,34 @@ impl ContentPanel {
 
     // === Collections view navigation ===
 
-    /// Select next collection in list.
+    /// Select next node in the collection tree.
     pub(super) fn select_collections_next(&mut self) {
-        if let LoadState::Loaded(collections) = &self.collections {
-            if collections.is_empty() {
-                return;
-            }
-            let current = self.collections_table_state.selected().unwrap_or(0);
-            let next = (current + 1).min(collections.len() - 1);
-            self.collections_table_state.select(Some(next));
+        let len = self.collection_tree.visible_len();
+        if len == 0 {
+            return;
         }
+        let current = self.collections_table_state.selected().unwrap_or(0);
+        let next = (current + 1).min(len - 1);
+        self.collections_table_state.select(Some(next));
     }
 
-    /// Select previous collection in list.
+    /// Select previous node in the collection tree.
     pub(super) fn select_collections_previous(&mut self) {
         let current = self.collections_table_state.selected().unwrap_or(0);
         let prev = current.saturating_sub(1);
         self.collections_table_state.select(Some(prev));
     }
 
-    /// Select first collection in list.
+    /// Select first node in the collection tree.
     pub(super) fn select_collections_first(&mut self) {
         self.collections_table_state.select(Some(0));
     }
 
-    /// Select last collection in list.
+    /// Select last visible node in the collection tree.
     pub(super) fn select_collections_last(&mut self) {
-        if let LoadState::Loaded(collections) = &self.collections
-            && !collections.is_empty()
-        {
-            self.collections_table_state
-                .select(Some(collections.len() - 1));
+        let len = self.collection_tree.visible_len();
+        if len > 0 {
+            self.collections_table_state.select(Some(len - 1));
         }
     }
 
@@ -234,15 +232,12 @@ impl ContentPanel {
     }
 
     /// Update collections data from AppData.
-    /// Automatically selects first item when data is loaded.
+    /// Rebuilds the collection tree and selects the root node when data is loaded.
     pub fn update_collections(&mut self, collections: &LoadState<Vec<CollectionItem>>) {
         self.collections = collections.clone();
 
-        // Auto-select first item when data is loaded
-        if let LoadState::Loaded(items) = collections
-            && !items.is_empty()
-            && self.collections_table_state.selected().is_none()
-        {
+        if let LoadState::Loaded(items) = collections {
+            self.collection_tree = CollectionTree::from_collections(items);
             self.collections_table_state.select(Some(0));
         }
     }
@@ -292,7 +287,6 @@ impl ContentPanel {
     // === Selected item getters ===
 
     /// Get the currently selected question ID.
-    /// Works in both Questions and CollectionQuestions views.
     /// Converts page-local selection index to global index.
     pub fn get_selected_question_id(&self) -> Option<u32> {
         if !self.is_questions_view() {
@@ -307,19 +301,18 @@ impl ContentPanel {
         None
     }
 
-    /// Get the currently selected collection info (id, name).
-    pub fn get_selected_collection_info(&self) -> Option<(u32, String)> {
+    /// Get the currently selected node in the collection tree.
+    pub fn get_selected_collection_node(&self) -> Option<TreeSelection> {
         if self.view != ContentView::Collections {
             return None;
         }
-        if let LoadState::Loaded(collections) = &self.collections
-            && let Some(selected) = self.collections_table_state.selected()
-        {
-            return collections
-                .get(selected)
-                .and_then(|c| c.id.map(|id| (id, c.name.clone())));
-        }
-        None
+        let selected = self.collections_table_state.selected()?;
+        let index = self.collection_tree.visible_index(selected)?;
+        self.collection_tree.node(index).map(|node| TreeSelection {
+            kind: node.kind,
+            id: node.id,
+            name: node.name.clone(),
+        })
     }
 
     /// Get the currently selected database info (id, name).
@@ -0,0 +1,193 @@
+//! Session persistence for the content panel.
+//!
+//! Converts the navigation path and the filter, sort and search of a
+//! question result to and from the `TuiState` records in mbr-core. Columns
+//! are saved by name so that state survives column changes of a question.
+
+use mbr_core::storage::tui_state::{ResultView, SavedFilter, SavedSortKey, SessionView};
+
+use super::filter_expr::{FilterCondition, FilterJoin, FilterOp};
+use super::types::{ContentView, SortKey, SortOrder};
+use super::{ContentPanel, column_position};
+
+impl ContentPanel {
+    /// Navigation path to reopen in a later session, from the tab's root
+    /// view. Stops at views that cannot be reopened (linked rows, history).
+    pub fn session_views(&self) -> Vec<SessionView> {
+        let mut views = Vec::new();
+        for view in self.navigation_stack.iter().chain([&self.view]) {
+            let saved = match view {
+                ContentView::Welcome => continue,
+                ContentView::Questions => SessionView::Questions,
+                ContentView::Collections => SessionView::Collections,
+                ContentView::Databases => SessionView::Databases,
+                ContentView::QueryResult => match self.result_question_id() {
+                    Some(question_id) => SessionView::QueryResult { question_id },
+                    None => break,
+                },
+                ContentView::DatabaseSchemas { db_id, db_name } => SessionView::DatabaseSchemas {
+                    db_id: *db_id,
+                    db_name: db_name.clone(),
+                },
+                ContentView::SchemaTables { db_id, schema_name } => SessionView::SchemaTables {
+                    db_id: *db_id,
+                    schema_name: schema_name.clone(),
+                },
+                ContentView::TablePreview {
+                    db_id,
+                    table_id,
+                    table_name,
+                } => SessionView::TablePreview {
+                    db_id: *db_id,
+                    table_id: *table_id,
+                    table_name: table_name.clone(),
+                },
+                ContentView::LinkedRows { .. } | ContentView::History => break,
+            };
+            views.push(saved);
+        }
+        views
+    }
+
+    /// Question of the open question result, also while linked rows opened
+    /// from it are shown.
+    fn result_question_id(&self) -> Option<u32> {
+        match self.link_stack.first() {
+            Some(origin) => Some(origin.question_id()),
+            None => self.query_result.as_ref().map(|r| r.question_id),
+        }
+    }
+
+    /// Filter, sort and search of the shown question result, for saving.
+    /// None unless an ungrouped question result is shown.
+    pub fn result_view_settings(&self) -> Option<(u32, ResultView)> {
+        if self.view != ContentView::QueryResult || self.is_grouped() {
+            return None;
+        }
+        let result = self.query_result.as_ref()?;
+        let name = |column: usize| result.columns.get(column).cloned().unwrap_or_default();
+        let view = ResultView {
+            filters: self
+                .filters
+                .iter()
+                .map(|c| SavedFilter {
+                    join: c.join.label().to_lowercase(),
+                    column: name(c.column),
+                    op: c.op.symbol().to_string(),
+                    value: c.value.clone(),
+                })
+                .collect(),
+            sort: self
+                .sort_keys
+                .iter()
+                .map(|k| SavedSortKey {
+                    column: name(k.column),
+                    descending: k.order == SortOrder::Descending,
+                })
+                .collect(),
+            search: self.result_search_text.clone(),
+        };
+        Some((result.question_id, view))
+    }
+
+    /// Apply saved filter, sort and search to the shown result.
+    /// Conditions on columns the result no longer has are dropped.
+    pub fn apply_saved_result_view(&mut self, saved: &ResultView) {
+        let Some(ref result) = self.query_result else {
+            return;
+        };
+        let filters: Vec<FilterCondition> = saved
+            .filters
+            .iter()
+            .filter_map(|f| {
+                let join = if f.join == "or" {
+                    FilterJoin::Or
+                } else {
+                    FilterJoin::And
+                };
+                let column = column_position(&result.columns, &f.column)?;
+                FilterCondition::new(join, column, FilterOp::from_symbol(&f.op)?, &f.value).ok()
+            })
+            .collect();
+        let sort_keys: Vec<SortKey> = saved
+            .sort
+            .iter()
+            .filter_map(|k| {
+                Some(SortKey {
+                    column: column_position(&result.columns, &k.column)?,
+                    order: if k.descending {
+                        SortOrder::Descending
+                    } else {
+                        SortOrder::Ascending
+                    },
+                })
+            })
+            .collect();
+
+        self.result_search_text = saved.search.clone();
+        self.update_result_search_indices();
+        self.set_filters(filters);
+        self.set_sort_keys(sort_keys);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::{ColumnType, QueryResultData};
+
+    fn panel() -> ContentPanel {
+        let row = |c: &str, v: &str| vec![c.to_string(), v.to_string()];
+        let mut panel = ContentPanel::new();
+        panel.set_view(ContentView::Questions);
+        panel.set_query_result(QueryResultData {
+            question_id: 9,
+            question_name: "Sales".to_string(),
+            columns: vec!["Country".to_string(), "Revenue".to_string()],
+            rows: vec![row("JP", "10"), row("US", "5"), row("JP", "20")],
+            column_types: vec![ColumnType::Text, ColumnType::Number],
+            column_keys: vec![],
+        });
+        panel
+    }
+
+    #[test]
+    fn test_result_view_round_trip() {
+        let mut panel = panel();
+        panel.set_filters(vec![
+            FilterCondition::new(FilterJoin::And, 0, FilterOp::Eq, "JP").unwrap(),
+        ]);
+        panel.set_sort_keys(vec![SortKey {
+            column: 1,
+            order: SortOrder::Descending,
+        }]);
+        let (question_id, saved) = panel.result_view_settings().unwrap();
+        assert_eq!(question_id, 9);
+        assert_eq!(saved.filters[0].op, "=");
+
+        let mut restored = self::panel();
+        restored.apply_saved_result_view(&saved);
+        assert_eq!(restored.filter_indices, Some(vec![0, 2]));
+        assert_eq!(restored.sort_keys, panel.sort_keys);
+        assert_eq!(restored.get_visible_row(0).unwrap()[1], "20");
+    }
+
+    #[test]
+    fn test_session_views_stop_at_linked_rows() {
+        let mut panel = panel();
+        assert_eq!(
+            panel.session_views(),
+            vec![
+                SessionView::Questions,
+                SessionView::QueryResult { question_id: 9 }
+            ]
+        );
+
+        let linked = panel.query_result.clone().unwrap();
+        panel.enter_linked_rows(QueryResultData {
+            question_id: 3,
+            ..linked
+        });
+        assert_eq!(panel.session_views().len(), 2);
+    }
+}
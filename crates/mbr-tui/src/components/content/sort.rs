@@ -1,10 +1,15 @@
 //! Sort functionality for query results.
 //!
 //! Provides sort modal handling, sort order cycling, and index-based sorting
-//! for memory-efficient sorting of large result sets.
+//! for memory-efficient sorting of large result sets. Results can be sorted
+//! by several keys; the sort is stable, so rows that compare equal keep the
+//! order of the (searched and filtered) result.
+
+use std::cmp::Ordering;
 
 use super::ContentPanel;
-use super::types::SortOrder;
+use super::filter_expr::is_null;
+use super::types::{ColumnType, SortKey, SortOrder};
 
 impl ContentPanel {
     /// Check if sort modal is active.
@@ -18,8 +23,8 @@ impl ContentPanel {
             && !result.columns.is_empty()
         {
             self.sort_mode_active = true;
-            // Start at currently sorted column or first column
-            self.sort_modal_selection = self.sort_column_index.unwrap_or(0);
+            // Start at the primary sort column or first column
+            self.sort_modal_selection = self.sort_keys.first().map_or(0, |k| k.column);
         }
     }
 
@@ -47,119 +52,291 @@ impl ContentPanel {
     /// Apply sort on selected column.
     /// If same column is selected, toggles between Ascending -> Descending -> None.
     pub fn apply_sort(&mut self) {
-        let selected_col = self.sort_modal_selection;
-
-        // Toggle sort order
-        if self.sort_column_index == Some(selected_col) {
-            // Same column - cycle through orders
-            self.sort_order = match self.sort_order {
-                SortOrder::None => SortOrder::Ascending,
-                SortOrder::Ascending => SortOrder::Descending,
-                SortOrder::Descending => SortOrder::None,
-            };
-            if self.sort_order == SortOrder::None {
-                // Restore original order by clearing indices
-                self.sort_column_index = None;
-                self.sort_indices = None;
-            } else {
-                // Sort indices (not data)
-                self.update_sort_indices();
-            }
-        } else {
-            // New column - start with ascending
-            self.sort_column_index = Some(selected_col);
-            self.sort_order = SortOrder::Ascending;
-            // Sort indices (not data)
-            self.update_sort_indices();
-        }
+        self.cycle_sort(self.sort_modal_selection);
 
         // Close modal
         self.sort_mode_active = false;
+    }
+
+    /// Add the selected column as a sort key, or cycle it if already a key.
+    /// Keeps the modal open so that several keys can be chosen.
+    pub fn apply_sort_key(&mut self) {
+        self.cycle_sort_key(self.sort_modal_selection);
+    }
+
+    /// Cycle the sort order of a column (used by the sort modal and header clicks).
+    /// Sorting by a single column: a new column starts Ascending; the sorted
+    /// column goes Ascending -> Descending -> None. Other sort keys are dropped.
+    pub fn cycle_sort(&mut self, column_index: usize) {
+        let order = match self.sort_keys.as_slice() {
+            [key] if key.column == column_index => next_order(key.order),
+            _ => SortOrder::Ascending,
+        };
+        self.sort_keys.clear();
+        if order != SortOrder::None {
+            self.sort_keys.push(SortKey {
+                column: column_index,
+                order,
+            });
+        }
+        self.sort_keys_changed();
+    }
+
+    /// Cycle a column as an additional sort key (Shift+click, Space in the sort modal).
+    /// A new column is appended Ascending; an existing key goes
+    /// Ascending -> Descending -> removed, keeping the priority of the other keys.
+    pub fn cycle_sort_key(&mut self, column_index: usize) {
+        match self.sort_keys.iter().position(|k| k.column == column_index) {
+            Some(pos) => match next_order(self.sort_keys[pos].order) {
+                SortOrder::None => {
+                    self.sort_keys.remove(pos);
+                }
+                order => self.sort_keys[pos].order = order,
+            },
+            None => self.sort_keys.push(SortKey {
+                column: column_index,
+                order: SortOrder::Ascending,
+            }),
+        }
+        self.sort_keys_changed();
+    }
+
+    /// Replace all sort keys (used by the `:sort` command).
+    /// Keys with `SortOrder::None` are ignored; an empty list restores the original order.
+    pub fn set_sort_keys(&mut self, keys: Vec<SortKey>) {
+        self.sort_keys = keys
+            .into_iter()
+            .filter(|k| k.order != SortOrder::None)
+            .collect();
+        self.sort_keys_changed();
+    }
 
-        // Reset to first page and first row after sort
+    /// Restore the original row order.
+    pub fn clear_sort(&mut self) {
+        self.set_sort_keys(Vec::new());
+    }
+
+    /// Re-sort and reset to the first page and first row.
+    fn sort_keys_changed(&mut self) {
+        // Sort indices (not data)
+        self.update_sort_indices();
         self.result_page = 0;
         self.result_table_state.select(Some(0));
     }
 
-    /// Update sort indices based on current sort column and order.
+    /// Update sort indices based on the current sort keys.
     /// Uses index-based sorting for memory efficiency (no data cloning).
     pub(super) fn update_sort_indices(&mut self) {
-        if self.sort_order == SortOrder::None {
+        let Some(ref result) = self.query_result else {
+            self.sort_indices = None;
+            return;
+        };
+
+        let keys: Vec<(SortKey, ColumnType)> = self
+            .sort_keys
+            .iter()
+            .filter(|k| k.column < result.columns.len() && k.order != SortOrder::None)
+            .map(|k| (*k, result.column_type(k.column)))
+            .collect();
+        if keys.is_empty() {
             self.sort_indices = None;
             return;
         }
 
-        let col_idx = match self.sort_column_index {
-            Some(idx) => idx,
-            None => {
-                self.sort_indices = None;
-                return;
-            }
-        };
+        // Create index array based on filtered or full row count
+        let row_count = self.visible_row_count();
+        let mut indices: Vec<usize> = (0..row_count).collect();
 
-        if let Some(ref result) = self.query_result {
-            if col_idx >= result.columns.len() {
-                self.sort_indices = None;
-                return;
-            }
-
-            // Create index array based on filtered or full row count
-            let row_count = self.visible_row_count();
-            let mut indices: Vec<usize> = (0..row_count).collect();
-
-            // Sort indices based on row values at col_idx
-            // When filter is active, we need to look up actual row through filter_indices
-            let order = self.sort_order;
-            let filter_indices = &self.filter_indices;
-            indices.sort_by(|&a, &b| {
-                // Get actual row index (through filter if active)
-                let actual_a = if let Some(fi) = filter_indices {
-                    fi[a]
-                } else {
-                    a
-                };
-                let actual_b = if let Some(fi) = filter_indices {
-                    fi[b]
-                } else {
-                    b
-                };
-
-                let val_a = result.rows[actual_a]
-                    .get(col_idx)
-                    .map(|s| s.as_str())
-                    .unwrap_or("");
-                let val_b = result.rows[actual_b]
-                    .get(col_idx)
-                    .map(|s| s.as_str())
-                    .unwrap_or("");
-
-                // Try numeric comparison first
-                let cmp = match (val_a.parse::<f64>(), val_b.parse::<f64>()) {
-                    (Ok(num_a), Ok(num_b)) => num_a
-                        .partial_cmp(&num_b)
-                        .unwrap_or(std::cmp::Ordering::Equal),
-                    _ => val_a.cmp(val_b), // Fall back to string comparison
-                };
-
-                match order {
-                    SortOrder::Ascending => cmp,
-                    SortOrder::Descending => cmp.reverse(),
-                    SortOrder::None => std::cmp::Ordering::Equal,
-                }
-            });
+        // When filter is active, we need to look up actual row through filter_indices
+        let filter_indices = &self.filter_indices;
+        let actual = |i: usize| filter_indices.as_ref().map_or(i, |fi| fi[i]);
+        let cell =
+            |row: usize, col: usize| result.rows[row].get(col).map(|s| s.as_str()).unwrap_or("");
+
+        // Stable sort: ties keep the current visible order
+        indices.sort_by(|&a, &b| {
+            let (row_a, row_b) = (actual(a), actual(b));
+            keys.iter()
+                .map(|(key, column_type)| {
+                    compare_cells(
+                        cell(row_a, key.column),
+                        cell(row_b, key.column),
+                        *column_type,
+                        key.order,
+                    )
+                })
+                .find(|ord| ord.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
 
-            self.sort_indices = Some(indices);
+        self.sort_indices = Some(indices);
+    }
+
+    /// Header indicator for a sorted column: arrow, plus the priority
+    /// when sorting by more than one key (e.g. ` ↑1`).
+    pub(super) fn sort_indicator(&self, column_index: usize) -> Option<String> {
+        let pos = self
+            .sort_keys
+            .iter()
+            .position(|k| k.column == column_index)?;
+        let arrow = match self.sort_keys[pos].order {
+            SortOrder::Ascending => "↑",
+            SortOrder::Descending => "↓",
+            SortOrder::None => return None,
+        };
+        if self.sort_keys.len() > 1 {
+            Some(format!(" {}{}", arrow, pos + 1))
+        } else {
+            Some(format!(" {}", arrow))
         }
     }
 
-    /// Get current sort info for display.
-    pub fn get_sort_info(&self) -> Option<(String, SortOrder)> {
-        if let (Some(col_idx), Some(result)) = (self.sort_column_index, &self.query_result)
-            && col_idx < result.columns.len()
-            && self.sort_order != SortOrder::None
-        {
-            return Some((result.columns[col_idx].clone(), self.sort_order));
+    /// Get current sort info for display: (column name, order) per key.
+    pub fn get_sort_info(&self) -> Vec<(String, SortOrder)> {
+        let Some(ref result) = self.query_result else {
+            return Vec::new();
+        };
+        self.sort_keys
+            .iter()
+            .filter_map(|k| Some((result.columns.get(k.column)?.clone(), k.order)))
+            .collect()
+    }
+}
+
+/// Next order when cycling a sort key.
+fn next_order(order: SortOrder) -> SortOrder {
+    match order {
+        SortOrder::None => SortOrder::Ascending,
+        SortOrder::Ascending => SortOrder::Descending,
+        SortOrder::Descending => SortOrder::None,
+    }
+}
+
+/// Compare two cells of a column according to its type and the sort order.
+///
+/// Numbers compare numerically and dates chronologically (ISO 8601 text).
+/// Text compares numerically when both sides are numbers, otherwise
+/// case-insensitively. NULLs sort last in either direction.
+fn compare_cells(a: &str, b: &str, column_type: ColumnType, order: SortOrder) -> Ordering {
+    match (is_null(a), is_null(b)) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+
+    let (a, b) = (a.trim(), b.trim());
+    let numeric = || match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.partial_cmp(&y),
+        _ => None,
+    };
+    let text = || {
+        a.to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b))
+    };
+
+    let cmp = match column_type {
+        ColumnType::Date => a.replace(' ', "T").cmp(&b.replace(' ', "T")),
+        ColumnType::Number | ColumnType::Text | ColumnType::Boolean => {
+            numeric().unwrap_or_else(text)
         }
-        None
+    };
+
+    match order {
+        SortOrder::Descending => cmp.reverse(),
+        SortOrder::Ascending | SortOrder::None => cmp,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::content::{FilterCondition, FilterJoin, FilterOp, QueryResultData};
+
+    fn panel() -> ContentPanel {
+        let mut panel = ContentPanel::new();
+        panel.set_query_result(QueryResultData {
+            question_id: 1,
+            question_name: "Sales".to_string(),
+            columns: vec![
+                "Country".to_string(),
+                "Revenue".to_string(),
+                "Date".to_string(),
+            ],
+            column_types: vec![ColumnType::Text, ColumnType::Number, ColumnType::Date],
+            column_keys: vec![],
+            rows: vec![
+                vec!["jp".into(), "9".into(), "2024-03-01".into()],
+                vec!["US".into(), "100".into(), "2024-01-15 10:00".into()],
+                vec!["jp".into(), "20".into(), "—".into()],
+                vec!["us".into(), "100".into(), "2023-12-31".into()],
+            ],
+        });
+        panel
+    }
+
+    fn first_column(panel: &ContentPanel, col: usize) -> Vec<String> {
+        panel
+            .get_visible_records()
+            .into_iter()
+            .map(|(_, row)| row[col].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_multi_column_sort_is_stable_and_typed() {
+        let mut panel = panel();
+        panel.cycle_sort(0);
+        panel.cycle_sort_key(1);
+        panel.cycle_sort_key(1);
+        assert_eq!(
+            panel.sort_keys,
+            vec![
+                SortKey {
+                    column: 0,
+                    order: SortOrder::Ascending
+                },
+                SortKey {
+                    column: 1,
+                    order: SortOrder::Descending
+                },
+            ]
+        );
+        // Country ascending (case-insensitive), revenue descending numerically,
+        // ties (US/us 100) keep their original order
+        assert_eq!(first_column(&panel, 1), vec!["20", "9", "100", "100"]);
+        assert_eq!(first_column(&panel, 0), vec!["jp", "jp", "US", "us"]);
+        assert_eq!(panel.sort_indicator(1), Some(" ↓2".to_string()));
+
+        // Removing a key keeps the others
+        panel.cycle_sort_key(1);
+        assert_eq!(panel.sort_keys.len(), 1);
+        assert_eq!(panel.sort_indicator(0), Some(" ↑".to_string()));
+
+        // Plain cycle replaces all keys
+        panel.cycle_sort_key(1);
+        panel.cycle_sort(2);
+        assert_eq!(panel.sort_keys.len(), 1);
+        // Dates chronological, NULL last
+        assert_eq!(
+            first_column(&panel, 2),
+            vec!["2023-12-31", "2024-01-15 10:00", "2024-03-01", "—"]
+        );
+    }
+
+    #[test]
+    fn test_sort_survives_filter_changes() {
+        let mut panel = panel();
+        panel.set_sort_keys(vec![SortKey {
+            column: 1,
+            order: SortOrder::Descending,
+        }]);
+        panel.set_filters(vec![
+            FilterCondition::new(FilterJoin::And, 0, FilterOp::Eq, "jp").unwrap(),
+        ]);
+        assert_eq!(first_column(&panel, 1), vec!["20", "9"]);
+        panel.clear_filter();
+        assert_eq!(first_column(&panel, 1), vec!["100", "100", "20", "9"]);
     }
 }
@@ -1,6 +1,8 @@
 //! Type definitions for the content panel.
 //!
-//! Contains view types, input modes, sort orders, and query result data structures.
+//! Contains view types, input modes, sort orders, column types, and query result data structures.
+
+use mbr_core::core::profile::ProfileKind;
 
 /// Input mode for text input fields.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -12,6 +14,20 @@ pub enum InputMode {
     Search,
 }
 
+/// Step of the filter modal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterStep {
+    /// List of active conditions (edit, remove, AND/OR)
+    Conditions,
+    /// Column selection
+    #[default]
+    Column,
+    /// Operator selection
+    Operator,
+    /// Value input
+    Value,
+}
+
 /// Sort order for query results.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SortOrder {
@@ -24,6 +40,57 @@ pub enum SortOrder {
     Descending,
 }
 
+/// A sort key of a multi-column sort. Earlier keys take priority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    /// Column index in the result
+    pub column: usize,
+    /// Sort direction (never `SortOrder::None`)
+    pub order: SortOrder,
+}
+
+/// Column data type, derived from the Metabase `base_type`.
+/// Drives type-aware filtering and sorting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnType {
+    #[default]
+    Text,
+    Number,
+    /// Dates, datetimes and times (ISO 8601 text)
+    Date,
+    Boolean,
+}
+
+impl ColumnType {
+    /// Map a Metabase base type such as `type/Integer` or `type/DateTime`.
+    pub fn from_base_type(base_type: &str) -> Self {
+        match base_type.trim_start_matches("type/") {
+            "Integer" | "BigInteger" | "Float" | "Decimal" | "Number" => ColumnType::Number,
+            "Date"
+            | "DateTime"
+            | "DateTimeWithTZ"
+            | "DateTimeWithLocalTZ"
+            | "DateTimeWithZoneID"
+            | "DateTimeWithZoneOffset"
+            | "Time"
+            | "TimeWithTZ"
+            | "TimeWithLocalTZ"
+            | "TimeWithZoneOffset" => ColumnType::Date,
+            "Boolean" => ColumnType::Boolean,
+            _ => ColumnType::Text,
+        }
+    }
+
+    /// How values are interpreted when profiling the column.
+    pub fn profile_kind(self) -> ProfileKind {
+        match self {
+            ColumnType::Number => ProfileKind::Number,
+            ColumnType::Date => ProfileKind::Date,
+            ColumnType::Text | ColumnType::Boolean => ProfileKind::Text,
+        }
+    }
+}
+
 /// Content view types with embedded navigation context.
 ///
 /// Views that represent drill-down navigation carry their context data directly,
@@ -37,11 +104,6 @@ pub enum ContentView {
     Collections,
     Databases,
     QueryResult,
-    /// Questions filtered by a specific collection (id, name)
-    CollectionQuestions {
-        id: u32,
-        name: String,
-    },
     /// Schemas in a specific database (db_id, db_name)
     DatabaseSchemas {
         db_id: u32,
@@ -58,6 +120,33 @@ pub enum ContentView {
         table_id: u32,
         table_name: String,
     },
+    /// Rows reached by following a key from a record (title)
+    LinkedRows {
+        title: String,
+    },
+    /// Executed questions of this and earlier sessions
+    History,
+}
+
+/// Key role of a result column, from the field metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnKey {
+    #[default]
+    None,
+    /// Primary key of its table (referenced by foreign keys in other tables)
+    Primary { table_id: u32, field_id: u32 },
+    /// Foreign key referencing a field in another table
+    Foreign { target_field_id: u32 },
+}
+
+/// A table field that rows can be looked up by (foreign-key navigation).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordLink {
+    pub database_id: u32,
+    pub table_id: u32,
+    pub field_id: u32,
+    /// Display label such as `Orders.User ID`
+    pub label: String,
 }
 
 /// Query result data for display in TUI.
@@ -71,6 +160,22 @@ pub struct QueryResultData {
     pub columns: Vec<String>,
     /// Row data (each cell as string)
     pub rows: Vec<Vec<String>>,
+    /// Column types (may be empty when unknown; missing entries are Text)
+    pub column_types: Vec<ColumnType>,
+    /// Column key roles (may be empty; missing entries are not keys)
+    pub column_keys: Vec<ColumnKey>,
+}
+
+impl QueryResultData {
+    /// Type of a column (Text when unknown).
+    pub fn column_type(&self, column: usize) -> ColumnType {
+        self.column_types.get(column).copied().unwrap_or_default()
+    }
+
+    /// Key role of a column (None when unknown).
+    pub fn column_key(&self, column: usize) -> ColumnKey {
+        self.column_keys.get(column).copied().unwrap_or_default()
+    }
 }
 
 /// Default rows per page for query result pagination.
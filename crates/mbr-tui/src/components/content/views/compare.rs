@@ -0,0 +1,118 @@
+//! Compared result pane of the split view.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::components::content::ContentPanel;
+use crate::components::content::compare::align_rows;
+use crate::components::styles::{header_style, result_row_highlight_style, text_dim};
+use crate::components::theme::theme;
+
+/// Width limits of a compared column.
+const MIN_COLUMN_WIDTH: usize = 4;
+const MAX_COLUMN_WIDTH: usize = 30;
+
+impl ContentPanel {
+    /// Render the buffer compared with the live result. Its rows follow the
+    /// rows of the live result's current page, and its columns scroll with it.
+    pub(in crate::components::content) fn render_compare_pane(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+    ) {
+        let Some(index) = self.split else {
+            return;
+        };
+        let (Some(left), Some(right)) = (self.query_result.as_ref(), self.buffer_data(index))
+        else {
+            return;
+        };
+        let left_rows: Vec<usize> = (0..self.visible_row_count())
+            .filter_map(|i| self.visible_row_index(i))
+            .collect();
+        let alignment = align_rows(left, &left_rows, right);
+        let page_end = ((self.result_page + 1) * self.rows_per_page).min(alignment.rows.len());
+        let page_start = (self.result_page * self.rows_per_page).min(page_end);
+        let page_rows = &alignment.rows[page_start..page_end];
+
+        // Column widths from the header and the values on this page
+        let width = |col: usize| {
+            page_rows
+                .iter()
+                .flatten()
+                .filter_map(|(r, _)| right.rows[*r].get(col))
+                .map(|v| v.width())
+                .chain([right.columns[col].width()])
+                .max()
+                .unwrap_or(0)
+                .clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH)
+        };
+        let first = self.scroll_x.min(right.columns.len().saturating_sub(1));
+        let mut remaining = area.width.saturating_sub(4) as usize;
+        let mut columns: Vec<(usize, usize)> = Vec::new();
+        for col in first..right.columns.len() {
+            let w = width(col);
+            if !columns.is_empty() && w + 1 > remaining {
+                break;
+            }
+            remaining = remaining.saturating_sub(w + 1);
+            columns.push((col, w));
+        }
+
+        let diff_style = Style::default()
+            .fg(theme().error)
+            .add_modifier(Modifier::BOLD);
+        let rows: Vec<Row> = page_rows
+            .iter()
+            .map(|aligned| match aligned {
+                Some((r, diff)) => Row::new(columns.iter().map(|&(col, _)| {
+                    let value = right.rows[*r].get(col).cloned().unwrap_or_default();
+                    if diff.contains(&col) {
+                        Cell::from(value).style(diff_style)
+                    } else {
+                        Cell::from(value)
+                    }
+                })),
+                None => Row::new([Cell::from("(no match)").style(text_dim())]),
+            })
+            .collect();
+
+        let summary = match alignment.key {
+            Some((ref key, _)) => format!(
+                "by {}: {} differ, {} only here",
+                key, alignment.differing, alignment.only_compared
+            ),
+            None => "by position".to_string(),
+        };
+        let table = Table::new(
+            rows,
+            columns
+                .iter()
+                .map(|&(_, w)| Constraint::Length(w as u16))
+                .collect::<Vec<_>>(),
+        )
+        .header(
+            Row::new(columns.iter().map(|&(col, _)| right.columns[col].as_str()))
+                .style(header_style())
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .title(format!(" {} · {} ", right.question_name, summary))
+                .borders(Borders::ALL)
+                .border_style(text_dim()),
+        )
+        .row_highlight_style(result_row_highlight_style());
+
+        // Selection and scroll position follow the live result
+        let mut state = TableState::default()
+            .with_selected(self.result_table_state.selected())
+            .with_offset(self.result_table_state.offset());
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
@@ -1,6 +1,5 @@
 //! Drill-down view rendering for nested data exploration.
 //!
-//! - Collection → Questions
 //! - Database → Schemas → Tables
 
 use ratatui::{
@@ -20,103 +19,6 @@ use crate::layout::questions_table::{ID_WIDTH, NAME_MIN_WIDTH};
 use crate::service::LoadState;
 
 impl ContentPanel {
-    /// Render collection questions view with table.
-    /// Shows questions filtered by a specific collection.
-    pub(in crate::components::content) fn render_collection_questions(
-        &mut self,
-        area: Rect,
-        frame: &mut Frame,
-        focused: bool,
-    ) {
-        // Get collection name from ContentView variant
-        let collection_name = match &self.view {
-            ContentView::CollectionQuestions { name, .. } => name.as_str(),
-            _ => "Unknown",
-        };
-
-        let title = format!(" {} ", collection_name);
-        let idle_msg = format!("Loading questions from '{}'...", collection_name);
-        let loading_msg = format!("Loading questions from '{}'...", collection_name);
-        let config = LoadStateConfig::new(&title, focused)
-            .with_idle_message(&idle_msg)
-            .with_loading_message(&loading_msg);
-
-        // Handle non-loaded states with helper
-        if render_non_loaded_state(frame, area, &self.questions, &config) {
-            return;
-        }
-
-        // Handle Loaded state
-        let questions = match &self.questions {
-            LoadState::Loaded(q) => q,
-            _ => return,
-        };
-
-        if questions.is_empty() {
-            let empty_title = format!(" {} (0) ", collection_name);
-            let empty_msg = format!("No questions found in '{}'", collection_name);
-            render_empty_with_hint(
-                frame,
-                area,
-                &LoadStateConfig::new(&empty_title, focused),
-                &empty_msg,
-                "Press Esc to go back",
-            );
-            return;
-        }
-
-        // Client-side pagination: show only current page slice
-        let offset = self.questions_offset as usize;
-        let page_size = self.questions_page_size as usize;
-        let end = (offset + page_size).min(questions.len());
-        let page_questions = &questions[offset..end];
-
-        let rows: Vec<Row> = page_questions
-            .iter()
-            .map(|q| {
-                Row::new(vec![
-                    Cell::from(q.id.to_string()),
-                    Cell::from(q.name.as_str()),
-                    Cell::from(q.description.as_deref().unwrap_or("—")),
-                ])
-            })
-            .collect();
-
-        let pagination_hint = self
-            .questions_pagination_info()
-            .map(|(page, total_pages, _)| format!(" [{}/{}]", page, total_pages))
-            .unwrap_or_default();
-
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(ID_WIDTH),
-                Constraint::Percentage(35), // Name
-                Constraint::Percentage(55), // Description (more space)
-            ],
-        )
-        .header(
-            Row::new(vec!["ID", "Name", "Description"])
-                .style(header_style())
-                .bottom_margin(1),
-        )
-        .block(
-            Block::default()
-                .title(format!(
-                    " {} ({}){} - Esc: back, n/p: page ",
-                    collection_name,
-                    questions.len(),
-                    pagination_hint,
-                ))
-                .borders(Borders::ALL)
-                .border_style(border_style(focused)),
-        )
-        .row_highlight_style(row_highlight_style())
-        .highlight_symbol(HIGHLIGHT_SYMBOL);
-
-        frame.render_stateful_widget(table, area, &mut self.table_state);
-    }
-
     /// Render database schemas view with table.
     /// Shows schemas in a specific database.
     pub(in crate::components::content) fn render_database_schemas(
@@ -200,6 +102,7 @@ impl ContentPanel {
         .highlight_symbol(HIGHLIGHT_SYMBOL);
 
         frame.render_stateful_widget(table, area, &mut self.schemas_table_state);
+        self.record_table_area(area);
     }
 
     /// Render schema tables view with table.
@@ -290,5 +193,6 @@ impl ContentPanel {
         .highlight_symbol(HIGHLIGHT_SYMBOL);
 
         frame.render_stateful_widget(table, area, &mut self.tables_table_state);
+        self.record_table_area(area);
     }
 }
@@ -1,18 +1,21 @@
-//! List view rendering for Questions, Collections, and Databases.
+//! List view rendering for Questions, the Collections tree, Databases, and
+//! the execution History.
 
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
 };
 
-use crate::components::content::{ContentPanel, InputMode};
+use crate::components::content::{ContentPanel, InputMode, TreeNodeKind};
 use crate::components::state_renderer::{LoadStateConfig, render_empty, render_non_loaded_state};
 use crate::components::styles::{
     HIGHLIGHT_SYMBOL, border_style, header_style, row_highlight_style,
 };
+use crate::components::styles::{text_accent, text_dim, text_error, text_key};
+use crate::components::theme::theme;
 use crate::layout::questions_table::{COLLECTION_WIDTH, ID_WIDTH, NAME_MIN_WIDTH};
 use crate::service::LoadState;
 
@@ -24,11 +27,7 @@ impl ContentPanel {
         frame: &mut Frame,
         focused: bool,
     ) {
-        let border_color = if focused {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let border_color = if focused { text_accent() } else { text_dim() };
 
         // Calculate layout: search bar (if visible) + table
         let show_search_bar = self.input_mode == InputMode::Search || self.active_search.is_some();
@@ -53,18 +52,18 @@ impl ContentPanel {
             };
 
             let search_style = if self.input_mode == InputMode::Search {
-                Style::default().fg(Color::Yellow)
+                text_key()
             } else {
-                Style::default().fg(Color::DarkGray)
+                text_dim()
             };
 
             let search_bar = Paragraph::new(search_text).style(search_style).block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(if self.input_mode == InputMode::Search {
-                        Style::default().fg(Color::Yellow)
+                        text_key()
                     } else {
-                        Style::default().fg(Color::DarkGray)
+                        text_dim()
                     })
                     .title(" Search (/ to start, Enter to search, Esc to cancel) "),
             );
@@ -109,7 +108,7 @@ impl ContentPanel {
                     Line::from(""),
                     Line::from(Span::styled(
                         "  Press 'r' to load questions, '/' to search",
-                        Style::default().fg(Color::DarkGray),
+                        text_dim(),
                     )),
                 ])
                 .block(
@@ -123,10 +122,7 @@ impl ContentPanel {
             LoadState::Loading => {
                 let paragraph = Paragraph::new(vec![
                     Line::from(""),
-                    Line::from(Span::styled(
-                        "  ⏳ Loading questions...",
-                        Style::default().fg(Color::Yellow),
-                    )),
+                    Line::from(Span::styled("  ⏳ Loading questions...", text_key())),
                 ])
                 .block(
                     Block::default()
@@ -139,15 +135,9 @@ impl ContentPanel {
             LoadState::Error(msg) => {
                 let paragraph = Paragraph::new(vec![
                     Line::from(""),
-                    Line::from(Span::styled(
-                        format!("  ❌ Error: {}", msg),
-                        Style::default().fg(Color::Red),
-                    )),
+                    Line::from(Span::styled(format!("  ❌ Error: {}", msg), text_error())),
                     Line::from(""),
-                    Line::from(Span::styled(
-                        "  Press 'r' to retry",
-                        Style::default().fg(Color::DarkGray),
-                    )),
+                    Line::from(Span::styled("  Press 'r' to retry", text_dim())),
                 ])
                 .block(
                     Block::default()
@@ -166,14 +156,11 @@ impl ContentPanel {
                     };
                     let paragraph = Paragraph::new(vec![
                         Line::from(""),
-                        Line::from(Span::styled(
-                            empty_msg,
-                            Style::default().fg(Color::DarkGray),
-                        )),
+                        Line::from(Span::styled(empty_msg, text_dim())),
                         Line::from(""),
                         Line::from(Span::styled(
                             "  Press '/' to search or Esc to clear search",
-                            Style::default().fg(Color::DarkGray),
+                            text_dim(),
                         )),
                     ])
                     .block(
@@ -219,7 +206,7 @@ impl ContentPanel {
                         Row::new(vec!["ID", "Name", "Collection"])
                             .style(
                                 Style::default()
-                                    .fg(Color::Yellow)
+                                    .fg(theme().key)
                                     .add_modifier(Modifier::BOLD),
                             )
                             .bottom_margin(1),
@@ -231,20 +218,20 @@ impl ContentPanel {
                             .border_style(border_color),
                     )
                     .row_highlight_style(
-                        Style::default()
-                            .fg(Color::Black)
-                            .bg(Color::Cyan)
+                        theme()
+                            .highlight(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     )
                     .highlight_symbol("► ");
 
                     frame.render_stateful_widget(table, table_area, &mut self.table_state);
+                    self.record_table_area(table_area);
                 }
             }
         }
     }
 
-    /// Render collections view with table.
+    /// Render collections view as an expandable tree.
     pub(in crate::components::content) fn render_collections(
         &mut self,
         area: Rect,
@@ -260,13 +247,7 @@ impl ContentPanel {
             return;
         }
 
-        // Handle Loaded state
-        let collections = match &self.collections {
-            LoadState::Loaded(c) => c,
-            _ => return,
-        };
-
-        if collections.is_empty() {
+        if self.collection_tree.is_empty() {
             render_empty(
                 frame,
                 area,
@@ -276,20 +257,45 @@ impl ContentPanel {
             return;
         }
 
-        // Create table rows (minimize clones)
-        let rows: Vec<Row> = collections
-            .iter()
-            .map(|c| {
-                let id_str =
-                    c.id.map(|id| id.to_string())
-                        .unwrap_or_else(|| "—".to_string());
-                let desc = c.description.as_deref().unwrap_or("—");
-                let location = c.location.as_deref().unwrap_or("/");
+        // Create one row per visible tree node
+        let rows: Vec<Row> = self
+            .collection_tree
+            .visible()
+            .into_iter()
+            .filter_map(|idx| self.collection_tree.node(idx))
+            .map(|node| {
+                let marker = if !node.kind.is_container() {
+                    "  "
+                } else if node.children.is_loading() {
+                    "⋯ "
+                } else if node.expanded {
+                    "▾ "
+                } else {
+                    "▸ "
+                };
+                let indent = "  ".repeat(node.depth);
+                let id_str = node
+                    .id
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "—".to_string());
+                let desc = match node.children.error() {
+                    Some(err) => format!("⚠ {}", err),
+                    None => node.description.as_deref().unwrap_or("—").to_string(),
+                };
+                let name_style = match node.kind {
+                    TreeNodeKind::Group | TreeNodeKind::Collection => {
+                        Style::default().add_modifier(Modifier::BOLD)
+                    }
+                    _ => Style::default(),
+                };
 
                 Row::new(vec![
+                    Cell::from(Span::styled(
+                        format!("{}{}{}", indent, marker, node.name),
+                        name_style,
+                    )),
+                    Cell::from(node.kind.label()),
                     Cell::from(id_str),
-                    Cell::from(c.name.as_str()),
-                    Cell::from(location),
                     Cell::from(desc),
                 ])
             })
@@ -298,20 +304,20 @@ impl ContentPanel {
         let table = Table::new(
             rows,
             [
-                Constraint::Length(ID_WIDTH),
                 Constraint::Min(NAME_MIN_WIDTH),
-                Constraint::Length(15), // Location
-                Constraint::Min(20),    // Description
+                Constraint::Length(12), // Type
+                Constraint::Length(ID_WIDTH),
+                Constraint::Min(20), // Description
             ],
         )
         .header(
-            Row::new(vec!["ID", "Name", "Location", "Description"])
+            Row::new(vec!["Name", "Type", "ID", "Description"])
                 .style(header_style())
                 .bottom_margin(1),
         )
         .block(
             Block::default()
-                .title(format!(" Collections ({}) ", collections.len()))
+                .title(" Collections - h/l: collapse/expand, Enter: open ")
                 .borders(Borders::ALL)
                 .border_style(border_style(focused)),
         )
@@ -319,6 +325,7 @@ impl ContentPanel {
         .highlight_symbol(HIGHLIGHT_SYMBOL);
 
         frame.render_stateful_widget(table, area, &mut self.collections_table_state);
+        self.record_table_area(area);
     }
 
     /// Render databases view with table.
@@ -394,5 +401,67 @@ impl ContentPanel {
         .highlight_symbol(HIGHLIGHT_SYMBOL);
 
         frame.render_stateful_widget(table, area, &mut self.databases_table_state);
+        self.record_table_area(area);
+    }
+
+    /// Render the history of executed questions (newest first).
+    pub(in crate::components::content) fn render_history(
+        &mut self,
+        area: Rect,
+        frame: &mut Frame,
+        focused: bool,
+    ) {
+        if self.history.is_empty() {
+            render_empty(
+                frame,
+                area,
+                &LoadStateConfig::new(" History (0) ", focused),
+                "No questions executed yet",
+            );
+            return;
+        }
+
+        let rows: Vec<Row> = self
+            .history
+            .iter()
+            .map(|entry| {
+                // "2026-10-18T09:30:00Z" -> "2026-10-18 09:30:00"
+                let when = entry.executed_at.replace('T', " ").replace('Z', "");
+                Row::new(vec![
+                    Cell::from(when),
+                    Cell::from(entry.question_id.to_string()),
+                    Cell::from(entry.name.as_str()),
+                    Cell::from(entry.rows.to_string()),
+                    Cell::from(format!("{} ms", entry.duration_ms)),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(19), // When
+                Constraint::Length(ID_WIDTH),
+                Constraint::Min(NAME_MIN_WIDTH),
+                Constraint::Length(8),  // Rows
+                Constraint::Length(10), // Time
+            ],
+        )
+        .header(
+            Row::new(vec!["When", "ID", "Question", "Rows", "Time"])
+                .style(header_style())
+                .bottom_margin(1),
+        )
+        .block(
+            Block::default()
+                .title(format!(" History ({}) ", self.history.len()))
+                .borders(Borders::ALL)
+                .border_style(border_style(focused)),
+        )
+        .row_highlight_style(row_highlight_style())
+        .highlight_symbol(HIGHLIGHT_SYMBOL);
+
+        frame.render_stateful_widget(table, area, &mut self.history_table_state);
+        self.record_table_area(area);
     }
 }
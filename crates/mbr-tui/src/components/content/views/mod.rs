@@ -5,10 +5,12 @@
 //!
 //! ## Module Structure
 //! - `welcome.rs`: Welcome screen and placeholder rendering
-//! - `lists.rs`: Questions, Collections, Databases list views
-//! - `drill_down.rs`: Collection questions, schemas, tables drill-down views
+//! - `lists.rs`: Questions list, Collections tree, Databases list views
+//! - `drill_down.rs`: Schemas and tables drill-down views
 //! - `results.rs`: Query result and table preview rendering
+//! - `compare.rs`: Compared result pane of the split view
 
+mod compare;
 mod drill_down;
 mod lists;
 mod results;
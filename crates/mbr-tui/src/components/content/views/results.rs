@@ -3,15 +3,19 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
+    style::{Modifier, Style},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
 };
 
+use crate::components::content::aggregate::format_number;
+use crate::components::content::types::FilterStep;
 use crate::components::content::{ContentPanel, ContentView, SortOrder};
 use crate::components::styles::{
     HIGHLIGHT_SYMBOL, border_style, header_style, multi_selected_style, result_row_highlight_style,
 };
+use crate::components::styles::{text_dim, text_key};
+use crate::components::theme::theme;
 
 impl ContentPanel {
     /// Render table preview view with query result table.
@@ -45,7 +49,7 @@ impl ContentPanel {
                 Line::from(""),
                 Line::from(Span::styled(
                     format!("  Loading preview for '{}'...", table_name),
-                    Style::default().fg(Color::Yellow),
+                    text_key(),
                 )),
             ])
             .block(
@@ -61,19 +65,13 @@ impl ContentPanel {
                 Line::from(Span::styled(
                     format!("  Table: {}", table_name),
                     Style::default()
-                        .fg(Color::White)
+                        .fg(theme().text)
                         .add_modifier(Modifier::BOLD),
                 )),
                 Line::from(""),
-                Line::from(Span::styled(
-                    "  No data in table",
-                    Style::default().fg(Color::DarkGray),
-                )),
+                Line::from(Span::styled("  No data in table", text_dim())),
                 Line::from(""),
-                Line::from(Span::styled(
-                    "  Press Esc to go back",
-                    Style::default().fg(Color::Yellow),
-                )),
+                Line::from(Span::styled("  Press Esc to go back", text_key())),
             ])
             .block(
                 Block::default()
@@ -99,14 +97,18 @@ impl ContentPanel {
             None => (false, false, String::new()),
             Some(result) => (true, result.rows.is_empty(), result.question_name.clone()),
         };
+        // Position among open buffers when there are several
+        let question_name = match self.active_buffer {
+            Some(index) if self.buffers.len() > 1 && self.view == ContentView::QueryResult => {
+                format!("{} [{}/{}]", question_name, index + 1, self.buffers.len())
+            }
+            _ => question_name,
+        };
 
         if !has_result {
             let paragraph = Paragraph::new(vec![
                 Line::from(""),
-                Line::from(Span::styled(
-                    "  No query result available",
-                    Style::default().fg(Color::DarkGray),
-                )),
+                Line::from(Span::styled("  No query result available", text_dim())),
             ])
             .block(
                 Block::default()
@@ -121,19 +123,13 @@ impl ContentPanel {
                 Line::from(Span::styled(
                     format!("  Query: {}", question_name),
                     Style::default()
-                        .fg(Color::White)
+                        .fg(theme().text)
                         .add_modifier(Modifier::BOLD),
                 )),
                 Line::from(""),
-                Line::from(Span::styled(
-                    "  No data returned",
-                    Style::default().fg(Color::DarkGray),
-                )),
+                Line::from(Span::styled("  No data returned", text_dim())),
                 Line::from(""),
-                Line::from(Span::styled(
-                    "  Press Esc to go back",
-                    Style::default().fg(Color::Yellow),
-                )),
+                Line::from(Span::styled("  Press Esc to go back", text_key())),
             ])
             .block(
                 Block::default()
@@ -182,18 +178,37 @@ impl ContentPanel {
         let page_start = self.result_page * self.rows_per_page;
         let page_end = (page_start + self.rows_per_page).min(total_rows);
 
-        // Calculate visible columns based on scroll_x
-        let total_cols = result.columns.len();
-        let scroll_x = self.scroll_x.min(total_cols.saturating_sub(1));
+        // Visible columns: pinned columns stay in place, the rest scroll with scroll_x
+        let (pinned_cols, scrollable_cols) = self.column_layout.visible_columns();
+        let scroll_x = self.scroll_x.min(scrollable_cols.len().saturating_sub(1));
 
-        // Calculate how many columns can fit (estimate based on min width)
-        let available_width = area.width.saturating_sub(4) as usize;
+        // Calculate how many scrollable columns fit next to the pinned ones
+        // (estimate based on min width, or the width override when set)
         let min_col_width = 15usize;
-        let visible_cols = (available_width / min_col_width).max(1).min(total_cols);
-        let end_col = (scroll_x + visible_cols).min(total_cols);
+        let estimate = |col: usize| {
+            self.column_layout
+                .width(col)
+                .map(|w| w as usize + 3)
+                .unwrap_or(min_col_width)
+        };
+        let available_width = area.width.saturating_sub(4) as usize;
+        let mut remaining =
+            available_width.saturating_sub(pinned_cols.iter().map(|&c| estimate(c)).sum());
+        let mut end_col = scroll_x;
+        for &col in &scrollable_cols[scroll_x.min(scrollable_cols.len())..] {
+            let width = estimate(col);
+            if end_col > scroll_x && width > remaining {
+                break;
+            }
+            remaining = remaining.saturating_sub(width);
+            end_col += 1;
+        }
 
-        // Slice columns based on scroll position (use reference, avoid clone)
-        let visible_columns = &result.columns[scroll_x..end_col];
+        let visible_columns: Vec<usize> = pinned_cols
+            .iter()
+            .chain(&scrollable_cols[scroll_x.min(end_col)..end_col])
+            .copied()
+            .collect();
         let visible_col_count = visible_columns.len();
 
         // Always include selection gutter column to prevent layout shift
@@ -201,22 +216,20 @@ impl ContentPanel {
         let mut constraints: Vec<Constraint> = Vec::new();
         constraints.push(Constraint::Length(2)); // Selection indicator column (always present)
 
-        // Use pre-computed column widths if available, otherwise fall back to heuristic
-        if let Some(ref cached_widths) = self.cached_column_widths {
-            constraints.extend((scroll_x..end_col).map(|col_idx| {
+        // Width overrides first, then pre-computed column widths, then the heuristic
+        constraints.extend(visible_columns.iter().map(|&col_idx| {
+            if let Some(width) = self.column_layout.width(col_idx) {
+                Constraint::Length(width + 2)
+            } else if let Some(ref cached_widths) = self.cached_column_widths {
                 let width = cached_widths.get(col_idx).copied().unwrap_or(15);
                 // Add 2 for padding
                 Constraint::Min(width.max(8) + 2)
-            }));
-        } else if visible_col_count <= 3 {
-            constraints.extend(
-                visible_columns
-                    .iter()
-                    .map(|_| Constraint::Ratio(1, visible_col_count as u32)),
-            );
-        } else {
-            constraints.extend(visible_columns.iter().map(|_| Constraint::Min(15)));
-        }
+            } else if visible_col_count <= 3 {
+                Constraint::Ratio(1, visible_col_count as u32)
+            } else {
+                Constraint::Min(15)
+            }
+        }));
 
         // Pre-collect row metadata to avoid borrow conflicts with render_stateful_widget.
         // We extract (original_idx, is_selected, sliced cells as owned) to release
@@ -232,11 +245,10 @@ impl ContentPanel {
                 let is_selected = original_idx
                     .map(|idx| self.is_row_selected(idx))
                     .unwrap_or(false);
-                let end = end_col.min(row.len());
-                // Use as_str() + to_owned() for the visible slice only (not all columns)
-                let cells: Vec<String> = row[scroll_x..end]
+                // Clone only the visible cells (not all columns)
+                let cells: Vec<String> = visible_columns
                     .iter()
-                    .map(|cell| cell.to_owned())
+                    .map(|&col| row.get(col).cloned().unwrap_or_default())
                     .collect();
                 Some(RowInfo { is_selected, cells })
             })
@@ -268,42 +280,41 @@ impl ContentPanel {
         header_cells.push(Cell::from("  "));
 
         // Add data column headers (avoid clone when no sort indicator needed)
-        header_cells.extend(
-            visible_columns
-                .iter()
-                .enumerate()
-                .map(|(visible_idx, col)| {
-                    let actual_col_idx = scroll_x + visible_idx;
-                    let is_sorted = self.sort_column_index == Some(actual_col_idx);
-
-                    if is_sorted {
-                        let indicator = match self.sort_order {
-                            SortOrder::Ascending => " ↑",
-                            SortOrder::Descending => " ↓",
-                            SortOrder::None => "",
-                        };
-                        Cell::from(format!("{}{}", col, indicator))
-                    } else {
-                        Cell::from(col.as_str())
-                    }
-                }),
-        );
-
-        // Build column indicator
-        let col_indicator = if total_cols > visible_cols {
+        header_cells.extend(visible_columns.iter().map(|&actual_col_idx| {
+            let col = &result.columns[actual_col_idx];
+            match self.sort_indicator(actual_col_idx) {
+                Some(indicator) => Cell::from(format!("{}{}", col, indicator)),
+                None => Cell::from(col.as_str()),
+            }
+        }));
+
+        // Build column indicator (scrollable columns only; pinned and hidden counted apart)
+        let scrollable_count = scrollable_cols.len();
+        let mut col_indicator = if end_col - scroll_x < scrollable_count {
             let left_arrow = if scroll_x > 0 { "← " } else { "  " };
-            let right_arrow = if end_col < total_cols { " →" } else { "  " };
+            let right_arrow = if end_col < scrollable_count {
+                " →"
+            } else {
+                "  "
+            };
             format!(
                 " {}Col {}-{}/{}{}",
                 left_arrow,
                 scroll_x + 1,
                 end_col,
-                total_cols,
+                scrollable_count,
                 right_arrow
             )
         } else {
             String::new()
         };
+        if !pinned_cols.is_empty() {
+            col_indicator.push_str(&format!(" [{} pinned]", pinned_cols.len()));
+        }
+        let hidden_count = result.columns.len() - pinned_cols.len() - scrollable_count;
+        if hidden_count > 0 {
+            col_indicator.push_str(&format!(" [{} hidden]", hidden_count));
+        }
 
         // Build page indicator
         let page_indicator = if total_pages > 1 {
@@ -319,19 +330,33 @@ impl ContentPanel {
             format!(" {} rows", total_rows)
         };
 
-        // Build sort indicator for title
-        let sort_indicator = if let Some((col_name, order)) = self.get_sort_info() {
-            let arrow = match order {
-                SortOrder::Ascending => "↑",
-                SortOrder::Descending => "↓",
-                SortOrder::None => "",
-            };
-            format!(" [Sort: {} {}]", col_name, arrow)
-        } else {
+        // Build sort indicator for title (keys in priority order)
+        let sort_info = self.get_sort_info();
+        let sort_indicator = if sort_info.is_empty() {
             String::new()
+        } else {
+            let keys: Vec<String> = sort_info
+                .iter()
+                .map(|(col_name, order)| {
+                    let arrow = match order {
+                        SortOrder::Ascending => "↑",
+                        SortOrder::Descending => "↓",
+                        SortOrder::None => "",
+                    };
+                    format!("{} {}", col_name, arrow)
+                })
+                .collect();
+            format!(" [Sort: {}]", keys.join(", "))
         };
 
-        let table = Table::new(rows, constraints)
+        // Summary footer over the visible (searched and filtered) rows
+        let footer = self
+            .summary_visible
+            .then(|| self.summary_footer(&visible_columns));
+
+        // Kept for click-to-sort hit-testing after the table takes ownership
+        let column_constraints = constraints.clone();
+        let mut table = Table::new(rows, constraints)
             .header(
                 Row::new(header_cells)
                     .style(header_style())
@@ -348,18 +373,127 @@ impl ContentPanel {
             )
             .row_highlight_style(result_row_highlight_style())
             .highlight_symbol(HIGHLIGHT_SYMBOL);
+        let footer_height = footer.as_ref().map_or(0, |(_, height)| *height);
+        if let Some((footer_row, _)) = footer {
+            table = table.footer(footer_row);
+        }
 
-        frame.render_stateful_widget(table, area, &mut self.result_table_state);
+        // Filter chip bar takes the first line when conditions are active
+        let table_area = if self.filters.is_empty() || area.height < 6 {
+            area
+        } else {
+            let chip_area = Rect::new(area.x + 1, area.y, area.width.saturating_sub(2), 1);
+            frame.render_widget(Paragraph::new(self.filter_chip_line()), chip_area);
+            Rect {
+                y: area.y + 1,
+                height: area.height - 1,
+                ..area
+            }
+        };
+
+        frame.render_stateful_widget(table, table_area, &mut self.result_table_state);
+        self.record_table_area(table_area);
+        self.reserve_table_footer(footer_height);
+        self.record_result_columns(table_area, &column_constraints, &visible_columns);
 
         // Render overlays
         if self.sort_mode_active {
             self.render_sort_modal(frame, area);
         }
+        if self.column_manager_active {
+            self.render_column_manager(frame, area);
+        }
         if self.filter_mode_active {
             self.render_filter_modal(frame, area);
         }
+        if self.group_modal_active {
+            self.render_group_modal(frame, area);
+        }
+        if self.column_profile.is_some() {
+            self.render_column_profile(frame, area);
+        }
         if self.result_search_active {
             self.render_result_search_bar(frame, area);
         }
     }
+
+    /// Build the filter chip bar: one chip per condition joined by AND/OR.
+    /// The chip selected in the filter modal's condition list is highlighted.
+    fn filter_chip_line(&self) -> Line<'static> {
+        let Some(ref result) = self.query_result else {
+            return Line::default();
+        };
+        let selected = (self.filter_mode_active
+            && self.filter_modal_step == FilterStep::Conditions)
+            .then_some(self.filter_condition_selection);
+
+        let mut spans = vec![Span::styled("Filter: ", text_dim())];
+        for (i, condition) in self.filters.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(
+                    format!(" {} ", condition.join.label()),
+                    text_key(),
+                ));
+            }
+            let column = result
+                .columns
+                .get(condition.column)
+                .map(|s| s.as_str())
+                .unwrap_or("?");
+            let style = if selected == Some(i) {
+                theme()
+                    .highlight(theme().secondary)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme().secondary)
+            };
+            spans.push(Span::styled(
+                format!("[{}]", condition.describe(column)),
+                style,
+            ));
+        }
+        if let Some(ref indices) = self.filter_indices {
+            spans.push(Span::styled(
+                format!("  {}/{} rows", indices.len(), result.rows.len()),
+                text_dim(),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    /// Build the summary footer row for the visible columns.
+    /// Numeric columns show count, sum, avg, min and max; others the count.
+    /// Returns the row and its height including the top margin.
+    fn summary_footer(&self, visible_columns: &[usize]) -> (Row<'static>, u16) {
+        let summary = self.visible_summary();
+        let has_numeric = visible_columns
+            .iter()
+            .any(|&c| summary.get(c).is_some_and(|s| s.numeric.is_some()));
+        let height: u16 = if has_numeric { 5 } else { 1 };
+
+        let stat = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{} ", label), text_dim()),
+                Span::styled(value, Style::default().add_modifier(Modifier::BOLD)),
+            ])
+        };
+
+        let mut cells = vec![Cell::from(Span::styled("Σ", text_key()))];
+        cells.extend(visible_columns.iter().map(|&col| {
+            let Some(column) = summary.get(col) else {
+                return Cell::from("");
+            };
+            let mut lines = vec![stat("n", column.count.to_string())];
+            if let Some(stats) = column.numeric {
+                lines.push(stat("Σ", format_number(stats.sum)));
+                lines.push(stat("avg", format_number(stats.avg)));
+                lines.push(stat("min", format_number(stats.min)));
+                lines.push(stat("max", format_number(stats.max)));
+            }
+            Cell::from(Text::from(lines))
+        }));
+
+        let row = Row::new(cells).height(height).top_margin(1);
+        (row, height + 1)
+    }
 }
@@ -2,12 +2,14 @@
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
 use crate::components::content::ContentPanel;
+use crate::components::styles::{text_accent, text_dim, text_key};
+use crate::components::theme::theme;
 
 impl ContentPanel {
     /// Render welcome view content.
@@ -16,11 +18,7 @@ impl ContentPanel {
         _area: Rect,
         focused: bool,
     ) -> Paragraph<'static> {
-        let border_style = if focused {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let border_style = if focused { text_accent() } else { text_dim() };
 
         let banner = r#"
                  _                  _           _
@@ -36,7 +34,7 @@ impl ContentPanel {
         for banner_line in banner.lines() {
             lines.push(Line::from(Span::styled(
                 banner_line.to_string(),
-                Style::default().fg(Color::Cyan),
+                text_accent(),
             )));
         }
 
@@ -44,32 +42,29 @@ impl ContentPanel {
         lines.push(Line::from(Span::styled(
             "  Welcome to mbr-tui!",
             Style::default()
-                .fg(Color::White)
+                .fg(theme().text)
                 .add_modifier(Modifier::BOLD),
         )));
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "  Select an item from the navigation panel to get started.",
-            Style::default().fg(Color::DarkGray),
+            text_dim(),
         )));
         lines.push(Line::from(""));
         lines.push(Line::from("  Quick Keys:"));
         lines.push(Line::from(Span::styled(
             "    Tab       - Switch panels",
-            Style::default().fg(Color::Yellow),
+            text_key(),
         )));
         lines.push(Line::from(Span::styled(
             "    ↑/↓ j/k   - Navigate items",
-            Style::default().fg(Color::Yellow),
+            text_key(),
         )));
         lines.push(Line::from(Span::styled(
             "    Enter     - Select item",
-            Style::default().fg(Color::Yellow),
-        )));
-        lines.push(Line::from(Span::styled(
-            "    q         - Quit",
-            Style::default().fg(Color::Yellow),
+            text_key(),
         )));
+        lines.push(Line::from(Span::styled("    q         - Quit", text_key())));
 
         Paragraph::new(lines)
             .block(
@@ -5,12 +5,14 @@
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Flex, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
 use super::clipboard::CopyFormat;
+use crate::components::styles::{text_accent, text_dim, text_key, text_normal};
+use crate::components::theme::theme;
 
 /// Copy menu state for format selection.
 pub struct CopyMenu {
@@ -113,7 +115,7 @@ impl CopyMenu {
         lines.push(Line::from(Span::styled(
             "  Format:",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme().accent)
                 .add_modifier(Modifier::BOLD),
         )));
 
@@ -123,17 +125,16 @@ impl CopyMenu {
             let key_hint = format!("[{}] ", format.key());
 
             let style = if is_selected {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
+                theme()
+                    .highlight(theme().accent)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::White)
+                text_normal()
             };
 
             lines.push(Line::from(vec![
                 Span::styled(prefix, style),
-                Span::styled(key_hint, Style::default().fg(Color::Yellow)),
+                Span::styled(key_hint, text_key()),
                 Span::styled(format.label(), style),
             ]));
         }
@@ -143,26 +144,23 @@ impl CopyMenu {
         // Header toggle (note: doesn't affect JSON)
         let header_indicator = if self.include_header { "[x]" } else { "[ ]" };
         lines.push(Line::from(vec![
-            Span::styled("  [", Style::default().fg(Color::DarkGray)),
-            Span::styled("h", Style::default().fg(Color::Yellow)),
-            Span::styled("] ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{} Include header", header_indicator),
-                Style::default().fg(Color::DarkGray),
-            ),
+            Span::styled("  [", text_dim()),
+            Span::styled("h", text_key()),
+            Span::styled("] ", text_dim()),
+            Span::styled(format!("{} Include header", header_indicator), text_dim()),
         ]));
 
         lines.push(Line::from(""));
 
         // Help line
         lines.push(Line::from(vec![
-            Span::styled("  [", Style::default().fg(Color::DarkGray)),
-            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-            Span::styled(" Move] [", Style::default().fg(Color::DarkGray)),
-            Span::styled("Enter", Style::default().fg(Color::Yellow)),
-            Span::styled(" Copy] [", Style::default().fg(Color::DarkGray)),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::styled(" Cancel]", Style::default().fg(Color::DarkGray)),
+            Span::styled("  [", text_dim()),
+            Span::styled("↑↓", text_key()),
+            Span::styled(" Move] [", text_dim()),
+            Span::styled("Enter", text_key()),
+            Span::styled(" Copy] [", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(" Cancel]", text_dim()),
         ]));
 
         // Dynamic title based on record count
@@ -178,7 +176,7 @@ impl CopyMenu {
                     .title(title)
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(text_accent()),
             )
             .alignment(Alignment::Left);
 
@@ -1,108 +1,108 @@
 //! Help overlay component.
 //!
-//! Displays a modal overlay showing all available keybindings.
+//! Displays a modal overlay showing all available keybindings,
+//! generated from the active `Keymap`.
 
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Flex, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use crate::components::styles::{text_accent, text_dim};
+use crate::components::theme::theme;
+use crate::keymap::{KeyAction, KeySection, Keymap};
+
 /// Help overlay showing keybindings.
 pub struct HelpOverlay;
 
 impl HelpOverlay {
-    /// Keybinding groups for display.
-    const GLOBAL_KEYS: &'static [(&'static str, &'static str)] = &[
-        ("q", "Quit application"),
-        ("Esc", "Quit / Back from result"),
-        ("1 / 2 / 3", "Switch to Questions/Collections/DBs"),
-        ("Tab", "Next tab"),
-        ("Shift+Tab", "Previous tab"),
-        ("r", "Refresh data"),
-        ("?", "Toggle help"),
-    ];
-
-    const NAVIGATION_KEYS: &'static [(&'static str, &'static str)] = &[
-        ("↑ / k", "Move up"),
-        ("↓ / j", "Move down"),
-        ("← / h", "Scroll left (columns)"),
-        ("→ / l", "Scroll right (columns)"),
-        ("PgUp / PgDn", "Scroll page up/down"),
-        ("n", "Next page"),
-        ("p", "Previous page"),
-        ("Home / g", "First page / First item"),
-        ("End / G", "Last page / Last item"),
-        ("Enter", "Execute query / Record detail"),
-        ("c", "Copy record(s) (result view)"),
-        ("Space", "Toggle row selection"),
+    /// Fixed keys that are not part of the configurable keymap.
+    const FIXED_KEYS: &'static [(&'static str, &'static str)] = &[
         ("Shift+↑/↓", "Range selection"),
         ("Shift+Home/End", "Range select to first/last"),
         ("Shift+PgUp/PgDn", "Range select by page"),
-        ("Ctrl+A", "Select all rows"),
-        ("Esc", "Clear selection / Back / Close"),
+        ("Enter / Esc", "Confirm / Cancel in dialogs"),
+        ("Click / Dbl-click", "Select / Open row"),
+        ("Click header", "Cycle sort"),
+        ("Shift+click header", "Add sort key"),
+        ("Wheel", "Scroll"),
     ];
 
-    /// Render the help overlay centered on screen.
-    pub fn render(frame: &mut Frame, area: Rect) {
+    /// Render the help overlay centered on screen, listing the active keymap.
+    ///
+    /// Global and navigation keys go in the left column; result keys, fixed
+    /// keys and the close hint in the right one.
+    pub fn render(frame: &mut Frame, area: Rect, keymap: &Keymap) {
         // Calculate centered popup area
-        let popup_area = Self::centered_rect(60, 70, area);
+        let popup_area = Self::centered_rect(80, 80, area);
 
         // Clear the background
         frame.render_widget(Clear, popup_area);
 
-        // Build help content
-        let mut lines: Vec<Line> = Vec::new();
+        let block = Block::default()
+            .title(format!(" Help (keys: {}) ", keymap.preset_name()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(text_accent());
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let mut left: Vec<Line> = vec![Line::from("")];
+        Self::push_section(&mut left, keymap, KeySection::Global);
+        left.push(Line::from(""));
+        Self::push_section(&mut left, keymap, KeySection::Navigation);
+
+        let mut right: Vec<Line> = vec![Line::from("")];
+        Self::push_section(&mut right, keymap, KeySection::Results);
+        right.push(Line::from(""));
+        Self::push_header(&mut right, "Selection & Dialogs");
+        for (key, action) in Self::FIXED_KEYS {
+            right.push(Self::format_keybinding(key, action));
+        }
 
-        // Title section
-        lines.push(Line::from(""));
+        right.push(Line::from(""));
+        let help_keys = keymap.describe(KeyAction::Help);
+        let close_hint = if help_keys.is_empty() {
+            "  Press Esc to close".to_string()
+        } else {
+            format!("  Press {} or Esc to close", help_keys)
+        };
+        right.push(Line::from(Span::styled(close_hint, text_dim())));
+
+        let [left_area, right_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(inner);
+        frame.render_widget(Paragraph::new(left).alignment(Alignment::Left), left_area);
+        frame.render_widget(Paragraph::new(right).alignment(Alignment::Left), right_area);
+    }
 
-        // Global keybindings section
-        lines.push(Line::from(Span::styled(
-            "  Global",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from("  ──────────────────────────────────"));
-        for (key, action) in Self::GLOBAL_KEYS {
-            lines.push(Self::format_keybinding(key, action));
+    /// Append a section header and the bound actions of that section.
+    /// Unbound actions are omitted.
+    fn push_section(lines: &mut Vec<Line<'static>>, keymap: &Keymap, section: KeySection) {
+        Self::push_header(lines, section.title());
+        for action in KeyAction::ALL
+            .into_iter()
+            .filter(|a| a.section() == section)
+        {
+            let keys = keymap.describe(action);
+            if !keys.is_empty() {
+                lines.push(Self::format_keybinding(&keys, action.description()));
+            }
         }
+    }
 
-        lines.push(Line::from(""));
-
-        // Navigation keybindings section
+    /// Append a section header with its underline.
+    fn push_header(lines: &mut Vec<Line<'static>>, title: &str) {
         lines.push(Line::from(Span::styled(
-            "  Navigation",
+            format!("  {}", title),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme().accent)
                 .add_modifier(Modifier::BOLD),
         )));
         lines.push(Line::from("  ──────────────────────────────────"));
-        for (key, action) in Self::NAVIGATION_KEYS {
-            lines.push(Self::format_keybinding(key, action));
-        }
-
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "  Press ? or Esc to close",
-            Style::default().fg(Color::DarkGray),
-        )));
-
-        // Create the paragraph widget
-        let help_text = Paragraph::new(lines)
-            .block(
-                Block::default()
-                    .title(" Help ")
-                    .title_alignment(Alignment::Center)
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
-            )
-            .alignment(Alignment::Left);
-
-        frame.render_widget(help_text, popup_area);
     }
 
     /// Format a single keybinding line.
@@ -110,9 +110,9 @@ impl HelpOverlay {
         Line::from(vec![
             Span::raw("    "),
             Span::styled(
-                format!("{:<14}", key),
+                format!("{:<18}", key),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme().key)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(action.to_string()),
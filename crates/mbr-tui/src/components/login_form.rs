@@ -0,0 +1,327 @@
+//! Login form overlay component.
+//!
+//! Asks for the Metabase URL and either a username and password or an API
+//! key. Shown when there is no session or the server rejects it; the App
+//! performs the login and retries the requests that failed.
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::components::styles::{
+    popup_style, text_accent, text_dim, text_error, text_key, text_normal,
+};
+use crate::components::theme::theme;
+use crate::service::Credentials;
+
+/// How to authenticate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginMethod {
+    Password,
+    ApiKey,
+}
+
+/// Rows of the form, in focus order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoginField {
+    Method,
+    Url,
+    Username,
+    Password,
+    ApiKey,
+}
+
+/// Login form state.
+pub struct LoginForm {
+    method: LoginMethod,
+    url: String,
+    username: String,
+    password: String,
+    api_key: String,
+    /// Focused row (index into `fields()`)
+    focus: usize,
+    /// Why the last attempt failed, or why the form was opened
+    error: Option<String>,
+    /// Whether a login is in progress
+    submitting: bool,
+}
+
+impl LoginForm {
+    /// Create a form prefilled with the URL and username, focused on the
+    /// first empty field.
+    pub fn new(method: LoginMethod, url: String, username: String) -> Self {
+        let mut form = Self {
+            method,
+            url,
+            username,
+            password: String::new(),
+            api_key: String::new(),
+            focus: 0,
+            error: None,
+            submitting: false,
+        };
+        form.focus = form
+            .fields()
+            .iter()
+            .position(|&f| f != LoginField::Method && form.value(f).is_empty())
+            .unwrap_or(1);
+        form
+    }
+
+    fn fields(&self) -> &'static [LoginField] {
+        match self.method {
+            LoginMethod::Password => &[
+                LoginField::Method,
+                LoginField::Url,
+                LoginField::Username,
+                LoginField::Password,
+            ],
+            LoginMethod::ApiKey => &[LoginField::Method, LoginField::Url, LoginField::ApiKey],
+        }
+    }
+
+    fn focused(&self) -> LoginField {
+        self.fields()[self.focus]
+    }
+
+    fn value(&self, field: LoginField) -> &str {
+        match field {
+            LoginField::Method => "",
+            LoginField::Url => &self.url,
+            LoginField::Username => &self.username,
+            LoginField::Password => &self.password,
+            LoginField::ApiKey => &self.api_key,
+        }
+    }
+
+    fn value_mut(&mut self) -> Option<&mut String> {
+        match self.focused() {
+            LoginField::Method => None,
+            LoginField::Url => Some(&mut self.url),
+            LoginField::Username => Some(&mut self.username),
+            LoginField::Password => Some(&mut self.password),
+            LoginField::ApiKey => Some(&mut self.api_key),
+        }
+    }
+
+    /// Move focus to the next row.
+    pub fn focus_next(&mut self) {
+        self.focus = (self.focus + 1) % self.fields().len();
+    }
+
+    /// Move focus to the previous row.
+    pub fn focus_previous(&mut self) {
+        let count = self.fields().len();
+        self.focus = (self.focus + count - 1) % count;
+    }
+
+    /// Switch between password and API key login.
+    pub fn toggle_method(&mut self) {
+        self.method = match self.method {
+            LoginMethod::Password => LoginMethod::ApiKey,
+            LoginMethod::ApiKey => LoginMethod::Password,
+        };
+        self.focus = self.focus.min(self.fields().len() - 1);
+    }
+
+    /// Whether the method row is focused (where Left/Right/Space toggle it).
+    pub fn is_method_focused(&self) -> bool {
+        self.focused() == LoginField::Method
+    }
+
+    /// Type a character into the focused field.
+    pub fn input_char(&mut self, c: char) {
+        if let Some(value) = self.value_mut() {
+            value.push(c);
+        }
+    }
+
+    /// Remove the last character of the focused field.
+    pub fn delete_char(&mut self) {
+        if let Some(value) = self.value_mut() {
+            value.pop();
+        }
+    }
+
+    /// Show an error below the fields.
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+        self.submitting = false;
+    }
+
+    /// Whether a login is in progress.
+    pub fn is_submitting(&self) -> bool {
+        self.submitting
+    }
+
+    /// Start a login: returns the URL and credentials, or an error if a
+    /// field is missing.
+    pub fn submit(&mut self) -> Result<(String, Credentials), String> {
+        let url = self.url.trim().to_string();
+        let credentials = match self.method {
+            LoginMethod::Password => Credentials::Password {
+                username: self.username.trim().to_string(),
+                password: self.password.clone(),
+            },
+            LoginMethod::ApiKey => Credentials::ApiKey(self.api_key.trim().to_string()),
+        };
+        let missing = match &credentials {
+            _ if url.is_empty() => Some("URL"),
+            Credentials::Password { username, .. } if username.is_empty() => Some("Username"),
+            Credentials::Password { password, .. } if password.is_empty() => Some("Password"),
+            Credentials::ApiKey(key) if key.is_empty() => Some("API key"),
+            _ => None,
+        };
+        if let Some(field) = missing {
+            let error = format!("{} is required", field);
+            self.set_error(error.clone());
+            return Err(error);
+        }
+        self.error = None;
+        self.submitting = true;
+        Ok((url, credentials))
+    }
+
+    /// Render the form as a centered overlay.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let height = (self.fields().len() as u16 + 7).min(area.height);
+        let vertical = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center);
+        let horizontal = Layout::horizontal([Constraint::Percentage(60)]).flex(Flex::Center);
+        let [popup_area] = vertical.areas(area);
+        let [popup_area] = horizontal.areas(popup_area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let mut lines = vec![Line::from("")];
+        for (i, &field) in self.fields().iter().enumerate() {
+            let is_focused = i == self.focus && !self.submitting;
+            let label_style = if is_focused {
+                theme()
+                    .highlight(theme().accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                text_key()
+            };
+            let (label, value) = match field {
+                LoginField::Method => {
+                    let mark = |method| {
+                        if self.method == method {
+                            "(•)"
+                        } else {
+                            "( )"
+                        }
+                    };
+                    (
+                        "Method",
+                        format!(
+                            "{} Password  {} API key",
+                            mark(LoginMethod::Password),
+                            mark(LoginMethod::ApiKey)
+                        ),
+                    )
+                }
+                LoginField::Url => ("URL", self.url.clone()),
+                LoginField::Username => ("Username", self.username.clone()),
+                LoginField::Password => ("Password", "•".repeat(self.password.chars().count())),
+                LoginField::ApiKey => ("API key", "•".repeat(self.api_key.chars().count())),
+            };
+            let cursor = if is_focused && field != LoginField::Method {
+                "_"
+            } else {
+                ""
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:>9} ", label), label_style),
+                Span::styled(format!(" {}{}", value, cursor), text_normal()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(match (&self.error, self.submitting) {
+            (_, true) => Line::from(Span::styled(" Logging in...", text_accent())),
+            (Some(error), false) => Line::from(Span::styled(format!(" {}", error), text_error())),
+            (None, false) => Line::from(""),
+        });
+        lines.push(Line::from(vec![
+            Span::styled(" Tab", text_key()),
+            Span::styled(" Next field  ", text_dim()),
+            Span::styled("←/→", text_key()),
+            Span::styled(" Method  ", text_dim()),
+            Span::styled("Enter", text_key()),
+            Span::styled(" Log in  ", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(" Cancel", text_dim()),
+        ]));
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(" Log in to Metabase ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(text_accent()),
+            )
+            .style(popup_style());
+
+        frame.render_widget(paragraph, popup_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_form_fields_and_submit() {
+        let mut form = LoginForm::new(
+            LoginMethod::Password,
+            "http://localhost:3000".to_string(),
+            "me@example.com".to_string(),
+        );
+        // Focus starts on the first empty field
+        assert_eq!(form.focused(), LoginField::Password);
+        assert!(form.submit().is_err());
+        assert!(!form.is_submitting());
+
+        "secret".chars().for_each(|c| form.input_char(c));
+        form.delete_char();
+        assert_eq!(
+            form.submit(),
+            Ok((
+                "http://localhost:3000".to_string(),
+                Credentials::Password {
+                    username: "me@example.com".to_string(),
+                    password: "secre".to_string(),
+                }
+            ))
+        );
+        assert!(form.is_submitting());
+    }
+
+    #[test]
+    fn test_login_form_api_key() {
+        let mut form = LoginForm::new(LoginMethod::Password, String::new(), String::new());
+        assert_eq!(form.focused(), LoginField::Url);
+        form.focus_previous();
+        assert!(form.is_method_focused());
+        form.toggle_method();
+        form.focus_previous();
+        assert_eq!(form.focused(), LoginField::ApiKey);
+        form.input_char('k');
+        form.input_char(' ');
+        form.focus_next();
+        form.focus_next();
+        "http://mb".chars().for_each(|c| form.input_char(c));
+        assert_eq!(
+            form.submit(),
+            Ok((
+                "http://mb".to_string(),
+                Credentials::ApiKey("k".to_string())
+            ))
+        );
+    }
+}
@@ -6,18 +6,30 @@ use crossterm::event::KeyEvent;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
+mod buffer_list;
+mod cell_viewer;
 pub mod clipboard;
+mod command_palette;
 mod content;
 mod copy_menu;
 mod help_overlay;
+mod login_form;
 mod record_detail;
 pub mod state_renderer;
 mod status_bar;
 pub mod styles;
+pub mod theme;
 
-pub use content::{ContentPanel, ContentView, InputMode, QueryResultData};
+pub use buffer_list::BufferList;
+pub use cell_viewer::CellViewer;
+pub use command_palette::{CommandPalette, PaletteEntry};
+pub use content::{
+    ColumnKey, ColumnType, ContentClick, ContentPanel, ContentView, FilterClause, FilterCondition,
+    FilterJoin, FilterOp, InputMode, QueryResultData, RecordLink, SortKey, SortOrder,
+};
 pub use copy_menu::CopyMenu;
 pub use help_overlay::HelpOverlay;
+pub use login_form::{LoginForm, LoginMethod};
 pub use record_detail::RecordDetailOverlay;
 pub use status_bar::StatusBar;
 
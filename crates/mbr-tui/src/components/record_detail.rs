@@ -1,11 +1,15 @@
 //! Record detail overlay component.
 //!
 //! Displays a modal overlay showing all fields of a selected record.
+//! Key fields are marked and can be followed to the rows they link to.
 
+use crate::components::styles::{popup_style, text_accent, text_dim, text_key, text_normal};
+use crate::components::theme::theme;
+use crate::components::{ColumnKey, RecordLink};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Flex, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
@@ -21,6 +25,10 @@ pub struct RecordDetailOverlay {
     selected_index: usize,
     /// Scroll offset for viewport management
     scroll_offset: usize,
+    /// Key role of each field
+    keys: Vec<ColumnKey>,
+    /// Referencing tables to choose from (links, selection, key value)
+    references: Option<(Vec<RecordLink>, usize, serde_json::Value)>,
 }
 
 impl RecordDetailOverlay {
@@ -31,9 +39,17 @@ impl RecordDetailOverlay {
             values,
             selected_index: 0,
             scroll_offset: 0,
+            keys: Vec::new(),
+            references: None,
         }
     }
 
+    /// Set the key role of each field.
+    pub fn with_keys(mut self, keys: Vec<ColumnKey>) -> Self {
+        self.keys = keys;
+        self
+    }
+
     /// Move cursor up by one line.
     pub fn scroll_up(&mut self) {
         if self.selected_index > 0 {
@@ -56,6 +72,63 @@ impl RecordDetailOverlay {
         self.values.get(self.selected_index).map(|s| s.as_str())
     }
 
+    /// Column names and values of the record.
+    pub fn record(&self) -> (&[String], &[String]) {
+        (&self.columns, &self.values)
+    }
+
+    /// Index of the currently selected field.
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    /// Key role of the currently selected field.
+    pub fn selected_key(&self) -> ColumnKey {
+        self.keys
+            .get(self.selected_index)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // === Reference chooser ===
+
+    /// Show the tables referencing a key value to choose from.
+    pub fn show_references(&mut self, links: Vec<RecordLink>, value: serde_json::Value) {
+        self.references = Some((links, 0, value));
+    }
+
+    /// Check if the reference chooser is shown.
+    pub fn is_choosing_reference(&self) -> bool {
+        self.references.is_some()
+    }
+
+    /// Move the reference selection up.
+    pub fn reference_up(&mut self) {
+        if let Some((_, selected, _)) = self.references.as_mut() {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// Move the reference selection down.
+    pub fn reference_down(&mut self) {
+        if let Some((links, selected, _)) = self.references.as_mut()
+            && *selected + 1 < links.len()
+        {
+            *selected += 1;
+        }
+    }
+
+    /// Selected reference and the key value to look up.
+    pub fn selected_reference(&self) -> Option<(RecordLink, serde_json::Value)> {
+        let (links, selected, value) = self.references.as_ref()?;
+        Some((links.get(*selected)?.clone(), value.clone()))
+    }
+
+    /// Close the reference chooser.
+    pub fn close_references(&mut self) {
+        self.references = None;
+    }
+
     /// Render the record detail overlay centered on screen.
     pub fn render(&mut self, frame: &mut Frame, area: Rect) {
         // Calculate centered popup area (70% width, 80% height)
@@ -128,29 +201,35 @@ impl RecordDetailOverlay {
             let (prefix, col_style, separator_style, val_style) = if is_selected {
                 (
                     "► ",
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Cyan)
+                    theme()
+                        .highlight(theme().accent)
                         .add_modifier(Modifier::BOLD),
-                    Style::default().fg(Color::Black).bg(Color::Cyan),
-                    Style::default().fg(Color::Black).bg(Color::Cyan),
+                    theme().highlight(theme().accent),
+                    theme().highlight(theme().accent),
                 )
             } else {
                 (
                     "  ",
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme().key)
                         .add_modifier(Modifier::BOLD),
-                    Style::default().fg(Color::DarkGray),
+                    text_dim(),
                     Style::default(),
                 )
             };
 
+            // Mark fields that can be followed
+            let marker = match self.keys.get(i) {
+                Some(ColumnKey::Foreign { .. }) => " →",
+                Some(ColumnKey::Primary { .. }) => " ←",
+                _ => "",
+            };
+
             lines.push(Line::from(vec![
                 Span::styled(
                     prefix,
                     if is_selected {
-                        Style::default().bg(Color::Cyan)
+                        theme().highlight(theme().accent)
                     } else {
                         Style::default()
                     },
@@ -158,6 +237,7 @@ impl RecordDetailOverlay {
                 Span::styled(padded_col, col_style),
                 Span::styled(" : ", separator_style),
                 Span::styled(val_display, val_style),
+                Span::styled(marker, text_key()),
             ]));
         }
 
@@ -186,22 +266,28 @@ impl RecordDetailOverlay {
             "  No fields".to_string()
         };
 
-        lines.push(Line::from(Span::styled(
-            scroll_info,
-            Style::default().fg(Color::DarkGray),
-        )));
+        lines.push(Line::from(Span::styled(scroll_info, text_dim())));
 
         // Help line
         lines.push(Line::from(""));
-        lines.push(Line::from(vec![
-            Span::styled("  [", Style::default().fg(Color::DarkGray)),
-            Span::styled("↑↓/jk", Style::default().fg(Color::Yellow)),
-            Span::styled(" Move] [", Style::default().fg(Color::DarkGray)),
-            Span::styled("c", Style::default().fg(Color::Yellow)),
-            Span::styled(" Copy] [", Style::default().fg(Color::DarkGray)),
-            Span::styled("Esc/Enter", Style::default().fg(Color::Yellow)),
-            Span::styled(" Close]", Style::default().fg(Color::DarkGray)),
-        ]));
+        let mut help = vec![
+            Span::styled("  [", text_dim()),
+            Span::styled("↑↓/jk", text_key()),
+            Span::styled(" Move] [", text_dim()),
+            Span::styled("c", text_key()),
+            Span::styled(" Copy] [", text_dim()),
+            Span::styled("v", text_key()),
+            Span::styled(" View] [", text_dim()),
+        ];
+        if self.selected_key() == ColumnKey::None {
+            help.push(Span::styled("Esc/Enter", text_key()));
+        } else {
+            help.push(Span::styled("Enter", text_key()));
+            help.push(Span::styled(" Follow key] [", text_dim()));
+            help.push(Span::styled("Esc", text_key()));
+        }
+        help.push(Span::styled(" Close]", text_dim()));
+        lines.push(Line::from(help));
 
         // Create the paragraph widget
         let detail_text = Paragraph::new(lines)
@@ -210,11 +296,68 @@ impl RecordDetailOverlay {
                     .title(" Record Detail ")
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(text_accent()),
             )
             .alignment(Alignment::Left);
 
         frame.render_widget(detail_text, popup_area);
+
+        if self.references.is_some() {
+            self.render_references(frame, popup_area);
+        }
+    }
+
+    /// Render the reference chooser over the record detail.
+    fn render_references(&self, frame: &mut Frame, area: Rect) {
+        let Some((links, selected, _)) = self.references.as_ref() else {
+            return;
+        };
+        let width = links
+            .iter()
+            .map(|l| l.label.width() + 6)
+            .max()
+            .unwrap_or(0)
+            .max(32) as u16;
+        let height = links.len() as u16 + 4;
+        let popup = Rect {
+            x: area.x + area.width.saturating_sub(width) / 2,
+            y: area.y + area.height.saturating_sub(height) / 2,
+            width: width.min(area.width),
+            height: height.min(area.height),
+        };
+        frame.render_widget(Clear, popup);
+
+        let mut lines: Vec<Line> = links
+            .iter()
+            .enumerate()
+            .map(|(i, link)| {
+                if i == *selected {
+                    Line::from(Span::styled(
+                        format!("► {}", link.label),
+                        theme().highlight(theme().accent),
+                    ))
+                } else {
+                    Line::from(Span::styled(format!("  {}", link.label), text_normal()))
+                }
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("[", text_dim()),
+            Span::styled("Enter", text_key()),
+            Span::styled(" Open] [", text_dim()),
+            Span::styled("Esc", text_key()),
+            Span::styled(" Cancel]", text_dim()),
+        ]));
+
+        let chooser = Paragraph::new(lines).style(popup_style()).block(
+            Block::default()
+                .title(" Referenced by ")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(text_accent()),
+        );
+        frame.render_widget(chooser, popup);
     }
 
     /// Truncate string to fit within max display width, adding "..." suffix.
@@ -6,11 +6,12 @@
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
+use crate::components::styles::{text_accent, text_dim, text_error, text_key};
 use crate::service::LoadState;
 
 /// Configuration for rendering LoadState views.
@@ -28,11 +29,7 @@ pub struct LoadStateConfig<'a> {
 impl<'a> LoadStateConfig<'a> {
     /// Create a new LoadStateConfig with the given title and focused state.
     pub fn new(title: &'a str, focused: bool) -> Self {
-        let border_style = if focused {
-            Style::default().fg(Color::Cyan)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
+        let border_style = if focused { text_accent() } else { text_dim() };
 
         Self {
             title,
@@ -61,7 +58,7 @@ pub fn render_idle(frame: &mut Frame, area: Rect, config: &LoadStateConfig<'_>)
         Line::from(""),
         Line::from(Span::styled(
             format!("  {}", config.idle_message),
-            Style::default().fg(Color::DarkGray),
+            text_dim(),
         )),
     ])
     .block(
@@ -79,7 +76,7 @@ pub fn render_loading(frame: &mut Frame, area: Rect, config: &LoadStateConfig<'_
         Line::from(""),
         Line::from(Span::styled(
             format!("  ⏳ {}", config.loading_message),
-            Style::default().fg(Color::Yellow),
+            text_key(),
         )),
     ])
     .block(
@@ -97,13 +94,10 @@ pub fn render_error(frame: &mut Frame, area: Rect, config: &LoadStateConfig<'_>,
         Line::from(""),
         Line::from(Span::styled(
             format!("  ❌ Error: {}", error_msg),
-            Style::default().fg(Color::Red),
+            text_error(),
         )),
         Line::from(""),
-        Line::from(Span::styled(
-            "  Press 'r' to retry",
-            Style::default().fg(Color::DarkGray),
-        )),
+        Line::from(Span::styled("  Press 'r' to retry", text_dim())),
     ])
     .block(
         Block::default()
@@ -123,10 +117,7 @@ pub fn render_empty(
 ) {
     let paragraph = Paragraph::new(vec![
         Line::from(""),
-        Line::from(Span::styled(
-            format!("  {}", empty_message),
-            Style::default().fg(Color::DarkGray),
-        )),
+        Line::from(Span::styled(format!("  {}", empty_message), text_dim())),
     ])
     .block(
         Block::default()
@@ -147,15 +138,9 @@ pub fn render_empty_with_hint(
 ) {
     let paragraph = Paragraph::new(vec![
         Line::from(""),
-        Line::from(Span::styled(
-            format!("  {}", empty_message),
-            Style::default().fg(Color::DarkGray),
-        )),
+        Line::from(Span::styled(format!("  {}", empty_message), text_dim())),
         Line::from(""),
-        Line::from(Span::styled(
-            format!("  {}", hint_message),
-            Style::default().fg(Color::DarkGray),
-        )),
+        Line::from(Span::styled(format!("  {}", hint_message), text_dim())),
     ])
     .block(
         Block::default()
@@ -203,6 +188,7 @@ pub fn render_non_loaded_state<T>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::style::Color;
 
     #[test]
     fn test_load_state_config_new() {
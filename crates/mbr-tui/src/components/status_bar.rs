@@ -6,12 +6,12 @@ use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
 
 use super::Component;
+use crate::components::styles::{text_dim, text_key, text_success};
 
 /// Key binding display item.
 #[derive(Debug, Clone)]
@@ -76,37 +76,31 @@ impl Component for StatusBar {
         // Add keybindings
         for (i, binding) in self.bindings.iter().enumerate() {
             if i > 0 {
-                spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(" │ ", text_dim()));
             }
-            spans.push(Span::styled(
-                format!(" {} ", binding.key),
-                Style::default().fg(Color::Yellow),
-            ));
+            spans.push(Span::styled(format!(" {} ", binding.key), text_key()));
             spans.push(Span::raw(binding.action));
         }
 
         // Add selection count if any rows are selected
         if self.selection_count > 0 {
-            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(" │ ", text_dim()));
             spans.push(Span::styled(
                 format!(" {} selected ", self.selection_count),
-                Style::default().fg(Color::Yellow),
+                text_key(),
             ));
         }
 
         // Add message if present
         if !self.message.is_empty() {
-            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-            spans.push(Span::styled(
-                self.message.clone(),
-                Style::default().fg(Color::Green),
-            ));
+            spans.push(Span::styled(" │ ", text_dim()));
+            spans.push(Span::styled(self.message.clone(), text_success()));
         }
 
         let paragraph = Paragraph::new(Line::from(spans)).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(text_dim()),
         );
 
         frame.render_widget(paragraph, area);
@@ -2,28 +2,35 @@
 //!
 //! This module provides consistent styling across all TUI views,
 //! eliminating duplication and ensuring visual consistency.
+//! Colors come from the active theme (see `theme`).
 //!
 //! Some styles are reserved for future View refactoring (PR 5, 6).
 
 #![allow(dead_code)]
 
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
+
+use super::theme::theme;
 
 // === Border Styles ===
 
 /// Border style for focused components.
-pub const BORDER_FOCUSED: Style = Style::new().fg(Color::Cyan);
+pub fn border_focused() -> Style {
+    Style::default().fg(theme().accent)
+}
 
 /// Border style for unfocused components.
-pub const BORDER_UNFOCUSED: Style = Style::new().fg(Color::DarkGray);
+pub fn border_unfocused() -> Style {
+    Style::default().fg(theme().muted)
+}
 
 /// Get border style based on focus state.
 #[inline]
 pub fn border_style(focused: bool) -> Style {
     if focused {
-        BORDER_FOCUSED
+        border_focused()
     } else {
-        BORDER_UNFOCUSED
+        border_unfocused()
     }
 }
 
@@ -32,25 +39,21 @@ pub fn border_style(focused: bool) -> Style {
 /// Style for table header text.
 pub fn header_style() -> Style {
     Style::default()
-        .fg(Color::Yellow)
+        .fg(theme().key)
         .add_modifier(Modifier::BOLD)
 }
 
-/// Style for table row when selected/highlighted (cyan background).
+/// Style for table row when selected/highlighted (accent background).
 pub fn row_highlight_style() -> Style {
-    Style::default()
-        .fg(Color::Black)
-        .bg(Color::Cyan)
-        .add_modifier(Modifier::BOLD)
+    let t = theme();
+    t.highlight(t.accent).add_modifier(Modifier::BOLD)
 }
 
-/// Style for query result row when selected (green background).
+/// Style for query result row when selected (success background).
 /// Used for data-focused views like QueryResult and TablePreview.
 pub fn result_row_highlight_style() -> Style {
-    Style::default()
-        .fg(Color::Black)
-        .bg(Color::Green)
-        .add_modifier(Modifier::BOLD)
+    let t = theme();
+    t.highlight(t.success).add_modifier(Modifier::BOLD)
 }
 
 /// Default highlight symbol for table selection.
@@ -59,38 +62,60 @@ pub const HIGHLIGHT_SYMBOL: &str = "► ";
 // === Multi-Select Styles ===
 
 /// Style for multi-selected rows (not cursor, but selected).
-/// Uses yellow background to distinguish from cursor highlight.
+/// Uses the marked background to distinguish from cursor highlight.
 pub fn multi_selected_style() -> Style {
-    Style::default().fg(Color::Black).bg(Color::Yellow)
+    let t = theme();
+    if t.monochrome {
+        Style::default().add_modifier(Modifier::UNDERLINED)
+    } else {
+        t.highlight(t.marked)
+    }
 }
 
 /// Style for cursor position that is also selected.
 /// Combines cursor highlight with selection indicator.
 pub fn cursor_and_selected_style() -> Style {
-    Style::default()
-        .fg(Color::Black)
-        .bg(Color::LightYellow)
+    let t = theme();
+    t.highlight(t.marked_cursor)
         .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
 }
 
 // === Text Styles ===
 
 /// Style for dimmed/hint text.
-pub const TEXT_DIM: Style = Style::new().fg(Color::DarkGray);
+pub fn text_dim() -> Style {
+    Style::default().fg(theme().muted)
+}
 
-/// Style for warning/loading text.
-pub const TEXT_WARNING: Style = Style::new().fg(Color::Yellow);
+/// Style for key hints and warning/loading text.
+pub fn text_key() -> Style {
+    Style::default().fg(theme().key)
+}
 
 /// Style for error text.
-pub const TEXT_ERROR: Style = Style::new().fg(Color::Red);
+pub fn text_error() -> Style {
+    Style::default().fg(theme().error)
+}
 
 /// Style for success text.
-pub const TEXT_SUCCESS: Style = Style::new().fg(Color::Green);
+pub fn text_success() -> Style {
+    Style::default().fg(theme().success)
+}
+
+/// Style for primary text.
+pub fn text_normal() -> Style {
+    Style::default().fg(theme().text)
+}
+
+/// Style for accent text (titles, section headers).
+pub fn text_accent() -> Style {
+    Style::default().fg(theme().accent)
+}
 
-/// Style for bold white text.
+/// Style for bold primary text.
 pub fn text_bold_white() -> Style {
     Style::default()
-        .fg(Color::White)
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD)
 }
 
@@ -99,21 +124,31 @@ pub fn text_bold_white() -> Style {
 /// Style for modal titles.
 pub fn modal_title_style() -> Style {
     Style::default()
-        .fg(Color::White)
+        .fg(theme().text)
         .add_modifier(Modifier::BOLD)
 }
 
+/// Background style for popups and modals.
+pub fn popup_style() -> Style {
+    theme().popup()
+}
+
 /// Style for selected items in lists.
 pub fn selected_style() -> Style {
-    Style::default()
-        .bg(Color::Blue)
-        .fg(Color::White)
-        .add_modifier(Modifier::BOLD)
+    let t = theme();
+    if t.monochrome {
+        t.highlight(t.selection).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+            .bg(t.selection)
+            .fg(t.text)
+            .add_modifier(Modifier::BOLD)
+    }
 }
 
 /// Style for selected index numbers.
 pub fn selected_index_style() -> Style {
-    Style::default().fg(Color::Cyan)
+    Style::default().fg(theme().accent)
 }
 
 // === Input Styles ===
@@ -121,18 +156,19 @@ pub fn selected_index_style() -> Style {
 /// Style for cursor indicator.
 pub fn cursor_style() -> Style {
     Style::default()
-        .fg(Color::Yellow)
+        .fg(theme().key)
         .add_modifier(Modifier::SLOW_BLINK)
 }
 
 /// Style for input text.
 pub fn input_text_style() -> Style {
-    Style::default().fg(Color::White)
+    Style::default().fg(theme().text)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::style::Color;
 
     #[test]
     fn test_border_style_focused() {
@@ -0,0 +1,385 @@
+//! Color themes for the TUI.
+//!
+//! Components read colors from semantic slots (`accent`, `muted`, `key`, ...)
+//! of the active theme instead of hardcoding ratatui `Color`s.
+//!
+//! - Built-in themes: `dark` (default), `light`, `high-contrast`, `solarized`, `no-color`
+//! - User themes: `~/.config/mbr-cli/themes/<name>.toml`, starting from a `base`
+//!   built-in and overriding individual slots
+//! - Selected by `theme = "..."` in the `[tui]` section of config.toml;
+//!   a non-empty `NO_COLOR` environment variable forces `no-color`
+//!
+//! The active theme is process-wide so that style helpers can read it without
+//! threading a theme through every render function.
+
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use mbr_core::storage::config::{Config, ThemeFile};
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::error::{TuiError, TuiResult};
+
+/// Semantic color palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Focused borders, titles, cursor rows in lists
+    pub accent: Color,
+    /// Unfocused borders, hints, secondary text
+    pub muted: Color,
+    /// Primary text
+    pub text: Color,
+    /// Text drawn on top of a highlight background
+    pub inverse: Color,
+    /// Key hints, table headers, in-progress states
+    pub key: Color,
+    /// Connected state, result cursor row, search modal
+    pub success: Color,
+    /// Errors
+    pub error: Color,
+    /// Sort/filter modals
+    pub secondary: Color,
+    /// Selected items in modal lists
+    pub selection: Color,
+    /// Multi-selected result rows
+    pub marked: Color,
+    /// Cursor row that is also multi-selected
+    pub marked_cursor: Color,
+    /// Background of popups and modals
+    pub popup_bg: Color,
+    /// Use reverse video instead of background colors for highlights
+    pub monochrome: bool,
+}
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        accent: Color::Cyan,
+        muted: Color::DarkGray,
+        text: Color::White,
+        inverse: Color::Black,
+        key: Color::Yellow,
+        success: Color::Green,
+        error: Color::Red,
+        secondary: Color::Magenta,
+        selection: Color::Blue,
+        marked: Color::Yellow,
+        marked_cursor: Color::LightYellow,
+        popup_bg: Color::Black,
+        monochrome: false,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        accent: Color::Blue,
+        muted: Color::DarkGray,
+        text: Color::Black,
+        inverse: Color::White,
+        key: Color::Rgb(175, 95, 0),
+        success: Color::Rgb(0, 135, 0),
+        error: Color::Red,
+        secondary: Color::Rgb(135, 0, 135),
+        selection: Color::Rgb(0, 95, 175),
+        marked: Color::Rgb(215, 175, 0),
+        marked_cursor: Color::Rgb(255, 215, 95),
+        popup_bg: Color::White,
+        monochrome: false,
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        accent: Color::LightCyan,
+        muted: Color::Gray,
+        text: Color::White,
+        inverse: Color::Black,
+        key: Color::LightYellow,
+        success: Color::LightGreen,
+        error: Color::LightRed,
+        secondary: Color::LightMagenta,
+        selection: Color::Blue,
+        marked: Color::Yellow,
+        marked_cursor: Color::LightYellow,
+        popup_bg: Color::Black,
+        monochrome: false,
+    };
+
+    /// Solarized dark palette.
+    pub const SOLARIZED: Theme = Theme {
+        accent: Color::Rgb(42, 161, 152),
+        muted: Color::Rgb(88, 110, 117),
+        text: Color::Rgb(147, 161, 161),
+        inverse: Color::Rgb(0, 43, 54),
+        key: Color::Rgb(181, 137, 0),
+        success: Color::Rgb(133, 153, 0),
+        error: Color::Rgb(220, 50, 47),
+        secondary: Color::Rgb(211, 54, 130),
+        selection: Color::Rgb(38, 139, 210),
+        marked: Color::Rgb(181, 137, 0),
+        marked_cursor: Color::Rgb(203, 75, 22),
+        popup_bg: Color::Rgb(0, 43, 54),
+        monochrome: false,
+    };
+
+    /// Terminal default colors only; highlights use reverse video.
+    pub const NO_COLOR: Theme = Theme {
+        accent: Color::Reset,
+        muted: Color::Reset,
+        text: Color::Reset,
+        inverse: Color::Reset,
+        key: Color::Reset,
+        success: Color::Reset,
+        error: Color::Reset,
+        secondary: Color::Reset,
+        selection: Color::Reset,
+        marked: Color::Reset,
+        marked_cursor: Color::Reset,
+        popup_bg: Color::Reset,
+        monochrome: true,
+    };
+
+    /// Built-in theme names in toggle order.
+    pub const BUILTIN_NAMES: [&'static str; 5] =
+        ["dark", "light", "high-contrast", "solarized", "no-color"];
+
+    /// Look up a built-in theme by name.
+    pub fn builtin(name: &str) -> Option<Theme> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "dark" | "default" => Some(Theme::DARK),
+            "light" => Some(Theme::LIGHT),
+            "high-contrast" => Some(Theme::HIGH_CONTRAST),
+            "solarized" => Some(Theme::SOLARIZED),
+            "no-color" | "none" => Some(Theme::NO_COLOR),
+            _ => None,
+        }
+    }
+
+    /// Build a theme from a user theme file.
+    pub fn from_file(file: &ThemeFile) -> Result<Theme, String> {
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme =
+            Theme::builtin(base).ok_or_else(|| format!("unknown base theme '{}'", base))?;
+
+        for (slot, value) in &file.colors {
+            let color = Color::from_str(value)
+                .map_err(|_| format!("{}: invalid color '{}'", slot, value))?;
+            let target = match slot.as_str() {
+                "accent" => &mut theme.accent,
+                "muted" => &mut theme.muted,
+                "text" => &mut theme.text,
+                "inverse" => &mut theme.inverse,
+                "key" => &mut theme.key,
+                "success" => &mut theme.success,
+                "error" => &mut theme.error,
+                "secondary" => &mut theme.secondary,
+                "selection" => &mut theme.selection,
+                "marked" => &mut theme.marked,
+                "marked_cursor" => &mut theme.marked_cursor,
+                "popup_bg" => &mut theme.popup_bg,
+                other => return Err(format!("unknown color slot '{}'", other)),
+            };
+            *target = color;
+        }
+        Ok(theme)
+    }
+
+    /// Style for text on a highlight background (reverse video when monochrome).
+    pub fn highlight(&self, bg: Color) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.inverse).bg(bg)
+        }
+    }
+
+    /// Background style for popups and modals.
+    pub fn popup(&self) -> Style {
+        Style::default().bg(self.popup_bg)
+    }
+}
+
+// === Active theme ===
+
+static ACTIVE: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+/// The active theme.
+pub fn theme() -> Theme {
+    *ACTIVE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Replace the active theme.
+pub fn set_theme(theme: Theme) {
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+/// Whether the `NO_COLOR` convention (https://no-color.org) is in effect.
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+// === Theme selection ===
+
+/// Available themes and the current one, for the runtime toggle.
+#[derive(Debug, Clone)]
+pub struct ThemeCycle {
+    entries: Vec<(String, Theme)>,
+    current: usize,
+}
+
+impl Default for ThemeCycle {
+    fn default() -> Self {
+        Self::with_current("dark", None)
+    }
+}
+
+impl ThemeCycle {
+    /// Built-in themes plus an optional user theme, starting at `current`.
+    fn with_current(current: &str, custom: Option<(String, Theme)>) -> Self {
+        let mut entries: Vec<(String, Theme)> = Theme::BUILTIN_NAMES
+            .iter()
+            .filter_map(|name| Theme::builtin(name).map(|t| (name.to_string(), t)))
+            .collect();
+        if let Some(custom) = custom {
+            entries.push(custom);
+        }
+        let current = entries
+            .iter()
+            .position(|(name, _)| name == current)
+            .unwrap_or(0);
+        Self { entries, current }
+    }
+
+    /// Resolve a configured theme name. Built-in names win; anything else is
+    /// loaded from the themes directory.
+    pub fn from_name(name: Option<&str>) -> TuiResult<Self> {
+        if no_color_requested() {
+            return Ok(Self::with_current("no-color", None));
+        }
+
+        let Some(name) = name else {
+            return Ok(Self::default());
+        };
+        if let Some(theme) = Theme::builtin(name) {
+            let canonical = Theme::BUILTIN_NAMES
+                .into_iter()
+                .find(|n| Theme::builtin(n) == Some(theme))
+                .unwrap_or("dark");
+            return Ok(Self::with_current(canonical, None));
+        }
+
+        let file = ThemeFile::load(name)
+            .map_err(|e| TuiError::Config(format!("theme '{}': {}", name, e)))?;
+        let theme = Theme::from_file(&file)
+            .map_err(|e| TuiError::Config(format!("theme '{}': {}", name, e)))?;
+        Ok(Self::with_current(name, Some((name.to_string(), theme))))
+    }
+
+    /// Load the theme selected in config.toml.
+    pub fn load() -> TuiResult<Self> {
+        let config = Config::load(None).map_err(|e| TuiError::Config(e.to_string()))?;
+        Self::from_name(config.tui.theme.as_deref())
+    }
+
+    /// Name of the current theme.
+    pub fn current_name(&self) -> &str {
+        &self.entries[self.current].0
+    }
+
+    /// The current theme.
+    pub fn current(&self) -> Theme {
+        self.entries[self.current].1
+    }
+
+    /// Names of all available themes.
+    pub fn names(&self) -> Vec<&str> {
+        self.entries.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Make the named theme current. Returns false if it is not available.
+    pub fn select(&mut self, name: &str) -> bool {
+        let wanted = Theme::builtin(name);
+        let found = self.entries.iter().position(|(entry_name, theme)| {
+            entry_name == name || (wanted == Some(*theme) && Theme::builtin(entry_name).is_some())
+        });
+        match found {
+            Some(index) => {
+                self.current = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Advance to the next theme and return its name.
+    pub fn next(&mut self) -> &str {
+        self.current = (self.current + 1) % self.entries.len();
+        self.current_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_builtin_lookup() {
+        assert_eq!(Theme::builtin("dark"), Some(Theme::DARK));
+        assert_eq!(Theme::builtin("High_Contrast"), Some(Theme::HIGH_CONTRAST));
+        assert_eq!(Theme::builtin("nope"), None);
+        for name in Theme::BUILTIN_NAMES {
+            assert!(Theme::builtin(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_theme_from_file() {
+        let mut colors = BTreeMap::new();
+        colors.insert("accent".to_string(), "#268bd2".to_string());
+        colors.insert("muted".to_string(), "grey".to_string());
+        let file = ThemeFile {
+            base: Some("light".to_string()),
+            colors,
+        };
+        let theme = Theme::from_file(&file).unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0x26, 0x8b, 0xd2));
+        assert_eq!(theme.muted, Color::Gray);
+        assert_eq!(theme.text, Theme::LIGHT.text);
+
+        let mut bad = file.clone();
+        bad.colors.insert("sparkle".to_string(), "red".to_string());
+        assert!(Theme::from_file(&bad).unwrap_err().contains("sparkle"));
+
+        let mut bad = file;
+        bad.colors
+            .insert("key".to_string(), "not-a-color".to_string());
+        assert!(
+            Theme::from_file(&bad)
+                .unwrap_err()
+                .contains("invalid color")
+        );
+    }
+
+    #[test]
+    fn test_monochrome_highlight_uses_reverse() {
+        let style = Theme::NO_COLOR.highlight(Theme::NO_COLOR.accent);
+        assert!(style.add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(style.bg, None);
+
+        let style = Theme::DARK.highlight(Color::Cyan);
+        assert_eq!(style.bg, Some(Color::Cyan));
+        assert_eq!(style.fg, Some(Color::Black));
+    }
+
+    #[test]
+    fn test_theme_cycle() {
+        let mut cycle =
+            ThemeCycle::with_current("ocean", Some(("ocean".to_string(), Theme::LIGHT)));
+        assert_eq!(cycle.current_name(), "ocean");
+        assert_eq!(cycle.next(), "dark");
+        assert_eq!(cycle.next(), "light");
+        assert_eq!(cycle.current(), Theme::LIGHT);
+
+        assert!(cycle.select("no_color"));
+        assert_eq!(cycle.current_name(), "no-color");
+        assert!(cycle.select("ocean"));
+        assert!(!cycle.select("sepia"));
+        assert_eq!(cycle.current_name(), "ocean");
+    }
+}
@@ -0,0 +1,778 @@
+//! Configurable key bindings.
+//!
+//! Key events are resolved to named `KeyAction`s through a `Keymap`, built
+//! from a preset (`default`, `vim`, `emacs`) plus overrides from the
+//! `[tui.keys]` section of config.toml:
+//!
+//! ```toml
+//! [tui.keys]
+//! preset = "vim"
+//! quit = ["q", "ctrl+c"]
+//! command_palette = "alt+x"
+//! copy = []              # unbind
+//! ```
+//!
+//! An action listed in the config replaces all of the preset's keys for that
+//! action. A key bound to two actions is rejected at load time. Text inputs
+//! and the Enter/Esc keys inside modal dialogs are fixed.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use mbr_core::storage::config::{Config, KeysConfig};
+
+use crate::error::{TuiError, TuiResult};
+
+// === Actions ===
+
+/// Help overlay section an action is listed under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeySection {
+    Global,
+    Navigation,
+    Results,
+}
+
+impl KeySection {
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeySection::Global => "Global",
+            KeySection::Navigation => "Navigation",
+            KeySection::Results => "Results",
+        }
+    }
+}
+
+/// A named, rebindable action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Quit,
+    Back,
+    Help,
+    CommandPalette,
+    CommandLine,
+    TabQuestions,
+    TabCollections,
+    TabDatabases,
+    NextTab,
+    PrevTab,
+    Refresh,
+    History,
+    Buffers,
+    NextBuffer,
+    PrevBuffer,
+    CycleTheme,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    NextPage,
+    PrevPage,
+    Open,
+    Search,
+    Sort,
+    Filter,
+    ClearFilter,
+    ClearSearch,
+    Columns,
+    Summary,
+    GroupBy,
+    Profile,
+    ViewCell,
+    SplitView,
+    ToggleSelect,
+    SelectAll,
+    Copy,
+}
+
+impl KeyAction {
+    /// All actions in help display order.
+    pub const ALL: [KeyAction; 41] = [
+        KeyAction::Quit,
+        KeyAction::Back,
+        KeyAction::TabQuestions,
+        KeyAction::TabCollections,
+        KeyAction::TabDatabases,
+        KeyAction::NextTab,
+        KeyAction::PrevTab,
+        KeyAction::Refresh,
+        KeyAction::History,
+        KeyAction::Buffers,
+        KeyAction::NextBuffer,
+        KeyAction::PrevBuffer,
+        KeyAction::CommandPalette,
+        KeyAction::CommandLine,
+        KeyAction::CycleTheme,
+        KeyAction::Help,
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+        KeyAction::PageUp,
+        KeyAction::PageDown,
+        KeyAction::NextPage,
+        KeyAction::PrevPage,
+        KeyAction::First,
+        KeyAction::Last,
+        KeyAction::Open,
+        KeyAction::Search,
+        KeyAction::Sort,
+        KeyAction::Filter,
+        KeyAction::ClearFilter,
+        KeyAction::ClearSearch,
+        KeyAction::Columns,
+        KeyAction::Summary,
+        KeyAction::GroupBy,
+        KeyAction::Profile,
+        KeyAction::ViewCell,
+        KeyAction::SplitView,
+        KeyAction::ToggleSelect,
+        KeyAction::SelectAll,
+        KeyAction::Copy,
+    ];
+
+    /// Name used in `[tui.keys]`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAction::Quit => "quit",
+            KeyAction::Back => "back",
+            KeyAction::Help => "help",
+            KeyAction::CommandPalette => "command_palette",
+            KeyAction::CommandLine => "command_line",
+            KeyAction::TabQuestions => "tab_questions",
+            KeyAction::TabCollections => "tab_collections",
+            KeyAction::TabDatabases => "tab_databases",
+            KeyAction::NextTab => "next_tab",
+            KeyAction::PrevTab => "prev_tab",
+            KeyAction::Refresh => "refresh",
+            KeyAction::History => "history",
+            KeyAction::Buffers => "buffers",
+            KeyAction::NextBuffer => "next_buffer",
+            KeyAction::PrevBuffer => "prev_buffer",
+            KeyAction::CycleTheme => "cycle_theme",
+            KeyAction::Up => "up",
+            KeyAction::Down => "down",
+            KeyAction::Left => "left",
+            KeyAction::Right => "right",
+            KeyAction::PageUp => "page_up",
+            KeyAction::PageDown => "page_down",
+            KeyAction::First => "first",
+            KeyAction::Last => "last",
+            KeyAction::NextPage => "next_page",
+            KeyAction::PrevPage => "prev_page",
+            KeyAction::Open => "open",
+            KeyAction::Search => "search",
+            KeyAction::Sort => "sort",
+            KeyAction::Filter => "filter",
+            KeyAction::ClearFilter => "clear_filter",
+            KeyAction::ClearSearch => "clear_search",
+            KeyAction::Columns => "columns",
+            KeyAction::Summary => "summary",
+            KeyAction::GroupBy => "group_by",
+            KeyAction::Profile => "profile",
+            KeyAction::ViewCell => "view_cell",
+            KeyAction::SplitView => "split_view",
+            KeyAction::ToggleSelect => "toggle_select",
+            KeyAction::SelectAll => "select_all",
+            KeyAction::Copy => "copy",
+        }
+    }
+
+    /// Look up an action by its config name.
+    pub fn from_name(name: &str) -> Option<KeyAction> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    /// Description shown in the help overlay.
+    pub fn description(&self) -> &'static str {
+        match self {
+            KeyAction::Quit => "Quit application",
+            KeyAction::Back => "Back / Clear selection / Quit",
+            KeyAction::Help => "Toggle help",
+            KeyAction::CommandPalette => "Command palette",
+            KeyAction::CommandLine => "Command line (:run, :filter, :sort, :group, :export)",
+            KeyAction::TabQuestions => "Switch to Questions",
+            KeyAction::TabCollections => "Switch to Collections",
+            KeyAction::TabDatabases => "Switch to Databases",
+            KeyAction::NextTab => "Next tab",
+            KeyAction::PrevTab => "Previous tab",
+            KeyAction::Refresh => "Refresh data",
+            KeyAction::History => "Execution history (Enter re-runs)",
+            KeyAction::Buffers => "Open results (show, compare, close)",
+            KeyAction::NextBuffer => "Next open result",
+            KeyAction::PrevBuffer => "Previous open result",
+            KeyAction::CycleTheme => "Toggle color theme",
+            KeyAction::Up => "Move up",
+            KeyAction::Down => "Move down",
+            KeyAction::Left => "Scroll left / Collapse collection",
+            KeyAction::Right => "Scroll right / Expand collection",
+            KeyAction::PageUp => "Scroll page up",
+            KeyAction::PageDown => "Scroll page down",
+            KeyAction::First => "First page / First item",
+            KeyAction::Last => "Last page / Last item",
+            KeyAction::NextPage => "Next page",
+            KeyAction::PrevPage => "Previous page",
+            KeyAction::Open => "Execute query / Expand / Record detail",
+            KeyAction::Search => "Search",
+            KeyAction::Sort => "Sort by column (Space adds a sort key)",
+            KeyAction::Filter => "Filter conditions (add, edit, remove)",
+            KeyAction::ClearFilter => "Clear filter",
+            KeyAction::ClearSearch => "Clear search",
+            KeyAction::Columns => "Manage columns (hide, reorder, pin, resize)",
+            KeyAction::Summary => "Toggle summary footer (count, sum, avg, min, max)",
+            KeyAction::GroupBy => "Group rows by columns",
+            KeyAction::Profile => "Profile column (distinct, nulls, top values, distribution)",
+            KeyAction::ViewCell => "View full cell value (wrapped, JSON/XML formatted)",
+            KeyAction::SplitView => "Compare with another result side by side",
+            KeyAction::ToggleSelect => "Toggle row selection",
+            KeyAction::SelectAll => "Select all rows",
+            KeyAction::Copy => "Copy record(s)",
+        }
+    }
+
+    /// Help overlay section.
+    pub fn section(&self) -> KeySection {
+        match self {
+            KeyAction::Quit
+            | KeyAction::Back
+            | KeyAction::Help
+            | KeyAction::CommandPalette
+            | KeyAction::CommandLine
+            | KeyAction::TabQuestions
+            | KeyAction::TabCollections
+            | KeyAction::TabDatabases
+            | KeyAction::NextTab
+            | KeyAction::PrevTab
+            | KeyAction::Refresh
+            | KeyAction::History
+            | KeyAction::Buffers
+            | KeyAction::NextBuffer
+            | KeyAction::PrevBuffer
+            | KeyAction::CycleTheme => KeySection::Global,
+            KeyAction::Up
+            | KeyAction::Down
+            | KeyAction::Left
+            | KeyAction::Right
+            | KeyAction::PageUp
+            | KeyAction::PageDown
+            | KeyAction::First
+            | KeyAction::Last
+            | KeyAction::NextPage
+            | KeyAction::PrevPage
+            | KeyAction::Open
+            | KeyAction::Search => KeySection::Navigation,
+            KeyAction::Sort
+            | KeyAction::Filter
+            | KeyAction::ClearFilter
+            | KeyAction::ClearSearch
+            | KeyAction::Columns
+            | KeyAction::Summary
+            | KeyAction::GroupBy
+            | KeyAction::Profile
+            | KeyAction::ViewCell
+            | KeyAction::SplitView
+            | KeyAction::ToggleSelect
+            | KeyAction::SelectAll
+            | KeyAction::Copy => KeySection::Results,
+        }
+    }
+}
+
+// === Key bindings ===
+
+/// A key plus modifiers, normalized so that lookups match crossterm events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Create a normalized binding.
+    ///
+    /// Shift is folded into the character for `Char` keys (`shift+g` == `G`)
+    /// and `shift+tab` becomes `BackTab`, since terminals report these
+    /// inconsistently.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Parse a key spec such as `q`, `G`, `ctrl+p`, `alt+<`, `shift+tab`, `pgdn`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err("empty key".to_string());
+        }
+
+        // The key itself may be '+', e.g. "+" or "ctrl++"
+        let (mod_part, key_part) = if spec == "+" {
+            ("", "+")
+        } else if let Some(mods) = spec.strip_suffix("++") {
+            (mods, "+")
+        } else {
+            match spec.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", spec),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mod_part.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{}' in '{}'", m, spec)),
+            };
+        }
+
+        let mut chars = key_part.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key_part.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", spec)),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if !self.modifiers.is_empty() => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+// === Presets ===
+
+/// Built-in binding presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Preset {
+    /// Arrow keys plus vim-style letters
+    #[default]
+    Default,
+    /// Default plus Ctrl+F/B/D/U paging
+    Vim,
+    /// Ctrl/Alt chords instead of bare-letter movement
+    Emacs,
+}
+
+type BindingTable = &'static [(KeyAction, &'static [&'static str])];
+
+const DEFAULT_BINDINGS: BindingTable = &[
+    (KeyAction::Quit, &["q", "Q", "ctrl+c"]),
+    (KeyAction::Back, &["esc"]),
+    (KeyAction::Help, &["?"]),
+    (KeyAction::CommandPalette, &["ctrl+p"]),
+    (KeyAction::CommandLine, &[":"]),
+    (KeyAction::TabQuestions, &["1"]),
+    (KeyAction::TabCollections, &["2"]),
+    (KeyAction::TabDatabases, &["3"]),
+    (KeyAction::NextTab, &["tab"]),
+    (KeyAction::PrevTab, &["backtab"]),
+    (KeyAction::Refresh, &["r"]),
+    (KeyAction::History, &["H"]),
+    (KeyAction::Buffers, &["B"]),
+    (KeyAction::NextBuffer, &["]"]),
+    (KeyAction::PrevBuffer, &["["]),
+    (KeyAction::CycleTheme, &["T"]),
+    (KeyAction::Up, &["up", "k"]),
+    (KeyAction::Down, &["down", "j"]),
+    (KeyAction::Left, &["left", "h"]),
+    (KeyAction::Right, &["right", "l"]),
+    (KeyAction::PageUp, &["pageup"]),
+    (KeyAction::PageDown, &["pagedown"]),
+    (KeyAction::First, &["home", "g"]),
+    (KeyAction::Last, &["end", "G"]),
+    (KeyAction::NextPage, &["n"]),
+    (KeyAction::PrevPage, &["p"]),
+    (KeyAction::Open, &["enter"]),
+    (KeyAction::Search, &["/"]),
+    (KeyAction::Sort, &["s"]),
+    (KeyAction::Filter, &["f"]),
+    (KeyAction::ClearFilter, &["F"]),
+    (KeyAction::ClearSearch, &["S"]),
+    (KeyAction::Columns, &["C"]),
+    (KeyAction::Summary, &["="]),
+    (KeyAction::GroupBy, &["b"]),
+    (KeyAction::Profile, &["P"]),
+    (KeyAction::ViewCell, &["v"]),
+    (KeyAction::SplitView, &["V"]),
+    (KeyAction::ToggleSelect, &["space"]),
+    (KeyAction::SelectAll, &["ctrl+a"]),
+    (KeyAction::Copy, &["c"]),
+];
+
+/// Applied on top of `DEFAULT_BINDINGS`.
+const VIM_OVERRIDES: BindingTable = &[
+    (KeyAction::PageUp, &["pageup", "ctrl+b", "ctrl+u"]),
+    (KeyAction::PageDown, &["pagedown", "ctrl+f", "ctrl+d"]),
+];
+
+/// Applied on top of `DEFAULT_BINDINGS`.
+const EMACS_OVERRIDES: BindingTable = &[
+    (KeyAction::Back, &["esc", "ctrl+g"]),
+    (KeyAction::CommandPalette, &["alt+x"]),
+    (KeyAction::Up, &["up", "ctrl+p"]),
+    (KeyAction::Down, &["down", "ctrl+n"]),
+    (KeyAction::Left, &["left", "ctrl+b"]),
+    (KeyAction::Right, &["right", "ctrl+f"]),
+    (KeyAction::PageUp, &["pageup", "alt+v"]),
+    (KeyAction::PageDown, &["pagedown", "ctrl+v"]),
+    (KeyAction::First, &["home", "alt+<"]),
+    (KeyAction::Last, &["end", "alt+>"]),
+    (KeyAction::Search, &["/", "ctrl+s"]),
+];
+
+impl Preset {
+    pub fn from_name(name: &str) -> Option<Preset> {
+        match name.to_lowercase().as_str() {
+            "default" => Some(Preset::Default),
+            "vim" => Some(Preset::Vim),
+            "emacs" => Some(Preset::Emacs),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Default => "default",
+            Preset::Vim => "vim",
+            Preset::Emacs => "emacs",
+        }
+    }
+
+    fn overrides(&self) -> BindingTable {
+        match self {
+            Preset::Default => &[],
+            Preset::Vim => VIM_OVERRIDES,
+            Preset::Emacs => EMACS_OVERRIDES,
+        }
+    }
+}
+
+// === Keymap ===
+
+/// Resolves key events to actions.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    preset: Preset,
+    /// Keys per action, in config order
+    keys: HashMap<KeyAction, Vec<KeyBinding>>,
+    /// Reverse lookup
+    lookup: HashMap<KeyBinding, KeyAction>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::Default)
+    }
+}
+
+impl Keymap {
+    /// Build a keymap from a built-in preset.
+    pub fn preset(preset: Preset) -> Self {
+        let mut specs = table_specs(DEFAULT_BINDINGS);
+        specs.extend(table_specs(preset.overrides()));
+        // Built-in tables are covered by tests, so they never fail to build
+        Self::build(preset, specs).expect("built-in keymap preset is valid")
+    }
+
+    /// Build a keymap from the `[tui.keys]` config section.
+    pub fn from_config(config: &KeysConfig) -> TuiResult<Self> {
+        let mut problems = Vec::new();
+
+        let preset = match config.preset.as_deref() {
+            None => Preset::Default,
+            Some(name) => Preset::from_name(name).unwrap_or_else(|| {
+                problems.push(format!("unknown preset '{}'", name));
+                Preset::Default
+            }),
+        };
+
+        let mut specs = table_specs(DEFAULT_BINDINGS);
+        specs.extend(table_specs(preset.overrides()));
+        for (name, keys) in &config.bindings {
+            match KeyAction::from_name(name) {
+                Some(action) => {
+                    specs.insert(action, keys.keys().into_iter().map(String::from).collect());
+                }
+                None => problems.push(format!("unknown action '{}'", name)),
+            }
+        }
+
+        match Self::build(preset, specs) {
+            Ok(keymap) if problems.is_empty() => Ok(keymap),
+            Ok(_) => Err(TuiError::Config(format!(
+                "[tui.keys]: {}",
+                problems.join("; ")
+            ))),
+            Err(mut errors) => {
+                problems.append(&mut errors);
+                Err(TuiError::Config(format!(
+                    "[tui.keys]: {}",
+                    problems.join("; ")
+                )))
+            }
+        }
+    }
+
+    /// Load the keymap from config.toml (default preset if there is no config).
+    pub fn load() -> TuiResult<Self> {
+        let config = Config::load(None).map_err(|e| TuiError::Config(e.to_string()))?;
+        Self::from_config(&config.tui.keys)
+    }
+
+    fn build(preset: Preset, specs: HashMap<KeyAction, Vec<String>>) -> Result<Self, Vec<String>> {
+        let mut errors = Vec::new();
+        let mut keys: HashMap<KeyAction, Vec<KeyBinding>> = HashMap::new();
+        let mut lookup: HashMap<KeyBinding, KeyAction> = HashMap::new();
+
+        // Iterate in display order so conflict messages are deterministic
+        for action in KeyAction::ALL {
+            let Some(action_specs) = specs.get(&action) else {
+                continue;
+            };
+            for spec in action_specs {
+                let binding = match KeyBinding::parse(spec) {
+                    Ok(binding) => binding,
+                    Err(e) => {
+                        errors.push(format!("{}: {}", action.name(), e));
+                        continue;
+                    }
+                };
+                match lookup.get(&binding) {
+                    Some(&other) if other != action => errors.push(format!(
+                        "'{}' is bound to both {} and {}",
+                        binding,
+                        other.name(),
+                        action.name()
+                    )),
+                    Some(_) => {}
+                    None => {
+                        lookup.insert(binding, action);
+                        keys.entry(action).or_default().push(binding);
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self {
+                preset,
+                keys,
+                lookup,
+            })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Resolve a key event to its bound action.
+    pub fn resolve(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyAction> {
+        self.lookup.get(&KeyBinding::new(code, modifiers)).copied()
+    }
+
+    /// Keys bound to an action.
+    pub fn keys_for(&self, action: KeyAction) -> &[KeyBinding] {
+        self.keys.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Display string for an action's keys, e.g. `↑ / k`.
+    pub fn describe(&self, action: KeyAction) -> String {
+        self.keys_for(action)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    /// Name of the preset this keymap is based on.
+    pub fn preset_name(&self) -> &'static str {
+        self.preset.name()
+    }
+}
+
+fn table_specs(table: BindingTable) -> HashMap<KeyAction, Vec<String>> {
+    table
+        .iter()
+        .map(|(action, keys)| (*action, keys.iter().map(|k| k.to_string()).collect()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mbr_core::storage::config::KeyList;
+
+    #[test]
+    fn test_parse_key_specs() {
+        let parse = |s| KeyBinding::parse(s).unwrap();
+        assert_eq!(
+            parse("q"),
+            KeyBinding::new(KeyCode::Char('q'), KeyModifiers::NONE)
+        );
+        assert_eq!(parse("shift+g"), parse("G"));
+        assert_eq!(
+            parse("ctrl+p"),
+            KeyBinding::new(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(parse("shift+tab"), parse("backtab"));
+        assert_eq!(parse("ctrl++").code, KeyCode::Char('+'));
+        assert_eq!(parse("PgDn").code, KeyCode::PageDown);
+        assert_eq!(parse("f5").code, KeyCode::F(5));
+        assert!(KeyBinding::parse("hyper+x").is_err());
+        assert!(KeyBinding::parse("f13").is_err());
+        assert!(KeyBinding::parse("").is_err());
+    }
+
+    #[test]
+    fn test_presets_are_valid_and_resolve() {
+        for preset in [Preset::Default, Preset::Vim, Preset::Emacs] {
+            let mut specs = table_specs(DEFAULT_BINDINGS);
+            specs.extend(table_specs(preset.overrides()));
+            assert!(Keymap::build(preset, specs).is_ok(), "{:?}", preset);
+        }
+
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.resolve(KeyCode::Char('G'), KeyModifiers::SHIFT),
+            Some(KeyAction::Last)
+        );
+        assert_eq!(
+            keymap.resolve(KeyCode::BackTab, KeyModifiers::SHIFT),
+            Some(KeyAction::PrevTab)
+        );
+        assert_eq!(keymap.resolve(KeyCode::Up, KeyModifiers::SHIFT), None);
+        assert_eq!(keymap.describe(KeyAction::Up), "↑ / k");
+
+        let emacs = Keymap::preset(Preset::Emacs);
+        assert_eq!(
+            emacs.resolve(KeyCode::Char('n'), KeyModifiers::CONTROL),
+            Some(KeyAction::Down)
+        );
+        assert_eq!(emacs.resolve(KeyCode::Char('j'), KeyModifiers::NONE), None);
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let mut config = KeysConfig {
+            preset: Some("vim".to_string()),
+            ..Default::default()
+        };
+        config
+            .bindings
+            .insert("copy".to_string(), KeyList::One("y".to_string()));
+        config
+            .bindings
+            .insert("select_all".to_string(), KeyList::Many(vec![]));
+
+        let keymap = Keymap::from_config(&config).unwrap();
+        assert_eq!(keymap.preset_name(), "vim");
+        assert_eq!(
+            keymap.resolve(KeyCode::Char('y'), KeyModifiers::NONE),
+            Some(KeyAction::Copy)
+        );
+        assert_eq!(keymap.resolve(KeyCode::Char('c'), KeyModifiers::NONE), None);
+        assert!(keymap.keys_for(KeyAction::SelectAll).is_empty());
+    }
+
+    #[test]
+    fn test_config_conflicts_and_errors() {
+        let mut config = KeysConfig::default();
+        config
+            .bindings
+            .insert("copy".to_string(), KeyList::One("j".to_string()));
+        let err = Keymap::from_config(&config).unwrap_err().to_string();
+        assert!(
+            err.contains("'j' is bound to both down and copy"),
+            "{}",
+            err
+        );
+
+        let mut config = KeysConfig {
+            preset: Some("nano".to_string()),
+            ..Default::default()
+        };
+        config
+            .bindings
+            .insert("teleport".to_string(), KeyList::One("t".to_string()));
+        let err = Keymap::from_config(&config).unwrap_err().to_string();
+        assert!(err.contains("unknown preset 'nano'"));
+        assert!(err.contains("unknown action 'teleport'"));
+    }
+}
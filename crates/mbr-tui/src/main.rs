@@ -3,7 +3,9 @@
 //! A terminal-based interface for interacting with Metabase,
 //! similar to lazygit, k9s, or htop.
 
+use clap::Parser;
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -12,16 +14,30 @@ use std::io::{self, stdout};
 
 mod action;
 mod app;
+mod command;
 mod components;
 mod error;
 mod event;
+mod keymap;
 mod layout;
 mod service;
 
 use app::App;
 
+/// Terminal UI for Metabase
+#[derive(Parser)]
+#[command(name = "mbr-tui", version)]
+struct Args {
+    /// Browse the snapshot taken by 'mbr-cli sync-metadata' instead of the
+    /// server
+    #[arg(long, env = "MBR_OFFLINE")]
+    offline: bool,
+}
+
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+
     // Set panic hook to restore terminal on panic
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
@@ -30,7 +46,7 @@ async fn main() -> std::io::Result<()> {
     }));
 
     // Create and run app (async event-driven loop)
-    let mut app = App::new();
+    let mut app = App::new(args.offline);
     let result = app.run_async().await;
 
     // Report errors
@@ -45,7 +61,7 @@ async fn main() -> std::io::Result<()> {
 pub(crate) fn setup_terminal() -> io::Result<ratatui::Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     ratatui::Terminal::new(backend)
 }
@@ -53,6 +69,6 @@ pub(crate) fn setup_terminal() -> io::Result<ratatui::Terminal<CrosstermBackend<
 /// Restore terminal to normal state.
 pub(crate) fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
     Ok(())
 }
@@ -1,17 +1,24 @@
 //! Service integration layer for mbr-core.
 //!
-//! Provides async data fetching using mbr-core's QuestionService and MetabaseClient.
+//! Provides async data fetching through mbr-core's `MetabaseApi`: a
+//! `MetabaseClient` when connected, the metadata snapshot when offline.
 
+use std::fmt;
 use std::sync::Arc;
 
 use mbr_core::api::client::MetabaseClient;
 use mbr_core::api::models::{
-    CollectionItem, CurrentUser, Database, QueryResult, Question, TableInfo,
+    CollectionEntry, CollectionItem, CurrentUser, Database, Field, QueryResult, Question, TableInfo,
 };
-use mbr_core::storage::config::Config;
-use mbr_core::storage::credentials::{Session, get_credentials, load_session};
+use mbr_core::api::traits::MetabaseApi;
+use mbr_core::error::{ApiError, AppError};
+use mbr_core::storage::config::{Config, HttpConfig};
+use mbr_core::storage::credentials::{
+    Session, get_credentials, load_session, now_iso8601, save_session,
+};
+use mbr_core::storage::metadata_snapshot::MetadataSnapshot;
 
-use crate::components::QueryResultData;
+use crate::components::{QueryResultData, RecordLink};
 
 /// Generic loading state for async data.
 ///
@@ -32,7 +39,6 @@ pub enum LoadState<T> {
 
 impl<T> LoadState<T> {
     /// Check if currently loading
-    #[allow(dead_code)]
     pub fn is_loading(&self) -> bool {
         matches!(self, LoadState::Loading)
     }
@@ -57,7 +63,6 @@ impl<T> LoadState<T> {
     }
 
     /// Get error message if in error state
-    #[allow(dead_code)]
     pub fn error(&self) -> Option<&str> {
         match self {
             LoadState::Error(msg) => Some(msg),
@@ -66,6 +71,55 @@ impl<T> LoadState<T> {
     }
 }
 
+/// Error of a service call.
+///
+/// Rejected credentials are kept apart so that the App can ask the user to
+/// log in again and retry the request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServiceError {
+    /// The session or API key was rejected (HTTP 401)
+    Unauthorized(String),
+    /// Any other failure
+    Failed(String),
+}
+
+impl ServiceError {
+    /// Wrap an API error with the context of the failed call.
+    fn new(context: impl fmt::Display, error: impl Into<AppError>) -> Self {
+        let error = error.into();
+        let message = format!("{}: {}", context, error);
+        if matches!(error, AppError::Api(ApiError::Unauthorized { .. })) {
+            ServiceError::Unauthorized(message)
+        } else {
+            ServiceError::Failed(message)
+        }
+    }
+
+    /// Whether the credentials were rejected.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, ServiceError::Unauthorized(_))
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::Unauthorized(message) | ServiceError::Failed(message) => {
+                f.write_str(message)
+            }
+        }
+    }
+}
+
+/// Credentials entered on the login screen.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credentials {
+    /// Username and password, exchanged for a session token
+    Password { username: String, password: String },
+    /// Metabase API key
+    ApiKey(String),
+}
+
 /// Application data state using LoadState for each resource.
 #[derive(Debug, Clone, Default)]
 pub struct AppData {
@@ -90,20 +144,56 @@ pub struct AppData {
 /// Designed to be wrapped in Arc for sharing across tokio tasks.
 #[derive(Clone)]
 pub struct ServiceClient {
-    client: MetabaseClient,
+    client: Arc<dyn MetabaseApi>,
+    /// Metadata snapshot answering the requests when offline
+    snapshot: Option<Arc<MetadataSnapshot>>,
 }
 
 impl ServiceClient {
-    /// Create a new service client from configuration
-    pub fn new(base_url: String, session_token: Option<String>) -> Result<Self, String> {
-        let client = if let Some(token) = session_token {
-            MetabaseClient::with_session_token(base_url, token)
-                .map_err(|e| format!("Failed to create client: {}", e))?
-        } else {
-            MetabaseClient::new(base_url).map_err(|e| format!("Failed to create client: {}", e))?
-        };
+    /// Create a service client on any `MetabaseApi` implementation.
+    pub fn new(client: Arc<dyn MetabaseApi>) -> Self {
+        Self {
+            client,
+            snapshot: None,
+        }
+    }
 
-        Ok(Self { client })
+    /// Create a service client from a stored session
+    pub fn from_session(session: &Session) -> Result<Self, String> {
+        let http = http_config()?;
+        let retry = http
+            .retry_config()
+            .map_err(|e| format!("Invalid configuration: {}", e))?;
+        let client = MetabaseClient::from_session(session)
+            .map_err(|e| format!("Failed to create client: {}", e))?
+            .with_http(&http)
+            .map_err(|e| format!("Invalid configuration: {}", e))?
+            .with_retry(retry);
+
+        Ok(Self::new(Arc::new(client)))
+    }
+
+    /// Create an offline service client answering from the metadata
+    /// snapshot of `url`.
+    pub fn offline(url: &str) -> Result<Self, String> {
+        let snapshot = MetadataSnapshot::load(url)
+            .map_err(|e| format!("Failed to load metadata snapshot: {}", e))?
+            .ok_or_else(|| {
+                format!(
+                    "No metadata snapshot for {} (run 'mbr-cli sync-metadata' while connected)",
+                    url
+                )
+            })?;
+        let snapshot = Arc::new(snapshot);
+        Ok(Self {
+            client: snapshot.clone(),
+            snapshot: Some(snapshot),
+        })
+    }
+
+    /// The metadata snapshot, when offline.
+    pub fn snapshot(&self) -> Option<&MetadataSnapshot> {
+        self.snapshot.as_deref()
     }
 
     /// Check if the client is authenticated
@@ -112,11 +202,11 @@ impl ServiceClient {
     }
 
     /// Validate session by fetching current user
-    pub async fn validate_auth(&self) -> Result<CurrentUser, String> {
+    pub async fn validate_auth(&self) -> Result<CurrentUser, ServiceError> {
         self.client
             .get_current_user()
             .await
-            .map_err(|e| format!("Authentication failed: {}", e))
+            .map_err(|e| ServiceError::new("Authentication failed", e))
     }
 
     /// Fetch questions list.
@@ -124,55 +214,54 @@ impl ServiceClient {
         &self,
         search: Option<&str>,
         limit: Option<u32>,
-    ) -> Result<Vec<Question>, String> {
+    ) -> Result<Vec<Question>, ServiceError> {
         self.client
             .list_questions(search, limit, None)
             .await
-            .map_err(|e| format!("Failed to fetch questions: {}", e))
+            .map_err(|e| ServiceError::new("Failed to fetch questions", e))
     }
 
     /// Execute a question query and return results
-    pub async fn execute_question(&self, id: u32) -> Result<QueryResult, String> {
+    pub async fn execute_question(&self, id: u32) -> Result<QueryResult, ServiceError> {
         self.client
             .execute_question(id, None)
             .await
-            .map_err(|e| format!("Query execution failed: {}", e))
+            .map_err(|e| ServiceError::new("Query execution failed", e))
     }
 
-    /// Fetch questions filtered by collection using server-side filtering.
-    pub async fn fetch_questions_by_collection(
+    /// Fetch the items of a collection (None = root collection)
+    pub async fn fetch_collection_items(
         &self,
-        collection_id: &str,
-        limit: Option<u32>,
-    ) -> Result<Vec<Question>, String> {
+        collection_id: Option<u32>,
+    ) -> Result<Vec<CollectionEntry>, ServiceError> {
         self.client
-            .list_questions(None, limit, Some(collection_id))
+            .list_collection_items(collection_id)
             .await
-            .map_err(|e| format!("Failed to fetch questions: {}", e))
+            .map_err(|e| ServiceError::new("Failed to fetch collection items", e))
     }
 
     /// Fetch collections list
-    pub async fn fetch_collections(&self) -> Result<Vec<CollectionItem>, String> {
+    pub async fn fetch_collections(&self) -> Result<Vec<CollectionItem>, ServiceError> {
         self.client
             .list_collections()
             .await
-            .map_err(|e| format!("Failed to fetch collections: {}", e))
+            .map_err(|e| ServiceError::new("Failed to fetch collections", e))
     }
 
     /// Fetch databases list
-    pub async fn fetch_databases(&self) -> Result<Vec<Database>, String> {
+    pub async fn fetch_databases(&self) -> Result<Vec<Database>, ServiceError> {
         self.client
             .list_databases()
             .await
-            .map_err(|e| format!("Failed to fetch databases: {}", e))
+            .map_err(|e| ServiceError::new("Failed to fetch databases", e))
     }
 
     /// Fetch schemas for a specific database
-    pub async fn fetch_schemas(&self, database_id: u32) -> Result<Vec<String>, String> {
+    pub async fn fetch_schemas(&self, database_id: u32) -> Result<Vec<String>, ServiceError> {
         self.client
             .list_schemas(database_id)
             .await
-            .map_err(|e| format!("Failed to fetch schemas: {}", e))
+            .map_err(|e| ServiceError::new("Failed to fetch schemas", e))
     }
 
     /// Fetch tables for a specific schema in a database
@@ -180,11 +269,11 @@ impl ServiceClient {
         &self,
         database_id: u32,
         schema: &str,
-    ) -> Result<Vec<TableInfo>, String> {
+    ) -> Result<Vec<TableInfo>, ServiceError> {
         self.client
             .list_tables(database_id, schema)
             .await
-            .map_err(|e| format!("Failed to fetch tables: {}", e))
+            .map_err(|e| ServiceError::new("Failed to fetch tables", e))
     }
 
     /// Preview table data (fetch sample rows)
@@ -193,52 +282,167 @@ impl ServiceClient {
         database_id: u32,
         table_id: u32,
         limit: u32,
-    ) -> Result<QueryResult, String> {
+    ) -> Result<QueryResult, ServiceError> {
         self.client
             .preview_table(database_id, table_id, limit)
             .await
-            .map_err(|e| format!("Failed to preview table: {}", e))
+            .map_err(|e| ServiceError::new("Failed to preview table", e))
     }
+
+    /// Resolve the field a foreign key points to.
+    pub async fn resolve_field_link(&self, field_id: u32) -> Result<RecordLink, ServiceError> {
+        let field = self
+            .client
+            .get_field(field_id)
+            .await
+            .map_err(|e| ServiceError::new("Failed to fetch field", e))?;
+        record_link(&field)
+            .ok_or_else(|| ServiceError::Failed(format!("Field {} has no table", field_id)))
+    }
+
+    /// Fields in other tables that reference a primary key.
+    pub async fn fetch_references(
+        &self,
+        table_id: u32,
+        field_id: u32,
+    ) -> Result<Vec<RecordLink>, ServiceError> {
+        let fks = self
+            .client
+            .list_table_fks(table_id)
+            .await
+            .map_err(|e| ServiceError::new("Failed to fetch references", e))?;
+        Ok(fks
+            .iter()
+            .filter(|fk| fk.destination_id.is_none_or(|id| id == field_id))
+            .filter_map(|fk| record_link(&fk.origin))
+            .collect())
+    }
+
+    /// Fetch the rows of a linked table whose key equals a value.
+    pub async fn fetch_linked_rows(
+        &self,
+        link: &RecordLink,
+        value: &serde_json::Value,
+        limit: u32,
+    ) -> Result<QueryResult, ServiceError> {
+        self.client
+            .query_table_rows(link.database_id, link.table_id, link.field_id, value, limit)
+            .await
+            .map_err(|e| ServiceError::new(format!("Failed to fetch {} rows", link.label), e))
+    }
+}
+
+/// Lookup link for a field (None when the table is unknown).
+fn record_link(field: &Field) -> Option<RecordLink> {
+    let table = field.table.as_ref()?;
+    let table_name = table.display_name.as_deref().unwrap_or(&table.name);
+    let field_name = field.display_name.as_deref().unwrap_or(&field.name);
+    Some(RecordLink {
+        database_id: table.db_id,
+        table_id: field.table_id,
+        field_id: field.id,
+        label: format!("{}.{}", table_name, field_name),
+    })
+}
+
+/// HTTP settings (retries, proxy, TLS) from the configuration.
+fn http_config() -> Result<HttpConfig, String> {
+    Config::load(None)
+        .map(|c| c.http)
+        .map_err(|e| format!("Invalid configuration: {}", e))
+}
+
+/// Metabase URL from the configuration (or `MBR_URL`), with a local default.
+pub fn configured_url() -> String {
+    Config::load(None)
+        .ok()
+        .and_then(|c| c.get_url().map(|cow| cow.into_owned()))
+        .unwrap_or_else(|| "http://localhost:3000".to_string())
 }
 
 /// Initialize service client from stored session or environment credentials.
 pub async fn init_service() -> Result<Arc<ServiceClient>, String> {
-    let config = Config::load(None).ok();
-    let base_url = config
-        .as_ref()
-        .and_then(|c| c.get_url().map(|cow| cow.into_owned()))
-        .unwrap_or_else(|| "http://localhost:3000".to_string());
+    let base_url = configured_url();
 
     // Try stored session first
     if let Some(session) = load_session()
         && session.url == base_url
     {
-        return ServiceClient::new(base_url, Some(session.session_token)).map(Arc::new);
+        return ServiceClient::from_session(&session).map(Arc::new);
     }
 
     // Try auto-login via environment variables
     if let Some((username, password)) = get_credentials() {
-        match MetabaseClient::login(&base_url, &username, &password).await {
+        match MetabaseClient::login(&base_url, &username, &password, &http_config()?).await {
             Ok(token) => {
                 let session = Session {
-                    session_token: token.clone(),
-                    url: base_url.clone(),
+                    session_token: token,
+                    url: base_url,
                     username,
-                    created_at: mbr_core::storage::credentials::now_iso8601(),
+                    created_at: now_iso8601(),
+                    api_key: None,
                 };
-                let _ = mbr_core::storage::credentials::save_session(&session);
-                return ServiceClient::new(base_url, Some(token)).map(Arc::new);
+                let _ = save_session(&session);
+                return ServiceClient::from_session(&session).map(Arc::new);
             }
             Err(e) => {
                 return Err(format!(
-                    "Auto-login failed: {}\n\nPlease run 'mbr login' first, or check MBR_USERNAME and MBR_PASSWORD.",
+                    "Auto-login failed: {} (check MBR_USERNAME and MBR_PASSWORD)",
                     e
                 ));
             }
         }
     }
 
-    Err("No active session.\n\nPlease run 'mbr login' first, or set MBR_USERNAME and MBR_PASSWORD environment variables.".to_string())
+    Err("No active session".to_string())
+}
+
+/// Log in from the login screen.
+///
+/// Checks the credentials by fetching the current user, then saves the
+/// session (and the URL, if it differs from the configured one) so that
+/// `mbr-cli` and later TUI sessions use it too.
+pub async fn login(url: &str, credentials: Credentials) -> Result<(Session, CurrentUser), String> {
+    let url = url.trim_end_matches('/').to_string();
+    let mut session = match credentials {
+        Credentials::Password { username, password } => {
+            let token = MetabaseClient::login(&url, &username, &password, &http_config()?)
+                .await
+                .map_err(|e| e.to_string())?;
+            Session {
+                session_token: token,
+                url: url.clone(),
+                username,
+                created_at: now_iso8601(),
+                api_key: None,
+            }
+        }
+        Credentials::ApiKey(key) => Session {
+            session_token: String::new(),
+            url: url.clone(),
+            username: String::new(),
+            created_at: now_iso8601(),
+            api_key: Some(key),
+        },
+    };
+
+    let user = ServiceClient::from_session(&session)?
+        .validate_auth()
+        .await
+        .map_err(|e| e.to_string())?;
+    if session.api_key.is_some() {
+        session.username = user.email.clone();
+    }
+    save_session(&session)?;
+
+    if configured_url() != url {
+        let mut config = Config::load(None).map_err(|e| e.to_string())?;
+        config.set_url(url);
+        config
+            .save(None)
+            .map_err(|e| format!("Failed to save URL: {}", e))?;
+    }
+    Ok((session, user))
 }
 
 /// Connection status for display
@@ -253,4 +457,6 @@ pub enum ConnectionStatus {
     Connected(String), // Username or email
     /// Connection failed
     Error(String),
+    /// Browsing the metadata snapshot without a connection
+    Offline,
 }